@@ -0,0 +1,69 @@
+//! Human-readable output of the Sunrise state.
+
+use anchor_lang::prelude::Pubkey;
+use sunrise_client::sunrise::State;
+
+/// Print `state`, or a placeholder if it does not exist (yet).
+pub fn print_state(label: &str, address: &Pubkey, state: Option<&State>) {
+    println!("{label}: state {address}");
+    let Some(state) = state else {
+        println!("  (account does not exist)");
+        return;
+    };
+
+    println!("  update authority:     {}", state.update_authority);
+    println!("  gSOL mint:            {}", state.gsol_mint);
+    println!("  gSOL pre-supply:      {}", state.pre_supply);
+    println!("  mint authority bump:  {}", state.gsol_mint_authority_bump);
+    println!("  yield account:        {}", state.yield_account);
+    println!("  treasury:             {}", state.treasury);
+    println!("  protocol fee (bps):   {}", state.protocol_fee_bps);
+    println!("  beam router:          {}", state.beam_router);
+
+    println!(
+        "  allocations ({} of {} slots used):",
+        state
+            .allocations
+            .iter()
+            .filter(|a| a.key != Pubkey::default())
+            .count(),
+        state.allocations.len()
+    );
+    for beam in state
+        .allocations
+        .iter()
+        .filter(|a| a.key != Pubkey::default())
+    {
+        println!(
+            "    {}  allocation: {:>3}%  partial gSOL supply: {}{}",
+            beam.key,
+            beam.allocation,
+            beam.partial_gsol_supply,
+            if beam.draining_mode {
+                "  (draining)"
+            } else {
+                ""
+            }
+        );
+    }
+
+    let report = &state.epoch_report;
+    println!(
+        "  epoch report (gSOL supply {}):",
+        report.current_gsol_supply
+    );
+    for (beam, details) in state.allocations.iter().zip(&report.beam_epoch_details) {
+        if beam.key == Pubkey::default() {
+            continue;
+        }
+        println!(
+            "    {}  epoch: {}  extractable: {}  extracted: {}  deficit: {}  protocol fee: {}",
+            beam.key,
+            details.epoch,
+            details.extractable_yield,
+            details.extracted_yield,
+            beam.deficit,
+            beam.protocol_fee
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! `sunrise`: admin CLI for the Sunrise core program.
+//!
+//! Every subcommand prints the state before and after the change. With `--dry-run` the
+//! transaction is simulated and the simulated state is printed instead. With `--unsigned` the
+//! transaction is printed base58-encoded without the fee payer's or update authority's signature,
+//! so that it can be signed by a multisig.
+
+mod display;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand};
+use display::print_state;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::message::Message;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use sunrise_client::sunrise::{
+    self, AllocationUpdate, RegisterStateInput, State, UpdateStateInput,
+};
+use sunrise_client::{decode, rpc, SunriseClient};
+
+#[derive(Parser)]
+#[clap(name = "sunrise", about = "Manage a Sunrise state and its beams")]
+struct Cli {
+    /// The RPC endpoint.
+    #[clap(long, short = 'u', default_value = "http://localhost:8899")]
+    url: String,
+
+    /// The keypair that pays for and signs the transaction.
+    /// Not needed with `--unsigned`.
+    #[clap(long, short = 'k', default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// The Sunrise core program.
+    #[clap(long, default_value_t = sunrise::ID)]
+    program_id: Pubkey,
+
+    /// The Sunrise state address.
+    #[clap(long, short = 's')]
+    state: Option<Pubkey>,
+
+    /// Simulate the transaction and print the resulting state, without sending it.
+    #[clap(long, conflicts_with = "unsigned")]
+    dry_run: bool,
+
+    /// Print the transaction, base58-encoded, instead of signing and sending it.
+    #[clap(long)]
+    unsigned: bool,
+
+    /// The fee payer of an `--unsigned` transaction. Defaults to the state's update authority.
+    #[clap(long, requires = "unsigned")]
+    fee_payer: Option<Pubkey>,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the state.
+    Show,
+    /// Register a new state. Its keypair is generated unless `--state-keypair` is given.
+    RegisterState {
+        #[clap(long)]
+        gsol_mint: Pubkey,
+        #[clap(long)]
+        update_authority: Pubkey,
+        #[clap(long)]
+        yield_account: Pubkey,
+        /// The number of beams the state has room for.
+        #[clap(long, default_value_t = 10)]
+        initial_capacity: u8,
+        #[clap(long)]
+        state_keypair: Option<String>,
+    },
+    /// Update the state's parameters. Unset parameters are left unchanged.
+    UpdateState {
+        #[clap(long)]
+        new_update_authority: Option<Pubkey>,
+        #[clap(long)]
+        new_yield_account: Option<Pubkey>,
+        #[clap(long)]
+        new_gsol_mint: Option<Pubkey>,
+        #[clap(long)]
+        new_gsol_mint_authority_bump: Option<u8>,
+        #[clap(long)]
+        new_treasury: Option<Pubkey>,
+        #[clap(long)]
+        new_protocol_fee_bps: Option<u16>,
+        #[clap(long)]
+        new_beam_router: Option<Pubkey>,
+    },
+    /// Register a beam with a zero allocation.
+    RegisterBeam { beam: Pubkey },
+    /// Grow the allocations to make room for more beams.
+    ResizeAllocations { additional_beams: u8 },
+    /// Set the allocations of beams, each given as `<BEAM>=<PERCENT>`.
+    UpdateAllocations {
+        #[clap(required = true, value_parser = parse_allocation)]
+        allocations: Vec<(Pubkey, u8)>,
+    },
+    /// Remove a beam, or put it in draining mode if it still has gSOL issued.
+    RemoveBeam { beam: Pubkey },
+    /// Transfer the gSOL mint authority away from the program.
+    ExportMintAuthority { new_authority: Pubkey },
+    /// Cover `amount` of the deficit of `beam` with the surplus of `covering_beam`.
+    SocialiseDeficit {
+        beam: Pubkey,
+        covering_beam: Pubkey,
+        amount: u64,
+    },
+    /// Create the state's backing oracle.
+    RegisterBackingOracle,
+    /// Grow the backing oracle to fit all the state's allocations.
+    ResizeBackingOracle,
+}
+
+fn parse_allocation(s: &str) -> Result<(Pubkey, u8)> {
+    let (beam, allocation) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected <BEAM>=<PERCENT>, got {s}"))?;
+    Ok((beam.parse()?, allocation.parse()?))
+}
+
+fn read_keypair(path: &str) -> Result<Keypair> {
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => format!("{}/{rest}", std::env::var("HOME")?),
+        None => path.to_string(),
+    };
+    read_keypair_file(&path).map_err(|e| anyhow!("failed to read keypair {path}: {e}"))
+}
+
+async fn fetch_state(rpc: &RpcClient, address: &Pubkey) -> Result<Option<State>> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())
+        .await?
+        .value;
+    account
+        .map(|account| decode(address, &account.data))
+        .transpose()
+        .map_err(Into::into)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+
+    let mut signers: Vec<Keypair> = vec![];
+    let (state_address, instruction, payer) = match &cli.command {
+        Command::RegisterState {
+            gsol_mint,
+            update_authority,
+            yield_account,
+            initial_capacity,
+            state_keypair,
+        } => {
+            let state_keypair = match state_keypair {
+                Some(path) => read_keypair(path)?,
+                None => Keypair::new(),
+            };
+            let state_address = state_keypair.pubkey();
+            let payer = fee_payer(&cli, update_authority)?;
+            let instruction = sunrise::register_state(
+                &cli.program_id,
+                &payer,
+                &state_address,
+                gsol_mint,
+                RegisterStateInput {
+                    update_authority: *update_authority,
+                    yield_account: *yield_account,
+                    initial_capacity: *initial_capacity,
+                },
+            );
+            signers.push(state_keypair);
+            (state_address, instruction, payer)
+        }
+        command => {
+            let state_address = cli.state.context("--state is required")?;
+            let client = rpc::sunrise_client(&rpc, &cli.program_id, &state_address).await?;
+            let Some(instruction) = admin_instruction(&cli, &client, command)? else {
+                print_state("Current", &state_address, Some(&client.state));
+                return Ok(());
+            };
+            // Instructions on an existing state are paid for by its update authority by default.
+            let payer = fee_payer(&cli, &client.state.update_authority)?;
+            (state_address, instruction, payer)
+        }
+    };
+
+    let before = fetch_state(&rpc, &state_address).await?;
+    print_state("Before", &state_address, before.as_ref());
+
+    if cli.unsigned {
+        let blockhash = rpc.get_latest_blockhash().await?;
+        let mut transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+        transaction.partial_sign(&signers.iter().collect::<Vec<_>>(), blockhash);
+        println!("Unsigned transaction (base58):");
+        println!(
+            "{}",
+            bs58::encode(bincode::serialize(&transaction)?).into_string()
+        );
+        return Ok(());
+    }
+
+    if cli.dry_run {
+        let after = simulate(&rpc, instruction, &payer, &state_address).await?;
+        print_state("After (simulated)", &state_address, after.as_ref());
+        return Ok(());
+    }
+
+    let keypair = read_keypair(&cli.keypair)?;
+    let mut all_signers: Vec<&dyn Signer> = vec![&keypair];
+    all_signers.extend(signers.iter().map(|s| s as &dyn Signer));
+    let signature = rpc::send(&rpc, &[instruction], &payer, &all_signers).await?;
+    println!("Transaction: {signature}");
+
+    let after = fetch_state(&rpc, &state_address).await?;
+    print_state("After", &state_address, after.as_ref());
+    Ok(())
+}
+
+/// The fee payer: the signing keypair, or with `--unsigned`, `--fee-payer` or `default`.
+fn fee_payer(cli: &Cli, default: &Pubkey) -> Result<Pubkey> {
+    if cli.unsigned {
+        Ok(cli.fee_payer.unwrap_or(*default))
+    } else {
+        Ok(read_keypair(&cli.keypair)?.pubkey())
+    }
+}
+
+/// Build the instruction of a subcommand acting on an existing state, or `None` for `show`.
+fn admin_instruction(
+    cli: &Cli,
+    client: &SunriseClient,
+    command: &Command,
+) -> Result<Option<Instruction>> {
+    let payer = || fee_payer(cli, &client.state.update_authority);
+    let instruction = match command {
+        Command::Show => return Ok(None),
+        Command::RegisterState { .. } => unreachable!("handled separately"),
+        Command::UpdateState {
+            new_update_authority,
+            new_yield_account,
+            new_gsol_mint,
+            new_gsol_mint_authority_bump,
+            new_treasury,
+            new_protocol_fee_bps,
+            new_beam_router,
+        } => client.update_state(UpdateStateInput {
+            new_update_authority: *new_update_authority,
+            new_yield_account: *new_yield_account,
+            new_gsol_mint: *new_gsol_mint,
+            new_gsol_mint_authority_bump: *new_gsol_mint_authority_bump,
+            new_treasury: *new_treasury,
+            new_protocol_fee_bps: *new_protocol_fee_bps,
+            new_beam_router: *new_beam_router,
+        }),
+        Command::RegisterBeam { beam } => client.register_beam(beam),
+        Command::ResizeAllocations { additional_beams } => {
+            client.resize_allocations(&payer()?, *additional_beams)
+        }
+        Command::UpdateAllocations { allocations } => client.update_allocations(
+            allocations
+                .iter()
+                .map(|(beam, new_allocation)| AllocationUpdate {
+                    beam: *beam,
+                    new_allocation: *new_allocation,
+                })
+                .collect(),
+        ),
+        Command::RemoveBeam { beam } => client.remove_beam(beam),
+        Command::ExportMintAuthority { new_authority } => {
+            client.export_mint_authority(new_authority)
+        }
+        Command::SocialiseDeficit {
+            beam,
+            covering_beam,
+            amount,
+        } => client.socialise_deficit(beam, covering_beam, *amount),
+        Command::RegisterBackingOracle => client.register_backing_oracle(&payer()?),
+        Command::ResizeBackingOracle => client.resize_backing_oracle(&payer()?),
+    };
+    Ok(Some(instruction))
+}
+
+/// Simulate `instruction` and return the state as it would be after it.
+async fn simulate(
+    rpc: &RpcClient,
+    instruction: Instruction,
+    payer: &Pubkey,
+    state_address: &Pubkey,
+) -> Result<Option<State>> {
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(payer)));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: vec![state_address.to_string()],
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc
+        .simulate_transaction_with_config(&transaction, config)
+        .await?
+        .value;
+    for log in result.logs.unwrap_or_default() {
+        println!("  {log}");
+    }
+    if let Some(err) = result.err {
+        bail!("simulation failed: {err}");
+    }
+
+    let account = result
+        .accounts
+        .and_then(|accounts| accounts.into_iter().next().flatten());
+    match account {
+        Some(account) => {
+            let data = account
+                .data
+                .decode()
+                .context("failed to decode the simulated state")?;
+            Ok(Some(decode(state_address, &data)?))
+        }
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,155 @@
+//! The beams cranked by the keeper.
+
+use crate::config::{BeamConfig, BeamKind};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::Result;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use sunrise_client::{marinade_lp, marinade_sp, rpc, spl};
+use sunrise_client::{MarinadeClient, MarinadeLpClient, SplClient};
+
+pub enum Beam {
+    Spl(SplClient),
+    Marinade(MarinadeClient),
+    MarinadeLp(MarinadeLpClient),
+}
+
+impl BeamKind {
+    /// The deployed program of beams of this kind.
+    pub fn program_id(self) -> Pubkey {
+        match self {
+            BeamKind::Spl => spl::ID,
+            BeamKind::Marinade => marinade_sp::ID,
+            BeamKind::MarinadeLp => marinade_lp::ID,
+        }
+    }
+
+    /// The kind of the beams of `program_id`, if it is one of the deployed beam programs.
+    pub fn of_program(program_id: &Pubkey) -> Option<Self> {
+        [BeamKind::Spl, BeamKind::Marinade, BeamKind::MarinadeLp]
+            .into_iter()
+            .find(|kind| kind.program_id() == *program_id)
+    }
+}
+
+impl Beam {
+    /// Fetch the current state of a configured beam.
+    pub async fn load(rpc: &RpcClient, config: &BeamConfig) -> Result<Self> {
+        let program_id = config.program_id.unwrap_or(config.kind.program_id());
+        let beam = match config.kind {
+            BeamKind::Spl => Beam::Spl(rpc::spl_client(rpc, &program_id, &config.state).await?),
+            BeamKind::Marinade => {
+                Beam::Marinade(rpc::marinade_client(rpc, &program_id, &config.state).await?)
+            }
+            BeamKind::MarinadeLp => {
+                Beam::MarinadeLp(rpc::marinade_lp_client(rpc, &program_id, &config.state).await?)
+            }
+        };
+        Ok(beam)
+    }
+
+    pub fn state_address(&self) -> Pubkey {
+        match self {
+            Beam::Spl(client) => client.state_address,
+            Beam::Marinade(client) => client.state_address,
+            Beam::MarinadeLp(client) => client.state_address,
+        }
+    }
+
+    /// The instructions to send before [Beam::update_epoch_report] in `epoch`.
+    ///
+    /// An SPL beam can only be priced once its stake pool has been updated for the epoch,
+    /// which is permissionless but left to whoever cranks the pool.
+    pub fn refresh(&self, epoch: u64) -> Vec<Instruction> {
+        match self {
+            Beam::Spl(client) if client.is_pool_stale(epoch) => client.refresh_pool_instructions(),
+            _ => vec![],
+        }
+    }
+
+    pub fn update_epoch_report(&self) -> Instruction {
+        match self {
+            Beam::Spl(client) => client.update_epoch_report(),
+            Beam::Marinade(client) => client.update_epoch_report(),
+            Beam::MarinadeLp(client) => client.update_epoch_report(),
+        }
+    }
+
+    pub fn extract_yield(&self, payer: &Pubkey) -> Instruction {
+        match self {
+            Beam::Spl(client) => client.extract_yield(payer),
+            Beam::Marinade(client) => client.extract_yield(payer),
+            Beam::MarinadeLp(client) => client.extract_yield(payer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keeper::all_beams_reported;
+    use beam_test_utils::beams::initialize_spl_beam;
+    use beam_test_utils::{
+        add_spl_stake_pool, beam_program_test, BeamTestContext, SPL_STAKE_POOL_EPOCH,
+    };
+    use solana_program_test::tokio;
+    use sunrise_client::spl::StakePool;
+
+    async fn spl_beam(context: &mut BeamTestContext, state_address: &Pubkey) -> Beam {
+        let state: spl::State = context.fetch(state_address).await;
+        let stake_pool: StakePool = context.fetch(&state.stake_pool).await;
+        let validator_list = context.account(&stake_pool.validator_list).await.unwrap();
+        let validator_list =
+            spl::decode_validator_list(&stake_pool.validator_list, &validator_list.data).unwrap();
+        Beam::Spl(SplClient::new(
+            spl::ID,
+            *state_address,
+            state,
+            stake_pool,
+            validator_list,
+            context.sunrise.clone(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn refreshes_the_stake_pool_of_an_spl_beam_before_reporting() {
+        let mut program_test = beam_program_test!(spl_beam, spl::ID);
+        add_spl_stake_pool(&mut program_test);
+        let mut context = BeamTestContext::start(program_test).await;
+        context.warp_to_epoch(SPL_STAKE_POOL_EPOCH).await;
+        let state_address = initialize_spl_beam(&mut context).await.state_address;
+        context.register_beam(&state_address).await;
+
+        // The stake pool was last updated in the previous epoch, so cannot be priced.
+        let epoch = SPL_STAKE_POOL_EPOCH + 1;
+        context.warp_to_epoch(epoch).await;
+        let beam = spl_beam(&mut context, &state_address).await;
+        assert!(context
+            .send(&[beam.update_epoch_report()], &[])
+            .await
+            .is_err());
+
+        let refresh = beam.refresh(epoch);
+        assert!(!refresh.is_empty());
+        for instruction in refresh {
+            context.send(&[instruction], &[]).await.unwrap();
+        }
+        context
+            .send(&[beam.update_epoch_report()], &[])
+            .await
+            .unwrap();
+
+        context.refresh_sunrise().await;
+        assert!(all_beams_reported(&context.sunrise.state, epoch));
+        let beam = spl_beam(&mut context, &state_address).await;
+        assert!(beam.refresh(epoch).is_empty());
+    }
+
+    #[test]
+    fn finds_the_kind_of_a_beam_program() {
+        for kind in [BeamKind::Spl, BeamKind::Marinade, BeamKind::MarinadeLp] {
+            assert_eq!(BeamKind::of_program(&kind.program_id()), Some(kind));
+        }
+        assert_eq!(BeamKind::of_program(&Pubkey::new_unique()), None);
+    }
+}
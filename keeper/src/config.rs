@@ -0,0 +1,116 @@
+//! The keeper's configuration file.
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
+
+/// The kinds of beam the keeper can crank.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BeamKind {
+    Spl,
+    Marinade,
+    MarinadeLp,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BeamConfig {
+    pub kind: BeamKind,
+    /// The beam state address, which is also the beam's key in the Sunrise state.
+    #[serde(deserialize_with = "pubkey")]
+    pub state: Pubkey,
+    /// The beam program. Defaults to the deployed program of the beam's kind.
+    #[serde(default, deserialize_with = "optional_pubkey")]
+    pub program_id: Option<Pubkey>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    pub rpc_url: String,
+    /// The keypair that pays for the cranks and is the beneficiary of the tickets it claims.
+    pub keypair: String,
+    #[serde(deserialize_with = "pubkey")]
+    pub sunrise_state: Pubkey,
+    #[serde(default, deserialize_with = "optional_pubkey")]
+    pub sunrise_program_id: Option<Pubkey>,
+    pub beams: Vec<BeamConfig>,
+    /// How often to check for a new epoch and for matured tickets.
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// How many times a transaction is attempted before giving up until the next poll.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_retry_delay_secs")]
+    pub retry_delay_secs: u64,
+    /// How long to wait for all beams to report before extracting yield anyway.
+    #[serde(default = "default_report_timeout_secs")]
+    pub report_timeout_secs: u64,
+    /// The least yield, net of the transaction fee, worth extracting from a beam.
+    #[serde(default)]
+    pub min_extract_lamports: u64,
+    /// Simulate transactions instead of sending them.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+fn default_max_retries() -> u32 {
+    5
+}
+
+fn default_retry_delay_secs() -> u64 {
+    10
+}
+
+fn default_report_timeout_secs() -> u64 {
+    600
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("invalid config {}", path.display()))
+    }
+}
+
+fn pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    s.parse().map_err(serde::de::Error::custom)
+}
+
+fn optional_pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Pubkey>, D::Error> {
+    pubkey(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_config_with_defaults() {
+        let config: Config = toml::from_str(
+            r#"
+            rpc_url = "http://localhost:8899"
+            keypair = "keeper.json"
+            sunrise_state = "11111111111111111111111111111111"
+
+            [[beams]]
+            kind = "marinade-lp"
+            state = "SysvarC1ock11111111111111111111111111111111"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.beams.len(), 1);
+        assert_eq!(config.beams[0].kind, BeamKind::MarinadeLp);
+        assert_eq!(config.beams[0].program_id, None);
+        assert_eq!(config.sunrise_program_id, None);
+        assert_eq!(config.max_retries, 5);
+        assert!(!config.dry_run);
+    }
+}
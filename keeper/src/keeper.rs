@@ -0,0 +1,418 @@
+//! The keeper loop: cranking epoch reports and yield extraction, and claiming tickets.
+
+use crate::beam::Beam;
+use crate::config::{BeamConfig, BeamKind, Config};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::Discriminator;
+use anyhow::{bail, Result};
+use log::{debug, error, info, warn};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::message::Message;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use std::time::{Duration, Instant};
+use sunrise_client::marinade::TicketAccountData;
+use sunrise_client::marinade_sp::{self, ProxyTicket};
+use sunrise_client::sunrise::{self, State};
+use sunrise_client::{decode, rpc};
+
+/// Whether every beam registered in `state` has reported for `epoch`.
+///
+/// Unlike `EpochReport::is_epoch_reported`, empty allocation slots are ignored,
+/// since they never report.
+pub fn all_beams_reported(state: &State, epoch: u64) -> bool {
+    state
+        .allocations
+        .iter()
+        .enumerate()
+        .filter(|(_, beam)| beam.key != Pubkey::default())
+        .all(|(idx, _)| {
+            state
+                .epoch_report
+                .is_epoch_reported_for_beam_idx(epoch, idx)
+        })
+}
+
+/// The beams registered in `state` that are not among the `configured` beam states.
+pub fn unconfigured_beams(state: &State, configured: &[Pubkey]) -> Vec<Pubkey> {
+    state
+        .allocations
+        .iter()
+        .map(|beam| beam.key)
+        .filter(|beam| *beam != Pubkey::default() && !configured.contains(beam))
+        .collect()
+}
+
+/// The yield that `beam` can extract in `epoch`, or `None` if it is not registered
+/// or has not reported for `epoch`.
+pub fn extractable_yield(state: &State, beam: &Pubkey, epoch: u64) -> Option<u64> {
+    let idx = state.allocations.iter().position(|b| b.key == *beam)?;
+    let details = state.epoch_report.beam_epoch_details.get(idx)?;
+    (details.epoch == epoch).then_some(details.extractable_yield)
+}
+
+/// Whether extracting `yield_lamports` is worth paying `fee` for.
+pub fn is_worth_extracting(yield_lamports: u64, fee: u64, min_extract_lamports: u64) -> bool {
+    yield_lamports > fee && yield_lamports - fee >= min_extract_lamports
+}
+
+/// Whether a Marinade ticket created in `created_epoch` has matured by `epoch`.
+pub fn is_ticket_matured(created_epoch: u64, epoch: u64) -> bool {
+    epoch > created_epoch
+}
+
+pub struct Keeper {
+    rpc: RpcClient,
+    payer: Keypair,
+    config: Config,
+}
+
+impl Keeper {
+    pub fn new(config: Config, payer: Keypair) -> Self {
+        let rpc =
+            RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+        Self { rpc, payer, config }
+    }
+
+    pub async fn current_epoch(&self) -> Result<u64> {
+        Ok(self.rpc.get_epoch_info().await?.epoch)
+    }
+
+    /// Crank each new epoch and claim matured tickets, forever.
+    pub async fn run(&self) -> Result<()> {
+        let mut cranked_epoch = None;
+        loop {
+            match self.current_epoch().await {
+                Ok(epoch) => {
+                    if cranked_epoch != Some(epoch) {
+                        match self.crank_epoch(epoch).await {
+                            Ok(()) => cranked_epoch = Some(epoch),
+                            Err(err) => error!("Failed to crank epoch {epoch}: {err:#}"),
+                        }
+                    }
+                    if let Err(err) = self.claim_tickets(epoch).await {
+                        error!("Failed to claim tickets: {err:#}");
+                    }
+                }
+                Err(err) => error!("Failed to get the current epoch: {err:#}"),
+            }
+            tokio::time::sleep(Duration::from_secs(self.config.poll_interval_secs)).await;
+        }
+    }
+
+    /// Update the epoch report of each beam, then extract the yield of each beam
+    /// for which it is worth the fee.
+    ///
+    /// Beams registered in the Sunrise state but missing from the config are cranked too,
+    /// if they belong to a known beam program.
+    ///
+    /// Fails if any step failed, so that the epoch is cranked again on the next poll.
+    pub async fn crank_epoch(&self, epoch: u64) -> Result<()> {
+        info!("Cranking epoch {epoch}");
+        let mut beams = vec![];
+        for config in self.beam_configs().await? {
+            beams.push(Beam::load(&self.rpc, &config).await?);
+        }
+
+        let mut failures = 0;
+        for beam in &beams {
+            if let Err(err) = self.update_epoch_report(beam, epoch).await {
+                error!("{err:#}");
+                failures += 1;
+            }
+        }
+
+        // extract_yield fails with EpochReportNotUpToDate for a beam that has not reported.
+        let state = self.wait_for_reports(epoch).await?;
+        for beam in &beams {
+            let Some(yield_lamports) = extractable_yield(&state, &beam.state_address(), epoch)
+            else {
+                warn!(
+                    "Beam {} has not reported for epoch {epoch}",
+                    beam.state_address()
+                );
+                // In a dry run the reports were only simulated.
+                if !self.config.dry_run {
+                    failures += 1;
+                }
+                continue;
+            };
+            let instruction = beam.extract_yield(&self.payer.pubkey());
+            let fee = self.fee(&instruction).await?;
+            if !is_worth_extracting(yield_lamports, fee, self.config.min_extract_lamports) {
+                info!(
+                    "Skipping extract_yield for {}: {yield_lamports} lamports of yield, fee {fee}",
+                    beam.state_address()
+                );
+                continue;
+            }
+            let label = format!("extract_yield for {}", beam.state_address());
+            if let Err(err) = self.execute(&label, &[instruction]).await {
+                error!("{err:#}");
+                failures += 1;
+            }
+        }
+
+        if failures > 0 {
+            bail!("{failures} steps failed");
+        }
+        Ok(())
+    }
+
+    /// The configured beams, and those registered in the Sunrise state that are not configured
+    /// but can be cranked as they belong to a known beam program.
+    async fn beam_configs(&self) -> Result<Vec<BeamConfig>> {
+        let program_id = self.config.sunrise_program_id.unwrap_or(sunrise::ID);
+        let client =
+            rpc::sunrise_client(&self.rpc, &program_id, &self.config.sunrise_state).await?;
+        let configured: Vec<_> = self.config.beams.iter().map(|beam| beam.state).collect();
+
+        let mut configs = self.config.beams.clone();
+        for beam in unconfigured_beams(&client.state, &configured) {
+            let owner = self.rpc.get_account(&beam).await?.owner;
+            match BeamKind::of_program(&owner) {
+                Some(kind) => {
+                    warn!("Beam {beam} is registered but not configured, cranking it as {kind:?}");
+                    configs.push(BeamConfig {
+                        kind,
+                        state: beam,
+                        program_id: Some(owner),
+                    });
+                }
+                None => warn!(
+                    "Beam {beam} of program {owner} is registered but not configured, \
+                     and cannot be cranked: the epoch will not be fully reported"
+                ),
+            }
+        }
+        Ok(configs)
+    }
+
+    /// Update the epoch report of `beam`, after refreshing what it is priced from.
+    async fn update_epoch_report(&self, beam: &Beam, epoch: u64) -> Result<()> {
+        let mut instructions = beam.refresh(epoch);
+        let label = format!("update_epoch_report for {}", beam.state_address());
+        // A simulated refresh has no effect, so it is simulated with the report.
+        if !self.config.dry_run {
+            for instruction in instructions.drain(..) {
+                let label = format!("refresh for {}", beam.state_address());
+                self.execute(&label, &[instruction]).await?;
+            }
+        }
+        instructions.push(beam.update_epoch_report());
+        self.execute(&label, &instructions).await
+    }
+
+    /// Wait until every registered beam has reported for `epoch`, or the timeout passes,
+    /// and return the state.
+    async fn wait_for_reports(&self, epoch: u64) -> Result<State> {
+        let program_id = self.config.sunrise_program_id.unwrap_or(sunrise::ID);
+        let started = Instant::now();
+        loop {
+            let client =
+                rpc::sunrise_client(&self.rpc, &program_id, &self.config.sunrise_state).await?;
+            // Nothing changes in a dry run.
+            if self.config.dry_run || all_beams_reported(&client.state, epoch) {
+                return Ok(client.state);
+            }
+            if started.elapsed() >= Duration::from_secs(self.config.report_timeout_secs) {
+                warn!("Not all beams have reported for epoch {epoch}");
+                return Ok(client.state);
+            }
+            tokio::time::sleep(Duration::from_secs(self.config.retry_delay_secs)).await;
+        }
+    }
+
+    /// Redeem the matured tickets of the Marinade beams of which the keeper is the beneficiary.
+    pub async fn claim_tickets(&self, epoch: u64) -> Result<()> {
+        for config in &self.config.beams {
+            if config.kind != BeamKind::Marinade {
+                continue;
+            }
+            let Beam::Marinade(client) = Beam::load(&self.rpc, config).await? else {
+                unreachable!("loaded a marinade beam");
+            };
+            let program_id = config.program_id.unwrap_or(marinade_sp::ID);
+            for (address, ticket) in self.tickets(&program_id, &config.state).await? {
+                let marinade_ticket: TicketAccountData =
+                    rpc::fetch(&self.rpc, &ticket.marinade_ticket_account).await?;
+                if !is_ticket_matured(marinade_ticket.created_epoch, epoch) {
+                    continue;
+                }
+                let label = format!("redeem_ticket {address}");
+                if let Err(err) = self
+                    .execute(&label, &[client.redeem_ticket(&address, &ticket)])
+                    .await
+                {
+                    error!("{err:#}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The proxy tickets of a Marinade beam of which the keeper is the beneficiary.
+    async fn tickets(
+        &self,
+        program_id: &Pubkey,
+        state: &Pubkey,
+    ) -> Result<Vec<(Pubkey, ProxyTicket)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &ProxyTicket::DISCRIMINATOR)),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8, state.as_ref())),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    8 + 32 + 32,
+                    self.payer.pubkey().as_ref(),
+                )),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        let accounts = self
+            .rpc
+            .get_program_accounts_with_config(program_id, config)
+            .await?;
+        accounts
+            .into_iter()
+            .map(|(address, account)| Ok((address, decode(&address, &account.data)?)))
+            .collect()
+    }
+
+    async fn fee(&self, instruction: &Instruction) -> Result<u64> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let message = Message::new_with_blockhash(
+            &[instruction.clone()],
+            Some(&self.payer.pubkey()),
+            &blockhash,
+        );
+        Ok(self.rpc.get_fee_for_message(&message).await?)
+    }
+
+    /// Send, or in a dry run simulate, a transaction, retrying on failure.
+    async fn execute(&self, label: &str, instructions: &[Instruction]) -> Result<()> {
+        let mut attempt = 1;
+        loop {
+            let result = if self.config.dry_run {
+                self.simulate(instructions).await
+            } else {
+                let signers: [&dyn Signer; 1] = [&self.payer];
+                rpc::send(&self.rpc, instructions, &self.payer.pubkey(), &signers)
+                    .await
+                    .map(|signature| info!("{label}: {signature}"))
+                    .map_err(Into::into)
+            };
+            match result {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt < self.config.max_retries => {
+                    warn!("{label} failed (attempt {attempt}): {err:#}");
+                    attempt += 1;
+                    tokio::time::sleep(Duration::from_secs(self.config.retry_delay_secs)).await;
+                }
+                Err(err) => bail!("{label} failed after {attempt} attempts: {err:#}"),
+            }
+        }
+    }
+
+    async fn simulate(&self, instructions: &[Instruction]) -> Result<()> {
+        let transaction =
+            Transaction::new_unsigned(Message::new(instructions, Some(&self.payer.pubkey())));
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            ..RpcSimulateTransactionConfig::default()
+        };
+        let result = self
+            .rpc
+            .simulate_transaction_with_config(&transaction, config)
+            .await?
+            .value;
+        for log in result.logs.unwrap_or_default() {
+            debug!("{log}");
+        }
+        match result.err {
+            Some(err) => bail!("simulation failed: {err}"),
+            None => {
+                info!("Simulation succeeded");
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sunrise_client::sunrise::{BeamDetails, EpochReport};
+
+    fn state(beams: &[Pubkey], capacity: usize, epoch: u64) -> State {
+        let mut allocations = vec![BeamDetails::default(); capacity];
+        for (slot, beam) in allocations.iter_mut().zip(beams) {
+            *slot = BeamDetails::new(*beam, 0);
+        }
+        State {
+            allocations,
+            epoch_report: EpochReport::new(capacity, epoch),
+            ..State::default()
+        }
+    }
+
+    #[test]
+    fn ignores_empty_slots_when_checking_reports() {
+        let beams = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut state = state(&beams, 4, 10);
+        assert!(!all_beams_reported(&state, 11));
+
+        state.epoch_report.beam_epoch_details[0].epoch = 11;
+        assert!(!all_beams_reported(&state, 11));
+
+        state.epoch_report.beam_epoch_details[1].epoch = 11;
+        assert!(all_beams_reported(&state, 11));
+        assert!(!state.epoch_report.is_epoch_reported(11));
+    }
+
+    #[test]
+    fn finds_registered_beams_missing_from_the_config() {
+        let beams = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let state = state(&beams, 4, 10);
+
+        assert_eq!(unconfigured_beams(&state, &beams), vec![]);
+        assert_eq!(unconfigured_beams(&state, &beams[..1]), vec![beams[1]]);
+        assert_eq!(unconfigured_beams(&state, &[]), beams.to_vec());
+    }
+
+    #[test]
+    fn extractable_yield_requires_a_current_report() {
+        let beam = Pubkey::new_unique();
+        let mut state = state(&[beam], 2, 10);
+        state.epoch_report.beam_epoch_details[0].extractable_yield = 500;
+
+        assert_eq!(extractable_yield(&state, &beam, 10), Some(500));
+        assert_eq!(extractable_yield(&state, &beam, 11), None);
+        assert_eq!(extractable_yield(&state, &Pubkey::new_unique(), 10), None);
+    }
+
+    #[test]
+    fn extracts_only_when_worth_the_fee() {
+        assert!(!is_worth_extracting(5_000, 5_000, 0));
+        assert!(is_worth_extracting(5_001, 5_000, 0));
+        assert!(!is_worth_extracting(10_000, 5_000, 5_001));
+        assert!(is_worth_extracting(10_000, 5_000, 5_000));
+    }
+
+    #[test]
+    fn tickets_mature_after_their_epoch() {
+        assert!(!is_ticket_matured(10, 10));
+        assert!(is_ticket_matured(10, 11));
+    }
+}
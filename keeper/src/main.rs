@@ -0,0 +1,70 @@
+//! `sunrise-keeper`: cranks the Sunrise beams every epoch.
+//!
+//! On each new epoch the keeper calls `update_epoch_report` on each configured beam, waits until
+//! they have all reported, and calls `extract_yield` on each beam whose yield is worth the fee.
+//! The stake pools of SPL beams are refreshed first, as they cannot be priced until updated,
+//! and beams registered in the Sunrise state but missing from the config are cranked as well.
+//! It also redeems the matured Marinade tickets it is the beneficiary of.
+
+mod beam;
+mod config;
+mod keeper;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use config::Config;
+use keeper::Keeper;
+use log::info;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[clap(name = "sunrise-keeper", about = "Crank the Sunrise beams every epoch")]
+struct Cli {
+    /// The keeper's configuration file.
+    #[clap(long, short = 'c', default_value = "keeper.toml")]
+    config: PathBuf,
+
+    /// Simulate transactions instead of sending them, overriding the config.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Crank the current epoch and claim matured tickets once, then exit.
+    #[clap(long)]
+    once: bool,
+}
+
+fn expand_home(path: &str) -> Result<String> {
+    Ok(match path.strip_prefix("~/") {
+        Some(rest) => format!("{}/{rest}", std::env::var("HOME")?),
+        None => path.to_string(),
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let cli = Cli::parse();
+
+    let mut config = Config::load(&cli.config)?;
+    config.dry_run |= cli.dry_run;
+    let keypair_path = expand_home(&config.keypair)?;
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("failed to read keypair {keypair_path}: {e}"))?;
+
+    info!(
+        "Keeper {} cranking {} beams of {}{}",
+        payer.pubkey(),
+        config.beams.len(),
+        config.sunrise_state,
+        if config.dry_run { " (dry run)" } else { "" }
+    );
+
+    let keeper = Keeper::new(config, payer);
+    if cli.once {
+        let epoch = keeper.current_epoch().await?;
+        keeper.crank_epoch(epoch).await?;
+        return keeper.claim_tickets(epoch).await;
+    }
+    keeper.run().await
+}
@@ -0,0 +1,276 @@
+//! Checked integer math shared by the beams.
+//!
+//! All ratios are computed with a wider intermediate type and checked on the way back down,
+//! so that overflows surface as a [MathError] rather than a panic or a silently truncated value.
+
+use anchor_lang::prelude::*;
+
+/// The number of basis points in 100%.
+pub const MAX_BPS: u16 = 10_000;
+
+pub type MathResult<T> = std::result::Result<T, MathError>;
+
+/// A failed calculation. Each variant surfaces on chain as its own error code,
+/// offset from the beams' own errors and those of the beam interface.
+#[error_code(offset = 7100)]
+#[derive(Eq, PartialEq)]
+pub enum MathError {
+    /// The result does not fit in the target type.
+    #[msg("The result of a calculation overflowed")]
+    Overflow,
+    /// A checked subtraction went below zero.
+    #[msg("The result of a calculation underflowed")]
+    Underflow,
+    /// A ratio with a zero denominator was requested.
+    #[msg("A calculation divided by zero")]
+    DivisionByZero,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Rounding {
+    Up,
+    Down,
+}
+
+/// calculate amount*numerator/denominator in u128, failing on overflow or a zero denominator.
+pub fn mul_div_u128(
+    amount: u128,
+    numerator: u128,
+    denominator: u128,
+    rounding: Rounding,
+) -> MathResult<u128> {
+    if denominator == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let product = amount.checked_mul(numerator).ok_or(MathError::Overflow)?;
+    let quotient = product / denominator;
+    match rounding {
+        Rounding::Up if product % denominator != 0 => {
+            quotient.checked_add(1).ok_or(MathError::Overflow)
+        }
+        _ => Ok(quotient),
+    }
+}
+
+/// calculate amount*numerator/denominator in u64, failing on overflow or a zero denominator.
+pub fn mul_div(
+    amount: u64,
+    numerator: u64,
+    denominator: u64,
+    rounding: Rounding,
+) -> MathResult<u64> {
+    let result = mul_div_u128(
+        amount as u128,
+        numerator as u128,
+        denominator as u128,
+        rounding,
+    )?;
+    u64::try_from(result).map_err(|_| MathError::Overflow)
+}
+
+/// calculate amount*numerator/denominator
+/// as value  = shares * share_price where share_price=total_value/total_shares
+/// or shares = amount_value / share_price where share_price=total_value/total_shares
+///     => shares = amount_value * 1/share_price where 1/share_price=total_shares/total_value
+///
+/// An empty pool (zero denominator) is valued one-to-one, so `amount` is returned unchanged.
+pub fn proportional_with_rounding(
+    amount: u64,
+    numerator: u64,
+    denominator: u64,
+    rounding: Rounding,
+) -> MathResult<u64> {
+    if denominator == 0 {
+        return Ok(amount);
+    }
+    mul_div(amount, numerator, denominator, rounding)
+}
+
+/// Same as [proportional_with_rounding], rounding down.
+pub fn proportional(amount: u64, numerator: u64, denominator: u64) -> MathResult<u64> {
+    proportional_with_rounding(amount, numerator, denominator, Rounding::Down)
+}
+
+/// Same as [proportional_with_rounding], rounding up.
+pub fn proportional_round_up(amount: u64, numerator: u64, denominator: u64) -> MathResult<u64> {
+    proportional_with_rounding(amount, numerator, denominator, Rounding::Up)
+}
+
+/// Signed version of [proportional_with_rounding], for balances that may be negative.
+/// Rounding is applied towards negative (Down) or positive (Up) infinity.
+pub fn proportional_i128(
+    amount: i128,
+    numerator: i128,
+    denominator: i128,
+    rounding: Rounding,
+) -> MathResult<i128> {
+    if denominator == 0 {
+        return Ok(amount);
+    }
+    let product = amount.checked_mul(numerator).ok_or(MathError::Overflow)?;
+    let quotient = product
+        .checked_div(denominator)
+        .ok_or(MathError::Overflow)?;
+    // Division truncates towards zero, so an inexact quotient needs adjusting
+    // upwards if it is positive, or downwards if it is negative.
+    let inexact = product % denominator != 0;
+    let positive = (product < 0) == (denominator < 0);
+    Ok(match rounding {
+        Rounding::Up if inexact && positive => quotient + 1,
+        Rounding::Down if inexact && !positive => quotient - 1,
+        _ => quotient,
+    })
+}
+
+/// calculate amount*bps/10_000.
+pub fn apply_bps(amount: u64, bps: u16, rounding: Rounding) -> MathResult<u64> {
+    mul_div(amount, bps as u64, MAX_BPS as u64, rounding)
+}
+
+/// The fee of `bps` basis points charged on `amount`, rounded up in favour of the fee recipient.
+pub fn fee_bps(amount: u64, bps: u16) -> MathResult<u64> {
+    apply_bps(amount, bps, Rounding::Up)
+}
+
+/// The amount remaining after deducting a fee of `bps` basis points (see [fee_bps]).
+pub fn net_of_fee_bps(amount: u64, bps: u16) -> MathResult<u64> {
+    amount
+        .checked_sub(fee_bps(amount, bps)?)
+        .ok_or(MathError::Underflow)
+}
+
+/// The signed difference `value - liabilities`.
+/// Positive values are a surplus, negative values a deficit.
+pub fn surplus(value: u64, liabilities: u64) -> MathResult<i64> {
+    i64::try_from(value as i128 - liabilities as i128).map_err(|_| MathError::Overflow)
+}
+
+/// Deducts a fee of `bps` basis points from a positive surplus (see [net_of_fee_bps]).
+/// A deficit is returned unchanged, as no fee is charged on it.
+pub fn net_surplus_of_fee_bps(surplus: i64, bps: u16) -> MathResult<i64> {
+    if surplus <= 0 {
+        return Ok(surplus);
+    }
+    let net = net_of_fee_bps(surplus as u64, bps)?;
+    i64::try_from(net).map_err(|_| MathError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_math_errors_have_distinct_codes() {
+        assert_eq!(u32::from(MathError::Overflow), 7100);
+        assert_eq!(u32::from(MathError::Underflow), 7101);
+        assert_eq!(u32::from(MathError::DivisionByZero), 7102);
+    }
+
+    #[test]
+    fn test_proportional() {
+        assert_eq!(proportional(100, 1, 3), Ok(33));
+        assert_eq!(proportional_round_up(100, 1, 3), Ok(34));
+        assert_eq!(proportional(100, 1, 0), Ok(100));
+        assert_eq!(proportional(u64::MAX, 2, 1), Err(MathError::Overflow));
+        assert_eq!(
+            mul_div(100, 1, 0, Rounding::Down),
+            Err(MathError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_proportional_i128() {
+        assert_eq!(proportional_i128(-100, 1, 3, Rounding::Down), Ok(-34));
+        assert_eq!(proportional_i128(-100, 1, 3, Rounding::Up), Ok(-33));
+        assert_eq!(proportional_i128(100, 1, -3, Rounding::Down), Ok(-34));
+        assert_eq!(proportional_i128(100, -1, -3, Rounding::Up), Ok(34));
+    }
+
+    #[test]
+    fn test_bps() {
+        assert_eq!(fee_bps(1_000, 30), Ok(3));
+        assert_eq!(fee_bps(1_001, 30), Ok(4));
+        assert_eq!(net_of_fee_bps(1_000, 30), Ok(997));
+        assert_eq!(net_of_fee_bps(1_000, MAX_BPS), Ok(0));
+        assert_eq!(
+            net_of_fee_bps(1_000, MAX_BPS + 1),
+            Err(MathError::Underflow)
+        );
+    }
+
+    #[test]
+    fn test_surplus() {
+        assert_eq!(surplus(110, 100), Ok(10));
+        assert_eq!(surplus(90, 100), Ok(-10));
+        assert_eq!(surplus(u64::MAX, 0), Err(MathError::Overflow));
+        assert_eq!(net_surplus_of_fee_bps(1_000, 30), Ok(997));
+        assert_eq!(net_surplus_of_fee_bps(-1_000, 30), Ok(-1_000));
+    }
+
+    proptest! {
+        #[test]
+        fn mul_div_matches_exact_result(a: u64, n: u64, d in 1u64..) {
+            let exact = (a as u128) * (n as u128);
+            let down = mul_div(a, n, d, Rounding::Down);
+            let up = mul_div(a, n, d, Rounding::Up);
+            match u64::try_from(exact / d as u128) {
+                Ok(expected) => prop_assert_eq!(down, Ok(expected)),
+                Err(_) => prop_assert_eq!(down, Err(MathError::Overflow)),
+            }
+            if let (Ok(down), Ok(up)) = (down, up) {
+                let remainder = exact % d as u128;
+                prop_assert_eq!(up, down + u64::from(remainder != 0));
+            }
+        }
+
+        #[test]
+        fn rounding_brackets_the_exact_ratio(a: u32, n: u32, d in 1u32..) {
+            let down = mul_div(a as u64, n as u64, d as u64, Rounding::Down).unwrap() as u128;
+            let up = mul_div(a as u64, n as u64, d as u64, Rounding::Up).unwrap() as u128;
+            let exact = (a as u128) * (n as u128);
+            prop_assert!(down * (d as u128) <= exact);
+            prop_assert!(up * (d as u128) >= exact);
+            prop_assert!(up - down <= 1);
+        }
+
+        #[test]
+        fn round_trip_never_creates_value(amount: u32, supply in 1u32.., value in 1u32..) {
+            // Converting lamports to shares and back never returns more than was put in.
+            let shares = proportional(amount as u64, supply as u64, value as u64).unwrap();
+            let lamports = proportional(shares, value as u64, supply as u64).unwrap();
+            prop_assert!(lamports <= amount as u64);
+        }
+
+        #[test]
+        fn signed_matches_unsigned(a: u32, n: u32, d in 1u32..) {
+            for rounding in [Rounding::Down, Rounding::Up] {
+                let unsigned = mul_div(a as u64, n as u64, d as u64, rounding).unwrap();
+                let signed = proportional_i128(a as i128, n as i128, d as i128, rounding).unwrap();
+                prop_assert_eq!(signed, unsigned as i128);
+            }
+        }
+
+        #[test]
+        fn signed_rounding_is_directional(a: i64, n: i64, d in (i64::MIN..0).prop_union(1..i64::MAX)) {
+            let exact = (a as i128) * (n as i128);
+            let down = proportional_i128(a as i128, n as i128, d as i128, Rounding::Down).unwrap();
+            let up = proportional_i128(a as i128, n as i128, d as i128, Rounding::Up).unwrap();
+            let (down_product, up_product) = (down * d as i128, up * d as i128);
+            if d > 0 {
+                prop_assert!(down_product <= exact && up_product >= exact);
+            } else {
+                prop_assert!(down_product >= exact && up_product <= exact);
+            }
+            prop_assert!(up - down <= 1);
+        }
+
+        #[test]
+        fn fee_and_net_sum_to_amount(amount: u64, bps in 0..=MAX_BPS) {
+            let fee = fee_bps(amount, bps).unwrap();
+            let net = net_of_fee_bps(amount, bps).unwrap();
+            prop_assert_eq!(fee + net, amount);
+            prop_assert!(fee <= amount);
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! Initializing the beams under test, and driving them through the instructions they share.
+
+use crate::{BeamTestContext, MARINADE_STATE, SPL_STAKE_POOL};
+use anchor_lang::prelude::Pubkey;
+use solana_sdk::instruction::Instruction;
+use sunrise_client::marinade::MarinadeState;
+use sunrise_client::{
+    marinade_lp, marinade_sp, native_stake, reserve, spl, MarinadeClient, MarinadeLpClient,
+    NativeStakeClient, ReserveClient, SplClient,
+};
+
+/// The account that holds the stake of a beam.
+pub enum BeamVault {
+    /// A token account, holding the beam's LST or liquidity pool tokens.
+    Tokens(Pubkey),
+    /// A system account, holding the beam's SOL.
+    Lamports(Pubkey),
+}
+
+/// A beam client, as the shared beam tests drive it.
+pub trait TestBeam {
+    fn state_address(&self) -> Pubkey;
+    fn vault(&self) -> BeamVault;
+    fn deposit(&self, depositor: &Pubkey, mint_gsol_to: &Pubkey, lamports: u64) -> Instruction;
+    fn withdraw(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Instruction;
+    fn quote_deposit(&self, lamports: u64) -> Instruction;
+    fn quote_withdraw(&self, lamports: u64) -> Instruction;
+    fn update_epoch_report(&self) -> Instruction;
+}
+
+/// A beam whose surplus, such as the value left by burned gSOL, can be extracted as yield.
+pub trait YieldingBeam: TestBeam {
+    fn burn(&self, burner: &Pubkey, gsol_token_account: &Pubkey, lamports: u64) -> Instruction;
+    fn extract_yield(&self, payer: &Pubkey) -> Instruction;
+}
+
+macro_rules! impl_test_beam {
+    ($client:ty, |$beam:ident| $vault:expr) => {
+        impl TestBeam for $client {
+            fn state_address(&self) -> Pubkey {
+                self.state_address
+            }
+
+            fn vault(&self) -> BeamVault {
+                let $beam = self;
+                $vault
+            }
+
+            fn deposit(
+                &self,
+                depositor: &Pubkey,
+                mint_gsol_to: &Pubkey,
+                lamports: u64,
+            ) -> Instruction {
+                <$client>::deposit(self, depositor, mint_gsol_to, lamports)
+            }
+
+            fn withdraw(
+                &self,
+                withdrawer: &Pubkey,
+                gsol_token_account: &Pubkey,
+                lamports: u64,
+                min_lamports_out: u64,
+            ) -> Instruction {
+                <$client>::withdraw(
+                    self,
+                    withdrawer,
+                    gsol_token_account,
+                    lamports,
+                    min_lamports_out,
+                )
+            }
+
+            fn quote_deposit(&self, lamports: u64) -> Instruction {
+                <$client>::quote_deposit(self, lamports)
+            }
+
+            fn quote_withdraw(&self, lamports: u64) -> Instruction {
+                <$client>::quote_withdraw(self, lamports)
+            }
+
+            fn update_epoch_report(&self) -> Instruction {
+                <$client>::update_epoch_report(self)
+            }
+        }
+    };
+}
+
+macro_rules! impl_yielding_beam {
+    ($client:ty) => {
+        impl YieldingBeam for $client {
+            fn burn(
+                &self,
+                burner: &Pubkey,
+                gsol_token_account: &Pubkey,
+                lamports: u64,
+            ) -> Instruction {
+                <$client>::burn(self, burner, gsol_token_account, lamports)
+            }
+
+            fn extract_yield(&self, payer: &Pubkey) -> Instruction {
+                <$client>::extract_yield(self, payer)
+            }
+        }
+    };
+}
+
+impl_test_beam!(SplClient, |beam| BeamVault::Tokens(beam.pool_token_vault()));
+impl_test_beam!(MarinadeClient, |beam| BeamVault::Tokens(beam.msol_vault()));
+impl_test_beam!(MarinadeLpClient, |beam| BeamVault::Tokens(
+    beam.liq_pool_token_vault()
+));
+impl_test_beam!(NativeStakeClient, |beam| BeamVault::Lamports(
+    beam.vault_authority()
+));
+impl_test_beam!(ReserveClient, |beam| BeamVault::Lamports(
+    beam.vault_authority()
+));
+
+impl_yielding_beam!(SplClient);
+impl_yielding_beam!(MarinadeClient);
+impl_yielding_beam!(MarinadeLpClient);
+
+/// Initialize an SPL beam of the Sunrise state, for the SPL stake pool fixture.
+pub async fn initialize_spl_beam(context: &mut BeamTestContext) -> SplClient {
+    let sunrise_state = context.sunrise.state_address;
+    let state_address = spl::derive_state_address(&spl::ID, &sunrise_state, &SPL_STAKE_POOL).0;
+    let stake_pool: spl::StakePool = context.fetch(&SPL_STAKE_POOL).await;
+    let initialize = spl::initialize(
+        &spl::ID,
+        &context.payer(),
+        &stake_pool.pool_mint,
+        spl::StateEntry {
+            update_authority: context.payer(),
+            stake_pool: SPL_STAKE_POOL,
+            sunrise_state,
+            vault_authority_bump: spl::derive_vault_authority(&spl::ID, &state_address).1,
+        },
+    );
+    context.send(&[initialize], &[]).await.unwrap();
+
+    let state: spl::State = context.fetch(&state_address).await;
+    let validator_list = context.account(&stake_pool.validator_list).await.unwrap();
+    let validator_list =
+        spl::decode_validator_list(&stake_pool.validator_list, &validator_list.data).unwrap();
+    SplClient::new(
+        spl::ID,
+        state_address,
+        state,
+        stake_pool,
+        validator_list,
+        context.sunrise.clone(),
+    )
+}
+
+/// Initialize a Marinade beam of the Sunrise state, for the Marinade state fixture.
+pub async fn initialize_marinade_beam(context: &mut BeamTestContext) -> MarinadeClient {
+    let sunrise_state = context.sunrise.state_address;
+    let state_address = marinade_sp::derive_state_address(&marinade_sp::ID, &sunrise_state).0;
+    let marinade: MarinadeState = context.fetch(&MARINADE_STATE).await;
+    let initialize = marinade_sp::initialize(
+        &marinade_sp::ID,
+        &context.payer(),
+        &marinade.msol_mint,
+        marinade_sp::StateEntry {
+            update_authority: context.payer(),
+            marinade_state: MARINADE_STATE,
+            sunrise_state,
+            vault_authority_bump: marinade_sp::derive_vault_authority(
+                &marinade_sp::ID,
+                &state_address,
+            )
+            .1,
+        },
+    );
+    context.send(&[initialize], &[]).await.unwrap();
+
+    let state: marinade_sp::State = context.fetch(&state_address).await;
+    MarinadeClient::new(
+        marinade_sp::ID,
+        state_address,
+        state,
+        marinade,
+        context.sunrise.clone(),
+    )
+}
+
+/// Initialize a Marinade liquidity pool beam of the Sunrise state, for the Marinade state
+/// fixture, that sends the mSOL leg of its withdrawals to `msol_beam`.
+pub async fn initialize_marinade_lp_beam(
+    context: &mut BeamTestContext,
+    msol_beam: &MarinadeClient,
+    target_liquidity_bps: u16,
+) -> MarinadeLpClient {
+    let sunrise_state = context.sunrise.state_address;
+    let state_address = marinade_lp::derive_state_address(&marinade_lp::ID, &sunrise_state).0;
+    let marinade: MarinadeState = context.fetch(&MARINADE_STATE).await;
+    let initialize = marinade_lp::initialize(
+        &marinade_lp::ID,
+        &context.payer(),
+        &marinade.liq_pool.lp_mint,
+        marinade_lp::StateEntry {
+            update_authority: context.payer(),
+            marinade_state: MARINADE_STATE,
+            sunrise_state,
+            vault_authority_bump: marinade_lp::derive_vault_authority(
+                &marinade_lp::ID,
+                &state_address,
+            )
+            .1,
+            msol_recipient_beam: msol_beam.state_address,
+            msol_token_account: msol_beam.msol_vault(),
+            target_liquidity_bps,
+        },
+    );
+    context.send(&[initialize], &[]).await.unwrap();
+
+    let state: marinade_lp::State = context.fetch(&state_address).await;
+    MarinadeLpClient::new(
+        marinade_lp::ID,
+        state_address,
+        state,
+        marinade,
+        context.sunrise.clone(),
+    )
+}
+
+/// Initialize a native stake beam of the Sunrise state.
+pub async fn initialize_native_stake_beam(context: &mut BeamTestContext) -> NativeStakeClient {
+    let sunrise_state = context.sunrise.state_address;
+    let state_address = native_stake::derive_state_address(&native_stake::ID, &sunrise_state).0;
+    let initialize = native_stake::initialize(
+        &native_stake::ID,
+        &context.payer(),
+        native_stake::StateEntry {
+            update_authority: context.payer(),
+            sunrise_state,
+            vault_authority_bump: native_stake::derive_vault_authority(
+                &native_stake::ID,
+                &state_address,
+            )
+            .1,
+        },
+    );
+    context.send(&[initialize], &[]).await.unwrap();
+
+    let state: native_stake::State = context.fetch(&state_address).await;
+    NativeStakeClient::new(
+        native_stake::ID,
+        state_address,
+        state,
+        context.sunrise.clone(),
+    )
+}
+
+/// Initialize a reserve beam of the Sunrise state.
+pub async fn initialize_reserve_beam(context: &mut BeamTestContext) -> ReserveClient {
+    let sunrise_state = context.sunrise.state_address;
+    let state_address = reserve::derive_state_address(&reserve::ID, &sunrise_state).0;
+    let initialize = reserve::initialize(
+        &reserve::ID,
+        &context.payer(),
+        reserve::StateEntry {
+            update_authority: context.payer(),
+            sunrise_state,
+            vault_authority_bump: reserve::derive_vault_authority(&reserve::ID, &state_address).1,
+        },
+    );
+    context.send(&[initialize], &[]).await.unwrap();
+
+    let state: reserve::State = context.fetch(&state_address).await;
+    ReserveClient::new(reserve::ID, state_address, state, context.sunrise.clone())
+}
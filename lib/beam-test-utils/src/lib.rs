@@ -0,0 +1,559 @@
+//! Shared `solana-program-test` harness for the beam integration tests.
+//!
+//! Sunrise core and the beam under test run natively, while the Marinade and SPL stake pool
+//! programs are loaded from the `.so` files in `packages/tests/fixtures`, together with the
+//! account fixtures that the TypeScript tests run against.
+//!
+//! [beams] initializes each beam and drives them through the instructions they share, and
+//! [suite] holds the tests that every beam suite runs against its beam.
+
+pub mod beams;
+pub mod suite;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, AnchorSerialize};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{spl_token, Mint, TokenAccount};
+use base64::Engine;
+use serde::Deserialize;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::bpf_loader;
+use solana_sdk::clock::{Clock, Epoch};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::stake::{
+    self,
+    stake_flags::StakeFlags,
+    state::{Authorized, Delegation, Lockup, Meta, Stake, StakeStateV2},
+};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_sdk::vote::{
+    self,
+    state::{VoteInit, VoteState, VoteStateVersions},
+};
+use spl_stake_pool::state::ValidatorList;
+use std::path::{Path, PathBuf};
+use sunrise_client::sunrise::{self, AllocationUpdate, RegisterStateInput, UpdateStateInput};
+use sunrise_client::{marinade, SunriseClient};
+
+/// The account fixtures of the Marinade program, as in `Anchor.toml`.
+const MARINADE_FIXTURES: &[&str] = &[
+    "marinade/state.json",
+    "marinade/msol_mint.json",
+    "marinade/msol_leg.json",
+    "marinade/treasury_msol_account.json",
+    "marinade/sol_leg_pda.json",
+    "marinade/lp_mint.json",
+    "marinade/reserve_pda.json",
+    "marinade/stake_list.json",
+    "marinade/validator_list.json",
+];
+
+/// The validator list fixture of the SPL stake pool.
+const SPL_VALIDATOR_LIST_FIXTURE: &str = "spl/validator_list.json";
+
+/// The other account fixtures of the SPL stake pool (a devnet pool), as in `Anchor.toml`.
+const SPL_STAKE_POOL_FIXTURES: &[&str] = &[
+    "spl/pool.json",
+    "spl/b_sol_mint_address.json",
+    "spl/reserve_account.json",
+    "spl/fees_depot.json",
+];
+
+/// The address of the SPL stake pool fixture.
+pub const SPL_STAKE_POOL: Pubkey =
+    anchor_lang::solana_program::pubkey!("azFVdHtAJN8BX3sbGAYkXvtdjdrT5U6rj9rovvUFos9");
+
+/// The epoch that the SPL stake pool fixture was last updated in.
+/// The SPL beam only prices the pool in the epoch it was updated in.
+pub const SPL_STAKE_POOL_EPOCH: u64 = 442;
+
+/// The vote account of a validator in the SPL stake pool fixture's validator list.
+pub const SPL_STAKE_POOL_VALIDATOR: Pubkey =
+    anchor_lang::solana_program::pubkey!("23AoPQc3EPkfLWb14cKiWNahh1H9rtb3UBk8gWseohjF");
+
+/// The Marinade state fixture.
+pub const MARINADE_STATE: Pubkey =
+    anchor_lang::solana_program::pubkey!("8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC");
+
+#[derive(Deserialize)]
+struct AccountFixture {
+    pubkey: String,
+    account: AccountFixtureData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountFixtureData {
+    lamports: u64,
+    data: (String, String),
+    owner: String,
+    executable: bool,
+    rent_epoch: u64,
+}
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../packages/tests/fixtures")
+}
+
+/// Add the account in the `solana account --output json` fixture at `path`,
+/// relative to `packages/tests/fixtures`.
+pub fn add_account_fixture(program_test: &mut ProgramTest, path: &str) {
+    let (address, account) = read_account_fixture(path);
+    program_test.add_account(address, account);
+}
+
+/// Read the address and account of the fixture at `path`, relative to `packages/tests/fixtures`.
+pub fn read_account_fixture(path: &str) -> (Pubkey, Account) {
+    let path = fixtures_dir().join(path);
+    let contents = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read fixture {}: {e}", path.display()));
+    let fixture: AccountFixture = serde_json::from_str(&contents).unwrap();
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(fixture.account.data.0)
+        .unwrap();
+    (
+        fixture.pubkey.parse().unwrap(),
+        Account {
+            lamports: fixture.account.lamports,
+            data,
+            owner: fixture.account.owner.parse().unwrap(),
+            executable: fixture.account.executable,
+            rent_epoch: fixture.account.rent_epoch,
+        },
+    )
+}
+
+/// Deploy the program in the fixture at `path`, relative to `packages/tests/fixtures`.
+pub fn add_program_fixture(program_test: &mut ProgramTest, program_id: Pubkey, path: &str) {
+    let path = fixtures_dir().join(path);
+    let data = std::fs::read(&path)
+        .unwrap_or_else(|e| panic!("failed to read program {}: {e}", path.display()));
+    program_test.add_account(
+        program_id,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: bpf_loader::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+}
+
+#[doc(hidden)]
+pub use solana_program_test;
+
+/// The program test of the beam crate `$beam`, whose program ID is `$id`, running natively
+/// with Sunrise core as [with_sunrise].
+#[macro_export]
+macro_rules! beam_program_test {
+    ($beam:ident, $id:expr) => {
+        $crate::with_sunrise($crate::solana_program_test::ProgramTest::new(
+            stringify!($beam),
+            $id,
+            $crate::solana_program_test::processor!($beam::entry),
+        ))
+    };
+}
+
+/// Add Sunrise core to the program test of a beam, running natively like the beam.
+pub fn with_sunrise(mut program_test: ProgramTest) -> ProgramTest {
+    program_test.add_program("sunrise_core", sunrise::ID, processor!(sunrise_core::entry));
+    program_test.prefer_bpf(false);
+    program_test
+}
+
+/// Add the Marinade program and its accounts.
+pub fn add_marinade(program_test: &mut ProgramTest) {
+    add_program_fixture(program_test, marinade::ID, "marinade/marinade_finance.so");
+    for fixture in MARINADE_FIXTURES {
+        add_account_fixture(program_test, fixture);
+    }
+}
+
+/// Add the SPL stake pool program and the accounts of its devnet pool.
+///
+/// The fixture does not include the pool's validator stake accounts, and its validators hold
+/// too little stake to withdraw from, so stake can only be withdrawn from the pool's reserve.
+pub fn add_spl_stake_pool(program_test: &mut ProgramTest) {
+    add_program_fixture(program_test, spl_stake_pool::id(), "spl/spl_stake_pool.so");
+    for fixture in SPL_STAKE_POOL_FIXTURES {
+        add_account_fixture(program_test, fixture);
+    }
+    add_account_fixture(program_test, SPL_VALIDATOR_LIST_FIXTURE);
+}
+
+/// Add the SPL stake pool as [add_spl_stake_pool], with a stake account of `lamports`
+/// delegated to [SPL_STAKE_POOL_VALIDATOR], so that stake can be withdrawn from the validator.
+///
+/// As the validator has active stake, the pool no longer allows withdrawals from its reserve.
+/// The stake is active from genesis, and is not counted in the pool's total lamports.
+pub fn add_spl_stake_pool_with_validator_stake(program_test: &mut ProgramTest, lamports: u64) {
+    add_program_fixture(program_test, spl_stake_pool::id(), "spl/spl_stake_pool.so");
+    for fixture in SPL_STAKE_POOL_FIXTURES {
+        add_account_fixture(program_test, fixture);
+    }
+
+    let withdraw_authority = spl_stake_pool::find_withdraw_authority_program_address(
+        &spl_stake_pool::id(),
+        &SPL_STAKE_POOL,
+    )
+    .0;
+    let validator_stake_account = spl_stake_pool::find_stake_program_address(
+        &spl_stake_pool::id(),
+        &SPL_STAKE_POOL_VALIDATOR,
+        &SPL_STAKE_POOL,
+        None,
+    )
+    .0;
+    let rent_exempt_reserve = Rent::default().minimum_balance(StakeStateV2::size_of());
+    let stake_state = StakeStateV2::Stake(
+        Meta {
+            rent_exempt_reserve,
+            authorized: Authorized::auto(&withdraw_authority),
+            lockup: Lockup::default(),
+        },
+        Stake {
+            // An activation epoch of Epoch::MAX marks bootstrap stake, which is always active.
+            delegation: Delegation::new(
+                &SPL_STAKE_POOL_VALIDATOR,
+                lamports - rent_exempt_reserve,
+                Epoch::MAX,
+            ),
+            credits_observed: 0,
+        },
+        StakeFlags::empty(),
+    );
+    program_test.add_account(
+        validator_stake_account,
+        Account::new_data_with_space(
+            lamports,
+            &stake_state,
+            StakeStateV2::size_of(),
+            &stake::program::ID,
+        )
+        .unwrap(),
+    );
+
+    // Record the stake in the validator's entry of the list, which the pool withdraws against.
+    let (address, mut validator_list) = read_account_fixture(SPL_VALIDATOR_LIST_FIXTURE);
+    let mut list = ValidatorList::deserialize(&mut &validator_list.data[..]).unwrap();
+    let validator = list
+        .validators
+        .iter_mut()
+        .find(|validator| validator.vote_account_address == SPL_STAKE_POOL_VALIDATOR)
+        .unwrap();
+    validator.active_stake_lamports = lamports.into();
+    list.serialize(&mut &mut validator_list.data[..]).unwrap();
+    program_test.add_account(address, validator_list);
+}
+
+/// Add a vote account of a new validator, that stake can be delegated to, and return its address.
+///
+/// The validator does not vote, so stake delegated to it earns no rewards.
+pub fn add_vote_account(program_test: &mut ProgramTest) -> Pubkey {
+    let vote_account = Pubkey::new_unique();
+    let node = Pubkey::new_unique();
+    let vote_state = VoteStateVersions::new_current(VoteState::new(
+        &VoteInit {
+            node_pubkey: node,
+            authorized_voter: node,
+            authorized_withdrawer: node,
+            commission: 0,
+        },
+        &Clock::default(),
+    ));
+    program_test.add_account(
+        vote_account,
+        Account::new_data_with_space(
+            Rent::default().minimum_balance(VoteState::size_of()),
+            &vote_state,
+            VoteState::size_of(),
+            &vote::program::ID,
+        )
+        .unwrap(),
+    );
+    vote_account
+}
+
+/// The custom error code of a failed transaction.
+pub fn error_code(error: BanksClientError) -> Option<u32> {
+    match error {
+        BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => Some(code),
+        _ => None,
+    }
+}
+
+/// A running program test with a Sunrise state, whose update authority is the payer.
+pub struct BeamTestContext {
+    pub ctx: ProgramTestContext,
+    pub sunrise: SunriseClient,
+}
+
+impl BeamTestContext {
+    /// Start `program_test` and register a Sunrise state with a funded yield account
+    /// and treasury, and a backing oracle.
+    pub async fn start(program_test: ProgramTest) -> Self {
+        let mut context = Self::start_without_treasury(program_test).await;
+        let treasury = Pubkey::new_unique();
+        let update_state = context.sunrise.update_state(UpdateStateInput {
+            new_treasury: Some(treasury),
+            ..UpdateStateInput::default()
+        });
+        context.send(&[update_state], &[]).await.unwrap();
+
+        // The protocol fee is paid into it with transfers, which can't leave it below rent exemption.
+        let rent = context.ctx.banks_client.get_rent().await.unwrap();
+        context.fund(&treasury, rent.minimum_balance(0)).await;
+
+        context.refresh_sunrise().await;
+        context
+    }
+
+    /// Start `program_test` and register a Sunrise state with a funded yield account
+    /// and a backing oracle, but no treasury, as states are registered.
+    pub async fn start_without_treasury(program_test: ProgramTest) -> Self {
+        let mut ctx = program_test.start_with_context().await;
+        let payer = ctx.payer.pubkey();
+        let state = Keypair::new();
+        let gsol_mint = Keypair::new();
+        let rent = ctx.banks_client.get_rent().await.unwrap();
+
+        let gsol_mint_authority =
+            sunrise::derive_gsol_mint_authority(&sunrise::ID, &state.pubkey()).0;
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &gsol_mint.pubkey(),
+                rent.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::ID,
+                &gsol_mint.pubkey(),
+                &gsol_mint_authority,
+                None,
+                9,
+            )
+            .unwrap(),
+            sunrise::register_state(
+                &sunrise::ID,
+                &payer,
+                &state.pubkey(),
+                &gsol_mint.pubkey(),
+                RegisterStateInput {
+                    update_authority: payer,
+                    yield_account: Pubkey::new_unique(),
+                    initial_capacity: 10,
+                },
+            ),
+        ];
+        send(&mut ctx, &instructions, &[&gsol_mint, &state])
+            .await
+            .unwrap();
+
+        let mut context = Self {
+            sunrise: fetch_sunrise(&mut ctx, &state.pubkey()).await,
+            ctx,
+        };
+
+        // Yield is paid into it with transfers, which can't leave it below rent exemption.
+        let yield_account = context.sunrise.state.yield_account;
+        context.fund(&yield_account, rent.minimum_balance(0)).await;
+
+        context.refresh_sunrise().await;
+        context
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.ctx.payer.pubkey()
+    }
+
+    /// Send `instructions` paid for by the payer and signed by `signers`.
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        send(&mut self.ctx, instructions, signers).await
+    }
+
+    /// Reload the Sunrise state.
+    pub async fn refresh_sunrise(&mut self) {
+        self.sunrise = fetch_sunrise(&mut self.ctx, &self.sunrise.state_address).await;
+    }
+
+    /// Register `beam` with the Sunrise state, with the whole allocation.
+    pub async fn register_beam(&mut self, beam: &Pubkey) {
+        self.register_beams(&[(*beam, 100)]).await;
+    }
+
+    /// Register each of `beams` with the Sunrise state, with their allocations.
+    pub async fn register_beams(&mut self, beams: &[(Pubkey, u8)]) {
+        let mut instructions: Vec<_> = beams
+            .iter()
+            .map(|(beam, _)| self.sunrise.register_beam(beam))
+            .collect();
+        instructions.push(
+            self.sunrise.update_allocations(
+                beams
+                    .iter()
+                    .map(|&(beam, new_allocation)| AllocationUpdate {
+                        beam,
+                        new_allocation,
+                    })
+                    .collect(),
+            ),
+        );
+        self.send(&instructions, &[]).await.unwrap();
+        self.refresh_sunrise().await;
+    }
+
+    /// Simulate the view `instruction` and decode its return data.
+    pub async fn view<T: AnchorDeserialize>(&mut self, instruction: Instruction) -> T {
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.payer()),
+            &[&self.ctx.payer],
+            blockhash,
+        );
+        let simulation = self
+            .ctx
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        if let Some(Err(e)) = simulation.result {
+            panic!("view failed: {e}");
+        }
+        let return_data = simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .expect("view returned no data");
+        T::try_from_slice(&return_data.data).unwrap()
+    }
+
+    pub async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.ctx.banks_client.get_account(*address).await.unwrap()
+    }
+
+    pub async fn fetch<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self
+            .account(address)
+            .await
+            .unwrap_or_else(|| panic!("account {address} not found"));
+        T::try_deserialize(&mut account.data.as_ref()).unwrap()
+    }
+
+    pub async fn balance(&mut self, address: &Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(*address).await.unwrap()
+    }
+
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        self.fetch::<TokenAccount>(address).await.amount
+    }
+
+    /// The gSOL balance of the associated token account of `owner`.
+    pub async fn gsol_balance(&mut self, owner: &Pubkey) -> u64 {
+        let gsol_account = get_associated_token_address(owner, &self.sunrise.state.gsol_mint);
+        self.token_balance(&gsol_account).await
+    }
+
+    pub async fn rent(&mut self) -> Rent {
+        self.ctx.banks_client.get_rent().await.unwrap()
+    }
+
+    pub async fn epoch(&mut self) -> u64 {
+        self.ctx
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .epoch
+    }
+
+    /// Transfer `lamports` from the payer to `to`.
+    pub async fn fund(&mut self, to: &Pubkey, lamports: u64) {
+        let transfer = system_instruction::transfer(&self.payer(), to, lamports);
+        self.send(&[transfer], &[]).await.unwrap();
+    }
+
+    /// A new funded account.
+    pub async fn create_user(&mut self, lamports: u64) -> Keypair {
+        let user = Keypair::new();
+        self.fund(&user.pubkey(), lamports).await;
+        user
+    }
+
+    /// A new staker funded with 100 SOL, and the address of their gSOL token account.
+    pub async fn create_staker(&mut self) -> (Keypair, Pubkey) {
+        let staker = self.create_user(100 * LAMPORTS_PER_SOL).await;
+        let gsol_mint = self.sunrise.state.gsol_mint;
+        let gsol_account = self
+            .create_token_account(&staker.pubkey(), &gsol_mint)
+            .await;
+        (staker, gsol_account)
+    }
+
+    /// Create the associated token account of `owner` for `mint`, and return its address.
+    pub async fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let create =
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &self.payer(),
+                owner,
+                mint,
+                &spl_token::ID,
+            );
+        self.send(&[create], &[]).await.unwrap();
+        get_associated_token_address(owner, mint)
+    }
+
+    /// Move to the first slot of `epoch`.
+    pub async fn warp_to_epoch(&mut self, epoch: u64) {
+        let slot = self
+            .ctx
+            .genesis_config()
+            .epoch_schedule
+            .get_first_slot_in_epoch(epoch);
+        self.ctx.warp_to_slot(slot).unwrap();
+    }
+}
+
+async fn send(
+    ctx: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    // A new blockhash for each transaction, so that repeating a transaction is not a duplicate.
+    let blockhash = ctx.get_new_latest_blockhash().await?;
+    let mut all_signers = vec![&ctx.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&ctx.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    ctx.banks_client.process_transaction(transaction).await
+}
+
+async fn fetch_sunrise(ctx: &mut ProgramTestContext, state_address: &Pubkey) -> SunriseClient {
+    let account = ctx
+        .banks_client
+        .get_account(*state_address)
+        .await
+        .unwrap()
+        .expect("sunrise state not found");
+    let state = sunrise::State::try_deserialize(&mut account.data.as_ref()).unwrap();
+    SunriseClient::new(sunrise::ID, *state_address, state)
+}
@@ -0,0 +1,178 @@
+//! The tests that each beam suite runs against its beam.
+//!
+//! Each test starts from a `context` with the Sunrise state and the initialized `beam`,
+//! and a `staker` funded with at least `deposit` lamports, holding `gsol_account`.
+//! Apart from [cannot_deposit_before_registration], the beam must already be registered.
+
+use crate::beams::{BeamVault, TestBeam, YieldingBeam};
+use crate::{error_code, BeamTestContext};
+use anchor_lang::prelude::Pubkey;
+use solana_program_test::BanksClientError;
+use solana_sdk::signature::{Keypair, Signer};
+use sunrise_client::{DepositQuote, WithdrawQuote};
+use sunrise_core::BeamError;
+
+/// The balance of the vault of `beam`, in beam tokens or lamports.
+async fn beam_vault_balance(context: &mut BeamTestContext, beam: &impl TestBeam) -> u64 {
+    match beam.vault() {
+        BeamVault::Tokens(vault) => context.token_balance(&vault).await,
+        BeamVault::Lamports(vault) => context.balance(&vault).await,
+    }
+}
+
+/// The index of the allocation, and of the epoch report details, of `beam`.
+fn allocation_index(context: &BeamTestContext, beam: &impl TestBeam) -> usize {
+    context
+        .sunrise
+        .state
+        .allocations
+        .iter()
+        .position(|allocation| allocation.key == beam.state_address())
+        .expect("beam not registered")
+}
+
+/// Deposit `lamports` for `staker`, checking the balances against the beam's quote.
+/// Returns the gSOL minted.
+pub async fn deposit(
+    context: &mut BeamTestContext,
+    beam: &impl TestBeam,
+    staker: &Keypair,
+    gsol_account: &Pubkey,
+    lamports: u64,
+) -> u64 {
+    let quote: DepositQuote = context.view(beam.quote_deposit(lamports)).await;
+    let staker_balance = context.balance(&staker.pubkey()).await;
+    let vault_balance = beam_vault_balance(context, beam).await;
+    let gsol_balance = context.token_balance(gsol_account).await;
+
+    let deposit = beam.deposit(&staker.pubkey(), gsol_account, lamports);
+    context.send(&[deposit], &[staker]).await.unwrap();
+
+    let vault_increase = match beam.vault() {
+        BeamVault::Tokens(_) => quote.beam_tokens_out,
+        BeamVault::Lamports(_) => lamports,
+    };
+    assert_eq!(
+        context.balance(&staker.pubkey()).await,
+        staker_balance - lamports
+    );
+    assert_eq!(
+        beam_vault_balance(context, beam).await,
+        vault_balance + vault_increase
+    );
+    assert_eq!(
+        context.token_balance(gsol_account).await,
+        gsol_balance + quote.gsol_out
+    );
+    quote.gsol_out
+}
+
+pub async fn cannot_deposit_before_registration(
+    context: &mut BeamTestContext,
+    beam: &impl TestBeam,
+    staker: &Keypair,
+    gsol_account: &Pubkey,
+    lamports: u64,
+) {
+    let deposit = beam.deposit(&staker.pubkey(), gsol_account, lamports);
+    let error = context.send(&[deposit], &[staker]).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(BeamError::UnidentifiedBeam))
+    );
+}
+
+/// Deposit `deposit` and withdraw `withdrawal`, checking the balances against the beam's quotes.
+pub async fn can_deposit_and_withdraw(
+    context: &mut BeamTestContext,
+    beam: &impl TestBeam,
+    staker: &Keypair,
+    gsol_account: &Pubkey,
+    deposit: u64,
+    withdrawal: u64,
+) {
+    let gsol = self::deposit(context, beam, staker, gsol_account, deposit).await;
+
+    let quote: WithdrawQuote = context.view(beam.quote_withdraw(withdrawal)).await;
+    let staker_balance = context.balance(&staker.pubkey()).await;
+    let vault_balance = beam_vault_balance(context, beam).await;
+
+    let withdraw = beam.withdraw(&staker.pubkey(), gsol_account, withdrawal, 0);
+    context.send(&[withdraw], &[staker]).await.unwrap();
+
+    let vault_decrease = match beam.vault() {
+        BeamVault::Tokens(_) => quote.beam_tokens_in,
+        BeamVault::Lamports(_) => quote.lamports_out,
+    };
+    assert_eq!(
+        context.balance(&staker.pubkey()).await,
+        staker_balance + quote.lamports_out
+    );
+    assert_eq!(
+        beam_vault_balance(context, beam).await,
+        vault_balance - vault_decrease
+    );
+    assert_eq!(context.token_balance(gsol_account).await, gsol - withdrawal);
+}
+
+/// Deposit `deposit`, and fail to withdraw `withdrawal` for a lamport more than quoted.
+/// Returns the error, for the suite to check against the beam's own error.
+pub async fn cannot_withdraw_below_the_minimum_out(
+    context: &mut BeamTestContext,
+    beam: &impl TestBeam,
+    staker: &Keypair,
+    gsol_account: &Pubkey,
+    deposit: u64,
+    withdrawal: u64,
+) -> BanksClientError {
+    let gsol = self::deposit(context, beam, staker, gsol_account, deposit).await;
+
+    let quote: WithdrawQuote = context.view(beam.quote_withdraw(withdrawal)).await;
+    let withdraw = beam.withdraw(
+        &staker.pubkey(),
+        gsol_account,
+        withdrawal,
+        quote.lamports_out + 1,
+    );
+    let error = context.send(&[withdraw], &[staker]).await.unwrap_err();
+
+    assert_eq!(context.token_balance(gsol_account).await, gsol);
+    error
+}
+
+/// In a `context` started without a treasury, deposit `deposit`, burn `burn` and extract
+/// the surplus that the burn leaves, all of which is paid into the yield account.
+pub async fn can_extract_yield_without_a_treasury(
+    context: &mut BeamTestContext,
+    beam: &impl YieldingBeam,
+    staker: &Keypair,
+    gsol_account: &Pubkey,
+    deposit: u64,
+    burn: u64,
+) {
+    // A registered state has no treasury until one is set, and so charges no protocol fee.
+    assert_eq!(context.sunrise.state.treasury, Pubkey::default());
+    assert_eq!(context.sunrise.treasury(), None);
+    self::deposit(context, beam, staker, gsol_account, deposit).await;
+    let burn = beam.burn(&staker.pubkey(), gsol_account, burn);
+    context.send(&[burn], &[staker]).await.unwrap();
+
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    let yield_account = context.sunrise.state.yield_account;
+    let yield_balance = context.balance(&yield_account).await;
+    let extract_yield = beam.extract_yield(&context.payer());
+    context.send(&[extract_yield], &[]).await.unwrap();
+
+    context.refresh_sunrise().await;
+    let index = allocation_index(context, beam);
+    let extracted_yield =
+        context.sunrise.state.epoch_report.beam_epoch_details[index].extracted_yield;
+    assert!(extracted_yield > 0);
+    assert_eq!(
+        context.balance(&yield_account).await,
+        yield_balance + extracted_yield
+    );
+    assert_eq!(context.sunrise.state.allocations[index].protocol_fee, 0);
+}
@@ -1,68 +1,32 @@
+pub mod liquidity_beam;
 pub mod vault_authority_seed;
 
+use beam_math::{proportional, proportional_with_rounding, MathError, MathResult, Rounding};
 use marinade_cpi::state::State as MarinadeState;
-use num_traits::{NumCast, PrimInt};
-use std::fmt::Debug;
-use std::ops::{Div, Mul};
-
-/// calculate amount*numerator/denominator
-/// as value  = shares * share_price where share_price=total_value/total_shares
-/// or shares = amount_value / share_price where share_price=total_value/total_shares
-///     => shares = amount_value * 1/share_price where 1/share_price=total_shares/total_value
-pub fn proportional<T>(amount: T, numerator: T, denominator: T) -> T
-where
-    T: PrimInt + Mul<Output = T> + Div<Output = T> + TryInto<i128>,
-    <T as TryInto<i128>>::Error: Debug,
-{
-    proportional_with_rounding(amount, numerator, denominator, RoundingMode::Down)
-}
-
-pub enum RoundingMode {
-    Up,
-    Down,
-}
-
-pub fn proportional_with_rounding<T>(
-    amount: T,
-    numerator: T,
-    denominator: T,
-    rounding_mode: RoundingMode,
-) -> T
-where
-    T: PrimInt + Mul<Output = T> + Div<Output = T> + TryInto<i128>,
-    <T as TryInto<i128>>::Error: Debug,
-{
-    if denominator == T::zero() {
-        return amount;
-    }
-
-    let amount_i128: i128 = amount.try_into().unwrap();
-    let numerator_i128: i128 = numerator.try_into().unwrap();
-    let denominator_i128: i128 = denominator.try_into().unwrap();
-
-    match rounding_mode {
-        RoundingMode::Up => {
-            // Round up by adding (denominator - 1) before dividing
-            let result = (amount_i128 * numerator_i128 + (denominator_i128 - 1)) / denominator_i128;
-            <T as NumCast>::from(result).unwrap()
-        }
-        RoundingMode::Down => {
-            // Default behavior (round down)
-            let result = amount_i128 * numerator_i128 / denominator_i128;
-            <T as NumCast>::from(result).unwrap()
-        }
-    }
-}
 
 // All lifted from https://github.com/marinade-finance/liquid-staking-program/blob/447f9607a8c755cac7ad63223febf047142c6c8f/programs/marinade-finance/src/state.rs#L227
-pub fn calc_msol_from_lamports(marinade_state: &MarinadeState, stake_lamports: u64) -> u64 {
-    proportional(
+pub fn calc_msol_from_lamports(
+    marinade_state: &MarinadeState,
+    stake_lamports: u64,
+) -> MathResult<u64> {
+    calc_msol_from_lamports_with_rounding(marinade_state, stake_lamports, Rounding::Down)
+}
+pub fn calc_msol_from_lamports_with_rounding(
+    marinade_state: &MarinadeState,
+    stake_lamports: u64,
+    rounding: Rounding,
+) -> MathResult<u64> {
+    proportional_with_rounding(
         stake_lamports,
         marinade_state.msol_supply,
         total_virtual_staked_lamports(marinade_state),
+        rounding,
     )
 }
-pub fn calc_lamports_from_msol_amount(marinade_state: &MarinadeState, msol_amount: u64) -> u64 {
+pub fn calc_lamports_from_msol_amount(
+    marinade_state: &MarinadeState,
+    msol_amount: u64,
+) -> MathResult<u64> {
     proportional(
         msol_amount,
         total_virtual_staked_lamports(marinade_state),
@@ -70,6 +34,43 @@ pub fn calc_lamports_from_msol_amount(marinade_state: &MarinadeState, msol_amoun
     )
 }
 
+/// The lamports received for liquid-unstaking `msol_amount`, net of the liquidity pool's fee,
+/// where `liq_pool_lamports` is the spendable SOL in the pool's SOL leg.
+/// The fee falls linearly from the maximum to the minimum fee with the liquidity left after the unstake,
+/// reaching the minimum at the pool's liquidity target.
+pub fn calc_liquid_unstake_lamports(
+    marinade_state: &MarinadeState,
+    liq_pool_lamports: u64,
+    msol_amount: u64,
+) -> MathResult<u64> {
+    let liq_pool = &marinade_state.liq_pool;
+    let user_remove_lamports = calc_lamports_from_msol_amount(marinade_state, msol_amount)?;
+    let fee_bps = if user_remove_lamports >= liq_pool_lamports {
+        liq_pool.lp_max_fee.basis_points
+    } else {
+        let lamports_after = liq_pool_lamports - user_remove_lamports;
+        if lamports_after >= liq_pool.lp_liquidity_target {
+            liq_pool.lp_min_fee.basis_points
+        } else {
+            let fee_range = liq_pool
+                .lp_max_fee
+                .basis_points
+                .saturating_sub(liq_pool.lp_min_fee.basis_points);
+            let fee_reduction = proportional(
+                fee_range as u64,
+                lamports_after,
+                liq_pool.lp_liquidity_target,
+            )?;
+            liq_pool.lp_max_fee.basis_points - fee_reduction as u32
+        }
+    };
+    let msol_fee = proportional(msol_amount, fee_bps as u64, beam_math::MAX_BPS as u64)?;
+    let msol_amount_net = msol_amount
+        .checked_sub(msol_fee)
+        .ok_or(MathError::Underflow)?;
+    calc_lamports_from_msol_amount(marinade_state, msol_amount_net)
+}
+
 fn total_cooling_down(marinade_state: &MarinadeState) -> u64 {
     marinade_state
         .stake_system
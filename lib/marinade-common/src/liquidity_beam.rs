@@ -0,0 +1,67 @@
+//! The Marinade liquidity pool beam's state, as read by the Marinade beam that funds its liquidity.
+//!
+//! The Marinade beam reads the liquidity beam's state through this mirror rather than
+//! depending on the liquidity beam's program crate, whose tests depend on the Marinade beam.
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use beam_math::{apply_bps, MathResult, Rounding};
+
+// The Marinade liquidity pool beam program.
+declare_id!("9Xek4q2hsdPm4yaRt4giQnVTTgRGwGhXQ1HBXbinuPTP");
+
+/// Mirrors `marinade_lp_beam::state::State`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct LiquidityBeamState {
+    pub update_authority: Pubkey,
+    pub marinade_state: Pubkey,
+    pub sunrise_state: Pubkey,
+    pub vault_authority_bump: u8,
+    pub msol_recipient_beam: Pubkey,
+    pub msol_token_account: Pubkey,
+    pub target_liquidity_bps: u16,
+}
+
+impl LiquidityBeamState {
+    /// The target liquidity in lamports for a gSOL supply of `gsol_supply`.
+    pub fn target_liquidity(&self, gsol_supply: u64) -> MathResult<u64> {
+        target_liquidity(gsol_supply, self.target_liquidity_bps)
+    }
+}
+
+/// The target liquidity in lamports for a gSOL supply of `gsol_supply`
+/// and a target share of `target_liquidity_bps`.
+pub fn target_liquidity(gsol_supply: u64, target_liquidity_bps: u16) -> MathResult<u64> {
+    apply_bps(gsol_supply, target_liquidity_bps, Rounding::Down)
+}
+
+impl Discriminator for LiquidityBeamState {
+    /// The discriminator of an anchor account named `State`.
+    const DISCRIMINATOR: [u8; 8] = [216, 146, 107, 94, 104, 75, 182, 177];
+}
+
+impl Owner for LiquidityBeamState {
+    fn owner() -> Pubkey {
+        ID
+    }
+}
+
+impl AccountSerialize for LiquidityBeamState {}
+
+impl AccountDeserialize for LiquidityBeamState {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if buf.len() < Self::DISCRIMINATOR.len() {
+            return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+        }
+        if buf[..Self::DISCRIMINATOR.len()] != Self::DISCRIMINATOR {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let mut data: &[u8] = &buf[Self::DISCRIMINATOR.len()..];
+        AnchorDeserialize::deserialize(&mut data)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
@@ -0,0 +1,236 @@
+//! Signed CPIs from a beam to the Sunrise core program.
+//!
+//! Each helper takes the seeds of the beam state PDA, which must be the beam registered in the core state.
+//! The `impl_*` macros generate the conversions from a beam's accounts struct to the core CPI accounts,
+//! given that it names its accounts according to the canonical layout in [crate::instruction].
+//!
+//! The address of the depositor's [Attribution] may be passed as the last remaining account of a
+//! beam instruction that mints or burns gSOL. The beam forwards it to the core program, which
+//! records the deposit or withdrawal against it once it is registered.
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+pub use sunrise_core::cpi::accounts::{
+    BurnGsol, ExtractYield, MintGsol, TransferGsol, UpdateEpochReport,
+};
+use sunrise_core::cpi::{
+    burn_gsol as cpi_burn_gsol, extract_yield as cpi_extract_yield, mint_gsol as cpi_mint_gsol,
+    transfer_gsol as cpi_transfer_gsol, update_epoch_report as cpi_update_epoch_report,
+};
+use sunrise_core::Attribution;
+
+/// Splits a depositor's [Attribution] off the end of a beam instruction's `remaining_accounts`,
+/// returning the accounts before it and the attribution, if there is one.
+///
+/// An attribution that is not registered yet is an empty account of the system program.
+pub fn split_attribution<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> (&'a [AccountInfo<'info>], Option<AccountInfo<'info>>) {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if is_attribution(last) => (rest, Some(last.clone())),
+        _ => (remaining_accounts, None),
+    }
+}
+
+fn is_attribution(account: &AccountInfo) -> bool {
+    if *account.owner == System::id() {
+        return account.data_is_empty();
+    }
+    *account.owner == sunrise_core::ID
+        && account
+            .try_borrow_data()
+            .map(|data| data.starts_with(&Attribution::DISCRIMINATOR))
+            .unwrap_or(false)
+}
+
+/// Mint `lamports` of gSOL, recording it against the depositor's [Attribution] from
+/// `remaining_accounts`, if it is registered.
+pub fn mint_gsol<'a>(
+    accounts: impl Into<MintGsol<'a>>,
+    cpi_program: AccountInfo<'a>,
+    state_seeds: &[&[u8]],
+    remaining_accounts: &[AccountInfo<'a>],
+    lamports: u64,
+) -> Result<()> {
+    let (_, attribution) = split_attribution(remaining_accounts);
+    cpi_mint_gsol(
+        CpiContext::new(cpi_program, accounts.into())
+            .with_signer(&[state_seeds])
+            .with_remaining_accounts(attribution.into_iter().collect()),
+        lamports,
+    )
+}
+
+/// Burn `lamports` of gSOL, recording it against the owner's [Attribution] from
+/// `remaining_accounts`, if it is registered.
+pub fn burn_gsol<'a>(
+    accounts: impl Into<BurnGsol<'a>>,
+    cpi_program: AccountInfo<'a>,
+    state_seeds: &[&[u8]],
+    remaining_accounts: &[AccountInfo<'a>],
+    lamports: u64,
+) -> Result<()> {
+    let (_, attribution) = split_attribution(remaining_accounts);
+    cpi_burn_gsol(
+        CpiContext::new(cpi_program, accounts.into())
+            .with_signer(&[state_seeds])
+            .with_remaining_accounts(attribution.into_iter().collect()),
+        lamports,
+    )
+}
+
+pub fn transfer_gsol<'a>(
+    accounts: impl Into<TransferGsol<'a>>,
+    cpi_program: AccountInfo<'a>,
+    state_seeds: &[&[u8]],
+    recipient_beam: Pubkey,
+    lamports: u64,
+) -> Result<()> {
+    cpi_transfer_gsol(
+        CpiContext::new(cpi_program, accounts.into()).with_signer(&[state_seeds]),
+        recipient_beam,
+        lamports,
+    )
+}
+
+/// The treasury account of a beam's `extract_yield`, if the `protocol_fee` on the extracted yield is due.
+///
+/// The treasury is optional in `extract_yield`, so that a state that charges no protocol fee,
+/// and may have no treasury to write to, can still have its yield extracted.
+/// Errors if a protocol fee is due but no treasury was passed.
+pub fn fee_treasury<T>(treasury: Option<T>, protocol_fee: u64) -> Result<Option<T>> {
+    match treasury {
+        _ if protocol_fee == 0 => Ok(None),
+        Some(treasury) => Ok(Some(treasury)),
+        None => Err(sunrise_core::BeamError::MissingTreasury.into()),
+    }
+}
+
+pub fn extract_yield<'a>(
+    accounts: impl Into<ExtractYield<'a>>,
+    cpi_program: AccountInfo<'a>,
+    state_seeds: &[&[u8]],
+    lamports: u64,
+) -> Result<()> {
+    cpi_extract_yield(
+        CpiContext::new(cpi_program, accounts.into()).with_signer(&[state_seeds]),
+        lamports,
+    )
+}
+
+pub fn update_epoch_report<'a>(
+    accounts: impl Into<UpdateEpochReport<'a>>,
+    cpi_program: AccountInfo<'a>,
+    state_seeds: &[&[u8]],
+    surplus: i64,
+) -> Result<()> {
+    cpi_update_epoch_report(
+        CpiContext::new(cpi_program, accounts.into()).with_signer(&[state_seeds]),
+        surplus,
+    )
+}
+
+/// Implements `From<&X> for MintGsol` for each of the given accounts structs,
+/// which must have `sunrise_state`, `state`, `gsol_mint`, `gsol_mint_authority`, `mint_gsol_to`,
+/// `sysvar_instructions` and `token_program` accounts.
+#[macro_export]
+macro_rules! impl_mint_gsol {
+    ($($accounts:ident),+ $(,)?) => {
+        $(
+            impl<'a> From<&$accounts<'a>> for $crate::core_cpi::MintGsol<'a> {
+                fn from(accounts: &$accounts<'a>) -> Self {
+                    Self {
+                        state: accounts.sunrise_state.to_account_info(),
+                        beam: accounts.state.to_account_info(),
+                        gsol_mint: accounts.gsol_mint.to_account_info(),
+                        gsol_mint_authority: accounts.gsol_mint_authority.to_account_info(),
+                        mint_gsol_to: accounts.mint_gsol_to.to_account_info(),
+                        sysvar_instructions: accounts.sysvar_instructions.to_account_info(),
+                        token_program: accounts.token_program.to_account_info(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `From<&X> for BurnGsol` for each of the given accounts structs,
+/// burning from the `gsol_token_account` owned by the named signer.
+#[macro_export]
+macro_rules! impl_burn_gsol {
+    ($($accounts:ident => $owner:ident),+ $(,)?) => {
+        $(
+            impl<'a> From<&$accounts<'a>> for $crate::core_cpi::BurnGsol<'a> {
+                fn from(accounts: &$accounts<'a>) -> Self {
+                    Self {
+                        state: accounts.sunrise_state.to_account_info(),
+                        beam: accounts.state.to_account_info(),
+                        gsol_mint: accounts.gsol_mint.to_account_info(),
+                        burn_gsol_from_owner: accounts.$owner.to_account_info(),
+                        burn_gsol_from: accounts.gsol_token_account.to_account_info(),
+                        sysvar_instructions: accounts.sysvar_instructions.to_account_info(),
+                        token_program: accounts.token_program.to_account_info(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `From<&X> for TransferGsol` for each of the given accounts structs.
+#[macro_export]
+macro_rules! impl_transfer_gsol {
+    ($($accounts:ident),+ $(,)?) => {
+        $(
+            impl<'a> From<&$accounts<'a>> for $crate::core_cpi::TransferGsol<'a> {
+                fn from(accounts: &$accounts<'a>) -> Self {
+                    Self {
+                        state: accounts.sunrise_state.to_account_info(),
+                        beam: accounts.state.to_account_info(),
+                        gsol_mint: accounts.gsol_mint.to_account_info(),
+                        sysvar_instructions: accounts.sysvar_instructions.to_account_info(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `From<&X> for ExtractYield` for each of the given accounts structs.
+#[macro_export]
+macro_rules! impl_extract_yield {
+    ($($accounts:ident),+ $(,)?) => {
+        $(
+            impl<'a> From<&$accounts<'a>> for $crate::core_cpi::ExtractYield<'a> {
+                fn from(accounts: &$accounts<'a>) -> Self {
+                    Self {
+                        state: accounts.sunrise_state.to_account_info(),
+                        beam: accounts.state.to_account_info(),
+                        sysvar_instructions: accounts.sysvar_instructions.to_account_info(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `From<&X> for UpdateEpochReport` for each of the given accounts structs,
+/// which must have a `backing_oracle` account.
+#[macro_export]
+macro_rules! impl_update_epoch_report {
+    ($($accounts:ident),+ $(,)?) => {
+        $(
+            impl<'a> From<&$accounts<'a>> for $crate::core_cpi::UpdateEpochReport<'a> {
+                fn from(accounts: &$accounts<'a>) -> Self {
+                    Self {
+                        state: accounts.sunrise_state.to_account_info(),
+                        beam: accounts.state.to_account_info(),
+                        gsol_mint: accounts.gsol_mint.to_account_info(),
+                        backing_oracle: accounts.backing_oracle.to_account_info(),
+                        sysvar_instructions: accounts.sysvar_instructions.to_account_info(),
+                    }
+                }
+            }
+        )+
+    };
+}
@@ -0,0 +1,351 @@
+//! The canonical beam instruction set.
+//!
+//! Each instruction has a fixed discriminator (its Anchor sighash) and fixed arguments.
+//! Its accounts start with the standard accounts below, in order, followed by the
+//! beam-specific accounts. Accounts marked writable here may be read-only in a given beam.
+//!
+//! The instructions that mint or burn gSOL optionally take the address of the depositor's writable
+//! [Attribution](sunrise_core::Attribution) as the last account, after any remaining accounts
+//! of the beam. Once it is registered, the deposit or withdrawal is recorded against it.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+
+pub const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+pub const ORDER_WITHDRAWAL: [u8; 8] = [186, 97, 67, 20, 250, 228, 255, 63];
+pub const REDEEM_TICKET: [u8; 8] = [129, 120, 104, 224, 155, 25, 22, 192];
+pub const BURN: [u8; 8] = [116, 110, 29, 56, 107, 219, 42, 93];
+pub const UPDATE_EPOCH_REPORT: [u8; 8] = [181, 106, 179, 149, 91, 180, 94, 195];
+pub const EXTRACT_YIELD: [u8; 8] = [50, 224, 118, 151, 119, 162, 175, 101];
+pub const QUOTE_DEPOSIT: [u8; 8] = [181, 208, 37, 150, 90, 54, 87, 204];
+pub const QUOTE_WITHDRAW: [u8; 8] = [200, 103, 235, 129, 119, 9, 220, 200];
+pub const GET_BEAM_VALUE: [u8; 8] = [65, 3, 234, 24, 232, 158, 200, 186];
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BeamInstruction {
+    /// Deposit `lamports` SOL into the beam and mint gSOL to the depositor.
+    Deposit { lamports: u64 },
+    /// Burn `lamports` gSOL and withdraw at least `min_lamports_out` SOL immediately.
+    Withdraw {
+        lamports: u64,
+        min_lamports_out: u64,
+    },
+    /// Burn `lamports` gSOL in exchange for a ticket redeemable once the withdrawal has settled.
+    OrderWithdrawal { lamports: u64 },
+    /// Redeem a settled withdrawal ticket.
+    RedeemTicket,
+    /// Burn `lamports` gSOL without withdrawing, donating the backing to the beam.
+    Burn { lamports: u64 },
+    /// Report the beam's surplus, or its deficit, to the core state.
+    UpdateEpochReport,
+    /// Extract the beam's yield to the yield account, less the protocol fee sent to the treasury.
+    ExtractYield,
+    /// View: quote a deposit of `lamports` SOL, returning a [DepositQuote](crate::view::DepositQuote).
+    QuoteDeposit { lamports: u64 },
+    /// View: quote an immediate withdrawal of `lamports` gSOL, returning a
+    /// [WithdrawQuote](crate::view::WithdrawQuote).
+    QuoteWithdraw { lamports: u64 },
+    /// View: get the beam's value, surplus and extractable yield, returning a
+    /// [BeamValue](crate::view::BeamValue).
+    GetBeamValue,
+}
+
+impl BeamInstruction {
+    pub fn discriminator(&self) -> [u8; 8] {
+        match self {
+            Self::Deposit { .. } => DEPOSIT,
+            Self::Withdraw { .. } => WITHDRAW,
+            Self::OrderWithdrawal { .. } => ORDER_WITHDRAWAL,
+            Self::RedeemTicket => REDEEM_TICKET,
+            Self::Burn { .. } => BURN,
+            Self::UpdateEpochReport => UPDATE_EPOCH_REPORT,
+            Self::ExtractYield => EXTRACT_YIELD,
+            Self::QuoteDeposit { .. } => QUOTE_DEPOSIT,
+            Self::QuoteWithdraw { .. } => QUOTE_WITHDRAW,
+            Self::GetBeamValue => GET_BEAM_VALUE,
+        }
+    }
+
+    /// The instruction data: the discriminator followed by the borsh-serialized arguments.
+    pub fn data(&self) -> Vec<u8> {
+        let mut data = self.discriminator().to_vec();
+        match self {
+            Self::Deposit { lamports }
+            | Self::OrderWithdrawal { lamports }
+            | Self::Burn { lamports }
+            | Self::QuoteDeposit { lamports }
+            | Self::QuoteWithdraw { lamports } => data.extend_from_slice(&lamports.to_le_bytes()),
+            Self::Withdraw {
+                lamports,
+                min_lamports_out,
+            } => {
+                data.extend_from_slice(&lamports.to_le_bytes());
+                data.extend_from_slice(&min_lamports_out.to_le_bytes());
+            }
+            Self::RedeemTicket
+            | Self::UpdateEpochReport
+            | Self::ExtractYield
+            | Self::GetBeamValue => {}
+        }
+        data
+    }
+
+    /// Builds the instruction for `beam_program`, with the standard accounts followed by `beam_accounts`.
+    pub fn to_instruction(
+        &self,
+        beam_program: Pubkey,
+        standard_accounts: &impl ToAccountMetas,
+        beam_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = standard_accounts.to_account_metas(None);
+        accounts.extend(beam_accounts);
+        Instruction {
+            program_id: beam_program,
+            accounts,
+            data: self.data(),
+        }
+    }
+}
+
+/// The standard accounts of `deposit`.
+pub struct DepositAccounts {
+    pub state: Pubkey,
+    pub sunrise_state: Pubkey,
+    pub depositor: Pubkey,
+    pub mint_gsol_to: Pubkey,
+    pub gsol_mint: Pubkey,
+    pub gsol_mint_authority: Pubkey,
+}
+
+impl ToAccountMetas for DepositAccounts {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.state, false),
+            AccountMeta::new(self.sunrise_state, false),
+            AccountMeta::new(self.depositor, true),
+            AccountMeta::new(self.mint_gsol_to, false),
+            AccountMeta::new(self.gsol_mint, false),
+            AccountMeta::new_readonly(self.gsol_mint_authority, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            AccountMeta::new_readonly(sunrise_core::ID, false),
+        ]
+    }
+}
+
+/// The standard accounts of `withdraw`, `order_withdrawal` and `burn`.
+/// The owner is the `withdrawer` or `burner` respectively.
+pub struct BurnGsolAccounts {
+    pub state: Pubkey,
+    pub sunrise_state: Pubkey,
+    pub owner: Pubkey,
+    pub gsol_token_account: Pubkey,
+    pub gsol_mint: Pubkey,
+}
+
+impl ToAccountMetas for BurnGsolAccounts {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.state, false),
+            AccountMeta::new(self.sunrise_state, false),
+            AccountMeta::new(self.owner, true),
+            AccountMeta::new(self.gsol_token_account, false),
+            AccountMeta::new(self.gsol_mint, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            AccountMeta::new_readonly(sunrise_core::ID, false),
+        ]
+    }
+}
+
+/// The standard accounts of `redeem_ticket`.
+pub struct RedeemTicketAccounts {
+    pub state: Pubkey,
+    pub beneficiary: Pubkey,
+}
+
+impl ToAccountMetas for RedeemTicketAccounts {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.state, false),
+            AccountMeta::new(self.beneficiary, true),
+        ]
+    }
+}
+
+/// The standard accounts of `update_epoch_report`.
+pub struct UpdateEpochReportAccounts {
+    pub state: Pubkey,
+    pub sunrise_state: Pubkey,
+    pub gsol_mint: Pubkey,
+    pub backing_oracle: Pubkey,
+}
+
+impl ToAccountMetas for UpdateEpochReportAccounts {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.state, false),
+            AccountMeta::new(self.sunrise_state, false),
+            AccountMeta::new_readonly(self.gsol_mint, false),
+            AccountMeta::new(self.backing_oracle, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            AccountMeta::new_readonly(sunrise_core::ID, false),
+        ]
+    }
+}
+
+/// The standard accounts of `extract_yield`.
+///
+/// The `treasury` is only needed while the core state charges a protocol fee.
+/// Without one, the `beam_program` is passed in its place, as Anchor expects for an optional account.
+pub struct ExtractYieldAccounts {
+    pub state: Pubkey,
+    pub sunrise_state: Pubkey,
+    pub yield_account: Pubkey,
+    pub treasury: Option<Pubkey>,
+    pub beam_program: Pubkey,
+}
+
+impl ToAccountMetas for ExtractYieldAccounts {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        let treasury = match self.treasury {
+            Some(treasury) => AccountMeta::new(treasury, false),
+            None => AccountMeta::new_readonly(self.beam_program, false),
+        };
+        vec![
+            AccountMeta::new_readonly(self.state, false),
+            AccountMeta::new(self.sunrise_state, false),
+            AccountMeta::new(self.yield_account, false),
+            treasury,
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            AccountMeta::new_readonly(sunrise_core::ID, false),
+        ]
+    }
+}
+
+/// The standard accounts of the view instructions `quote_deposit`, `quote_withdraw` and `get_beam_value`.
+/// None are writable, and no signer is needed, so views can be simulated by any client.
+pub struct ViewAccounts {
+    pub state: Pubkey,
+    pub sunrise_state: Pubkey,
+}
+
+impl ToAccountMetas for ViewAccounts {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.state, false),
+            AccountMeta::new_readonly(self.sunrise_state, false),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    fn sighash(name: &str) -> [u8; 8] {
+        hash(format!("global:{}", name).as_bytes()).to_bytes()[..8]
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_discriminators() {
+        assert_eq!(DEPOSIT, sighash("deposit"));
+        assert_eq!(WITHDRAW, sighash("withdraw"));
+        assert_eq!(ORDER_WITHDRAWAL, sighash("order_withdrawal"));
+        assert_eq!(REDEEM_TICKET, sighash("redeem_ticket"));
+        assert_eq!(BURN, sighash("burn"));
+        assert_eq!(UPDATE_EPOCH_REPORT, sighash("update_epoch_report"));
+        assert_eq!(EXTRACT_YIELD, sighash("extract_yield"));
+        assert_eq!(QUOTE_DEPOSIT, sighash("quote_deposit"));
+        assert_eq!(QUOTE_WITHDRAW, sighash("quote_withdraw"));
+        assert_eq!(GET_BEAM_VALUE, sighash("get_beam_value"));
+    }
+
+    /// The keys of the accounts that must sign.
+    fn signers(accounts: &impl ToAccountMetas) -> Vec<Pubkey> {
+        accounts
+            .to_account_metas(None)
+            .into_iter()
+            .filter(|meta| meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect()
+    }
+
+    #[test]
+    fn test_signers() {
+        let deposit = DepositAccounts {
+            state: Pubkey::new_unique(),
+            sunrise_state: Pubkey::new_unique(),
+            depositor: Pubkey::new_unique(),
+            mint_gsol_to: Pubkey::new_unique(),
+            gsol_mint: Pubkey::new_unique(),
+            gsol_mint_authority: Pubkey::new_unique(),
+        };
+        assert_eq!(signers(&deposit), vec![deposit.depositor]);
+
+        let burn = BurnGsolAccounts {
+            state: Pubkey::new_unique(),
+            sunrise_state: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            gsol_token_account: Pubkey::new_unique(),
+            gsol_mint: Pubkey::new_unique(),
+        };
+        assert_eq!(signers(&burn), vec![burn.owner]);
+
+        let redeem_ticket = RedeemTicketAccounts {
+            state: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+        };
+        assert_eq!(signers(&redeem_ticket), vec![redeem_ticket.beneficiary]);
+
+        let update_epoch_report = UpdateEpochReportAccounts {
+            state: Pubkey::new_unique(),
+            sunrise_state: Pubkey::new_unique(),
+            gsol_mint: Pubkey::new_unique(),
+            backing_oracle: Pubkey::new_unique(),
+        };
+        assert!(signers(&update_epoch_report).is_empty());
+    }
+
+    #[test]
+    fn test_extract_yield_accounts() {
+        let beam_program = Pubkey::new_unique();
+        let mut accounts = ExtractYieldAccounts {
+            state: Pubkey::new_unique(),
+            sunrise_state: Pubkey::new_unique(),
+            yield_account: Pubkey::new_unique(),
+            treasury: None,
+            beam_program,
+        };
+        assert_eq!(
+            accounts.to_account_metas(None)[3],
+            AccountMeta::new_readonly(beam_program, false)
+        );
+
+        let treasury = Pubkey::new_unique();
+        accounts.treasury = Some(treasury);
+        assert_eq!(
+            accounts.to_account_metas(None)[3],
+            AccountMeta::new(treasury, false)
+        );
+    }
+
+    #[test]
+    fn test_data() {
+        let data = BeamInstruction::Withdraw {
+            lamports: 1,
+            min_lamports_out: 2,
+        }
+        .data();
+        assert_eq!(data[..8], WITHDRAW);
+        assert_eq!(data[8..16], 1u64.to_le_bytes());
+        assert_eq!(data[16..], 2u64.to_le_bytes());
+        assert_eq!(BeamInstruction::ExtractYield.data(), EXTRACT_YIELD.to_vec());
+        let data = BeamInstruction::QuoteWithdraw { lamports: 3 }.data();
+        assert_eq!(data[..8], QUOTE_WITHDRAW);
+        assert_eq!(data[8..], 3u64.to_le_bytes());
+    }
+}
@@ -0,0 +1,18 @@
+//! The standard interface shared by all Sunrise beams.
+//!
+//! A conforming beam exposes the instructions in [instruction] under their canonical names and
+//! arguments, and lists the accounts described there first, in the given order,
+//! followed by any accounts specific to the beam. Clients can then call any beam generically,
+//! supplying the beam-specific accounts as a tail.
+//!
+//! The view instructions in [instruction] make no state changes, and return the types in [view]
+//! through the transaction return data.
+//!
+//! [core_cpi] contains helpers for making the signed CPIs from a beam to the Sunrise core program.
+//!
+//! A beam stubs the instructions it does not support with [unsupported].
+
+pub mod core_cpi;
+pub mod instruction;
+pub mod unsupported;
+pub mod view;
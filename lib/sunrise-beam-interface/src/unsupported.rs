@@ -0,0 +1,28 @@
+//! The shared stub for the instructions of the standard interface that a beam does not support.
+//!
+//! Anchor requires the accounts struct of each instruction to be declared in the program crate,
+//! so a beam declares an empty `Unsupported` accounts struct for its unsupported instructions,
+//! and returns [unsupported] from them:
+//!
+//! ```ignore
+//! pub fn order_withdrawal(_ctx: Context<Unsupported>, _lamports: u64) -> Result<()> {
+//!     sunrise_beam_interface::unsupported::unsupported()
+//! }
+//!
+//! #[derive(Accounts)]
+//! pub struct Unsupported {}
+//! ```
+
+use anchor_lang::prelude::*;
+
+/// Errors of the standard interface, offset from the beams' own errors, which start at 6000.
+#[error_code(offset = 7000)]
+pub enum BeamInterfaceError {
+    #[msg("This instruction is not supported by this beam")]
+    UnsupportedInstruction,
+}
+
+/// The result of an instruction of the standard interface that the beam does not support.
+pub fn unsupported<T>() -> Result<T> {
+    err!(BeamInterfaceError::UnsupportedInstruction)
+}
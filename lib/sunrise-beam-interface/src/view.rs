@@ -0,0 +1,38 @@
+//! The results of the beam view instructions.
+//!
+//! View instructions make no state changes. They return one of these types, borsh-serialized,
+//! through the transaction return data, so that a client can simulate the instruction
+//! and read the result as computed on-chain.
+
+use anchor_lang::prelude::*;
+
+/// The result of `quote_deposit`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DepositQuote {
+    /// The gSOL that would be minted to the depositor.
+    pub gsol_out: u64,
+    /// The beam tokens (e.g. pool tokens or mSOL) the beam would receive, if any.
+    pub beam_tokens_out: u64,
+}
+
+/// The result of `quote_withdraw`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WithdrawQuote {
+    /// The lamports the withdrawer would receive immediately, net of fees.
+    pub lamports_out: u64,
+    /// The beam tokens (e.g. pool tokens or mSOL) the beam would spend, if any.
+    pub beam_tokens_in: u64,
+}
+
+/// The result of `get_beam_value`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct BeamValue {
+    /// The value in lamports of everything the beam holds.
+    pub value: u64,
+    /// The gSOL issued by the beam, i.e. its liabilities.
+    pub gsol_supply: u64,
+    /// The surplus reported by `update_epoch_report`, negative if the beam is in deficit.
+    pub surplus: i64,
+    /// The yield that `extract_yield` would extract.
+    pub extractable_yield: u64,
+}
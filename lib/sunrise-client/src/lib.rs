@@ -0,0 +1,78 @@
+//! A Rust client for the Sunrise core program and its beams.
+//!
+//! Each program has a module containing its PDA derivations and a client which,
+//! given the program's decoded state account, resolves the accounts of its instructions
+//! and builds them:
+//!
+//! - [sunrise]: the admin and attribution instructions of the Sunrise core program.
+//! - [spl]: the SPL stake-pool beam.
+//! - [marinade_sp]: the Marinade stake-pool beam.
+//! - [marinade_lp]: the Marinade liquidity-pool beam.
+//! - [native_stake]: the native stake beam.
+//! - [reserve]: the reserve beam.
+//!
+//! The builders are synchronous and make no network requests. [rpc] contains async helpers that
+//! fetch and decode the accounts needed to construct the clients, simulate the beam view
+//! instructions and send transactions.
+
+pub mod marinade;
+pub mod marinade_lp;
+pub mod marinade_sp;
+pub mod native_stake;
+pub mod reserve;
+pub mod rpc;
+pub mod spl;
+pub mod sunrise;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+
+pub use marinade_lp::MarinadeLpClient;
+pub use marinade_sp::MarinadeClient;
+pub use native_stake::NativeStakeClient;
+pub use reserve::ReserveClient;
+pub use spl::SplClient;
+pub use sunrise::SunriseClient;
+pub use sunrise_beam_interface::view::{BeamValue, DepositQuote, WithdrawQuote};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("RPC error: {0}")]
+    Rpc(#[from] solana_client::client_error::ClientError),
+
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+
+    #[error("Failed to decode account {0}: {1}")]
+    InvalidAccountData(Pubkey, anchor_lang::error::Error),
+
+    #[error("Simulation failed: {0}")]
+    SimulationFailed(String),
+
+    #[error("The simulated instruction returned no data")]
+    NoReturnData,
+
+    #[error("Invalid return data: {0}")]
+    InvalidReturnData(String),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Decode the data of an anchor account of type `T`, checking its discriminator.
+pub fn decode<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|e| ClientError::InvalidAccountData(*address, e))
+}
+
+/// Build an instruction from anchor-generated accounts and instruction data.
+pub(crate) fn instruction(
+    program_id: &Pubkey,
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
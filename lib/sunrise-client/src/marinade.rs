@@ -0,0 +1,50 @@
+//! PDAs of the Marinade program, shared by the Marinade beams.
+
+use anchor_lang::prelude::Pubkey;
+
+pub use marinade_cpi::{State as MarinadeState, TicketAccountData, ID};
+
+/// The size of a Marinade ticket account, which must be created before ordering a withdrawal.
+pub const TICKET_ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8;
+
+fn derive(marinade_state: &Pubkey, seed: &[u8]) -> Pubkey {
+    Pubkey::find_program_address(&[marinade_state.as_ref(), seed], &ID).0
+}
+
+/// The PDA holding Marinade's SOL reserve.
+pub fn reserve_address(marinade_state: &Pubkey) -> Pubkey {
+    derive(marinade_state, b"reserve")
+}
+
+/// The mint authority of mSOL.
+pub fn msol_mint_authority(marinade_state: &Pubkey) -> Pubkey {
+    derive(marinade_state, b"st_mint")
+}
+
+/// The SOL leg of the liquidity pool.
+pub fn liq_pool_sol_leg(marinade_state: &Pubkey) -> Pubkey {
+    derive(marinade_state, b"liq_sol")
+}
+
+/// The authority of the mSOL leg of the liquidity pool.
+pub fn liq_pool_msol_leg_authority(marinade_state: &Pubkey) -> Pubkey {
+    derive(marinade_state, b"liq_st_sol_authority")
+}
+
+/// The mint authority of the liquidity pool token.
+pub fn liq_pool_mint_authority(marinade_state: &Pubkey) -> Pubkey {
+    derive(marinade_state, b"liq_mint")
+}
+
+/// The flag preventing a validator from being added twice to the validator list.
+pub fn duplication_flag(marinade_state: &Pubkey, vote_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            marinade_state.as_ref(),
+            b"unique_validator",
+            vote_account.as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
@@ -0,0 +1,325 @@
+//! The Marinade liquidity-pool beam.
+//!
+//! The beam's `order_withdrawal` and `redeem_ticket` instructions are unsupported,
+//! so no builders are provided for them.
+
+use crate::marinade::{self, MarinadeState};
+use crate::{instruction, SunriseClient};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{instruction::Instruction, system_program, sysvar};
+use anchor_spl::associated_token::get_associated_token_address;
+use marinade_lp_beam::{accounts, constants, instruction as ix};
+
+pub use marinade_lp_beam::state::{State, StateEntry, YieldBreakdown};
+pub use marinade_lp_beam::ID;
+
+/// Derive the address of the state account for this beam.
+pub fn derive_state_address(program_id: &Pubkey, sunrise_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[constants::STATE, sunrise_state.as_ref()], program_id)
+}
+
+/// Derive the address of the PDA authority for this beam's liquidity pool token vault.
+pub fn derive_vault_authority(program_id: &Pubkey, state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[state.as_ref(), constants::VAULT_AUTHORITY], program_id)
+}
+
+/// Derive the address of the yield breakdown account of a beam state.
+pub fn derive_yield_breakdown(program_id: &Pubkey, state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[state.as_ref(), constants::YIELD_BREAKDOWN], program_id)
+}
+
+/// Build an `initialize` instruction for a new beam state.
+pub fn initialize(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    liq_pool_mint: &Pubkey,
+    input: StateEntry,
+) -> Instruction {
+    let state = derive_state_address(program_id, &input.sunrise_state).0;
+    let vault_authority = derive_vault_authority(program_id, &state).0;
+    let accounts = accounts::Initialize {
+        payer: *payer,
+        state,
+        yield_breakdown: derive_yield_breakdown(program_id, &state).0,
+        liq_pool_mint: *liq_pool_mint,
+        liq_pool_vault: get_associated_token_address(&vault_authority, liq_pool_mint),
+        vault_authority,
+        token_program: anchor_spl::token::ID,
+        system_program: system_program::id(),
+        associated_token_program: anchor_spl::associated_token::ID,
+    };
+    instruction(program_id, accounts, ix::Initialize { input })
+}
+
+/// Build a `resize_state` instruction for a beam state created before it had a target liquidity.
+/// Such a state cannot be deserialized, so is addressed by its Sunrise state.
+pub fn resize_state(
+    program_id: &Pubkey,
+    update_authority: &Pubkey,
+    sunrise_state: &Pubkey,
+    target_liquidity_bps: u16,
+) -> Instruction {
+    let accounts = accounts::ResizeState {
+        update_authority: *update_authority,
+        state: derive_state_address(program_id, sunrise_state).0,
+        sunrise_state: *sunrise_state,
+        system_program: system_program::id(),
+    };
+    let data = ix::ResizeState {
+        target_liquidity_bps,
+    };
+    instruction(program_id, accounts, data)
+}
+
+/// A Marinade liquidity-pool beam state, the Marinade state it deposits into,
+/// and the Sunrise state it is registered with.
+#[derive(Clone)]
+pub struct MarinadeLpClient {
+    pub program_id: Pubkey,
+    /// The beam state address.
+    pub state_address: Pubkey,
+    /// The deserialized on-chain account for this beam state.
+    pub state: State,
+    /// The deserialized Marinade state account.
+    pub marinade: MarinadeState,
+    pub sunrise: SunriseClient,
+}
+
+impl MarinadeLpClient {
+    pub fn new(
+        program_id: Pubkey,
+        state_address: Pubkey,
+        state: State,
+        marinade: MarinadeState,
+        sunrise: SunriseClient,
+    ) -> Self {
+        Self {
+            program_id,
+            state_address,
+            state,
+            marinade,
+            sunrise,
+        }
+    }
+
+    pub fn vault_authority(&self) -> Pubkey {
+        derive_vault_authority(&self.program_id, &self.state_address).0
+    }
+
+    /// The beam's liquidity pool token account.
+    pub fn liq_pool_token_vault(&self) -> Pubkey {
+        get_associated_token_address(&self.vault_authority(), &self.marinade.liq_pool.lp_mint)
+    }
+
+    pub fn yield_breakdown(&self) -> Pubkey {
+        derive_yield_breakdown(&self.program_id, &self.state_address).0
+    }
+
+    pub fn update(&self, update_input: StateEntry) -> Instruction {
+        let accounts = accounts::Update {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+        };
+        instruction(&self.program_id, accounts, ix::Update { update_input })
+    }
+
+    pub fn deposit(&self, depositor: &Pubkey, mint_gsol_to: &Pubkey, lamports: u64) -> Instruction {
+        let marinade_state = &self.state.marinade_state;
+        let accounts = accounts::Deposit {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            depositor: *depositor,
+            mint_gsol_to: *mint_gsol_to,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            gsol_mint_authority: self.sunrise.gsol_mint_authority(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_state: *marinade_state,
+            liq_pool_mint: self.marinade.liq_pool.lp_mint,
+            liq_pool_token_vault: self.liq_pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            liq_pool_msol_leg_authority: marinade::liq_pool_msol_leg_authority(marinade_state),
+            liq_pool_mint_authority: marinade::liq_pool_mint_authority(marinade_state),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            marinade_program: marinade::ID,
+            yield_breakdown: self.yield_breakdown(),
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::Deposit { lamports });
+        self.sunrise.with_attribution(instruction, depositor)
+    }
+
+    pub fn withdraw(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Instruction {
+        let marinade_state = &self.state.marinade_state;
+        let accounts = accounts::Withdraw {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_state: *marinade_state,
+            liq_pool_mint: self.marinade.liq_pool.lp_mint,
+            liq_pool_token_vault: self.liq_pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            transfer_msol_to: self.state.msol_token_account,
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            liq_pool_msol_leg_authority: marinade::liq_pool_msol_leg_authority(marinade_state),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            marinade_program: marinade::ID,
+            yield_breakdown: self.yield_breakdown(),
+        };
+        let data = ix::Withdraw {
+            lamports,
+            min_lamports_out,
+        };
+        let instruction = instruction(&self.program_id, accounts, data);
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    pub fn burn(&self, burner: &Pubkey, gsol_token_account: &Pubkey, lamports: u64) -> Instruction {
+        let accounts = accounts::Burn {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            burner: *burner,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::Burn { lamports });
+        self.sunrise.with_attribution(instruction, burner)
+    }
+
+    pub fn extract_yield(&self, payer: &Pubkey) -> Instruction {
+        let marinade_state = &self.state.marinade_state;
+        let accounts = accounts::ExtractYield {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            yield_account: self.sunrise.state.yield_account,
+            treasury: self.sunrise.treasury(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_state: *marinade_state,
+            payer: *payer,
+            liq_pool_mint: self.marinade.liq_pool.lp_mint,
+            liq_pool_token_vault: self.liq_pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            transfer_msol_to: self.state.msol_token_account,
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            liq_pool_msol_leg_authority: marinade::liq_pool_msol_leg_authority(marinade_state),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            marinade_program: marinade::ID,
+            yield_breakdown: self.yield_breakdown(),
+        };
+        instruction(&self.program_id, accounts, ix::ExtractYield {})
+    }
+
+    /// Move any liquidity above the target size to the mSOL recipient beam.
+    pub fn rebalance_liquidity(&self) -> Instruction {
+        let marinade_state = &self.state.marinade_state;
+        let accounts = accounts::RebalanceLiquidity {
+            state: self.state_address,
+            marinade_state: *marinade_state,
+            sunrise_state: self.state.sunrise_state,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            liq_pool_mint: self.marinade.liq_pool.lp_mint,
+            liq_pool_token_vault: self.liq_pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            transfer_msol_to: self.state.msol_token_account,
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            liq_pool_msol_leg_authority: marinade::liq_pool_msol_leg_authority(marinade_state),
+            msol_mint: self.marinade.msol_mint,
+            msol_mint_authority: marinade::msol_mint_authority(marinade_state),
+            reserve_pda: marinade::reserve_address(marinade_state),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_program: marinade::ID,
+            yield_breakdown: self.yield_breakdown(),
+        };
+        instruction(&self.program_id, accounts, ix::RebalanceLiquidity {})
+    }
+
+    pub fn update_epoch_report(&self) -> Instruction {
+        let marinade_state = &self.state.marinade_state;
+        let accounts = accounts::UpdateEpochReport {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            backing_oracle: self.sunrise.backing_oracle(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_state: *marinade_state,
+            liq_pool_mint: self.marinade.liq_pool.lp_mint,
+            liq_pool_token_vault: self.liq_pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            liq_pool_msol_leg_authority: marinade::liq_pool_msol_leg_authority(marinade_state),
+            yield_breakdown: self.yield_breakdown(),
+        };
+        instruction(&self.program_id, accounts, ix::UpdateEpochReport {})
+    }
+
+    /// Create the yield breakdown account of a beam initialized without one.
+    pub fn init_yield_breakdown(&self) -> Instruction {
+        let accounts = accounts::InitYieldBreakdown {
+            state: self.state_address,
+            marinade_state: self.state.marinade_state,
+            update_authority: self.state.update_authority,
+            yield_breakdown: self.yield_breakdown(),
+            liq_pool_mint: self.marinade.liq_pool.lp_mint,
+            liq_pool_token_vault: self.liq_pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(&self.state.marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            system_program: system_program::id(),
+        };
+        instruction(&self.program_id, accounts, ix::InitYieldBreakdown {})
+    }
+
+    fn view_accounts(&self) -> accounts::View {
+        accounts::View {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            marinade_state: self.state.marinade_state,
+            liq_pool_mint: self.marinade.liq_pool.lp_mint,
+            liq_pool_token_vault: self.liq_pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(&self.state.marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+        }
+    }
+
+    pub fn quote_deposit(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteDeposit { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn quote_withdraw(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteWithdraw { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn get_beam_value(&self) -> Instruction {
+        instruction(&self.program_id, self.view_accounts(), ix::GetBeamValue {})
+    }
+}
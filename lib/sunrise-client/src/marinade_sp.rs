@@ -0,0 +1,405 @@
+//! The Marinade stake-pool beam.
+
+use crate::marinade::{self, MarinadeState};
+use crate::{instruction, MarinadeLpClient, SunriseClient};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{
+    instruction::Instruction, rent::Rent, stake, system_instruction, system_program, sysvar,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use marinade_beam::{accounts, constants, instruction as ix};
+
+pub use marinade_beam::state::{State, StateEntry};
+pub use marinade_beam::{ProxyTicket, ID};
+
+/// Derive the address of the state account for this beam.
+pub fn derive_state_address(program_id: &Pubkey, sunrise_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[constants::STATE, sunrise_state.as_ref()], program_id)
+}
+
+/// Derive the address of the PDA authority for this beam's mSOL vault.
+pub fn derive_vault_authority(program_id: &Pubkey, state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[state.as_ref(), constants::VAULT_AUTHORITY], program_id)
+}
+
+/// Build an `initialize` instruction for a new beam state.
+pub fn initialize(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    msol_mint: &Pubkey,
+    input: StateEntry,
+) -> Instruction {
+    let state = derive_state_address(program_id, &input.sunrise_state).0;
+    let msol_vault_authority = derive_vault_authority(program_id, &state).0;
+    let accounts = accounts::Initialize {
+        payer: *payer,
+        state,
+        msol_mint: *msol_mint,
+        msol_vault: get_associated_token_address(&msol_vault_authority, msol_mint),
+        msol_vault_authority,
+        token_program: anchor_spl::token::ID,
+        system_program: system_program::id(),
+        associated_token_program: anchor_spl::associated_token::ID,
+    };
+    instruction(program_id, accounts, ix::Initialize { input })
+}
+
+/// A Marinade beam state, the Marinade state it deposits into,
+/// and the Sunrise state it is registered with.
+#[derive(Clone)]
+pub struct MarinadeClient {
+    pub program_id: Pubkey,
+    /// The beam state address.
+    pub state_address: Pubkey,
+    /// The deserialized on-chain account for this beam state.
+    pub state: State,
+    /// The deserialized Marinade state account.
+    pub marinade: MarinadeState,
+    pub sunrise: SunriseClient,
+}
+
+impl MarinadeClient {
+    pub fn new(
+        program_id: Pubkey,
+        state_address: Pubkey,
+        state: State,
+        marinade: MarinadeState,
+        sunrise: SunriseClient,
+    ) -> Self {
+        Self {
+            program_id,
+            state_address,
+            state,
+            marinade,
+            sunrise,
+        }
+    }
+
+    pub fn vault_authority(&self) -> Pubkey {
+        derive_vault_authority(&self.program_id, &self.state_address).0
+    }
+
+    /// The beam's mSOL token account.
+    pub fn msol_vault(&self) -> Pubkey {
+        get_associated_token_address(&self.vault_authority(), &self.marinade.msol_mint)
+    }
+
+    pub fn update(&self, update_input: StateEntry) -> Instruction {
+        let accounts = accounts::Update {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+        };
+        instruction(&self.program_id, accounts, ix::Update { update_input })
+    }
+
+    pub fn deposit(&self, depositor: &Pubkey, mint_gsol_to: &Pubkey, lamports: u64) -> Instruction {
+        let marinade_state = &self.state.marinade_state;
+        let accounts = accounts::Deposit {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            depositor: *depositor,
+            mint_gsol_to: *mint_gsol_to,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            gsol_mint_authority: self.sunrise.gsol_mint_authority(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_state: *marinade_state,
+            msol_mint: self.marinade.msol_mint,
+            msol_vault: self.msol_vault(),
+            vault_authority: self.vault_authority(),
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(marinade_state),
+            liq_pool_msol_leg_authority: marinade::liq_pool_msol_leg_authority(marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            msol_mint_authority: marinade::msol_mint_authority(marinade_state),
+            reserve_pda: marinade::reserve_address(marinade_state),
+            marinade_program: marinade::ID,
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::Deposit { lamports });
+        self.sunrise.with_attribution(instruction, depositor)
+    }
+
+    /// Deposit `stake_account`, delegated to `vote_account`, which is at `validator_index`
+    /// in Marinade's validator list.
+    pub fn deposit_stake_account(
+        &self,
+        stake_owner: &Pubkey,
+        stake_account: &Pubkey,
+        vote_account: &Pubkey,
+        validator_index: u32,
+        mint_gsol_to: &Pubkey,
+    ) -> Instruction {
+        let marinade_state = &self.state.marinade_state;
+        let accounts = accounts::DepositStake {
+            state: self.state_address,
+            marinade_state: *marinade_state,
+            sunrise_state: self.state.sunrise_state,
+            stake_owner: *stake_owner,
+            stake_account: *stake_account,
+            mint_gsol_to: *mint_gsol_to,
+            msol_mint: self.marinade.msol_mint,
+            msol_vault: self.msol_vault(),
+            vault_authority: self.vault_authority(),
+            gsol_mint: self.sunrise.state.gsol_mint,
+            gsol_mint_authority: self.sunrise.gsol_mint_authority(),
+            sysvar_instructions: sysvar::instructions::id(),
+            validator_list: self.marinade.validator_system.validator_list.account,
+            stake_list: self.marinade.stake_system.stake_list.account,
+            duplication_flag: marinade::duplication_flag(marinade_state, vote_account),
+            msol_mint_authority: marinade::msol_mint_authority(marinade_state),
+            stake_program: stake::program::ID,
+            sunrise_program: self.sunrise.program_id,
+            marinade_program: marinade::ID,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let data = ix::DepositStakeAccount { validator_index };
+        let instruction = instruction(&self.program_id, accounts, data);
+        self.sunrise.with_attribution(instruction, stake_owner)
+    }
+
+    pub fn withdraw(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Instruction {
+        let accounts = accounts::Withdraw {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_state: self.state.marinade_state,
+            msol_mint: self.marinade.msol_mint,
+            msol_vault: self.msol_vault(),
+            vault_authority: self.vault_authority(),
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(&self.state.marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            treasury_msol_account: self.marinade.treasury_msol_account,
+            marinade_program: marinade::ID,
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let data = ix::Withdraw {
+            lamports,
+            min_lamports_out,
+        };
+        let instruction = instruction(&self.program_id, accounts, data);
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    pub fn withdraw_as_lst(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        transfer_msol_to: &Pubkey,
+        lamports: u64,
+    ) -> Instruction {
+        let accounts = accounts::WithdrawAsLst {
+            state: self.state_address,
+            marinade_state: self.state.marinade_state,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            msol_mint: self.marinade.msol_mint,
+            msol_vault: self.msol_vault(),
+            vault_authority: self.vault_authority(),
+            transfer_msol_to: *transfer_msol_to,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::WithdrawAsLst { lamports });
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    /// Create the Marinade ticket account that [MarinadeClient::order_withdrawal] fills.
+    /// `ticket_account` must sign.
+    pub fn create_ticket_account(
+        &self,
+        payer: &Pubkey,
+        ticket_account: &Pubkey,
+        rent: &Rent,
+    ) -> Instruction {
+        system_instruction::create_account(
+            payer,
+            ticket_account,
+            rent.minimum_balance(marinade::TICKET_ACCOUNT_SIZE),
+            marinade::TICKET_ACCOUNT_SIZE as u64,
+            &marinade::ID,
+        )
+    }
+
+    /// Order a delayed unstake into the Marinade ticket account `new_ticket_account`,
+    /// which must have been created with [MarinadeClient::create_ticket_account].
+    /// The new `proxy_ticket_account` must sign.
+    pub fn order_withdrawal(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        new_ticket_account: &Pubkey,
+        proxy_ticket_account: &Pubkey,
+        lamports: u64,
+    ) -> Instruction {
+        let accounts = accounts::OrderWithdrawal {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_state: self.state.marinade_state,
+            msol_mint: self.marinade.msol_mint,
+            msol_vault: self.msol_vault(),
+            vault_authority: self.vault_authority(),
+            new_ticket_account: *new_ticket_account,
+            proxy_ticket_account: *proxy_ticket_account,
+            marinade_program: marinade::ID,
+            clock: sysvar::clock::id(),
+            rent: sysvar::rent::id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::OrderWithdrawal { lamports });
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    pub fn burn(&self, burner: &Pubkey, gsol_token_account: &Pubkey, lamports: u64) -> Instruction {
+        let accounts = accounts::Burn {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            burner: *burner,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            vault_authority: self.vault_authority(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::Burn { lamports });
+        self.sunrise.with_attribution(instruction, burner)
+    }
+
+    pub fn redeem_ticket(
+        &self,
+        proxy_ticket_account: &Pubkey,
+        ticket: &ProxyTicket,
+    ) -> Instruction {
+        let accounts = accounts::RedeemTicket {
+            state: self.state_address,
+            beneficiary: ticket.beneficiary,
+            marinade_state: self.state.marinade_state,
+            sunrise_ticket_account: *proxy_ticket_account,
+            marinade_ticket_account: ticket.marinade_ticket_account,
+            reserve_pda: marinade::reserve_address(&self.state.marinade_state),
+            vault_authority: self.vault_authority(),
+            marinade_program: marinade::ID,
+            clock: sysvar::clock::id(),
+            system_program: system_program::id(),
+        };
+        instruction(&self.program_id, accounts, ix::RedeemTicket {})
+    }
+
+    pub fn extract_yield(&self, payer: &Pubkey) -> Instruction {
+        let accounts = accounts::ExtractYield {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            yield_account: self.sunrise.state.yield_account,
+            treasury: self.sunrise.treasury(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_state: self.state.marinade_state,
+            payer: *payer,
+            msol_mint: self.marinade.msol_mint,
+            msol_vault: self.msol_vault(),
+            vault_authority: self.vault_authority(),
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(&self.state.marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            treasury_msol_account: self.marinade.treasury_msol_account,
+            marinade_program: marinade::ID,
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        instruction(&self.program_id, accounts, ix::ExtractYield {})
+    }
+
+    /// Top up the liquidity buffer of the paired `liquidity_beam` to its target size.
+    pub fn fund_liquidity_beam(&self, liquidity_beam: &MarinadeLpClient) -> Instruction {
+        let marinade_state = &self.state.marinade_state;
+        let accounts = accounts::FundLiquidityBeam {
+            state: self.state_address,
+            marinade_state: *marinade_state,
+            sunrise_state: self.state.sunrise_state,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            liquidity_beam: liquidity_beam.state_address,
+            liquidity_beam_vault: liquidity_beam.liq_pool_token_vault(),
+            liquidity_beam_vault_authority: liquidity_beam.vault_authority(),
+            msol_mint: self.marinade.msol_mint,
+            msol_vault: self.msol_vault(),
+            vault_authority: self.vault_authority(),
+            liq_pool_mint: self.marinade.liq_pool.lp_mint,
+            liq_pool_mint_authority: marinade::liq_pool_mint_authority(marinade_state),
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(marinade_state),
+            liq_pool_msol_leg: self.marinade.liq_pool.msol_leg,
+            treasury_msol_account: self.marinade.treasury_msol_account,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_program: marinade::ID,
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        instruction(&self.program_id, accounts, ix::FundLiquidityBeam {})
+    }
+
+    pub fn update_epoch_report(&self) -> Instruction {
+        let accounts = accounts::UpdateEpochReport {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            backing_oracle: self.sunrise.backing_oracle(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            marinade_state: self.state.marinade_state,
+            msol_mint: self.marinade.msol_mint,
+            msol_vault: self.msol_vault(),
+            vault_authority: self.vault_authority(),
+        };
+        instruction(&self.program_id, accounts, ix::UpdateEpochReport {})
+    }
+
+    fn view_accounts(&self) -> accounts::View {
+        accounts::View {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            marinade_state: self.state.marinade_state,
+            msol_mint: self.marinade.msol_mint,
+            msol_vault: self.msol_vault(),
+            vault_authority: self.vault_authority(),
+            liq_pool_sol_leg_pda: marinade::liq_pool_sol_leg(&self.state.marinade_state),
+        }
+    }
+
+    pub fn quote_deposit(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteDeposit { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn quote_withdraw(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteWithdraw { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn get_beam_value(&self) -> Instruction {
+        instruction(&self.program_id, self.view_accounts(), ix::GetBeamValue {})
+    }
+}
@@ -0,0 +1,362 @@
+//! The native stake beam.
+//!
+//! The builders of the instructions that value the beam's stake pass every stake account held
+//! by the beam, as the beam state was last fetched, so the client must be reconstructed after
+//! the beam's stake accounts change.
+
+use crate::{instruction, SunriseClient};
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::{instruction::Instruction, stake, system_program, sysvar};
+use native_stake_beam::{accounts, instruction as ix, seeds};
+
+pub use native_stake_beam::state::{State, StateEntry, WithdrawalTicket};
+pub use native_stake_beam::ID;
+
+/// Derive the address of the state account for this beam.
+pub fn derive_state_address(program_id: &Pubkey, sunrise_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[seeds::STATE, sunrise_state.as_ref()], program_id)
+}
+
+/// Derive the address of the PDA vault that holds the beam's undelegated SOL,
+/// and is the authority of its stake accounts.
+pub fn derive_vault_authority(program_id: &Pubkey, state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[state.as_ref(), seeds::VAULT_AUTHORITY], program_id)
+}
+
+/// Derive the address of the withdrawal ticket for a delayed withdrawal into `stake_account`.
+pub fn derive_withdrawal_ticket(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    stake_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            state.as_ref(),
+            seeds::WITHDRAWAL_TICKET,
+            stake_account.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Build an `initialize` instruction for a new beam state.
+pub fn initialize(program_id: &Pubkey, payer: &Pubkey, input: StateEntry) -> Instruction {
+    let state = derive_state_address(program_id, &input.sunrise_state).0;
+    let accounts = accounts::Initialize {
+        payer: *payer,
+        state,
+        vault_authority: derive_vault_authority(program_id, &state).0,
+        system_program: system_program::id(),
+    };
+    instruction(program_id, accounts, ix::Initialize { input })
+}
+
+/// A native stake beam state and the Sunrise state it is registered with.
+#[derive(Clone)]
+pub struct NativeStakeClient {
+    pub program_id: Pubkey,
+    /// The beam state address.
+    pub state_address: Pubkey,
+    /// The deserialized on-chain account for this beam state.
+    pub state: State,
+    pub sunrise: SunriseClient,
+}
+
+impl NativeStakeClient {
+    pub fn new(
+        program_id: Pubkey,
+        state_address: Pubkey,
+        state: State,
+        sunrise: SunriseClient,
+    ) -> Self {
+        Self {
+            program_id,
+            state_address,
+            state,
+            sunrise,
+        }
+    }
+
+    /// The PDA vault that holds the beam's undelegated SOL.
+    pub fn vault_authority(&self) -> Pubkey {
+        derive_vault_authority(&self.program_id, &self.state_address).0
+    }
+
+    /// The stake accounts held by the beam, in state order,
+    /// as passed to the instructions that value the beam's stake.
+    fn stake_accounts(&self) -> impl Iterator<Item = AccountMeta> + '_ {
+        self.state
+            .stake_accounts
+            .iter()
+            .map(|stake_account| AccountMeta::new_readonly(*stake_account, false))
+    }
+
+    /// Append the beam's stake accounts to `instruction`.
+    fn with_stake_accounts(&self, mut instruction: Instruction) -> Instruction {
+        instruction.accounts.extend(self.stake_accounts());
+        instruction
+    }
+
+    pub fn update(&self, update_input: StateEntry) -> Instruction {
+        let accounts = accounts::Update {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+        };
+        instruction(&self.program_id, accounts, ix::Update { update_input })
+    }
+
+    pub fn add_validator(&self, vote_account: &Pubkey) -> Instruction {
+        let accounts = accounts::Update {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+        };
+        let data = ix::AddValidator {
+            vote_account: *vote_account,
+        };
+        instruction(&self.program_id, accounts, data)
+    }
+
+    pub fn remove_validator(&self, vote_account: &Pubkey) -> Instruction {
+        let accounts = accounts::Update {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+        };
+        let data = ix::RemoveValidator {
+            vote_account: *vote_account,
+        };
+        instruction(&self.program_id, accounts, data)
+    }
+
+    /// Delegate `lamports` from the vault to `vote_account`, in the new `new_stake_account`,
+    /// which must sign.
+    pub fn delegate(
+        &self,
+        new_stake_account: &Pubkey,
+        vote_account: &Pubkey,
+        lamports: u64,
+    ) -> Instruction {
+        let accounts = accounts::Delegate {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+            vault_authority: self.vault_authority(),
+            new_stake_account: *new_stake_account,
+            vote_account: *vote_account,
+            sysvar_clock: sysvar::clock::id(),
+            sysvar_rent: sysvar::rent::id(),
+            sysvar_stake_history: sysvar::stake_history::id(),
+            stake_config: stake::config::ID,
+            native_stake_program: stake::program::ID,
+            system_program: system_program::id(),
+        };
+        instruction(&self.program_id, accounts, ix::Delegate { lamports })
+    }
+
+    pub fn deactivate_stake(&self, stake_account: &Pubkey) -> Instruction {
+        let accounts = accounts::DeactivateStake {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+            vault_authority: self.vault_authority(),
+            stake_account: *stake_account,
+            sysvar_clock: sysvar::clock::id(),
+            native_stake_program: stake::program::ID,
+        };
+        instruction(&self.program_id, accounts, ix::DeactivateStake {})
+    }
+
+    /// Merge `source_stake_account` into `destination_stake_account`.
+    pub fn merge_stake(
+        &self,
+        destination_stake_account: &Pubkey,
+        source_stake_account: &Pubkey,
+    ) -> Instruction {
+        let accounts = accounts::MergeStake {
+            state: self.state_address,
+            vault_authority: self.vault_authority(),
+            destination_stake_account: *destination_stake_account,
+            source_stake_account: *source_stake_account,
+            sysvar_clock: sysvar::clock::id(),
+            sysvar_stake_history: sysvar::stake_history::id(),
+            native_stake_program: stake::program::ID,
+        };
+        instruction(&self.program_id, accounts, ix::MergeStake {})
+    }
+
+    pub fn withdraw_inactive_stake(&self, stake_account: &Pubkey) -> Instruction {
+        let accounts = accounts::WithdrawInactiveStake {
+            state: self.state_address,
+            vault_authority: self.vault_authority(),
+            stake_account: *stake_account,
+            sysvar_clock: sysvar::clock::id(),
+            sysvar_stake_history: sysvar::stake_history::id(),
+            native_stake_program: stake::program::ID,
+        };
+        instruction(&self.program_id, accounts, ix::WithdrawInactiveStake {})
+    }
+
+    pub fn deposit(&self, depositor: &Pubkey, mint_gsol_to: &Pubkey, lamports: u64) -> Instruction {
+        let accounts = accounts::Deposit {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            depositor: *depositor,
+            mint_gsol_to: *mint_gsol_to,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            gsol_mint_authority: self.sunrise.gsol_mint_authority(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            vault_authority: self.vault_authority(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::Deposit { lamports });
+        self.sunrise.with_attribution(instruction, depositor)
+    }
+
+    pub fn withdraw(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Instruction {
+        let accounts = accounts::Withdraw {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            vault_authority: self.vault_authority(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let data = ix::Withdraw {
+            lamports,
+            min_lamports_out,
+        };
+        let instruction = instruction(&self.program_id, accounts, data);
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    /// Order a delayed withdrawal of `lamports`, split from the beam's `stake_account`
+    /// into the new `new_stake_account`, which must sign.
+    pub fn order_withdrawal(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        stake_account: &Pubkey,
+        new_stake_account: &Pubkey,
+        lamports: u64,
+    ) -> Instruction {
+        let accounts = accounts::OrderWithdrawal {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            stake_account: *stake_account,
+            new_stake_account: *new_stake_account,
+            withdrawal_ticket: derive_withdrawal_ticket(
+                &self.program_id,
+                &self.state_address,
+                new_stake_account,
+            )
+            .0,
+            vault_authority: self.vault_authority(),
+            sysvar_clock: sysvar::clock::id(),
+            native_stake_program: stake::program::ID,
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::OrderWithdrawal { lamports });
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    pub fn redeem_ticket(&self, ticket: &WithdrawalTicket) -> Instruction {
+        let accounts = accounts::RedeemTicket {
+            state: self.state_address,
+            beneficiary: ticket.beneficiary,
+            withdrawal_ticket: derive_withdrawal_ticket(
+                &self.program_id,
+                &self.state_address,
+                &ticket.stake_account,
+            )
+            .0,
+            stake_account: ticket.stake_account,
+            vault_authority: self.vault_authority(),
+            sysvar_clock: sysvar::clock::id(),
+            sysvar_stake_history: sysvar::stake_history::id(),
+            native_stake_program: stake::program::ID,
+        };
+        instruction(&self.program_id, accounts, ix::RedeemTicket {})
+    }
+
+    pub fn burn(&self, burner: &Pubkey, gsol_token_account: &Pubkey, lamports: u64) -> Instruction {
+        let accounts = accounts::Burn {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            burner: *burner,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::Burn { lamports });
+        self.sunrise.with_attribution(instruction, burner)
+    }
+
+    pub fn update_epoch_report(&self) -> Instruction {
+        let accounts = accounts::UpdateEpochReport {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            backing_oracle: self.sunrise.backing_oracle(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            vault_authority: self.vault_authority(),
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::UpdateEpochReport {});
+        self.with_stake_accounts(instruction)
+    }
+
+    pub fn extract_yield(&self) -> Instruction {
+        let accounts = accounts::ExtractYield {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            yield_account: self.sunrise.state.yield_account,
+            treasury: self.sunrise.treasury(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            vault_authority: self.vault_authority(),
+            system_program: system_program::id(),
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::ExtractYield {});
+        self.with_stake_accounts(instruction)
+    }
+
+    fn view_accounts(&self) -> accounts::View {
+        accounts::View {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            vault_authority: self.vault_authority(),
+        }
+    }
+
+    pub fn quote_deposit(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteDeposit { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn quote_withdraw(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteWithdraw { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn get_beam_value(&self) -> Instruction {
+        let instruction = instruction(&self.program_id, self.view_accounts(), ix::GetBeamValue {});
+        self.with_stake_accounts(instruction)
+    }
+}
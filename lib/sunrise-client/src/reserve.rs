@@ -0,0 +1,153 @@
+//! The reserve beam.
+//!
+//! The beam's `order_withdrawal`, `redeem_ticket`, `burn` and `extract_yield` instructions
+//! are unsupported, so no builders are provided for them.
+
+use crate::{instruction, SunriseClient};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{instruction::Instruction, system_program, sysvar};
+use reserve_beam::{accounts, instruction as ix, seeds};
+
+pub use reserve_beam::state::{State, StateEntry};
+pub use reserve_beam::ID;
+
+/// Derive the address of the state account for this beam.
+pub fn derive_state_address(program_id: &Pubkey, sunrise_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[seeds::STATE, sunrise_state.as_ref()], program_id)
+}
+
+/// Derive the address of the PDA vault that holds the reserve's SOL.
+pub fn derive_vault_authority(program_id: &Pubkey, state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[state.as_ref(), seeds::VAULT_AUTHORITY], program_id)
+}
+
+/// Build an `initialize` instruction for a new beam state.
+pub fn initialize(program_id: &Pubkey, payer: &Pubkey, input: StateEntry) -> Instruction {
+    let state = derive_state_address(program_id, &input.sunrise_state).0;
+    let accounts = accounts::Initialize {
+        payer: *payer,
+        state,
+        vault_authority: derive_vault_authority(program_id, &state).0,
+        system_program: system_program::id(),
+    };
+    instruction(program_id, accounts, ix::Initialize { input })
+}
+
+/// A reserve beam state and the Sunrise state it is registered with.
+#[derive(Clone)]
+pub struct ReserveClient {
+    pub program_id: Pubkey,
+    /// The beam state address.
+    pub state_address: Pubkey,
+    /// The deserialized on-chain account for this beam state.
+    pub state: State,
+    pub sunrise: SunriseClient,
+}
+
+impl ReserveClient {
+    pub fn new(
+        program_id: Pubkey,
+        state_address: Pubkey,
+        state: State,
+        sunrise: SunriseClient,
+    ) -> Self {
+        Self {
+            program_id,
+            state_address,
+            state,
+            sunrise,
+        }
+    }
+
+    /// The PDA vault that holds the reserve's SOL.
+    pub fn vault_authority(&self) -> Pubkey {
+        derive_vault_authority(&self.program_id, &self.state_address).0
+    }
+
+    pub fn update(&self, update_input: StateEntry) -> Instruction {
+        let accounts = accounts::Update {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+        };
+        instruction(&self.program_id, accounts, ix::Update { update_input })
+    }
+
+    pub fn deposit(&self, depositor: &Pubkey, mint_gsol_to: &Pubkey, lamports: u64) -> Instruction {
+        let accounts = accounts::Deposit {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            depositor: *depositor,
+            mint_gsol_to: *mint_gsol_to,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            gsol_mint_authority: self.sunrise.gsol_mint_authority(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            vault_authority: self.vault_authority(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::Deposit { lamports });
+        self.sunrise.with_attribution(instruction, depositor)
+    }
+
+    pub fn withdraw(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Instruction {
+        let accounts = accounts::Withdraw {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            vault_authority: self.vault_authority(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let data = ix::Withdraw {
+            lamports,
+            min_lamports_out,
+        };
+        let instruction = instruction(&self.program_id, accounts, data);
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    pub fn update_epoch_report(&self) -> Instruction {
+        let accounts = accounts::UpdateEpochReport {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            backing_oracle: self.sunrise.backing_oracle(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+        };
+        instruction(&self.program_id, accounts, ix::UpdateEpochReport {})
+    }
+
+    fn view_accounts(&self) -> accounts::View {
+        accounts::View {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            vault_authority: self.vault_authority(),
+        }
+    }
+
+    pub fn quote_deposit(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteDeposit { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn quote_withdraw(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteWithdraw { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn get_beam_value(&self) -> Instruction {
+        instruction(&self.program_id, self.view_accounts(), ix::GetBeamValue {})
+    }
+}
@@ -0,0 +1,184 @@
+//! Async wrappers over an RPC client: fetching the accounts needed to construct the program
+//! clients, simulating the beam view instructions, and sending transactions.
+
+use crate::marinade::MarinadeState;
+use crate::{
+    decode, marinade_lp, marinade_sp, native_stake, reserve, spl, sunrise, ClientError,
+    MarinadeClient, MarinadeLpClient, NativeStakeClient, ReserveClient, Result, SplClient,
+    SunriseClient,
+};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize};
+use base64::Engine;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::message::Message;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+/// Fetch and decode the anchor account of type `T` at `address`.
+pub async fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())
+        .await?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    decode(address, &account.data)
+}
+
+/// Fetch an instance for an existing Sunrise state.
+pub async fn sunrise_client(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    state_address: &Pubkey,
+) -> Result<SunriseClient> {
+    let state: sunrise::State = fetch(rpc, state_address).await?;
+    Ok(SunriseClient::new(*program_id, *state_address, state))
+}
+
+/// Fetch an instance for an existing SPL beam state,
+/// registered with a Sunrise state owned by the default Sunrise program.
+pub async fn spl_client(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    state_address: &Pubkey,
+) -> Result<SplClient> {
+    let state: spl::State = fetch(rpc, state_address).await?;
+    let stake_pool: spl::StakePool = fetch(rpc, &state.stake_pool).await?;
+    let validator_list = rpc
+        .get_account_with_commitment(&stake_pool.validator_list, rpc.commitment())
+        .await?
+        .value
+        .ok_or(ClientError::AccountNotFound(stake_pool.validator_list))?;
+    let validator_list =
+        spl::decode_validator_list(&stake_pool.validator_list, &validator_list.data)?;
+    let sunrise = sunrise_client(rpc, &sunrise::ID, &state.sunrise_state).await?;
+    Ok(SplClient::new(
+        *program_id,
+        *state_address,
+        state,
+        stake_pool,
+        validator_list,
+        sunrise,
+    ))
+}
+
+/// Fetch an instance for an existing Marinade beam state,
+/// registered with a Sunrise state owned by the default Sunrise program.
+pub async fn marinade_client(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    state_address: &Pubkey,
+) -> Result<MarinadeClient> {
+    let state: marinade_sp::State = fetch(rpc, state_address).await?;
+    let marinade: MarinadeState = fetch(rpc, &state.marinade_state).await?;
+    let sunrise = sunrise_client(rpc, &sunrise::ID, &state.sunrise_state).await?;
+    Ok(MarinadeClient::new(
+        *program_id,
+        *state_address,
+        state,
+        marinade,
+        sunrise,
+    ))
+}
+
+/// Fetch an instance for an existing Marinade liquidity-pool beam state,
+/// registered with a Sunrise state owned by the default Sunrise program.
+pub async fn marinade_lp_client(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    state_address: &Pubkey,
+) -> Result<MarinadeLpClient> {
+    let state: marinade_lp::State = fetch(rpc, state_address).await?;
+    let marinade: MarinadeState = fetch(rpc, &state.marinade_state).await?;
+    let sunrise = sunrise_client(rpc, &sunrise::ID, &state.sunrise_state).await?;
+    Ok(MarinadeLpClient::new(
+        *program_id,
+        *state_address,
+        state,
+        marinade,
+        sunrise,
+    ))
+}
+
+/// Fetch an instance for an existing native stake beam state,
+/// registered with a Sunrise state owned by the default Sunrise program.
+pub async fn native_stake_client(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    state_address: &Pubkey,
+) -> Result<NativeStakeClient> {
+    let state: native_stake::State = fetch(rpc, state_address).await?;
+    let sunrise = sunrise_client(rpc, &sunrise::ID, &state.sunrise_state).await?;
+    Ok(NativeStakeClient::new(
+        *program_id,
+        *state_address,
+        state,
+        sunrise,
+    ))
+}
+
+/// Fetch an instance for an existing reserve beam state,
+/// registered with a Sunrise state owned by the default Sunrise program.
+pub async fn reserve_client(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    state_address: &Pubkey,
+) -> Result<ReserveClient> {
+    let state: reserve::State = fetch(rpc, state_address).await?;
+    let sunrise = sunrise_client(rpc, &sunrise::ID, &state.sunrise_state).await?;
+    Ok(ReserveClient::new(
+        *program_id,
+        *state_address,
+        state,
+        sunrise,
+    ))
+}
+
+/// Simulate a view instruction, such as one built by `quote_deposit`, and decode its result.
+///
+/// `payer` is only used as the fee payer of the simulated transaction, so it must exist,
+/// but it does not sign.
+pub async fn simulate_view<T: AnchorDeserialize>(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    instruction: Instruction,
+) -> Result<T> {
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(payer)));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc
+        .simulate_transaction_with_config(&transaction, config)
+        .await?
+        .value;
+    if let Some(err) = result.err {
+        return Err(ClientError::SimulationFailed(format!(
+            "{err}, logs: {:?}",
+            result.logs.unwrap_or_default()
+        )));
+    }
+
+    let (data, _encoding) = result.return_data.ok_or(ClientError::NoReturnData)?.data;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| ClientError::InvalidReturnData(e.to_string()))?;
+    T::try_from_slice(&bytes).map_err(|e| ClientError::InvalidReturnData(e.to_string()))
+}
+
+/// Sign `instructions` with `signers`, which must include `payer`, send them in a single
+/// transaction, and wait for it to be confirmed.
+pub async fn send(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &[&dyn Signer],
+) -> Result<Signature> {
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction =
+        Transaction::new_signed_with_payer(instructions, Some(payer), signers, blockhash);
+    Ok(rpc.send_and_confirm_transaction(&transaction).await?)
+}
@@ -0,0 +1,888 @@
+//! The SPL stake-pool beam.
+
+use crate::{instruction, ClientError, SunriseClient};
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::borsh::BorshDeserialize;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::{instruction::Instruction, stake, system_program, sysvar};
+use anchor_spl::associated_token::get_associated_token_address;
+use spl_beam::{accounts, instruction as ix, seeds};
+use spl_stake_pool::MAX_VALIDATORS_TO_UPDATE;
+use std::num::NonZeroU32;
+
+pub use spl_beam::cpi_interface::stake_pool::StakePool;
+pub use spl_beam::state::{State, StateEntry, WithdrawalTicket};
+pub use spl_beam::ID;
+pub use spl_stake_pool::state::{ValidatorList, ValidatorStakeInfo};
+
+/// Derive the address of the state account for this beam.
+pub fn derive_state_address(
+    program_id: &Pubkey,
+    sunrise_state: &Pubkey,
+    stake_pool: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[seeds::STATE, sunrise_state.as_ref(), stake_pool.as_ref()],
+        program_id,
+    )
+}
+
+/// Derive the address of the PDA authority for this beam's token vault.
+pub fn derive_vault_authority(program_id: &Pubkey, state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[state.as_ref(), seeds::VAULT_AUTHORITY], program_id)
+}
+
+/// Derive the address of the stake account that yield is extracted into.
+pub fn derive_extract_yield_stake_account(program_id: &Pubkey, state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[state.as_ref(), seeds::EXTRACT_YIELD_STAKE_ACCOUNT],
+        program_id,
+    )
+}
+
+/// Derive the address of the ticket of a withdrawal ordered into `stake_account`.
+pub fn derive_withdrawal_ticket(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    stake_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            state.as_ref(),
+            seeds::WITHDRAWAL_TICKET,
+            stake_account.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Decode the validator list of a stake pool.
+/// The list is followed by unused space for more validators, which is ignored.
+pub fn decode_validator_list(address: &Pubkey, data: &[u8]) -> crate::Result<ValidatorList> {
+    ValidatorList::deserialize(&mut &data[..]).map_err(|_| {
+        ClientError::InvalidAccountData(*address, ErrorCode::AccountDidNotDeserialize.into())
+    })
+}
+
+/// Build an `initialize` instruction for a new beam state over `input.stake_pool`.
+pub fn initialize(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    pool_mint: &Pubkey,
+    input: StateEntry,
+) -> Instruction {
+    let state = derive_state_address(program_id, &input.sunrise_state, &input.stake_pool).0;
+    let vault_authority = derive_vault_authority(program_id, &state).0;
+    let accounts = accounts::Initialize {
+        payer: *payer,
+        state,
+        pool_mint: *pool_mint,
+        pool_token_vault: get_associated_token_address(&vault_authority, pool_mint),
+        vault_authority,
+        token_program: anchor_spl::token::ID,
+        system_program: system_program::id(),
+        associated_token_program: anchor_spl::associated_token::ID,
+    };
+    instruction(program_id, accounts, ix::Initialize { input })
+}
+
+/// An SPL beam state, the stake pool it deposits into, and the Sunrise state it is registered with.
+#[derive(Clone)]
+pub struct SplClient {
+    pub program_id: Pubkey,
+    /// The beam state address.
+    pub state_address: Pubkey,
+    /// The deserialized on-chain account for this beam state.
+    pub state: State,
+    /// The deserialized stake pool account.
+    pub stake_pool: StakePool,
+    /// The deserialized validator list of the stake pool.
+    pub validator_list: ValidatorList,
+    pub sunrise: SunriseClient,
+}
+
+impl SplClient {
+    pub fn new(
+        program_id: Pubkey,
+        state_address: Pubkey,
+        state: State,
+        stake_pool: StakePool,
+        validator_list: ValidatorList,
+        sunrise: SunriseClient,
+    ) -> Self {
+        Self {
+            program_id,
+            state_address,
+            state,
+            stake_pool,
+            validator_list,
+            sunrise,
+        }
+    }
+
+    pub fn vault_authority(&self) -> Pubkey {
+        derive_vault_authority(&self.program_id, &self.state_address).0
+    }
+
+    /// The beam's pool token account.
+    pub fn pool_token_vault(&self) -> Pubkey {
+        get_associated_token_address(&self.vault_authority(), &self.stake_pool.pool_mint)
+    }
+
+    pub fn stake_pool_withdraw_authority(&self) -> Pubkey {
+        spl_stake_pool::find_withdraw_authority_program_address(
+            &spl_stake_pool::id(),
+            &self.state.stake_pool,
+        )
+        .0
+    }
+
+    /// The pool's stake account for the validator with `vote_account`,
+    /// or None if the validator is not in the pool's validator list.
+    pub fn validator_stake_account(&self, vote_account: &Pubkey) -> Option<Pubkey> {
+        let validator = self
+            .validator_list
+            .validators
+            .iter()
+            .find(|validator| validator.vote_account_address == *vote_account)?;
+        let (validator_stake_account, _) = spl_stake_pool::find_stake_program_address(
+            &spl_stake_pool::id(),
+            vote_account,
+            &self.state.stake_pool,
+            NonZeroU32::new(validator.validator_seed_suffix.into()),
+        );
+        Some(validator_stake_account)
+    }
+
+    /// The pool stake account to split withdrawn stake from: that of the validator with
+    /// `vote_account` if given, or else of the pool's preferred withdrawal validator,
+    /// falling back to the pool's reserve.
+    pub fn stake_account_to_split(&self, vote_account: Option<Pubkey>) -> Pubkey {
+        vote_account
+            .or(self.stake_pool.preferred_withdraw_validator_vote_address)
+            .and_then(|vote_account| self.validator_stake_account(&vote_account))
+            .unwrap_or(self.stake_pool.reserve_stake)
+    }
+
+    pub fn update(&self, update_input: StateEntry) -> Instruction {
+        let accounts = accounts::Update {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+        };
+        instruction(&self.program_id, accounts, ix::Update { update_input })
+    }
+
+    pub fn deposit(&self, depositor: &Pubkey, mint_gsol_to: &Pubkey, lamports: u64) -> Instruction {
+        let accounts = accounts::Deposit {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            depositor: *depositor,
+            mint_gsol_to: *mint_gsol_to,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            gsol_mint_authority: self.sunrise.gsol_mint_authority(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            stake_pool: self.state.stake_pool,
+            pool_mint: self.stake_pool.pool_mint,
+            pool_token_vault: self.pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            stake_pool_withdraw_authority: self.stake_pool_withdraw_authority(),
+            reserve_stake_account: self.stake_pool.reserve_stake,
+            manager_fee_account: self.stake_pool.manager_fee_account,
+            spl_stake_pool_program: spl_stake_pool::id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::Deposit { lamports });
+        self.sunrise.with_attribution(instruction, depositor)
+    }
+
+    /// Deposit `stake_account`, delegated to the pool validator whose pool stake account
+    /// is `validator_stake_account`.
+    pub fn deposit_stake(
+        &self,
+        stake_owner: &Pubkey,
+        stake_account: &Pubkey,
+        validator_stake_account: &Pubkey,
+        mint_gsol_to: &Pubkey,
+    ) -> Instruction {
+        let accounts = accounts::DepositStake {
+            state: self.state_address,
+            stake_pool: self.state.stake_pool,
+            sunrise_state: self.state.sunrise_state,
+            stake_owner: *stake_owner,
+            stake_account: *stake_account,
+            mint_gsol_to: *mint_gsol_to,
+            pool_mint: self.stake_pool.pool_mint,
+            pool_token_vault: self.pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            validator_list: self.stake_pool.validator_list,
+            stake_pool_deposit_authority: self.stake_pool.stake_deposit_authority,
+            stake_pool_withdraw_authority: self.stake_pool_withdraw_authority(),
+            reserve_stake_account: self.stake_pool.reserve_stake,
+            validator_stake_account: *validator_stake_account,
+            manager_fee_account: self.stake_pool.manager_fee_account,
+            sysvar_stake_history: sysvar::stake_history::id(),
+            sysvar_clock: sysvar::clock::id(),
+            native_stake_program: stake::program::ID,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            gsol_mint_authority: self.sunrise.gsol_mint_authority(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            spl_stake_pool_program: spl_stake_pool::id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::DepositStake {});
+        self.sunrise.with_attribution(instruction, stake_owner)
+    }
+
+    pub fn withdraw(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Instruction {
+        let accounts = accounts::Withdraw {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            stake_pool: self.state.stake_pool,
+            pool_mint: self.stake_pool.pool_mint,
+            pool_token_vault: self.pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            stake_pool_withdraw_authority: self.stake_pool_withdraw_authority(),
+            reserve_stake_account: self.stake_pool.reserve_stake,
+            manager_fee_account: self.stake_pool.manager_fee_account,
+            sysvar_stake_history: sysvar::stake_history::id(),
+            sysvar_clock: sysvar::clock::id(),
+            native_stake_program: stake::program::ID,
+            spl_stake_pool_program: spl_stake_pool::id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let data = ix::Withdraw {
+            lamports,
+            min_lamports_out,
+        };
+        let instruction = instruction(&self.program_id, accounts, data);
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    pub fn withdraw_as_lst(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        transfer_pool_tokens_to: &Pubkey,
+        lamports: u64,
+    ) -> Instruction {
+        let accounts = accounts::WithdrawAsLst {
+            state: self.state_address,
+            stake_pool: self.state.stake_pool,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            pool_mint: self.stake_pool.pool_mint,
+            pool_token_vault: self.pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            transfer_pool_tokens_to: *transfer_pool_tokens_to,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::WithdrawAsLst { lamports });
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    /// Withdraw into `new_stake_account`, which must also sign, split from the pool's stake account
+    /// for `vote_account` as resolved by [SplClient::stake_account_to_split].
+    /// The withdrawer is the authority of the new stake account.
+    pub fn withdraw_stake(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        new_stake_account: &Pubkey,
+        lamports: u64,
+        vote_account: Option<Pubkey>,
+    ) -> Instruction {
+        let accounts = accounts::WithdrawStake {
+            state: self.state_address,
+            stake_pool: self.state.stake_pool,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            new_stake_account: *new_stake_account,
+            pool_mint: self.stake_pool.pool_mint,
+            pool_token_vault: self.pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            stake_pool_withdraw_authority: self.stake_pool_withdraw_authority(),
+            validator_stake_list: self.stake_pool.validator_list,
+            stake_account_to_split: self.stake_account_to_split(vote_account),
+            manager_fee_account: self.stake_pool.manager_fee_account,
+            sysvar_stake_history: sysvar::stake_history::id(),
+            sysvar_clock: sysvar::clock::id(),
+            native_stake_program: stake::program::ID,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            spl_stake_pool_program: spl_stake_pool::id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let data = ix::WithdrawStake {
+            lamports,
+            vote_account,
+        };
+        let instruction = instruction(&self.program_id, accounts, data);
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    pub fn burn(&self, burner: &Pubkey, gsol_token_account: &Pubkey, lamports: u64) -> Instruction {
+        let accounts = accounts::Burn {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            burner: *burner,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            stake_pool: self.state.stake_pool,
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::Burn { lamports });
+        self.sunrise.with_attribution(instruction, burner)
+    }
+
+    /// Order a withdrawal into `new_stake_account`, which must also sign, split from the pool's
+    /// stake account for `vote_account` as resolved by [SplClient::stake_account_to_split].
+    /// The ticket can be redeemed with [SplClient::redeem_ticket] once the stake has deactivated.
+    pub fn order_withdrawal(
+        &self,
+        withdrawer: &Pubkey,
+        gsol_token_account: &Pubkey,
+        new_stake_account: &Pubkey,
+        lamports: u64,
+        vote_account: Option<Pubkey>,
+    ) -> Instruction {
+        let accounts = accounts::OrderWithdrawal {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            withdrawer: *withdrawer,
+            gsol_token_account: *gsol_token_account,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            stake_pool: self.state.stake_pool,
+            new_stake_account: *new_stake_account,
+            withdrawal_ticket: derive_withdrawal_ticket(
+                &self.program_id,
+                &self.state_address,
+                new_stake_account,
+            )
+            .0,
+            pool_mint: self.stake_pool.pool_mint,
+            pool_token_vault: self.pool_token_vault(),
+            vault_authority: self.vault_authority(),
+            stake_pool_withdraw_authority: self.stake_pool_withdraw_authority(),
+            validator_stake_list: self.stake_pool.validator_list,
+            stake_account_to_split: self.stake_account_to_split(vote_account),
+            manager_fee_account: self.stake_pool.manager_fee_account,
+            sysvar_clock: sysvar::clock::id(),
+            native_stake_program: stake::program::ID,
+            spl_stake_pool_program: spl_stake_pool::id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let instruction = instruction(&self.program_id, accounts, ix::OrderWithdrawal { lamports });
+        self.sunrise.with_attribution(instruction, withdrawer)
+    }
+
+    pub fn redeem_ticket(&self, ticket: &WithdrawalTicket) -> Instruction {
+        let accounts = accounts::RedeemTicket {
+            state: self.state_address,
+            beneficiary: ticket.beneficiary,
+            withdrawal_ticket: derive_withdrawal_ticket(
+                &self.program_id,
+                &self.state_address,
+                &ticket.stake_account,
+            )
+            .0,
+            stake_account: ticket.stake_account,
+            vault_authority: self.vault_authority(),
+            sysvar_clock: sysvar::clock::id(),
+            sysvar_stake_history: sysvar::stake_history::id(),
+            native_stake_program: stake::program::ID,
+        };
+        instruction(&self.program_id, accounts, ix::RedeemTicket {})
+    }
+
+    /// Extract the beam's yield, split from the stake account of the pool's preferred
+    /// withdrawal validator, or its reserve.
+    pub fn extract_yield(&self, payer: &Pubkey) -> Instruction {
+        let accounts = accounts::ExtractYield {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            yield_account: self.sunrise.state.yield_account,
+            treasury: self.sunrise.treasury(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            stake_pool: self.state.stake_pool,
+            payer: *payer,
+            pool_mint: self.stake_pool.pool_mint,
+            new_stake_account: derive_extract_yield_stake_account(
+                &self.program_id,
+                &self.state_address,
+            )
+            .0,
+            vault_authority: self.vault_authority(),
+            pool_token_vault: self.pool_token_vault(),
+            stake_pool_withdraw_authority: self.stake_pool_withdraw_authority(),
+            validator_stake_list: self.stake_pool.validator_list,
+            stake_account_to_split: self.stake_account_to_split(None),
+            manager_fee_account: self.stake_pool.manager_fee_account,
+            sysvar_clock: sysvar::clock::id(),
+            native_stake_program: stake::program::ID,
+            sysvar_stake_history: sysvar::stake_history::id(),
+            spl_stake_pool_program: spl_stake_pool::id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        instruction(&self.program_id, accounts, ix::ExtractYield {})
+    }
+
+    pub fn update_epoch_report(&self) -> Instruction {
+        let accounts = accounts::UpdateEpochReport {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            gsol_mint: self.sunrise.state.gsol_mint,
+            backing_oracle: self.sunrise.backing_oracle(),
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: self.sunrise.program_id,
+            stake_pool: self.state.stake_pool,
+            pool_mint: self.stake_pool.pool_mint,
+            vault_authority: self.vault_authority(),
+            pool_token_vault: self.pool_token_vault(),
+        };
+        instruction(&self.program_id, accounts, ix::UpdateEpochReport {})
+    }
+
+    /// Crank the stake pool's balances, starting at `start_index` of its validator list.
+    /// `validator_stake_accounts` holds the validator and transient stake account pair
+    /// of each validator to update.
+    pub fn refresh_pool(
+        &self,
+        start_index: u32,
+        validator_stake_accounts: &[(Pubkey, Pubkey)],
+    ) -> Instruction {
+        let accounts = accounts::RefreshPool {
+            state: self.state_address,
+            stake_pool: self.state.stake_pool,
+            stake_pool_withdraw_authority: self.stake_pool_withdraw_authority(),
+            validator_list: self.stake_pool.validator_list,
+            reserve_stake_account: self.stake_pool.reserve_stake,
+            manager_fee_account: self.stake_pool.manager_fee_account,
+            pool_mint: self.stake_pool.pool_mint,
+            sysvar_clock: sysvar::clock::id(),
+            sysvar_stake_history: sysvar::stake_history::id(),
+            native_stake_program: stake::program::ID,
+            spl_stake_pool_program: spl_stake_pool::id(),
+            token_program: anchor_spl::token::ID,
+        };
+        let mut refresh = instruction(&self.program_id, accounts, ix::RefreshPool { start_index });
+        refresh
+            .accounts
+            .extend(
+                validator_stake_accounts
+                    .iter()
+                    .flat_map(|(validator, transient)| {
+                        [
+                            AccountMeta::new(*validator, false),
+                            AccountMeta::new(*transient, false),
+                        ]
+                    }),
+            );
+        refresh
+    }
+
+    /// The validator and transient stake account pair of each validator in the pool's
+    /// validator list, as passed to [SplClient::refresh_pool].
+    pub fn validator_stake_account_pairs(&self) -> Vec<(Pubkey, Pubkey)> {
+        self.validator_list
+            .validators
+            .iter()
+            .map(|validator| {
+                let vote_account = &validator.vote_account_address;
+                let (validator_stake_account, _) = spl_stake_pool::find_stake_program_address(
+                    &spl_stake_pool::id(),
+                    vote_account,
+                    &self.state.stake_pool,
+                    NonZeroU32::new(validator.validator_seed_suffix.into()),
+                );
+                let (transient_stake_account, _) =
+                    spl_stake_pool::find_transient_stake_program_address(
+                        &spl_stake_pool::id(),
+                        vote_account,
+                        &self.state.stake_pool,
+                        validator.transient_seed_suffix.into(),
+                    );
+                (validator_stake_account, transient_stake_account)
+            })
+            .collect()
+    }
+
+    /// The [SplClient::refresh_pool] instructions that update the whole validator list,
+    /// as many validators at a time as the stake pool's own update instructions.
+    /// The last one also updates the pool's balance.
+    pub fn refresh_pool_instructions(&self) -> Vec<Instruction> {
+        let pairs = self.validator_stake_account_pairs();
+        if pairs.is_empty() {
+            return vec![self.refresh_pool(0, &[])];
+        }
+        pairs
+            .chunks(MAX_VALIDATORS_TO_UPDATE)
+            .enumerate()
+            .map(|(batch, pairs)| {
+                self.refresh_pool((batch * MAX_VALIDATORS_TO_UPDATE) as u32, pairs)
+            })
+            .collect()
+    }
+
+    /// Whether the stake pool must be refreshed before the beam can be priced in `epoch`.
+    pub fn is_pool_stale(&self, epoch: u64) -> bool {
+        self.stake_pool.last_update_epoch < epoch
+    }
+
+    fn view_accounts(&self) -> accounts::View {
+        accounts::View {
+            state: self.state_address,
+            sunrise_state: self.state.sunrise_state,
+            stake_pool: self.state.stake_pool,
+            pool_mint: self.stake_pool.pool_mint,
+            vault_authority: self.vault_authority(),
+            pool_token_vault: self.pool_token_vault(),
+        }
+    }
+
+    pub fn quote_deposit(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteDeposit { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn quote_withdraw(&self, lamports: u64) -> Instruction {
+        let data = ix::QuoteWithdraw { lamports };
+        instruction(&self.program_id, self.view_accounts(), data)
+    }
+
+    pub fn get_beam_value(&self) -> Instruction {
+        instruction(&self.program_id, self.view_accounts(), ix::GetBeamValue {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sunrise::{self, derive_attribution};
+    use anchor_lang::prelude::borsh::BorshSerialize;
+    use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+    use spl_stake_pool::state::{AccountType, ValidatorListHeader};
+
+    const SEED_SUFFIX: u32 = 7;
+
+    fn spl_client(preferred_withdraw_validator: Option<Pubkey>, vote_account: Pubkey) -> SplClient {
+        let stake_pool = spl_stake_pool::state::StakePool {
+            account_type: AccountType::StakePool,
+            validator_list: Pubkey::new_unique(),
+            reserve_stake: Pubkey::new_unique(),
+            pool_mint: Pubkey::new_unique(),
+            manager_fee_account: Pubkey::new_unique(),
+            preferred_withdraw_validator_vote_address: preferred_withdraw_validator,
+            ..Default::default()
+        };
+        let stake_pool =
+            StakePool::try_deserialize(&mut &stake_pool.try_to_vec().unwrap()[..]).unwrap();
+        let validator_list = ValidatorList {
+            header: ValidatorListHeader {
+                account_type: AccountType::ValidatorList,
+                max_validators: 1,
+            },
+            validators: vec![ValidatorStakeInfo {
+                vote_account_address: vote_account,
+                validator_seed_suffix: SEED_SUFFIX.into(),
+                ..Default::default()
+            }],
+        };
+        let state = State {
+            sunrise_state: Pubkey::new_unique(),
+            stake_pool: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let sunrise =
+            SunriseClient::new(sunrise::ID, state.sunrise_state, sunrise::State::default());
+        let state_address = derive_state_address(&ID, &state.sunrise_state, &state.stake_pool).0;
+        SplClient::new(
+            ID,
+            state_address,
+            state,
+            stake_pool,
+            validator_list,
+            sunrise,
+        )
+    }
+
+    fn expected_validator_stake_account(client: &SplClient, vote_account: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                vote_account.as_ref(),
+                client.state.stake_pool.as_ref(),
+                &SEED_SUFFIX.to_le_bytes(),
+            ],
+            &spl_stake_pool::id(),
+        )
+        .0
+    }
+
+    #[test]
+    fn test_derive_addresses() {
+        let sunrise_state = Pubkey::new_unique();
+        let stake_pool = Pubkey::new_unique();
+        let state = derive_state_address(&ID, &sunrise_state, &stake_pool).0;
+        assert_eq!(
+            state,
+            Pubkey::find_program_address(
+                &[b"sunrise_spl", sunrise_state.as_ref(), stake_pool.as_ref()],
+                &ID
+            )
+            .0
+        );
+        assert_eq!(
+            derive_vault_authority(&ID, &state).0,
+            Pubkey::find_program_address(&[state.as_ref(), b"vault_authority"], &ID).0
+        );
+        assert_eq!(
+            derive_extract_yield_stake_account(&ID, &state).0,
+            Pubkey::find_program_address(&[state.as_ref(), b"extract_yield_stake_account"], &ID).0
+        );
+        let stake_account = Pubkey::new_unique();
+        assert_eq!(
+            derive_withdrawal_ticket(&ID, &state, &stake_account).0,
+            Pubkey::find_program_address(
+                &[state.as_ref(), b"withdrawal_ticket", stake_account.as_ref()],
+                &ID
+            )
+            .0
+        );
+    }
+
+    #[test]
+    fn test_stake_account_to_split() {
+        let vote_account = Pubkey::new_unique();
+        let client = spl_client(None, vote_account);
+        let validator_stake_account = expected_validator_stake_account(&client, &vote_account);
+
+        assert_eq!(
+            client.validator_stake_account(&vote_account),
+            Some(validator_stake_account)
+        );
+        assert_eq!(client.validator_stake_account(&Pubkey::new_unique()), None);
+        assert_eq!(
+            client.stake_account_to_split(Some(vote_account)),
+            validator_stake_account
+        );
+        assert_eq!(
+            client.stake_account_to_split(None),
+            client.stake_pool.reserve_stake
+        );
+
+        // The pool's preferred withdrawal validator is used if no vote account is given.
+        let client = spl_client(Some(vote_account), vote_account);
+        assert_eq!(
+            client.stake_account_to_split(None),
+            expected_validator_stake_account(&client, &vote_account)
+        );
+    }
+
+    #[test]
+    fn test_order_withdrawal_accounts() {
+        let vote_account = Pubkey::new_unique();
+        let client = spl_client(None, vote_account);
+        let withdrawer = Pubkey::new_unique();
+        let gsol_token_account = Pubkey::new_unique();
+        let new_stake_account = Pubkey::new_unique();
+
+        let instruction = client.order_withdrawal(
+            &withdrawer,
+            &gsol_token_account,
+            &new_stake_account,
+            100,
+            Some(vote_account),
+        );
+
+        let vault_authority = client.vault_authority();
+        let mut expected = accounts::OrderWithdrawal {
+            state: client.state_address,
+            sunrise_state: client.state.sunrise_state,
+            withdrawer,
+            gsol_token_account,
+            gsol_mint: client.sunrise.state.gsol_mint,
+            sysvar_instructions: sysvar::instructions::id(),
+            sunrise_program: sunrise::ID,
+            stake_pool: client.state.stake_pool,
+            new_stake_account,
+            withdrawal_ticket: Pubkey::find_program_address(
+                &[
+                    client.state_address.as_ref(),
+                    b"withdrawal_ticket",
+                    new_stake_account.as_ref(),
+                ],
+                &ID,
+            )
+            .0,
+            pool_mint: client.stake_pool.pool_mint,
+            pool_token_vault: get_associated_token_address(
+                &vault_authority,
+                &client.stake_pool.pool_mint,
+            ),
+            vault_authority,
+            stake_pool_withdraw_authority: Pubkey::find_program_address(
+                &[client.state.stake_pool.as_ref(), b"withdraw"],
+                &spl_stake_pool::id(),
+            )
+            .0,
+            validator_stake_list: client.stake_pool.validator_list,
+            stake_account_to_split: expected_validator_stake_account(&client, &vote_account),
+            manager_fee_account: client.stake_pool.manager_fee_account,
+            sysvar_clock: sysvar::clock::id(),
+            native_stake_program: stake::program::ID,
+            spl_stake_pool_program: spl_stake_pool::id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None);
+        expected.push(AccountMeta::new(
+            derive_attribution(&sunrise::ID, &client.state.sunrise_state, &withdrawer).0,
+            false,
+        ));
+        assert_eq!(instruction.accounts, expected);
+    }
+
+    #[test]
+    fn test_redeem_ticket_accounts() {
+        let client = spl_client(None, Pubkey::new_unique());
+        let ticket = WithdrawalTicket {
+            state: client.state_address,
+            stake_account: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+        };
+
+        let instruction = client.redeem_ticket(&ticket);
+
+        let expected = accounts::RedeemTicket {
+            state: client.state_address,
+            beneficiary: ticket.beneficiary,
+            withdrawal_ticket: derive_withdrawal_ticket(
+                &ID,
+                &client.state_address,
+                &ticket.stake_account,
+            )
+            .0,
+            stake_account: ticket.stake_account,
+            vault_authority: client.vault_authority(),
+            sysvar_clock: sysvar::clock::id(),
+            sysvar_stake_history: sysvar::stake_history::id(),
+            native_stake_program: stake::program::ID,
+        }
+        .to_account_metas(None);
+        assert_eq!(instruction.accounts, expected);
+        // The beneficiary signs, and receives the lamports of the stake account.
+        assert!(instruction.accounts[1].is_signer);
+        assert!(instruction.accounts[1].is_writable);
+    }
+
+    #[test]
+    fn test_refresh_pool_instructions() {
+        let mut client = spl_client(None, Pubkey::new_unique());
+        let refresh_accounts = accounts::RefreshPool {
+            state: client.state_address,
+            stake_pool: client.state.stake_pool,
+            stake_pool_withdraw_authority: client.stake_pool_withdraw_authority(),
+            validator_list: client.stake_pool.validator_list,
+            reserve_stake_account: client.stake_pool.reserve_stake,
+            manager_fee_account: client.stake_pool.manager_fee_account,
+            pool_mint: client.stake_pool.pool_mint,
+            sysvar_clock: sysvar::clock::id(),
+            sysvar_stake_history: sysvar::stake_history::id(),
+            native_stake_program: stake::program::ID,
+            spl_stake_pool_program: spl_stake_pool::id(),
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None)
+        .len();
+
+        // A pool without validators only has its balance updated.
+        client.validator_list.validators.clear();
+        let instructions = client.refresh_pool_instructions();
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].accounts.len(), refresh_accounts);
+
+        client.validator_list.validators = (0..MAX_VALIDATORS_TO_UPDATE + 2)
+            .map(|_| ValidatorStakeInfo {
+                vote_account_address: Pubkey::new_unique(),
+                ..Default::default()
+            })
+            .collect();
+        let pairs = client.validator_stake_account_pairs();
+        assert_eq!(
+            pairs[0].0,
+            client
+                .validator_stake_account(&client.validator_list.validators[0].vote_account_address)
+                .unwrap()
+        );
+
+        let instructions = client.refresh_pool_instructions();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            instructions[0].data,
+            ix::RefreshPool { start_index: 0 }.data()
+        );
+        assert_eq!(
+            instructions[1].data,
+            ix::RefreshPool {
+                start_index: MAX_VALIDATORS_TO_UPDATE as u32
+            }
+            .data()
+        );
+        assert_eq!(
+            instructions[0].accounts[refresh_accounts..],
+            pairs[..MAX_VALIDATORS_TO_UPDATE]
+                .iter()
+                .flat_map(|(validator, transient)| [
+                    AccountMeta::new(*validator, false),
+                    AccountMeta::new(*transient, false)
+                ])
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(instructions[1].accounts.len(), refresh_accounts + 2 * 2);
+    }
+
+    #[test]
+    fn test_is_pool_stale() {
+        let mut client = spl_client(None, Pubkey::new_unique());
+        let stake_pool = spl_stake_pool::state::StakePool {
+            account_type: AccountType::StakePool,
+            last_update_epoch: 10,
+            ..Default::default()
+        };
+        client.stake_pool =
+            StakePool::try_deserialize(&mut &stake_pool.try_to_vec().unwrap()[..]).unwrap();
+
+        assert!(!client.is_pool_stale(10));
+        assert!(client.is_pool_stale(11));
+    }
+}
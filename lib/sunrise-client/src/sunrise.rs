@@ -0,0 +1,326 @@
+//! The Sunrise core program.
+//!
+//! Only the admin, attribution and impact certificate instructions can be built here.
+//! `mint_gsol`, `burn_gsol`, `transfer_gsol`, `update_epoch_report` and `extract_yield` must be
+//! signed by a beam's state PDA, so they are only ever called by CPI from a beam.
+
+use crate::instruction;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_spl::associated_token::get_associated_token_address;
+use sunrise_core::{accounts, instruction as ix, seeds};
+
+pub use sunrise_core::{
+    AllocationUpdate, AttributedYield, Attribution, BackingOracle, BeamDetails, EpochReport,
+    ImpactCertificate, ImpactProject, RegisterStateInput, State, UpdateStateInput, ID,
+    IMPACT_PERIOD_EPOCHS,
+};
+
+/// Derive the address of the gSOL mint authority PDA of a state.
+pub fn derive_gsol_mint_authority(program_id: &Pubkey, state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[state.as_ref(), seeds::GSOL_AUTHORITY], program_id)
+}
+
+/// Derive the address of the backing oracle of a state.
+pub fn derive_backing_oracle(program_id: &Pubkey, state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[state.as_ref(), seeds::BACKING_ORACLE], program_id)
+}
+
+/// Derive the address of the attribution of `owner` against a state.
+pub fn derive_attribution(program_id: &Pubkey, state: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[state.as_ref(), seeds::ATTRIBUTION, owner.as_ref()],
+        program_id,
+    )
+}
+
+/// Derive the address of the impact project with `recipient` of a state.
+pub fn derive_impact_project(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    recipient: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[state.as_ref(), seeds::IMPACT_PROJECT, recipient.as_ref()],
+        program_id,
+    )
+}
+
+/// Derive the address of the impact certificate of `owner` for a period.
+pub fn derive_impact_certificate(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    owner: &Pubkey,
+    period: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            state.as_ref(),
+            seeds::IMPACT_CERTIFICATE,
+            owner.as_ref(),
+            period.to_le_bytes().as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Derive the address of the mint of an impact certificate's token.
+pub fn derive_impact_certificate_mint(program_id: &Pubkey, certificate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[certificate.as_ref(), seeds::IMPACT_CERTIFICATE_MINT],
+        program_id,
+    )
+}
+
+/// Append the depositor's `attribution` to a beam instruction that mints or burns gSOL.
+/// Once it is registered, the deposit or withdrawal is recorded against it.
+pub fn with_attribution(mut instruction: Instruction, attribution: &Pubkey) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new(*attribution, false));
+    instruction
+}
+
+/// Build a `register_state` instruction. The new `state` account must also sign.
+pub fn register_state(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    state: &Pubkey,
+    gsol_mint: &Pubkey,
+    input: RegisterStateInput,
+) -> Instruction {
+    let accounts = accounts::RegisterState {
+        payer: *payer,
+        state: *state,
+        gsol_mint: *gsol_mint,
+        gsol_mint_authority: derive_gsol_mint_authority(program_id, state).0,
+        backing_oracle: derive_backing_oracle(program_id, state).0,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+    instruction(program_id, accounts, ix::RegisterState { input })
+}
+
+/// A Sunrise state and the program that owns it.
+#[derive(Clone)]
+pub struct SunriseClient {
+    pub program_id: Pubkey,
+    /// The state address.
+    pub state_address: Pubkey,
+    /// The deserialized on-chain account for this state.
+    pub state: State,
+}
+
+impl SunriseClient {
+    pub fn new(program_id: Pubkey, state_address: Pubkey, state: State) -> Self {
+        Self {
+            program_id,
+            state_address,
+            state,
+        }
+    }
+
+    pub fn gsol_mint_authority(&self) -> Pubkey {
+        derive_gsol_mint_authority(&self.program_id, &self.state_address).0
+    }
+
+    pub fn backing_oracle(&self) -> Pubkey {
+        derive_backing_oracle(&self.program_id, &self.state_address).0
+    }
+
+    /// The treasury to pass to a beam's `extract_yield`, if the state has one.
+    pub fn treasury(&self) -> Option<Pubkey> {
+        (self.state.treasury != Pubkey::default()).then_some(self.state.treasury)
+    }
+
+    pub fn update_state(&self, input: UpdateStateInput) -> Instruction {
+        let accounts = accounts::UpdateState {
+            state: self.state_address,
+            update_authority: self.state.update_authority,
+        };
+        instruction(&self.program_id, accounts, ix::UpdateState { input })
+    }
+
+    pub fn register_beam(&self, beam_account: &Pubkey) -> Instruction {
+        let accounts = accounts::RegisterBeam {
+            state: self.state_address,
+            update_authority: self.state.update_authority,
+            beam_account: *beam_account,
+        };
+        instruction(&self.program_id, accounts, ix::RegisterBeam {})
+    }
+
+    pub fn resize_allocations(&self, payer: &Pubkey, additional_beams: u8) -> Instruction {
+        let accounts = accounts::ResizeAllocations {
+            update_authority: self.state.update_authority,
+            payer: *payer,
+            state: self.state_address,
+            backing_oracle: self.backing_oracle(),
+            system_program: system_program::id(),
+        };
+        let data = ix::ResizeAllocations { additional_beams };
+        instruction(&self.program_id, accounts, data)
+    }
+
+    pub fn update_allocations(&self, new_allocations: Vec<AllocationUpdate>) -> Instruction {
+        let accounts = accounts::UpdateBeamAllocations {
+            state: self.state_address,
+            update_authority: self.state.update_authority,
+        };
+        let data = ix::UpdateAllocations { new_allocations };
+        instruction(&self.program_id, accounts, data)
+    }
+
+    pub fn remove_beam(&self, beam: &Pubkey) -> Instruction {
+        let accounts = accounts::RemoveBeam {
+            state: self.state_address,
+            update_authority: self.state.update_authority,
+        };
+        instruction(&self.program_id, accounts, ix::RemoveBeam { beam: *beam })
+    }
+
+    pub fn export_mint_authority(&self, new_authority: &Pubkey) -> Instruction {
+        let accounts = accounts::ExportMintAuthority {
+            update_authority: self.state.update_authority,
+            state: self.state_address,
+            gsol_mint: self.state.gsol_mint,
+            gsol_mint_authority: self.gsol_mint_authority(),
+            new_authority: *new_authority,
+            token_program: anchor_spl::token::ID,
+        };
+        instruction(&self.program_id, accounts, ix::ExportMintAuthority {})
+    }
+
+    pub fn socialise_deficit(
+        &self,
+        beam: &Pubkey,
+        covering_beam: &Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let accounts = accounts::SocialiseDeficit {
+            state: self.state_address,
+            update_authority: self.state.update_authority,
+        };
+        let data = ix::SocialiseDeficit {
+            beam: *beam,
+            covering_beam: *covering_beam,
+            amount,
+        };
+        instruction(&self.program_id, accounts, data)
+    }
+
+    pub fn register_backing_oracle(&self, payer: &Pubkey) -> Instruction {
+        let accounts = accounts::RegisterBackingOracle {
+            payer: *payer,
+            state: self.state_address,
+            backing_oracle: self.backing_oracle(),
+            system_program: system_program::id(),
+        };
+        instruction(&self.program_id, accounts, ix::RegisterBackingOracle {})
+    }
+
+    pub fn resize_backing_oracle(&self, payer: &Pubkey) -> Instruction {
+        let accounts = accounts::ResizeBackingOracle {
+            payer: *payer,
+            state: self.state_address,
+            backing_oracle: self.backing_oracle(),
+            system_program: system_program::id(),
+        };
+        instruction(&self.program_id, accounts, ix::ResizeBackingOracle {})
+    }
+
+    pub fn attribution(&self, owner: &Pubkey) -> Pubkey {
+        derive_attribution(&self.program_id, &self.state_address, owner).0
+    }
+
+    /// Append the attribution of `owner` to a beam instruction that mints or burns gSOL.
+    /// See [with_attribution].
+    pub fn with_attribution(&self, instruction: Instruction, owner: &Pubkey) -> Instruction {
+        with_attribution(instruction, &self.attribution(owner))
+    }
+
+    /// Build a `register_attribution` instruction. The `owner` must also sign.
+    pub fn register_attribution(&self, payer: &Pubkey, owner: &Pubkey) -> Instruction {
+        let accounts = accounts::RegisterAttribution {
+            payer: *payer,
+            owner: *owner,
+            state: self.state_address,
+            attribution: self.attribution(owner),
+            system_program: system_program::id(),
+        };
+        instruction(&self.program_id, accounts, ix::RegisterAttribution {})
+    }
+
+    /// View: returns the [AttributedYield] of `owner`, whose gSOL is held in `gsol_token_account`.
+    pub fn get_attributed_yield(&self, owner: &Pubkey, gsol_token_account: &Pubkey) -> Instruction {
+        let accounts = accounts::ViewAttribution {
+            state: self.state_address,
+            attribution: self.attribution(owner),
+            gsol_token_account: *gsol_token_account,
+        };
+        instruction(&self.program_id, accounts, ix::GetAttributedYield {})
+    }
+
+    pub fn impact_project(&self, recipient: &Pubkey) -> Pubkey {
+        derive_impact_project(&self.program_id, &self.state_address, recipient).0
+    }
+
+    pub fn register_impact_project(
+        &self,
+        payer: &Pubkey,
+        recipient: &Pubkey,
+        name: String,
+    ) -> Instruction {
+        let accounts = accounts::RegisterImpactProject {
+            payer: *payer,
+            state: self.state_address,
+            update_authority: self.state.update_authority,
+            recipient: *recipient,
+            project: self.impact_project(recipient),
+            system_program: system_program::id(),
+        };
+        instruction(
+            &self.program_id,
+            accounts,
+            ix::RegisterImpactProject { name },
+        )
+    }
+
+    pub fn impact_certificate(&self, owner: &Pubkey, period: u64) -> Pubkey {
+        derive_impact_certificate(&self.program_id, &self.state_address, owner, period).0
+    }
+
+    /// Build a `claim_impact_certificate` instruction for `period`, crediting the impact
+    /// `project`, given the `owner`'s `gsol_token_account`. The `owner` must also sign.
+    pub fn claim_impact_certificate(
+        &self,
+        payer: &Pubkey,
+        owner: &Pubkey,
+        gsol_token_account: &Pubkey,
+        project: &Pubkey,
+        period: u64,
+    ) -> Instruction {
+        let certificate = self.impact_certificate(owner, period);
+        let certificate_mint = derive_impact_certificate_mint(&self.program_id, &certificate).0;
+        let accounts = accounts::ClaimImpactCertificate {
+            payer: *payer,
+            owner: *owner,
+            state: self.state_address,
+            attribution: self.attribution(owner),
+            gsol_token_account: *gsol_token_account,
+            project: *project,
+            certificate,
+            certificate_mint,
+            certificate_token_account: get_associated_token_address(owner, &certificate_mint),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::id(),
+        };
+        instruction(
+            &self.program_id,
+            accounts,
+            ix::ClaimImpactCertificate { period },
+        )
+    }
+}
@@ -86,11 +86,6 @@ export type MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marinadeState",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "sunriseState",
           "isMut": true,
@@ -107,32 +102,42 @@ export type MarinadeBeam = {
           "isSigner": false
         },
         {
-          "name": "msolMint",
+          "name": "gsolMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "msolVault",
-          "isMut": true,
+          "name": "gsolMintAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "gsolMintAuthority",
-          "isMut": false,
+          "name": "msolMint",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "msolVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
@@ -161,11 +166,6 @@ export type MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "sunriseProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "marinadeProgram",
           "isMut": false,
@@ -320,17 +320,18 @@ export type MarinadeBeam = {
     },
     {
       "name": "withdraw",
+      "docs": [
+        "Withdraws `lamports` via a liquid unstake.",
+        "",
+        "Errors if the withdrawer receives fewer than `min_lamports_out` lamports",
+        "after the liquid unstake fee."
+      ],
       "accounts": [
         {
           "name": "state",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marinadeState",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "sunriseState",
           "isMut": true,
@@ -354,6 +355,21 @@ export type MarinadeBeam = {
             "Verified in CPI to Sunrise program."
           ]
         },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "msolMint",
           "isMut": true,
@@ -384,16 +400,6 @@ export type MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sunriseProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "marinadeProgram",
           "isMut": false,
@@ -414,20 +420,28 @@ export type MarinadeBeam = {
         {
           "name": "lamports",
           "type": "u64"
+        },
+        {
+          "name": "minLamportsOut",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "orderWithdrawal",
+      "name": "withdrawAsLst",
+      "docs": [
+        "Withdrawing as an LST burns gSOL and transfers the equivalent amount of mSOL",
+        "out of the beam's vault, rather than unstaking it. This incurs no unstake fee."
+      ],
       "accounts": [
         {
           "name": "state",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "marinadeState",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -455,7 +469,7 @@ export type MarinadeBeam = {
         },
         {
           "name": "msolMint",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -468,26 +482,108 @@ export type MarinadeBeam = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "transferMsolTo",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The token account that receives the withdrawn mSOL."
+          ]
+        },
         {
           "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "newTicketAccount",
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "orderWithdrawal",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "proxyTicketAccount",
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "withdrawer",
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "gsolTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "marinadeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "msolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "msolVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "newTicketAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proxyTicketAccount",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "marinadeProgram",
           "isMut": false,
@@ -550,35 +646,35 @@ export type MarinadeBeam = {
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
-          "isMut": false,
-          "isSigner": false
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
         },
         {
-          "name": "systemProgram",
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -598,16 +694,16 @@ export type MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marinadeState",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "beneficiary",
           "isMut": false,
           "isSigner": true
         },
+        {
+          "name": "marinadeState",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "sunriseTicketAccount",
           "isMut": true,
@@ -659,6 +755,30 @@ export type MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "yieldAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Only needed while the state charges a protocol fee."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "marinadeState",
           "isMut": true,
@@ -684,11 +804,6 @@ export type MarinadeBeam = {
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "yieldAccount",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "liqPoolSolLegPda",
           "isMut": true,
@@ -704,16 +819,6 @@ export type MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sunriseProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "marinadeProgram",
           "isMut": false,
@@ -733,7 +838,15 @@ export type MarinadeBeam = {
       "args": []
     },
     {
-      "name": "updateEpochReport",
+      "name": "fundLiquidityBeam",
+      "docs": [
+        "Permissionless crank that tops up the Marinade LP beam's liquidity buffer to its target size.",
+        "",
+        "If the gSOL the liquidity beam is responsible for is below its target share of the gSOL supply,",
+        "mSOL worth the shortfall, grossed up for the liquid unstake fee, is liquid-unstaked from this beam's",
+        "vault, and the SOL received is added as liquidity to the Marinade liq_pool on behalf of the liquidity beam.",
+        "The gSOL backed by the added liquidity is transferred to the liquidity beam."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -741,30 +854,122 @@ export type MarinadeBeam = {
           "isSigner": false
         },
         {
-          "name": "sunriseState",
+          "name": "marinadeState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "marinadeState",
-          "isMut": false,
+          "name": "sunriseState",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "msolMint",
+          "name": "gsolMint",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The gSOL mint, whose supply determines the target liquidity."
+          ]
         },
         {
-          "name": "msolVault",
+          "name": "liquidityBeam",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The Marinade LP beam funded by this beam. Its withdrawn mSOL must be sent to this beam's vault."
+          ]
+        },
+        {
+          "name": "liquidityBeamVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liquidityBeamVaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "msolVault",
+          "isMut": true,
+          "isSigner": false
         },
         {
           "name": "vaultAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMintAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasuryMsolAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateEpochReport",
+      "accounts": [
+        {
+          "name": "state",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "gsolMint",
           "isMut": false,
@@ -774,6 +979,11 @@ export type MarinadeBeam = {
             "Verified in CPI to Sunrise program."
           ]
         },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "sysvarInstructions",
           "isMut": false,
@@ -783,9 +993,183 @@ export type MarinadeBeam = {
           "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": []
+    },
+    {
+      "name": "quoteDeposit",
+      "docs": [
+        "View: quotes a [deposit] of `lamports`, returning the gSOL that would be minted",
+        "and the mSOL that the beam would receive."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "DepositQuote"
+      }
+    },
+    {
+      "name": "quoteWithdraw",
+      "docs": [
+        "View: quotes a [withdraw] of `lamports`, returning the lamports that would be received",
+        "after the liquid unstake fee, and the mSOL that the beam would unstake."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "WithdrawQuote"
+      }
+    },
+    {
+      "name": "getBeamValue",
+      "docs": [
+        "View: returns the value of the mSOL held by the beam, the surplus that [update_epoch_report]",
+        "would report and the yield that [extract_yield] would extract, before the liquid unstake fee."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "BeamValue"
+      }
     }
   ],
   "accounts": [
@@ -901,6 +1285,21 @@ export type MarinadeBeam = {
       "code": 6004,
       "name": "TooManyTicketsClaimed",
       "msg": "The amount of delayed unstake tickets requested to be recovered exceeds the amount in the report"
+    },
+    {
+      "code": 6005,
+      "name": "WithdrawalBelowMinimum",
+      "msg": "The withdrawal paid out fewer lamports than the requested minimum"
+    },
+    {
+      "code": 6006,
+      "name": "LiquidityWithinTarget",
+      "msg": "The liquidity beam's liquidity is not below its target"
+    },
+    {
+      "code": 6007,
+      "name": "UnpairedLiquidityBeam",
+      "msg": "The liquidity beam does not send its withdrawn mSOL to this beam"
     }
   ]
 };
@@ -993,11 +1392,6 @@ export const IDL: MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marinadeState",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "sunriseState",
           "isMut": true,
@@ -1014,32 +1408,42 @@ export const IDL: MarinadeBeam = {
           "isSigner": false
         },
         {
-          "name": "msolMint",
+          "name": "gsolMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "msolVault",
-          "isMut": true,
+          "name": "gsolMintAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "gsolMintAuthority",
-          "isMut": false,
+          "name": "msolMint",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "msolVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
@@ -1068,11 +1472,6 @@ export const IDL: MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "sunriseProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "marinadeProgram",
           "isMut": false,
@@ -1227,17 +1626,207 @@ export const IDL: MarinadeBeam = {
     },
     {
       "name": "withdraw",
+      "docs": [
+        "Withdraws `lamports` via a liquid unstake.",
+        "",
+        "Errors if the withdrawer receives fewer than `min_lamports_out` lamports",
+        "after the liquid unstake fee."
+      ],
       "accounts": [
         {
           "name": "state",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "withdrawer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "gsolTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "marinadeState",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "msolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "msolVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasuryMsolAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        },
+        {
+          "name": "minLamportsOut",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawAsLst",
+      "docs": [
+        "Withdrawing as an LST burns gSOL and transfers the equivalent amount of mSOL",
+        "out of the beam's vault, rather than unstaking it. This incurs no unstake fee."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "withdrawer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "gsolTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "msolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "transferMsolTo",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The token account that receives the withdrawn mSOL."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "orderWithdrawal",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "sunriseState",
           "isMut": true,
@@ -1261,6 +1850,251 @@ export const IDL: MarinadeBeam = {
             "Verified in CPI to Sunrise program."
           ]
         },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "msolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "msolVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "newTicketAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proxyTicketAccount",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "marinadeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "burn",
+      "docs": [
+        "Burning is withdrawing without redeeming the pool tokens. The result is a beam that is \"worth more\"",
+        "than the SOL that has been staked into it, i.e. the pool tokens are more valuable than the SOL.",
+        "This allows yield extraction and can be seen as a form of \"donation\"."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "burner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "gsolTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "redeemTicket",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "beneficiary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "marinadeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseTicketAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeTicketAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reservePda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "clock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "extractYield",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "yieldAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Only needed while the state charges a protocol fee."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "msolMint",
           "isMut": true,
@@ -1291,16 +2125,6 @@ export const IDL: MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sunriseProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "marinadeProgram",
           "isMut": false,
@@ -1317,19 +2141,22 @@ export const IDL: MarinadeBeam = {
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "lamports",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "orderWithdrawal",
+      "name": "fundLiquidityBeam",
+      "docs": [
+        "Permissionless crank that tops up the Marinade LP beam's liquidity buffer to its target size.",
+        "",
+        "If the gSOL the liquidity beam is responsible for is below its target share of the gSOL supply,",
+        "mSOL worth the shortfall, grossed up for the liquid unstake fee, is liquid-unstaked from this beam's",
+        "vault, and the SOL received is added as liquidity to the Marinade liq_pool on behalf of the liquidity beam.",
+        "The gSOL backed by the added liquidity is transferred to the liquidity beam."
+      ],
       "accounts": [
         {
           "name": "state",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1342,211 +2169,154 @@ export const IDL: MarinadeBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "withdrawer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "gsolTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "gsolMint",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "docs": [
-            "Verified in CPI to Sunrise program."
+            "The gSOL mint, whose supply determines the target liquidity."
           ]
         },
         {
-          "name": "msolMint",
-          "isMut": true,
-          "isSigner": false
+          "name": "liquidityBeam",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The Marinade LP beam funded by this beam. Its withdrawn mSOL must be sent to this beam's vault."
+          ]
         },
         {
-          "name": "msolVault",
+          "name": "liquidityBeamVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sysvarInstructions",
+          "name": "liquidityBeamVaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "newTicketAccount",
+          "name": "msolMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "proxyTicketAccount",
+          "name": "msolVault",
           "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "sunriseProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "marinadeProgram",
-          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "clock",
-          "isMut": false,
+          "name": "vaultAuthority",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "liqPoolMint",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "liqPoolMintAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "lamports",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "burn",
-      "docs": [
-        "Burning is withdrawing without redeeming the pool tokens. The result is a beam that is \"worth more\"",
-        "than the SOL that has been staked into it, i.e. the pool tokens are more valuable than the SOL.",
-        "This allows yield extraction and can be seen as a form of \"donation\"."
-      ],
-      "accounts": [
-        {
-          "name": "state",
+          "name": "liqPoolSolLegPda",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sunriseState",
+          "name": "liqPoolMsolLeg",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "burner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "gsolTokenAccount",
+          "name": "treasuryMsolAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "marinadeProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
-        },
-        {
-          "name": "sysvarInstructions",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "lamports",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "redeemTicket",
+      "name": "updateEpochReport",
       "accounts": [
         {
           "name": "state",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marinadeState",
+          "name": "sunriseState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "beneficiary",
+          "name": "gsolMint",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false,
+          "docs": [
+            "Required to update the core state epoch report",
+            "Verified in CPI to Sunrise program."
+          ]
         },
         {
-          "name": "sunriseTicketAccount",
+          "name": "backingOracle",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "marinadeTicketAccount",
-          "isMut": true,
+          "name": "sysvarInstructions",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reservePda",
-          "isMut": true,
+          "name": "sunriseProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
-          "isMut": true,
+          "name": "marinadeState",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marinadeProgram",
+          "name": "msolMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "clock",
+          "name": "msolVault",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         }
@@ -1554,7 +2324,11 @@ export const IDL: MarinadeBeam = {
       "args": []
     },
     {
-      "name": "extractYield",
+      "name": "quoteDeposit",
+      "docs": [
+        "View: quotes a [deposit] of `lamports`, returning the gSOL that would be minted",
+        "and the mSOL that the beam would receive."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -1563,27 +2337,22 @@ export const IDL: MarinadeBeam = {
         },
         {
           "name": "sunriseState",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "marinadeState",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
         {
           "name": "msolMint",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "msolVault",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1591,56 +2360,81 @@ export const IDL: MarinadeBeam = {
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "yieldAccount",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "liqPoolSolLegPda",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "liqPoolMsolLeg",
-          "isMut": true,
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "DepositQuote"
+      }
+    },
+    {
+      "name": "quoteWithdraw",
+      "docs": [
+        "View: quotes a [withdraw] of `lamports`, returning the lamports that would be received",
+        "after the liquid unstake fee, and the mSOL that the beam would unstake."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "treasuryMsolAccount",
-          "isMut": true,
+          "name": "sunriseState",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "marinadeState",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "msolMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marinadeProgram",
+          "name": "msolVault",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "liqPoolSolLegPda",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "WithdrawQuote"
+      }
     },
     {
-      "name": "updateEpochReport",
+      "name": "getBeamValue",
+      "docs": [
+        "View: returns the value of the mSOL held by the beam, the surplus that [update_epoch_report]",
+        "would report and the yield that [extract_yield] would extract, before the liquid unstake fee."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -1649,7 +2443,7 @@ export const IDL: MarinadeBeam = {
         },
         {
           "name": "sunriseState",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1673,26 +2467,15 @@ export const IDL: MarinadeBeam = {
           "isSigner": false
         },
         {
-          "name": "gsolMint",
-          "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "Required to update the core state epoch report",
-            "Verified in CPI to Sunrise program."
-          ]
-        },
-        {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sunriseProgram",
+          "name": "liqPoolSolLegPda",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [],
+      "returns": {
+        "defined": "BeamValue"
+      }
     }
   ],
   "accounts": [
@@ -1808,6 +2591,21 @@ export const IDL: MarinadeBeam = {
       "code": 6004,
       "name": "TooManyTicketsClaimed",
       "msg": "The amount of delayed unstake tickets requested to be recovered exceeds the amount in the report"
+    },
+    {
+      "code": 6005,
+      "name": "WithdrawalBelowMinimum",
+      "msg": "The withdrawal paid out fewer lamports than the requested minimum"
+    },
+    {
+      "code": 6006,
+      "name": "LiquidityWithinTarget",
+      "msg": "The liquidity beam's liquidity is not below its target"
+    },
+    {
+      "code": 6007,
+      "name": "UnpairedLiquidityBeam",
+      "msg": "The liquidity beam does not send its withdrawn mSOL to this beam"
     }
   ]
 };
@@ -15,6 +15,11 @@ export type MarinadeLpBeam = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "liqPoolMint",
           "isMut": false,
@@ -82,16 +87,49 @@ export type MarinadeLpBeam = {
       ]
     },
     {
-      "name": "deposit",
+      "name": "resizeState",
+      "docs": [
+        "Resizes a state created before `target_liquidity_bps` was added, which cannot be",
+        "deserialized (and so updated) until it has room for it, and sets its target liquidity."
+      ],
       "accounts": [
+        {
+          "name": "updateAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "state",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Its discriminator and update authority are checked in the handler."
+          ]
+        },
+        {
+          "name": "sunriseState",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marinadeState",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "targetLiquidityBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "deposit",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -110,35 +148,45 @@ export type MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "liqPoolMint",
+          "name": "gsolMint",
           "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "gsolMintAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "liqPoolTokenVault",
-          "isMut": true,
+          "name": "sysvarInstructions",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "marinadeState",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
+          "isSigner": false
         },
         {
-          "name": "gsolMintAuthority",
-          "isMut": false,
+          "name": "liqPoolMint",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "liqPoolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
@@ -173,14 +221,17 @@ export type MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "marinadeProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marinadeProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity added."
+          ]
         }
       ],
       "args": [
@@ -192,17 +243,17 @@ export type MarinadeLpBeam = {
     },
     {
       "name": "withdraw",
+      "docs": [
+        "Withdraws `lamports` by removing liquidity from the Marinade liq_pool.",
+        "",
+        "Errors if the withdrawer receives fewer than `min_lamports_out` lamports."
+      ],
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "marinadeState",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "sunriseState",
           "isMut": true,
@@ -218,6 +269,29 @@ export type MarinadeLpBeam = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "liqPoolMint",
           "isMut": true,
@@ -269,33 +343,27 @@ export type MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "gsolMint",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
-        },
-        {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sunriseProgram",
+          "name": "marinadeProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marinadeProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity removed."
+          ]
         }
       ],
       "args": [
         {
           "name": "lamports",
           "type": "u64"
+        },
+        {
+          "name": "minLamportsOut",
+          "type": "u64"
         }
       ]
     },
@@ -327,16 +395,6 @@ export type MarinadeLpBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "gsolMint",
           "isMut": true,
@@ -354,6 +412,16 @@ export type MarinadeLpBeam = {
           "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -366,7 +434,12 @@ export type MarinadeLpBeam = {
     {
       "name": "orderWithdrawal",
       "accounts": [],
-      "args": []
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
     },
     {
       "name": "redeemTicket",
@@ -382,25 +455,44 @@ export type MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "marinadeState",
+          "name": "sunriseState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sunriseState",
+          "name": "yieldAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "treasury",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Only needed while the state charges a protocol fee."
+          ]
         },
         {
-          "name": "yieldAccount",
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "liqPoolMint",
           "isMut": true,
@@ -452,25 +544,33 @@ export type MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sunriseProgram",
+          "name": "marinadeProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marinadeProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity extracted."
+          ]
         }
       ],
       "args": []
     },
     {
-      "name": "updateEpochReport",
+      "name": "rebalanceLiquidity",
+      "docs": [
+        "Permissionless crank that keeps the liquidity buffer at its target size.",
+        "",
+        "If the gSOL this beam is responsible for exceeds its target share of the gSOL supply,",
+        "the surplus liquidity is removed and moved to the mSOL recipient beam (typically the Marinade-SP beam).",
+        "The mSOL leg is sent to the recipient's mSOL token account, and the SOL leg is staked",
+        "with Marinade into the same account. The gSOL backed by the surplus is transferred to the recipient beam.",
+        "",
+        "A shortfall is filled from the Marinade-SP beam by its `fund_liquidity_beam` crank."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -479,7 +579,7 @@ export type MarinadeLpBeam = {
         },
         {
           "name": "marinadeState",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -492,8 +592,7 @@ export type MarinadeLpBeam = {
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "Required to update the core state epoch report",
-            "Verified in CPI to Sunrise program."
+            "The gSOL mint, whose supply determines the target liquidity."
           ]
         },
         {
@@ -508,9 +607,17 @@ export type MarinadeLpBeam = {
         },
         {
           "name": "vaultAuthority",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "transferMsolTo",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Receives the mSOL leg of the removed liquidity, and the mSOL minted for the SOL leg."
+          ]
+        },
         {
           "name": "liqPoolSolLegPda",
           "isMut": true,
@@ -523,9 +630,34 @@ export type MarinadeLpBeam = {
         },
         {
           "name": "liqPoolMsolLegAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "msolMintAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "reservePda",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "sysvarInstructions",
           "isMut": false,
@@ -535,14 +667,343 @@ export type MarinadeLpBeam = {
           "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "marinadeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity removed."
+          ]
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "state",
+      "name": "updateEpochReport",
+      "docs": [
+        "Reports the beam's surplus to sunrise, and checkpoints its yield breakdown."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Required to update the core state epoch report",
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLegAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity held."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initYieldBreakdown",
+      "docs": [
+        "Creates the yield breakdown account of a beam initialized without one,",
+        "using the current value of the beam's liquidity pool position as its cost basis."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "updateAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolTokenVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "quoteDeposit",
+      "docs": [
+        "View: quotes a [deposit] of `lamports`, returning the gSOL that would be minted",
+        "and the liq_pool tokens that the beam would receive."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolTokenVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "DepositQuote"
+      }
+    },
+    {
+      "name": "quoteWithdraw",
+      "docs": [
+        "View: quotes a [withdraw] of `lamports`, returning the lamports that would be received",
+        "from the SOL leg of the pool, and the liq_pool tokens that the beam would burn.",
+        "The mSOL leg goes to the mSOL recipient beam, not the withdrawer."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolTokenVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "WithdrawQuote"
+      }
+    },
+    {
+      "name": "getBeamValue",
+      "docs": [
+        "View: returns the value of the beam's liquidity pool position, the surplus that",
+        "[update_epoch_report] would report and the SOL leg that [extract_yield] would extract."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolTokenVault",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "BeamValue"
+      }
+    }
+  ],
+  "accounts": [
+    {
+      "name": "state",
       "type": {
         "kind": "struct",
         "fields": [
@@ -589,6 +1050,80 @@ export type MarinadeLpBeam = {
               "The token-account that receives msol when withdrawing liquidity."
             ],
             "type": "publicKey"
+          },
+          {
+            "name": "targetLiquidityBps",
+            "docs": [
+              "The target size of the liquidity buffer, as a share of the gSOL supply in basis points."
+            ],
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "yieldBreakdown",
+      "docs": [
+        "Splits the yield earned on the beam's liquidity pool position into",
+        "liquidity pool fee income and mSOL staking appreciation.",
+        "",
+        "The account tracks the cost basis of the position: the SOL value of the liq_pool tokens held,",
+        "as of the last checkpoint, adjusted by the value of liquidity deposited and withdrawn since.",
+        "Flows are recorded at the value they move, so they are never counted as yield.",
+        "A checkpoint, taken when reporting and extracting yield, attributes the change in value",
+        "over the basis and moves the basis to the current value of the position."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The beam state this breakdown belongs to."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "liqPoolTokens",
+            "docs": [
+              "The liq_pool tokens held by the beam, as recorded by the beam."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lamportsValue",
+            "docs": [
+              "The cost basis of the liq_pool tokens held, in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "msolPrice",
+            "docs": [
+              "The value of one mSOL in lamports at the last checkpoint."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "docs": [
+              "The epoch of the last checkpoint."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeIncome",
+            "docs": [
+              "The total yield earned from liquidity pool swap fees, in lamports."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "stakingAppreciation",
+            "docs": [
+              "The total yield earned from the appreciation of the mSOL leg, in lamports."
+            ],
+            "type": "i64"
           }
         ]
       }
@@ -623,6 +1158,10 @@ export type MarinadeLpBeam = {
           {
             "name": "msolTokenAccount",
             "type": "publicKey"
+          },
+          {
+            "name": "targetLiquidityBps",
+            "type": "u16"
           }
         ]
       }
@@ -635,55 +1174,450 @@ export type MarinadeLpBeam = {
       "msg": "An error occurred during calculation"
     },
     {
-      "code": 6001,
-      "name": "Unimplemented",
-      "msg": "This feature is unimplemented for this beam"
+      "code": 6001,
+      "name": "Unimplemented",
+      "msg": "This feature is unimplemented for this beam"
+    },
+    {
+      "code": 6002,
+      "name": "InsufficientYieldBalance",
+      "msg": "The yield balance is insufficient to extract yield"
+    },
+    {
+      "code": 6003,
+      "name": "WithdrawalBelowMinimum",
+      "msg": "The withdrawal paid out fewer lamports than the requested minimum"
+    },
+    {
+      "code": 6004,
+      "name": "LiquidityWithinTarget",
+      "msg": "The liquidity is not above its target"
+    },
+    {
+      "code": 6005,
+      "name": "InvalidTargetLiquidity",
+      "msg": "The target liquidity must not exceed 10000 basis points"
+    },
+    {
+      "code": 6006,
+      "name": "StateAlreadyResized",
+      "msg": "The state has already been resized"
+    }
+  ]
+};
+
+export const IDL: MarinadeLpBeam = {
+  "version": "0.1.0",
+  "name": "marinade_lp_beam",
+  "instructions": [
+    {
+      "name": "initialize",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The token account of the liquidity pool tokens held by the beam."
+          ]
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "input",
+          "type": {
+            "defined": "StateEntry"
+          }
+        }
+      ]
+    },
+    {
+      "name": "update",
+      "accounts": [
+        {
+          "name": "updateAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "updateInput",
+          "type": {
+            "defined": "StateEntry"
+          }
+        }
+      ]
+    },
+    {
+      "name": "resizeState",
+      "docs": [
+        "Resizes a state created before `target_liquidity_bps` was added, which cannot be",
+        "deserialized (and so updated) until it has room for it, and sets its target liquidity."
+      ],
+      "accounts": [
+        {
+          "name": "updateAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Its discriminator and update authority are checked in the handler."
+          ]
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "targetLiquidityBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "deposit",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositor",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "mintGsolTo",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "gsolMintAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLegAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMintAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity added."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6002,
-      "name": "InsufficientYieldBalance",
-      "msg": "The yield balance is insufficient to extract yield"
-    }
-  ]
-};
-
-export const IDL: MarinadeLpBeam = {
-  "version": "0.1.0",
-  "name": "marinade_lp_beam",
-  "instructions": [
+      "name": "withdraw",
+      "docs": [
+        "Withdraws `lamports` by removing liquidity from the Marinade liq_pool.",
+        "",
+        "Errors if the withdrawer receives fewer than `min_lamports_out` lamports."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "withdrawer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "gsolTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "transferMsolTo",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "When withdrawing from the Marinade LP, the withdrawal is part SOL, part mSOL.",
+            "The SOL portion is transferred to the user (withdrawer) and the mSOL portion",
+            "is transferred to the msol_token_account owned by the marinade stake pool."
+          ]
+        },
+        {
+          "name": "liqPoolSolLegPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLegAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity removed."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        },
+        {
+          "name": "minLamportsOut",
+          "type": "u64"
+        }
+      ]
+    },
     {
-      "name": "initialize",
+      "name": "burn",
+      "docs": [
+        "Burning is withdrawing without redeeming the pool tokens. The result is a beam that is \"worth more\"",
+        "than the SOL that has been staked into it, i.e. the pool tokens are more valuable than the SOL.",
+        "This allows yield extraction and can be seen as a form of \"donation\"."
+      ],
       "accounts": [
         {
-          "name": "payer",
+          "name": "state",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "state",
+          "name": "sunriseState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "liqPoolMint",
-          "isMut": false,
+          "name": "burner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "gsolTokenAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "liqPoolVault",
+          "name": "gsolMint",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "The token account of the liquidity pool tokens held by the beam."
+            "Verified in CPI to Sunrise program."
           ]
         },
         {
-          "name": "vaultAuthority",
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
@@ -693,45 +1627,35 @@ export const IDL: MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "input",
-          "type": {
-            "defined": "StateEntry"
-          }
+          "name": "lamports",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "update",
-      "accounts": [
-        {
-          "name": "updateAuthority",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "state",
-          "isMut": true,
-          "isSigner": false
-        }
-      ],
+      "name": "orderWithdrawal",
+      "accounts": [],
       "args": [
         {
-          "name": "updateInput",
-          "type": {
-            "defined": "StateEntry"
-          }
+          "name": "lamports",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "deposit",
+      "name": "redeemTicket",
+      "accounts": [],
+      "args": []
+    },
+    {
+      "name": "extractYield",
       "accounts": [
         {
           "name": "state",
@@ -739,25 +1663,44 @@ export const IDL: MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "marinadeState",
+          "name": "sunriseState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sunriseState",
+          "name": "yieldAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "depositor",
+          "name": "treasury",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Only needed while the state charges a protocol fee."
+          ]
         },
         {
-          "name": "mintGsolTo",
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "liqPoolMint",
           "isMut": true,
@@ -774,23 +1717,15 @@ export const IDL: MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "transferMsolTo",
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "Verified in CPI to Sunrise program."
+            "When withdrawing from the Marinade LP, the withdrawal is part SOL, part mSOL.",
+            "The SOL portion is transferred to the user (withdrawer) and the mSOL portion",
+            "is transferred to the msol_token_account owned by the marinade stake pool."
           ]
         },
-        {
-          "name": "gsolMintAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "liqPoolSolLegPda",
           "isMut": true,
@@ -803,12 +1738,7 @@ export const IDL: MarinadeLpBeam = {
         },
         {
           "name": "liqPoolMsolLegAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "liqPoolMintAuthority",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -822,25 +1752,33 @@ export const IDL: MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "marinadeProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marinadeProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity extracted."
+          ]
         }
       ],
-      "args": [
-        {
-          "name": "lamports",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "withdraw",
+      "name": "rebalanceLiquidity",
+      "docs": [
+        "Permissionless crank that keeps the liquidity buffer at its target size.",
+        "",
+        "If the gSOL this beam is responsible for exceeds its target share of the gSOL supply,",
+        "the surplus liquidity is removed and moved to the mSOL recipient beam (typically the Marinade-SP beam).",
+        "The mSOL leg is sent to the recipient's mSOL token account, and the SOL leg is staked",
+        "with Marinade into the same account. The gSOL backed by the surplus is transferred to the recipient beam.",
+        "",
+        "A shortfall is filled from the Marinade-SP beam by its `fund_liquidity_beam` crank."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -858,14 +1796,12 @@ export const IDL: MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "withdrawer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "gsolTokenAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "gsolMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gSOL mint, whose supply determines the target liquidity."
+          ]
         },
         {
           "name": "liqPoolMint",
@@ -879,7 +1815,7 @@ export const IDL: MarinadeLpBeam = {
         },
         {
           "name": "vaultAuthority",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -887,9 +1823,7 @@ export const IDL: MarinadeLpBeam = {
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "When withdrawing from the Marinade LP, the withdrawal is part SOL, part mSOL.",
-            "The SOL portion is transferred to the user (withdrawer) and the mSOL portion",
-            "is transferred to the msol_token_account owned by the marinade stake pool."
+            "Receives the mSOL leg of the removed liquidity, and the mSOL minted for the SOL leg."
           ]
         },
         {
@@ -904,6 +1838,21 @@ export const IDL: MarinadeLpBeam = {
         },
         {
           "name": "liqPoolMsolLegAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "msolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "msolMintAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "reservePda",
           "isMut": true,
           "isSigner": false
         },
@@ -913,151 +1862,212 @@ export const IDL: MarinadeLpBeam = {
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity removed."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateEpochReport",
+      "docs": [
+        "Reports the beam's surplus to sunrise, and checkpoints its yield breakdown."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Required to update the core state epoch report",
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "marinadeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "liqPoolSolLegPda",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
+          "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
-          "isMut": false,
+          "name": "liqPoolMsolLeg",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
-          "isMut": false,
+          "name": "liqPoolMsolLegAuthority",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "marinadeProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "yieldBreakdown",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The beam's yield breakdown, updated with the liquidity held."
+          ]
         }
       ],
-      "args": [
-        {
-          "name": "lamports",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "burn",
+      "name": "initYieldBreakdown",
       "docs": [
-        "Burning is withdrawing without redeeming the pool tokens. The result is a beam that is \"worth more\"",
-        "than the SOL that has been staked into it, i.e. the pool tokens are more valuable than the SOL.",
-        "This allows yield extraction and can be seen as a form of \"donation\"."
+        "Creates the yield breakdown account of a beam initialized without one,",
+        "using the current value of the beam's liquidity pool position as its cost basis."
       ],
       "accounts": [
         {
           "name": "state",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseState",
-          "isMut": true,
+          "name": "marinadeState",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "burner",
+          "name": "updateAuthority",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "gsolTokenAccount",
+          "name": "yieldBreakdown",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "liqPoolMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "liqPoolTokenVault",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "liqPoolSolLegPda",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "liqPoolMsolLeg",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "lamports",
-          "type": "u64"
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
-    },
-    {
-      "name": "orderWithdrawal",
-      "accounts": [],
-      "args": []
-    },
-    {
-      "name": "redeemTicket",
-      "accounts": [],
+      ],
       "args": []
     },
     {
-      "name": "extractYield",
+      "name": "quoteDeposit",
+      "docs": [
+        "View: quotes a [deposit] of `lamports`, returning the gSOL that would be minted",
+        "and the liq_pool tokens that the beam would receive."
+      ],
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "marinadeState",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "sunriseState",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "yieldAccount",
-          "isMut": true,
+          "name": "marinadeState",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "liqPoolMint",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "liqPoolTokenVault",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1065,94 +2075,116 @@ export const IDL: MarinadeLpBeam = {
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "transferMsolTo",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "When withdrawing from the Marinade LP, the withdrawal is part SOL, part mSOL.",
-            "The SOL portion is transferred to the user (withdrawer) and the mSOL portion",
-            "is transferred to the msol_token_account owned by the marinade stake pool."
-          ]
-        },
         {
           "name": "liqPoolSolLegPda",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "liqPoolMsolLeg",
-          "isMut": true,
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "DepositQuote"
+      }
+    },
+    {
+      "name": "quoteWithdraw",
+      "docs": [
+        "View: quotes a [withdraw] of `lamports`, returning the lamports that would be received",
+        "from the SOL leg of the pool, and the liq_pool tokens that the beam would burn.",
+        "The mSOL leg goes to the mSOL recipient beam, not the withdrawer."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "liqPoolMsolLegAuthority",
-          "isMut": true,
+          "name": "sunriseState",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "marinadeState",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "liqPoolMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "liqPoolTokenVault",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marinadeProgram",
+          "name": "liqPoolSolLegPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqPoolMsolLeg",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "WithdrawQuote"
+      }
     },
     {
-      "name": "updateEpochReport",
+      "name": "getBeamValue",
+      "docs": [
+        "View: returns the value of the beam's liquidity pool position, the surplus that",
+        "[update_epoch_report] would report and the SOL leg that [extract_yield] would extract."
+      ],
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "marinadeState",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "sunriseState",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "marinadeState",
           "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "Required to update the core state epoch report",
-            "Verified in CPI to Sunrise program."
-          ]
+          "isSigner": false
         },
         {
           "name": "liqPoolMint",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "liqPoolTokenVault",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1162,31 +2194,19 @@ export const IDL: MarinadeLpBeam = {
         },
         {
           "name": "liqPoolSolLegPda",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "liqPoolMsolLeg",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "liqPoolMsolLegAuthority",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "liqPoolMsolLeg",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [],
+      "returns": {
+        "defined": "BeamValue"
+      }
     }
   ],
   "accounts": [
@@ -1238,6 +2258,80 @@ export const IDL: MarinadeLpBeam = {
               "The token-account that receives msol when withdrawing liquidity."
             ],
             "type": "publicKey"
+          },
+          {
+            "name": "targetLiquidityBps",
+            "docs": [
+              "The target size of the liquidity buffer, as a share of the gSOL supply in basis points."
+            ],
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "yieldBreakdown",
+      "docs": [
+        "Splits the yield earned on the beam's liquidity pool position into",
+        "liquidity pool fee income and mSOL staking appreciation.",
+        "",
+        "The account tracks the cost basis of the position: the SOL value of the liq_pool tokens held,",
+        "as of the last checkpoint, adjusted by the value of liquidity deposited and withdrawn since.",
+        "Flows are recorded at the value they move, so they are never counted as yield.",
+        "A checkpoint, taken when reporting and extracting yield, attributes the change in value",
+        "over the basis and moves the basis to the current value of the position."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The beam state this breakdown belongs to."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "liqPoolTokens",
+            "docs": [
+              "The liq_pool tokens held by the beam, as recorded by the beam."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lamportsValue",
+            "docs": [
+              "The cost basis of the liq_pool tokens held, in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "msolPrice",
+            "docs": [
+              "The value of one mSOL in lamports at the last checkpoint."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "docs": [
+              "The epoch of the last checkpoint."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeIncome",
+            "docs": [
+              "The total yield earned from liquidity pool swap fees, in lamports."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "stakingAppreciation",
+            "docs": [
+              "The total yield earned from the appreciation of the mSOL leg, in lamports."
+            ],
+            "type": "i64"
           }
         ]
       }
@@ -1272,6 +2366,10 @@ export const IDL: MarinadeLpBeam = {
           {
             "name": "msolTokenAccount",
             "type": "publicKey"
+          },
+          {
+            "name": "targetLiquidityBps",
+            "type": "u16"
           }
         ]
       }
@@ -1292,6 +2390,26 @@ export const IDL: MarinadeLpBeam = {
       "code": 6002,
       "name": "InsufficientYieldBalance",
       "msg": "The yield balance is insufficient to extract yield"
+    },
+    {
+      "code": 6003,
+      "name": "WithdrawalBelowMinimum",
+      "msg": "The withdrawal paid out fewer lamports than the requested minimum"
+    },
+    {
+      "code": 6004,
+      "name": "LiquidityWithinTarget",
+      "msg": "The liquidity is not above its target"
+    },
+    {
+      "code": 6005,
+      "name": "InvalidTargetLiquidity",
+      "msg": "The target liquidity must not exceed 10000 basis points"
+    },
+    {
+      "code": 6006,
+      "name": "StateAlreadyResized",
+      "msg": "The state has already been resized"
     }
   ]
 };
@@ -80,17 +80,18 @@ export type SplBeam = {
     },
     {
       "name": "deposit",
+      "docs": [
+        "Deposits `lamports` into the stake pool.",
+        "",
+        "The gSOL minted is the value of the pool tokens actually received by the beam,",
+        "which is less than `lamports` if the pool charges deposit fees."
+      ],
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "stakePool",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "sunriseState",
           "isMut": true,
@@ -107,56 +108,61 @@ export type SplBeam = {
           "isSigner": false
         },
         {
-          "name": "poolMint",
+          "name": "gsolMint",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
         },
         {
-          "name": "poolTokenVault",
-          "isMut": true,
+          "name": "gsolMintAuthority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "stakePoolWithdrawAuthority",
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveStakeAccount",
+          "name": "stakePool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "managerFeeAccount",
+          "name": "poolMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "poolTokenVault",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
+          "isSigner": false
         },
         {
-          "name": "gsolMintAuthority",
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "stakePoolWithdrawAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
-          "isMut": false,
+          "name": "reserveStakeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "managerFeeAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -184,6 +190,12 @@ export type SplBeam = {
     },
     {
       "name": "depositStake",
+      "docs": [
+        "Deposits a stake account into the stake pool.",
+        "",
+        "The gSOL minted is the value of the pool tokens actually received by the beam,",
+        "which is less than the delegated stake if the pool charges deposit fees."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -318,17 +330,18 @@ export type SplBeam = {
     },
     {
       "name": "withdraw",
+      "docs": [
+        "Withdraws `lamports` from the stake pool's reserve.",
+        "",
+        "Errors if the withdrawer receives fewer than `min_lamports_out` lamports",
+        "after the pool's SOL withdrawal fee."
+      ],
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "stakePool",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "sunriseState",
           "isMut": true,
@@ -344,6 +357,29 @@ export type SplBeam = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "poolMint",
           "isMut": true,
@@ -390,30 +426,102 @@ export type SplBeam = {
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "splStakePoolProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        },
+        {
+          "name": "minLamportsOut",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawAsLst",
+      "docs": [
+        "Withdrawing as an LST burns gSOL and transfers the equivalent amount of pool tokens",
+        "out of the beam's vault, rather than redeeming them. This incurs no withdrawal fee."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
+          "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "withdrawer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "gsolTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "poolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "splStakePoolProgram",
+          "name": "transferPoolTokensTo",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The token account that receives the withdrawn pool tokens."
+          ]
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
@@ -448,11 +556,6 @@ export type SplBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "stakePool",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "burner",
           "isMut": true,
@@ -481,6 +584,11 @@ export type SplBeam = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "stakePool",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -501,6 +609,16 @@ export type SplBeam = {
     },
     {
       "name": "withdrawStake",
+      "docs": [
+        "Withdraws `lamports` from the stake pool into `new_stake_account`, with the withdrawer as",
+        "its stake and withdraw authority.",
+        "The rent of the stake account is deducted from the withdrawal, and only the lamports",
+        "actually withdrawn are burned in gSOL.",
+        "",
+        "The stake is split from the pool's stake account for the validator with vote account",
+        "`vote_account`, falling back to the pool's preferred withdrawal validator if none is given.",
+        "If neither is set, the stake is split from the account passed as `stake_account_to_split`."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -529,8 +647,11 @@ export type SplBeam = {
         },
         {
           "name": "newStakeAccount",
-          "isMut": false,
-          "isSigner": false
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program."
+          ]
         },
         {
           "name": "poolMint",
@@ -620,21 +741,28 @@ export type SplBeam = {
         {
           "name": "lamports",
           "type": "u64"
+        },
+        {
+          "name": "voteAccount",
+          "type": {
+            "option": "publicKey"
+          }
         }
       ]
     },
     {
       "name": "orderWithdrawal",
-      "accounts": [],
-      "args": []
-    },
-    {
-      "name": "redeemTicket",
-      "accounts": [],
-      "args": []
-    },
-    {
-      "name": "updateEpochReport",
+      "docs": [
+        "Orders a delayed withdrawal of `lamports`, avoiding the pool's SOL withdrawal fee",
+        "and reserve liquidity limits.",
+        "",
+        "The lamports are withdrawn from the pool as a stake account, which is deactivated.",
+        "As with `withdraw_stake`, the rent of the stake account is deducted,",
+        "and only the lamports withdrawn are burned in gSOL.",
+        "A ticket is created mapping the stake account to the withdrawer, which can be",
+        "redeemed once the stake account has cooled down.",
+        "Stake split from the pool's reserve is not delegated, so its ticket can be redeemed at once."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -647,69 +775,50 @@ export type SplBeam = {
           "isSigner": false
         },
         {
-          "name": "stakePool",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "poolMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "vaultAuthority",
-          "isMut": false,
-          "isSigner": false
+          "name": "withdrawer",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "poolTokenVault",
-          "isMut": false,
+          "name": "gsolTokenAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "gsolMint",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "docs": [
-            "Required to update the core state epoch report",
             "Verified in CPI to Sunrise program."
           ]
         },
-        {
-          "name": "sunriseProgram",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "extractYield",
-      "accounts": [
+        },
         {
-          "name": "state",
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseState",
+          "name": "stakePool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakePool",
+          "name": "newStakeAccount",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true,
+          "docs": [
+            "The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program."
+          ]
         },
         {
-          "name": "payer",
+          "name": "withdrawalTicket",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "poolMint",
@@ -717,48 +826,962 @@ export type SplBeam = {
           "isSigner": false
         },
         {
-          "name": "yieldAccount",
+          "name": "poolTokenVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "newStakeAccount",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program."
-          ]
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "vaultAuthority",
+          "name": "stakePoolWithdrawAuthority",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "validatorStakeList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakeAccountToSplit",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "managerFeeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarClock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nativeStakeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "splStakePoolProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "redeemTicket",
+      "docs": [
+        "Redeems a withdrawal ticket, sending the lamports in its stake account to the beneficiary.",
+        "",
+        "Errors if the stake account has not yet cooled down."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "beneficiary",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "withdrawalTicket",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarClock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarStakeHistory",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nativeStakeProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateEpochReport",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Required to update the core state epoch report",
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolTokenVault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "extractYield",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "yieldAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Only needed while the state charges a protocol fee."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "poolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newStakeAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program."
+          ]
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakePoolWithdrawAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "validatorStakeList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakeAccountToSplit",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "managerFeeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarClock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nativeStakeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarStakeHistory",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "splStakePoolProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "refreshPool",
+      "docs": [
+        "Permissionless wrapper around the stake pool's update crank.",
+        "",
+        "Updates the balances of the validators in the pool's validator list from `start_index`,",
+        "using the validator and transient stake account pairs passed as remaining accounts.",
+        "If this reaches the end of the validator list, the pool's total balance is also updated,",
+        "allowing the pool to be priced again in the current epoch."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakePoolWithdrawAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "validatorList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveStakeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "managerFeeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarClock",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarStakeHistory",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nativeStakeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "splStakePoolProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "startIndex",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "quoteDeposit",
+      "docs": [
+        "View: quotes a [deposit] of `lamports`, returning the gSOL that would be minted",
+        "and the pool tokens that the beam would receive."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolTokenVault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "DepositQuote"
+      }
+    },
+    {
+      "name": "quoteWithdraw",
+      "docs": [
+        "View: quotes a [withdraw] of `lamports`, returning the lamports that would be received",
+        "after the pool's SOL withdrawal fee, and the pool tokens that the beam would spend."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolTokenVault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "WithdrawQuote"
+      }
+    },
+    {
+      "name": "getBeamValue",
+      "docs": [
+        "View: returns the value of the pool tokens held by the beam, the surplus that",
+        "[update_epoch_report] would report and the yield that [extract_yield] would extract."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolTokenVault",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "BeamValue"
+      }
+    }
+  ],
+  "accounts": [
+    {
+      "name": "state",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "updateAuthority",
+            "docs": [
+              "The update authority of the state."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "stakePool",
+            "docs": [
+              "The state account of the spl pool."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "sunriseState",
+            "docs": [
+              "The state of the main sunrise beam."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "vaultAuthorityBump",
+            "docs": [
+              "The bump of the PDA that can authorize spending from the vault",
+              "that holds pool tokens."
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "withdrawalTicket",
+      "docs": [
+        "Maps a deactivating stake account, created by ordering a withdrawal, to the gSOL holder",
+        "that ordered it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The beam state that the withdrawal was ordered from."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "stakeAccount",
+            "docs": [
+              "The stake account holding the withdrawn lamports."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "beneficiary",
+            "docs": [
+              "The account that can redeem the ticket."
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "StateEntry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "updateAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakePool",
+            "type": "publicKey"
+          },
+          {
+            "name": "sunriseState",
+            "type": "publicKey"
+          },
+          {
+            "name": "vaultAuthorityBump",
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "NotDelegated",
+      "msg": "No delegation for stake account deposit"
+    },
+    {
+      "code": 6001,
+      "name": "CalculationFailure",
+      "msg": "An error occurred during calculation"
+    },
+    {
+      "code": 6002,
+      "name": "Unimplemented",
+      "msg": "This feature is unimplemented for this beam"
+    },
+    {
+      "code": 6003,
+      "name": "YieldStakeAccountNotCooledDown",
+      "msg": "The yield stake account cannot yet be claimed"
+    },
+    {
+      "code": 6004,
+      "name": "InsufficientYieldToExtract",
+      "msg": "The yield being extracted is insufficient to cover the rent of the stake account"
+    },
+    {
+      "code": 6005,
+      "name": "WithdrawalBelowMinimum",
+      "msg": "The withdrawal paid out fewer lamports than the requested minimum"
+    },
+    {
+      "code": 6006,
+      "name": "WithdrawalTicketNotRedeemable",
+      "msg": "The stake account of this withdrawal ticket cannot yet be claimed"
+    },
+    {
+      "code": 6007,
+      "name": "ValidatorNotInPool",
+      "msg": "The validator is not in the stake pool's validator list"
+    },
+    {
+      "code": 6008,
+      "name": "NotPreferredDepositValidator",
+      "msg": "The stake pool only accepts stake deposits delegated to its preferred validator"
+    },
+    {
+      "code": 6009,
+      "name": "InvalidValidatorStakeAccount",
+      "msg": "The stake account is not the stake pool's stake account for the validator"
+    },
+    {
+      "code": 6010,
+      "name": "StakePoolNotUpdated",
+      "msg": "The stake pool has not been updated in the current epoch"
+    },
+    {
+      "code": 6011,
+      "name": "InvalidValidatorList",
+      "msg": "The validator list is not the stake pool's validator list"
+    }
+  ]
+};
+
+export const IDL: SplBeam = {
+  "version": "0.1.0",
+  "name": "spl_beam",
+  "instructions": [
+    {
+      "name": "initialize",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "input",
+          "type": {
+            "defined": "StateEntry"
+          }
+        }
+      ]
+    },
+    {
+      "name": "update",
+      "accounts": [
+        {
+          "name": "updateAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "updateInput",
+          "type": {
+            "defined": "StateEntry"
+          }
+        }
+      ]
+    },
+    {
+      "name": "deposit",
+      "docs": [
+        "Deposits `lamports` into the stake pool.",
+        "",
+        "The gSOL minted is the value of the pool tokens actually received by the beam,",
+        "which is less than `lamports` if the pool charges deposit fees."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositor",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "mintGsolTo",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "gsolMintAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePoolWithdrawAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "reserveStakeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "managerFeeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "splStakePoolProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "depositStake",
+      "docs": [
+        "Deposits a stake account into the stake pool.",
+        "",
+        "The gSOL minted is the value of the pool tokens actually received by the beam,",
+        "which is less than the delegated stake if the pool charges deposit fees."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "stakeOwner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "stakeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mintGsolTo",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "poolTokenVault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakePoolWithdrawAuthority",
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "validatorStakeList",
+          "name": "validatorList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakeAccountToSplit",
+          "name": "stakePoolDepositAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "stakePoolWithdrawAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "reserveStakeAccount",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "validatorStakeAccount",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "managerFeeAccount",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "sysvarStakeHistory",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "sysvarClock",
           "isMut": false,
@@ -770,7 +1793,15 @@ export type SplBeam = {
           "isSigner": false
         },
         {
-          "name": "sysvarStakeHistory",
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "gsolMintAuthority",
           "isMut": false,
           "isSigner": false
         },
@@ -801,124 +1832,64 @@ export type SplBeam = {
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "state",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "updateAuthority",
-            "docs": [
-              "The update authority of the state."
-            ],
-            "type": "publicKey"
-          },
-          {
-            "name": "stakePool",
-            "docs": [
-              "The state account of the spl pool."
-            ],
-            "type": "publicKey"
-          },
-          {
-            "name": "sunriseState",
-            "docs": [
-              "The state of the main sunrise beam."
-            ],
-            "type": "publicKey"
-          },
-          {
-            "name": "vaultAuthorityBump",
-            "docs": [
-              "The bump of the PDA that can authorize spending from the vault",
-              "that holds pool tokens."
-            ],
-            "type": "u8"
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "StateEntry",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "updateAuthority",
-            "type": "publicKey"
-          },
-          {
-            "name": "stakePool",
-            "type": "publicKey"
-          },
-          {
-            "name": "sunriseState",
-            "type": "publicKey"
-          },
-          {
-            "name": "vaultAuthorityBump",
-            "type": "u8"
-          }
-        ]
-      }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "NotDelegated",
-      "msg": "No delegation for stake account deposit"
-    },
-    {
-      "code": 6001,
-      "name": "CalculationFailure",
-      "msg": "An error occurred during calculation"
-    },
-    {
-      "code": 6002,
-      "name": "Unimplemented",
-      "msg": "This feature is unimplemented for this beam"
-    },
-    {
-      "code": 6003,
-      "name": "YieldStakeAccountNotCooledDown",
-      "msg": "The yield stake account cannot yet be claimed"
     },
     {
-      "code": 6004,
-      "name": "InsufficientYieldToExtract",
-      "msg": "The yield being extracted is insufficient to cover the rent of the stake account"
-    }
-  ]
-};
-
-export const IDL: SplBeam = {
-  "version": "0.1.0",
-  "name": "spl_beam",
-  "instructions": [
-    {
-      "name": "initialize",
+      "name": "withdraw",
+      "docs": [
+        "Withdraws `lamports` from the stake pool's reserve.",
+        "",
+        "Errors if the withdrawer receives fewer than `min_lamports_out` lamports",
+        "after the pool's SOL withdrawal fee."
+      ],
       "accounts": [
         {
-          "name": "payer",
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "withdrawer",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "state",
+          "name": "gsolTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "poolMint",
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "stakePool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "poolTokenVault",
           "isMut": true,
@@ -930,55 +1901,68 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "stakePoolWithdrawAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "reserveStakeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "managerFeeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarStakeHistory",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "sysvarClock",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "input",
-          "type": {
-            "defined": "StateEntry"
-          }
-        }
-      ]
-    },
-    {
-      "name": "update",
-      "accounts": [
+          "name": "nativeStakeProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "updateAuthority",
-          "isMut": true,
-          "isSigner": true
+          "name": "splStakePoolProgram",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "state",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "updateInput",
-          "type": {
-            "defined": "StateEntry"
-          }
+          "name": "lamports",
+          "type": "u64"
+        },
+        {
+          "name": "minLamportsOut",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "deposit",
+      "name": "withdrawAsLst",
+      "docs": [
+        "Withdrawing as an LST burns gSOL and transfers the equivalent amount of pool tokens",
+        "out of the beam's vault, rather than redeeming them. This incurs no withdrawal fee."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -987,7 +1971,7 @@ export const IDL: SplBeam = {
         },
         {
           "name": "stakePool",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -996,18 +1980,18 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "depositor",
+          "name": "withdrawer",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "mintGsolTo",
+          "name": "gsolTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "poolMint",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -1021,17 +2005,69 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "stakePoolWithdrawAuthority",
+          "name": "transferPoolTokensTo",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The token account that receives the withdrawn pool tokens."
+          ]
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveStakeAccount",
+          "name": "sunriseProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "burn",
+      "docs": [
+        "Burning is withdrawing without redeeming the pool tokens. The result is a beam that is \"worth more\"",
+        "than the SOL that has been staked into it, i.e. the pool tokens are more valuable than the SOL.",
+        "This allows yield extraction and can be seen as a form of \"donation\"."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "managerFeeAccount",
+          "name": "burner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "gsolTokenAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -1043,11 +2079,6 @@ export const IDL: SplBeam = {
             "Verified in CPI to Sunrise program."
           ]
         },
-        {
-          "name": "gsolMintAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "sysvarInstructions",
           "isMut": false,
@@ -1059,7 +2090,7 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "splStakePoolProgram",
+          "name": "stakePool",
           "isMut": false,
           "isSigner": false
         },
@@ -1082,7 +2113,17 @@ export const IDL: SplBeam = {
       ]
     },
     {
-      "name": "depositStake",
+      "name": "withdrawStake",
+      "docs": [
+        "Withdraws `lamports` from the stake pool into `new_stake_account`, with the withdrawer as",
+        "its stake and withdraw authority.",
+        "The rent of the stake account is deducted from the withdrawal, and only the lamports",
+        "actually withdrawn are burned in gSOL.",
+        "",
+        "The stake is split from the pool's stake account for the validator with vote account",
+        "`vote_account`, falling back to the pool's preferred withdrawal validator if none is given.",
+        "If neither is set, the stake is split from the account passed as `stake_account_to_split`."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -1100,19 +2141,22 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "stakeOwner",
+          "name": "withdrawer",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "stakeAccount",
+          "name": "gsolTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "mintGsolTo",
+          "name": "newStakeAccount",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true,
+          "docs": [
+            "The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program."
+          ]
         },
         {
           "name": "poolMint",
@@ -1129,29 +2173,19 @@ export const IDL: SplBeam = {
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "validatorList",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "stakePoolDepositAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "stakePoolWithdrawAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveStakeAccount",
+          "name": "validatorStakeList",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "validatorStakeAccount",
-          "isMut": false,
+          "name": "stakeAccountToSplit",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -1182,11 +2216,6 @@ export const IDL: SplBeam = {
             "Verified in CPI to Sunrise program."
           ]
         },
-        {
-          "name": "gsolMintAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "sysvarInstructions",
           "isMut": false,
@@ -1213,21 +2242,38 @@ export const IDL: SplBeam = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        },
+        {
+          "name": "voteAccount",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
     },
     {
-      "name": "withdraw",
+      "name": "orderWithdrawal",
+      "docs": [
+        "Orders a delayed withdrawal of `lamports`, avoiding the pool's SOL withdrawal fee",
+        "and reserve liquidity limits.",
+        "",
+        "The lamports are withdrawn from the pool as a stake account, which is deactivated.",
+        "As with `withdraw_stake`, the rent of the stake account is deducted,",
+        "and only the lamports withdrawn are burned in gSOL.",
+        "A ticket is created mapping the stake account to the withdrawer, which can be",
+        "redeemed once the stake account has cooled down.",
+        "Stake split from the pool's reserve is not delegated, so its ticket can be redeemed at once."
+      ],
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "stakePool",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "sunriseState",
           "isMut": true,
@@ -1244,65 +2290,83 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "poolMint",
+          "name": "gsolMint",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "Verified in CPI to Sunrise program."
+          ]
         },
         {
-          "name": "poolTokenVault",
-          "isMut": true,
+          "name": "sysvarInstructions",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "stakePoolWithdrawAuthority",
-          "isMut": false,
+          "name": "stakePool",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveStakeAccount",
+          "name": "newStakeAccount",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program."
+          ]
+        },
+        {
+          "name": "withdrawalTicket",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "managerFeeAccount",
+          "name": "poolMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sysvarStakeHistory",
-          "isMut": false,
+          "name": "poolTokenVault",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sysvarClock",
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "nativeStakeProgram",
+          "name": "stakePoolWithdrawAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "validatorStakeList",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
+          "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "stakeAccountToSplit",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "managerFeeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarClock",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "nativeStakeProgram",
           "isMut": false,
           "isSigner": false
         },
@@ -1330,11 +2394,11 @@ export const IDL: SplBeam = {
       ]
     },
     {
-      "name": "burn",
+      "name": "redeemTicket",
       "docs": [
-        "Burning is withdrawing without redeeming the pool tokens. The result is a beam that is \"worth more\"",
-        "than the SOL that has been staked into it, i.e. the pool tokens are more valuable than the SOL.",
-        "This allows yield extraction and can be seen as a form of \"donation\"."
+        "Redeems a withdrawal ticket, sending the lamports in its stake account to the beneficiary.",
+        "",
+        "Errors if the stake account has not yet cooled down."
       ],
       "accounts": [
         {
@@ -1343,33 +2407,70 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "sunriseState",
+          "name": "beneficiary",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "withdrawalTicket",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakePool",
+          "name": "stakeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "burner",
-          "isMut": true,
-          "isSigner": true
+          "name": "sysvarClock",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "gsolTokenAccount",
+          "name": "sysvarStakeHistory",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nativeStakeProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateEpochReport",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sunriseState",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "gsolMint",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "docs": [
+            "Required to update the core state epoch report",
             "Verified in CPI to Sunrise program."
           ]
         },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "sysvarInstructions",
           "isMut": false,
@@ -1381,25 +2482,30 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "stakePool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "poolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "poolTokenVault",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "lamports",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "withdrawStake",
+      "name": "extractYield",
       "accounts": [
         {
           "name": "state",
@@ -1407,45 +2513,67 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "stakePool",
+          "name": "sunriseState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sunriseState",
+          "name": "yieldAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "withdrawer",
+          "name": "treasury",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Only needed while the state charges a protocol fee."
+          ]
         },
         {
-          "name": "gsolTokenAccount",
-          "isMut": true,
+          "name": "sysvarInstructions",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "newStakeAccount",
+          "name": "sunriseProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "poolMint",
+          "name": "stakePool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "poolTokenVault",
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "poolMint",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "newStakeAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program."
+          ]
+        },
         {
           "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "poolTokenVault",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "stakePoolWithdrawAuthority",
           "isMut": false,
@@ -1466,11 +2594,6 @@ export const IDL: SplBeam = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "sysvarStakeHistory",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "sysvarClock",
           "isMut": false,
@@ -1482,20 +2605,7 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "gsolMint",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Verified in CPI to Sunrise program."
-          ]
-        },
-        {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sunriseProgram",
+          "name": "sysvarStakeHistory",
           "isMut": false,
           "isSigner": false
         },
@@ -1515,25 +2625,18 @@ export const IDL: SplBeam = {
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "lamports",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "orderWithdrawal",
-      "accounts": [],
-      "args": []
-    },
-    {
-      "name": "redeemTicket",
-      "accounts": [],
       "args": []
     },
     {
-      "name": "updateEpochReport",
+      "name": "refreshPool",
+      "docs": [
+        "Permissionless wrapper around the stake pool's update crank.",
+        "",
+        "Updates the balances of the validators in the pool's validator list from `start_index`,",
+        "using the validator and transient stake account pairs passed as remaining accounts.",
+        "If this reaches the end of the validator list, the pool's total balance is also updated,",
+        "allowing the pool to be priced again in the current epoch."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -1541,54 +2644,74 @@ export const IDL: SplBeam = {
           "isSigner": false
         },
         {
-          "name": "sunriseState",
+          "name": "stakePool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "stakePool",
+          "name": "stakePoolWithdrawAuthority",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "validatorList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveStakeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "managerFeeAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "poolMint",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vaultAuthority",
+          "name": "sysvarClock",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "poolTokenVault",
+          "name": "sysvarStakeHistory",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "nativeStakeProgram",
           "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "Required to update the core state epoch report",
-            "Verified in CPI to Sunrise program."
-          ]
+          "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "splStakePoolProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "startIndex",
+          "type": "u32"
+        }
+      ]
     },
     {
-      "name": "extractYield",
+      "name": "quoteDeposit",
+      "docs": [
+        "View: quotes a [deposit] of `lamports`, returning the gSOL that would be minted",
+        "and the pool tokens that the beam would receive."
+      ],
       "accounts": [
         {
           "name": "state",
@@ -1597,37 +2720,19 @@ export const IDL: SplBeam = {
         },
         {
           "name": "sunriseState",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "stakePool",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
         {
           "name": "poolMint",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "yieldAccount",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "newStakeAccount",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program."
-          ]
-        },
         {
           "name": "vaultAuthority",
           "isMut": false,
@@ -1635,71 +2740,110 @@ export const IDL: SplBeam = {
         },
         {
           "name": "poolTokenVault",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "stakePoolWithdrawAuthority",
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "DepositQuote"
+      }
+    },
+    {
+      "name": "quoteWithdraw",
+      "docs": [
+        "View: quotes a [withdraw] of `lamports`, returning the lamports that would be received",
+        "after the pool's SOL withdrawal fee, and the pool tokens that the beam would spend."
+      ],
+      "accounts": [
+        {
+          "name": "state",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "validatorStakeList",
-          "isMut": true,
+          "name": "sunriseState",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "stakeAccountToSplit",
-          "isMut": true,
+          "name": "stakePool",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "managerFeeAccount",
-          "isMut": true,
+          "name": "poolMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sysvarClock",
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "nativeStakeProgram",
+          "name": "poolTokenVault",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "sysvarStakeHistory",
+          "name": "lamports",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": "WithdrawQuote"
+      }
+    },
+    {
+      "name": "getBeamValue",
+      "docs": [
+        "View: returns the value of the pool tokens held by the beam, the surplus that",
+        "[update_epoch_report] would report and the yield that [extract_yield] would extract."
+      ],
+      "accounts": [
+        {
+          "name": "state",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "sunriseState",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sunriseProgram",
+          "name": "stakePool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "splStakePoolProgram",
+          "name": "poolMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "vaultAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "poolTokenVault",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [],
+      "returns": {
+        "defined": "BeamValue"
+      }
     }
   ],
   "accounts": [
@@ -1739,6 +2883,39 @@ export const IDL: SplBeam = {
           }
         ]
       }
+    },
+    {
+      "name": "withdrawalTicket",
+      "docs": [
+        "Maps a deactivating stake account, created by ordering a withdrawal, to the gSOL holder",
+        "that ordered it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The beam state that the withdrawal was ordered from."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "stakeAccount",
+            "docs": [
+              "The stake account holding the withdrawn lamports."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "beneficiary",
+            "docs": [
+              "The account that can redeem the ticket."
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
     }
   ],
   "types": [
@@ -1792,6 +2969,41 @@ export const IDL: SplBeam = {
       "code": 6004,
       "name": "InsufficientYieldToExtract",
       "msg": "The yield being extracted is insufficient to cover the rent of the stake account"
+    },
+    {
+      "code": 6005,
+      "name": "WithdrawalBelowMinimum",
+      "msg": "The withdrawal paid out fewer lamports than the requested minimum"
+    },
+    {
+      "code": 6006,
+      "name": "WithdrawalTicketNotRedeemable",
+      "msg": "The stake account of this withdrawal ticket cannot yet be claimed"
+    },
+    {
+      "code": 6007,
+      "name": "ValidatorNotInPool",
+      "msg": "The validator is not in the stake pool's validator list"
+    },
+    {
+      "code": 6008,
+      "name": "NotPreferredDepositValidator",
+      "msg": "The stake pool only accepts stake deposits delegated to its preferred validator"
+    },
+    {
+      "code": 6009,
+      "name": "InvalidValidatorStakeAccount",
+      "msg": "The stake account is not the stake pool's stake account for the validator"
+    },
+    {
+      "code": 6010,
+      "name": "StakePoolNotUpdated",
+      "msg": "The stake pool has not been updated in the current epoch"
+    },
+    {
+      "code": 6011,
+      "name": "InvalidValidatorList",
+      "msg": "The validator list is not the stake pool's validator list"
     }
   ]
 };
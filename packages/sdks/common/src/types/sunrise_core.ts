@@ -5,7 +5,7 @@ export type SunriseCore = {
     {
       "name": "registerState",
       "docs": [
-        "Initializes a [State], setting its initial parameters."
+        "Initializes a [State], setting its initial parameters, and its [BackingOracle]."
       ],
       "accounts": [
         {
@@ -28,6 +28,11 @@ export type SunriseCore = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -51,7 +56,9 @@ export type SunriseCore = {
     {
       "name": "updateState",
       "docs": [
-        "Updates a [State] without modifying its [BeamDetails] list."
+        "Updates a [State] without modifying its [BeamDetails] list.",
+        "",
+        "Errors if the protocol fee exceeds 100%, or is set without a treasury."
       ],
       "accounts": [
         {
@@ -106,7 +113,8 @@ export type SunriseCore = {
     {
       "name": "resizeAllocations",
       "docs": [
-        "Resize the state so it can append `additional` more allocations."
+        "Resize the state so it can append `additional` more allocations,",
+        "and its [BackingOracle] to match."
       ],
       "accounts": [
         {
@@ -124,6 +132,11 @@ export type SunriseCore = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -317,7 +330,7 @@ export type SunriseCore = {
       "docs": [
         "Removes a beam from the state.",
         "",
-        "Errors if the beam's allocation is not set to zero."
+        "Errors if the beam's allocation is not set to zero, or if it has a deficit."
       ],
       "accounts": [
         {
@@ -380,7 +393,11 @@ export type SunriseCore = {
     {
       "name": "updateEpochReport",
       "docs": [
-        "Updates the Epoch Report Account, which stores the amount of yield extracted or extractable over time"
+        "Updates the Epoch Report Account, which stores the amount of yield extracted or extractable over time",
+        "",
+        "The beam reports a signed `surplus`: positive values are extractable yield and",
+        "negative values are recorded as a deficit against the beam.",
+        "The [BackingOracle] is updated with the new report."
       ],
       "accounts": [
         {
@@ -402,6 +419,11 @@ export type SunriseCore = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "sysvarInstructions",
           "isMut": false,
@@ -410,15 +432,20 @@ export type SunriseCore = {
       ],
       "args": [
         {
-          "name": "extractableYield",
-          "type": "u64"
+          "name": "surplus",
+          "type": "i64"
         }
       ]
     },
     {
       "name": "extractYield",
       "docs": [
-        "CPI request from a beam program to extract yield from Sunrise"
+        "CPI request from a beam program to extract yield from Sunrise",
+        "",
+        "The protocol fee share of the extracted yield, which the beam sends to the treasury,",
+        "is recorded on the epoch report.",
+        "",
+        "Errors while any beam has an outstanding deficit."
       ],
       "accounts": [
         {
@@ -450,192 +477,840 @@ export type SunriseCore = {
           "type": "u64"
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "state",
+      "name": "socialiseDeficit",
       "docs": [
-        "The state for the Sunrise beam controller program."
+        "Covers `amount` of the deficit of `beam` with the surplus of `covering_beam`.",
+        "",
+        "Errors if either beam has not reported this epoch, or if `amount` exceeds",
+        "the deficit of `beam` or the surplus of `covering_beam`."
       ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "updateAuthority",
-            "docs": [
-              "Update authority for this state."
-            ],
-            "type": "publicKey"
-          },
-          {
-            "name": "gsolMint",
-            "docs": [
-              "The Sunrise gSol mint."
-            ],
-            "type": "publicKey"
-          },
-          {
-            "name": "preSupply",
-            "docs": [
-              "The gSol mint supply when this program started",
-              "monitoring it."
-            ],
-            "type": "u64"
-          },
-          {
-            "name": "gsolMintAuthorityBump",
-            "docs": [
-              "Bump of the gSol mint authority PDA."
-            ],
-            "type": "u8"
-          },
-          {
-            "name": "yieldAccount",
-            "docs": [
-              "The Sunrise yield account."
-            ],
-            "type": "publicKey"
-          },
-          {
-            "name": "reservedSpace",
-            "docs": [
-              "Reserved space for adding future fields."
-            ],
-            "type": {
-              "array": [
-                "u32",
-                32
-              ]
-            }
-          },
-          {
-            "name": "allocations",
-            "docs": [
-              "Holds [BeamDetails] for all supported beams."
-            ],
-            "type": {
-              "vec": {
-                "defined": "BeamDetails"
-              }
-            }
-          },
-          {
-            "name": "epochReport",
-            "type": {
-              "defined": "EpochReport"
-            }
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "updateAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "beam",
+          "type": "publicKey"
+        },
+        {
+          "name": "coveringBeam",
+          "type": "publicKey"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
     {
-      "name": "BeamDetails",
+      "name": "registerBackingOracle",
       "docs": [
-        "Holds information about a registered beam."
+        "Initializes the [BackingOracle] of a state, with capacity for all of its allocations.",
+        "",
+        "Only needed for states registered before the oracle was created with the state."
       ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "key",
-            "docs": [
-              "The beam's signer for mint and burn requests."
-            ],
-            "type": "publicKey"
-          },
-          {
-            "name": "allocation",
-            "docs": [
-              "This beam's allocation expressed as a percentage."
-            ],
-            "type": "u8"
-          },
-          {
-            "name": "partialGsolSupply",
-            "docs": [
-              "The total amount of circulating gsol this beam is responsible for."
-            ],
-            "type": "u64"
-          },
-          {
-            "name": "drainingMode",
-            "docs": [
-              "A beam in drain accepts withdrawals but not deposits."
-            ],
-            "type": "bool"
-          },
-          {
-            "name": "reservedSpace",
-            "docs": [
-              "Reserved space for adding future fields."
-            ],
-            "type": {
-              "array": [
-                "u32",
-                32
-              ]
-            }
-          }
-        ]
-      }
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "RegisterStateInput",
+      "name": "resizeBackingOracle",
       "docs": [
-        "Arguments for registering a new [State]."
+        "Resizes the [BackingOracle] to match the capacity of the state's allocations.",
+        "",
+        "Only needed for states whose allocations were resized before their oracle was registered."
       ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "updateAuthority",
-            "type": "publicKey"
-          },
-          {
-            "name": "yieldAccount",
-            "type": "publicKey"
-          },
-          {
-            "name": "initialCapacity",
-            "type": "u8"
-          }
-        ]
-      }
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "UpdateStateInput",
+      "name": "registerAttribution",
       "docs": [
-        "Arguments for updating [State] parameters."
+        "Opts the `owner` in to yield attribution, by initializing their [Attribution].",
+        "",
+        "Once it is registered, the gSOL minted to and burned from the owner through a beam",
+        "that is passed the attribution is recorded in it.",
+        "Only yield extracted after registering is attributed."
       ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "newUpdateAuthority",
-            "type": {
-              "option": "publicKey"
-            }
-          },
-          {
-            "name": "newYieldAccount",
-            "type": {
-              "option": "publicKey"
-            }
-          },
-          {
-            "name": "newGsolMint",
-            "type": {
-              "option": "publicKey"
-            }
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "attribution",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "getAttributedYield",
+      "docs": [
+        "View: returns the [AttributedYield] of an [Attribution] as of the current epoch,",
+        "with its balance capped at the gSOL held in the owner's token account."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "attribution",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "gsolTokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The owner's gSOL token account, whose balance caps the attributed balance."
+          ]
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": "AttributedYield"
+      }
+    },
+    {
+      "name": "registerImpactProject",
+      "docs": [
+        "Registers an [ImpactProject] funded by the state's yield, to be credited by",
+        "[ImpactCertificate]s.",
+        "",
+        "Errors if the name is longer than [ImpactProject::MAX_NAME_LEN] bytes."
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "updateAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "project",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "claimImpactCertificate",
+      "docs": [
+        "Issues the `owner` an [ImpactCertificate] for the yield attributed to them since",
+        "their last certificate, crediting the [ImpactProject] of their choice,",
+        "along with a non-transferable certificate token.",
+        "",
+        "The attributed balance is first capped at the gSOL held in the owner's token account.",
+        "",
+        "Errors if `period` is not the current period, if a certificate was already claimed",
+        "in it, or if no yield has been attributed since the last certificate."
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "attribution",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolTokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The owner's gSOL token account, whose balance caps the attributed balance."
+          ]
+        },
+        {
+          "name": "project",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "certificate",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "certificateMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "certificateTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "period",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "state",
+      "docs": [
+        "The state for the Sunrise beam controller program."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "updateAuthority",
+            "docs": [
+              "Update authority for this state."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "gsolMint",
+            "docs": [
+              "The Sunrise gSol mint."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "preSupply",
+            "docs": [
+              "The gSol mint supply when this program started",
+              "monitoring it."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "gsolMintAuthorityBump",
+            "docs": [
+              "Bump of the gSol mint authority PDA."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "yieldAccount",
+            "docs": [
+              "The Sunrise yield account."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "docs": [
+              "The account receiving the protocol fee on extracted yield."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "protocolFeeBps",
+            "docs": [
+              "The share of extracted yield sent to the treasury, in basis points."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "yieldPerGsol",
+            "docs": [
+              "The cumulative yield extracted per gSOL issued through the beams, net of the protocol fee,",
+              "scaled by [YIELD_PER_GSOL_SCALE].",
+              "Used to attribute the extracted yield to depositors with an [Attribution]."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "beamRouter",
+            "docs": [
+              "The beam router program, trusted to call beams on behalf of users as the top-level program.",
+              "The default key trusts no router."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "reservedSpace",
+            "docs": [
+              "Reserved space for adding future fields.",
+              "New fields are carved out of this space, so that existing state accounts keep their layout."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                46
+              ]
+            }
+          },
+          {
+            "name": "allocations",
+            "docs": [
+              "Holds [BeamDetails] for all supported beams."
+            ],
+            "type": {
+              "vec": {
+                "defined": "BeamDetails"
+              }
+            }
+          },
+          {
+            "name": "epochReport",
+            "type": {
+              "defined": "EpochReport"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "attribution",
+      "docs": [
+        "A depositor's opt-in record of the gSOL minted to and burned from them by the beams,",
+        "which attributes to them a share of the yield extracted while they hold it.",
+        "",
+        "The beams update the attribution when it is passed after the accounts of a deposit",
+        "or withdrawal. gSOL moved between token accounts is not followed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The state this attribution is recorded against."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "The depositor."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump of the attribution PDA."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "gsolBalance",
+            "docs": [
+              "The gSOL attributed to the owner."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timeWeightedBalance",
+            "docs": [
+              "The gSOL balance summed over each epoch it was held for, in gSOL-epochs."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "attributedYield",
+            "docs": [
+              "The yield attributed to the owner as of the last update, in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "updatedEpoch",
+            "docs": [
+              "The epoch of the last update."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "yieldPerGsol",
+            "docs": [
+              "The state's [yield_per_gsol][State::yield_per_gsol] as of the last update."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "certifiedEpoch",
+            "docs": [
+              "The epoch that the last [ImpactCertificate] runs up to, or the registration epoch."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "certifiedYield",
+            "docs": [
+              "The attributed yield already covered by impact certificates, in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "certifiedTimeWeightedBalance",
+            "docs": [
+              "The time-weighted balance already covered by impact certificates, in gSOL-epochs."
+            ],
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "impactCertificate",
+      "docs": [
+        "A record of the yield that a depositor funded over a range of epochs, claimed from",
+        "their [Attribution] at most once per period, crediting an [ImpactProject].",
+        "",
+        "The certificate is issued to the owner as the only token of its `mint`, which is",
+        "frozen in the owner's token account so that it can't be transferred.",
+        "",
+        "The token carries no Metaplex or Token-2022 metadata: this account is its metadata.",
+        "The mint is a PDA of the certificate (seeds `[certificate, \"impact_certificate_mint\"]`),",
+        "and the certificate remains its freeze authority, so a certificate can be found from its",
+        "token and checked with [ImpactCertificate::verify]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The state this certificate is issued against."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "The depositor the certificate is issued to."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "docs": [
+              "The mint of the certificate token."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump of the certificate PDA."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "period",
+            "docs": [
+              "The period the certificate was claimed in. See [ImpactCertificate::period]."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "startEpoch",
+            "docs": [
+              "The first epoch covered by the certificate."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "endEpoch",
+            "docs": [
+              "The epoch the certificate was claimed in, which ends the range it covers."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "attributedYield",
+            "docs": [
+              "The yield attributed to the owner over the range, in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timeWeightedBalance",
+            "docs": [
+              "The owner's gSOL balance summed over each epoch in the range, in gSOL-epochs."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "project",
+            "docs": [
+              "The [ImpactProject] that the owner credited the yield to."
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "impactProject",
+      "docs": [
+        "A project funded by the yield of a state, which depositors can credit the yield attributed",
+        "to them to in their [ImpactCertificate]s. Registered by the state's update authority."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The state whose yield funds the project."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "recipient",
+            "docs": [
+              "The account of the project that receives its share of the yield."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump of the project PDA."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "name",
+            "docs": [
+              "The project's name, of at most [ImpactProject::MAX_NAME_LEN] bytes."
+            ],
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "backingOracle",
+      "docs": [
+        "The value backing gSOL, as last reported by the beams.",
+        "Updated whenever a beam updates its epoch report."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The state this oracle reports on."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump of the oracle PDA."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "updatedSlot",
+            "docs": [
+              "The slot at which the oracle was last updated."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "gsolSupply",
+            "docs": [
+              "The gSOL supply issued through the beams."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalBacking",
+            "docs": [
+              "The total value backing the gSOL supply, in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "backingPerGsol",
+            "docs": [
+              "The value backing [ONE_GSOL], in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "beamBacking",
+            "docs": [
+              "Holds the [BeamBacking] of each beam, in the same order as the state's allocations."
+            ],
+            "type": {
+              "vec": {
+                "defined": "BeamBacking"
+              }
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "BeamDetails",
+      "docs": [
+        "Holds information about a registered beam."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "key",
+            "docs": [
+              "The beam's signer for mint and burn requests."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "allocation",
+            "docs": [
+              "This beam's allocation expressed as a percentage."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "partialGsolSupply",
+            "docs": [
+              "The total amount of circulating gsol this beam is responsible for."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "drainingMode",
+            "docs": [
+              "A beam in drain accepts withdrawals but not deposits."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "deficit",
+            "docs": [
+              "The amount by which the beam's value fell short of its partial gSOL supply when it last reported."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "docs": [
+              "The share of the yield extracted by this beam that was sent to the treasury."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reservedSpace",
+            "docs": [
+              "Reserved space for adding future fields."
+            ],
+            "type": {
+              "array": [
+                "u32",
+                28
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "RegisterStateInput",
+      "docs": [
+        "Arguments for registering a new [State]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "updateAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "yieldAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "initialCapacity",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "UpdateStateInput",
+      "docs": [
+        "Arguments for updating [State] parameters."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "newUpdateAuthority",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "newYieldAccount",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "newGsolMint",
+            "type": {
+              "option": "publicKey"
+            }
           },
           {
             "name": "newGsolMintAuthorityBump",
             "type": {
               "option": "u8"
             }
+          },
+          {
+            "name": "newTreasury",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "newProtocolFeeBps",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "newBeamRouter",
+            "type": {
+              "option": "publicKey"
+            }
           }
         ]
       }
@@ -683,23 +1358,77 @@ export type SunriseCore = {
       }
     },
     {
-      "name": "BeamEpochDetails",
+      "name": "BeamEpochDetails",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "epoch",
+            "docs": [
+              "The most recent epoch that this beam has reported its extractable yield for"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "extractableYield",
+            "type": "u64"
+          },
+          {
+            "name": "extractedYield",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "AttributedYield",
+      "docs": [
+        "The yield attributed to a depositor, as returned by the `get_attributed_yield` view."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "gsolBalance",
+            "docs": [
+              "The gSOL attributed to the depositor."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timeWeightedBalance",
+            "docs": [
+              "The gSOL balance summed over each epoch it was held for, in gSOL-epochs."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "attributedYield",
+            "docs": [
+              "The cumulative yield attributed to the depositor, in lamports."
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BeamBacking",
+      "docs": [
+        "The value backing the gSOL issued by a beam."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "epoch",
-            "docs": [
-              "The most recent epoch that this beam has reported its extractable yield for"
-            ],
-            "type": "u64"
-          },
-          {
-            "name": "extractableYield",
-            "type": "u64"
+            "name": "beam",
+            "type": "publicKey"
           },
           {
-            "name": "extractedYield",
+            "name": "backing",
+            "docs": [
+              "The value held by the beam, net of any exit fees, in lamports."
+            ],
             "type": "u64"
           }
         ]
@@ -771,6 +1500,66 @@ export type SunriseCore = {
       "code": 6012,
       "name": "Overflow",
       "msg": "Overflow"
+    },
+    {
+      "code": 6013,
+      "name": "OutstandingDeficit",
+      "msg": "A beam has an outstanding deficit"
+    },
+    {
+      "code": 6014,
+      "name": "DeficitExceeded",
+      "msg": "Amount exceeds the beam's deficit"
+    },
+    {
+      "code": 6015,
+      "name": "InsufficientSurplus",
+      "msg": "Insufficient surplus in the covering beam"
+    },
+    {
+      "code": 6016,
+      "name": "BackingOracleTooSmall",
+      "msg": "The backing oracle must be resized"
+    },
+    {
+      "code": 6017,
+      "name": "StaleBackingOracle",
+      "msg": "The backing oracle is stale"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidAttribution",
+      "msg": "The attribution does not belong to this depositor"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidImpactPeriod",
+      "msg": "Impact certificates can only be claimed for the current period"
+    },
+    {
+      "code": 6020,
+      "name": "NoImpactToCertify",
+      "msg": "No yield has been attributed since the last impact certificate"
+    },
+    {
+      "code": 6021,
+      "name": "MissingTreasury",
+      "msg": "The treasury must be passed while a protocol fee is charged"
+    },
+    {
+      "code": 6022,
+      "name": "MissingBeamSignature",
+      "msg": "The beam must sign the request"
+    },
+    {
+      "code": 6023,
+      "name": "InvalidImpactCertificate",
+      "msg": "The token account does not hold the impact certificate's token"
+    },
+    {
+      "code": 6024,
+      "name": "ImpactProjectNameTooLong",
+      "msg": "The impact project's name is too long"
     }
   ]
 };
@@ -780,55 +1569,294 @@ export const IDL: SunriseCore = {
   "name": "sunrise_core",
   "instructions": [
     {
-      "name": "registerState",
+      "name": "registerState",
+      "docs": [
+        "Initializes a [State], setting its initial parameters, and its [BackingOracle]."
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "gsolMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMintAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "input",
+          "type": {
+            "defined": "RegisterStateInput"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateState",
+      "docs": [
+        "Updates a [State] without modifying its [BeamDetails] list.",
+        "",
+        "Errors if the protocol fee exceeds 100%, or is set without a treasury."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "updateAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "input",
+          "type": {
+            "defined": "UpdateStateInput"
+          }
+        }
+      ]
+    },
+    {
+      "name": "registerBeam",
+      "docs": [
+        "Registers a beam by appending a newly-created [BeamDetails] with an",
+        "allocation of 0 to the [State]. Currently, this triggers a resize if needed.",
+        "",
+        "The `beam` is an account that will be expected to sign CPI requests to this program.",
+        "",
+        "Errors if a resize is needed but the `alloc_window` is zero."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "updateAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "beamAccount",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resizeAllocations",
+      "docs": [
+        "Resize the state so it can append `additional` more allocations,",
+        "and its [BackingOracle] to match."
+      ],
+      "accounts": [
+        {
+          "name": "updateAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "backingOracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "additionalBeams",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "updateAllocations",
+      "docs": [
+        "Updates allocations for beams.",
+        "",
+        "Errors if the sum of allocations after the update doesn't equal 100, or if",
+        "one of the keys in `new_allocations` refers to an unrecognized beam."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "updateAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAllocations",
+          "type": {
+            "vec": {
+              "defined": "AllocationUpdate"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "mintGsol",
+      "docs": [
+        "CPI request from a beam program to mint gSol.",
+        "",
+        "This checks for the signature of the account with the registered key, and",
+        "verifies that the immediate calling program owns that account."
+      ],
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "beam",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMintAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintGsolTo",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "burnGsol",
       "docs": [
-        "Initializes a [State], setting its initial parameters."
+        "CPI request from a beam program to burn gSol.",
+        "",
+        "Same invariants as for [minting][sunrise_core::mint_gsol()]."
       ],
       "accounts": [
         {
-          "name": "payer",
+          "name": "state",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "state",
-          "isMut": true,
+          "name": "beam",
+          "isMut": false,
           "isSigner": true
         },
         {
           "name": "gsolMint",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "gsolMintAuthority",
+          "name": "burnGsolFromOwner",
           "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "burnGsolFrom",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "input",
-          "type": {
-            "defined": "RegisterStateInput"
-          }
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "updateState",
+      "name": "transferGsol",
       "docs": [
-        "Updates a [State] without modifying its [BeamDetails] list."
+        "CPI request from a beam program to transfer gSol.",
+        "",
+        "Same invariants as for [minting][sunrise_core::mint_gsol()].",
+        "Errors if the recipient beam is not registered in the state."
       ],
       "accounts": [
         {
@@ -837,29 +1865,38 @@ export const IDL: SunriseCore = {
           "isSigner": false
         },
         {
-          "name": "updateAuthority",
+          "name": "beam",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "input",
-          "type": {
-            "defined": "UpdateStateInput"
-          }
+          "name": "recipientBeam",
+          "type": "publicKey"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "registerBeam",
+      "name": "removeBeam",
       "docs": [
-        "Registers a beam by appending a newly-created [BeamDetails] with an",
-        "allocation of 0 to the [State]. Currently, this triggers a resize if needed.",
-        "",
-        "The `beam` is an account that will be expected to sign CPI requests to this program.",
+        "Removes a beam from the state.",
         "",
-        "Errors if a resize is needed but the `alloc_window` is zero."
+        "Errors if the beam's allocation is not set to zero, or if it has a deficit."
       ],
       "accounts": [
         {
@@ -867,13 +1904,52 @@ export const IDL: SunriseCore = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "updateAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "beam",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "exportMintAuthority",
+      "docs": [
+        "Exports the gsol mint authority to a new account."
+      ],
+      "accounts": [
         {
           "name": "updateAuthority",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "beamAccount",
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gsolMintAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "newAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -881,77 +1957,100 @@ export const IDL: SunriseCore = {
       "args": []
     },
     {
-      "name": "resizeAllocations",
+      "name": "updateEpochReport",
       "docs": [
-        "Resize the state so it can append `additional` more allocations."
+        "Updates the Epoch Report Account, which stores the amount of yield extracted or extractable over time",
+        "",
+        "The beam reports a signed `surplus`: positive values are extractable yield and",
+        "negative values are recorded as a deficit against the beam.",
+        "The [BackingOracle] is updated with the new report."
       ],
       "accounts": [
         {
-          "name": "updateAuthority",
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "beam",
           "isMut": false,
-          "isSigner": true
+          "isSigner": true,
+          "docs": [
+            "The beam updating its epoch report.",
+            "This is verified in the handler to be a beam attached to this state."
+          ]
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
+          "name": "gsolMint",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "state",
+          "name": "backingOracle",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "sysvarInstructions",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "additionalBeams",
-          "type": "u8"
+          "name": "surplus",
+          "type": "i64"
         }
       ]
     },
     {
-      "name": "updateAllocations",
+      "name": "extractYield",
       "docs": [
-        "Updates allocations for beams.",
+        "CPI request from a beam program to extract yield from Sunrise",
         "",
-        "Errors if the sum of allocations after the update doesn't equal 100, or if",
-        "one of the keys in `new_allocations` refers to an unrecognized beam."
+        "The protocol fee share of the extracted yield, which the beam sends to the treasury,",
+        "is recorded on the epoch report.",
+        "",
+        "Errors while any beam has an outstanding deficit."
       ],
       "accounts": [
         {
           "name": "state",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The core sunrise state - will have its epoch report updated."
+          ]
         },
         {
-          "name": "updateAuthority",
+          "name": "beam",
           "isMut": false,
-          "isSigner": true
+          "isSigner": true,
+          "docs": [
+            "The beam contributing the extracted yield.",
+            "This is verified in the handler to be a beam attached to this state."
+          ]
+        },
+        {
+          "name": "sysvarInstructions",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "newAllocations",
-          "type": {
-            "vec": {
-              "defined": "AllocationUpdate"
-            }
-          }
+          "name": "amountInLamports",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "mintGsol",
+      "name": "socialiseDeficit",
       "docs": [
-        "CPI request from a beam program to mint gSol.",
+        "Covers `amount` of the deficit of `beam` with the surplus of `covering_beam`.",
         "",
-        "This checks for the signature of the account with the registered key, and",
-        "verifies that the immediate calling program owns that account."
+        "Errors if either beam has not reported this epoch, or if `amount` exceeds",
+        "the deficit of `beam` or the surplus of `covering_beam`."
       ],
       "accounts": [
         {
@@ -960,169 +2059,224 @@ export const IDL: SunriseCore = {
           "isSigner": false
         },
         {
-          "name": "beam",
+          "name": "updateAuthority",
           "isMut": false,
           "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "beam",
+          "type": "publicKey"
         },
         {
-          "name": "gsolMint",
+          "name": "coveringBeam",
+          "type": "publicKey"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "registerBackingOracle",
+      "docs": [
+        "Initializes the [BackingOracle] of a state, with capacity for all of its allocations.",
+        "",
+        "Only needed for states registered before the oracle was created with the state."
+      ],
+      "accounts": [
+        {
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "gsolMintAuthority",
+          "name": "state",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "mintGsolTo",
+          "name": "backingOracle",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "burnGsol",
+      "name": "resizeBackingOracle",
       "docs": [
-        "CPI request from a beam program to burn gSol.",
+        "Resizes the [BackingOracle] to match the capacity of the state's allocations.",
         "",
-        "Same invariants as for [minting][sunrise_core::mint_gsol()]."
+        "Only needed for states whose allocations were resized before their oracle was registered."
       ],
       "accounts": [
         {
-          "name": "state",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "beam",
+          "name": "state",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "backingOracle",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "burnGsolFromOwner",
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerAttribution",
+      "docs": [
+        "Opts the `owner` in to yield attribution, by initializing their [Attribution].",
+        "",
+        "Once it is registered, the gSOL minted to and burned from the owner through a beam",
+        "that is passed the attribution is recorded in it.",
+        "Only yield extracted after registering is attributed."
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "burnGsolFrom",
-          "isMut": true,
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
-          "isMut": false,
+          "name": "attribution",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "transferGsol",
+      "name": "getAttributedYield",
       "docs": [
-        "CPI request from a beam program to transfer gSol.",
-        "",
-        "Same invariants as for [minting][sunrise_core::mint_gsol()].",
-        "Errors if the recipient beam is not registered in the state."
+        "View: returns the [AttributedYield] of an [Attribution] as of the current epoch,",
+        "with its balance capped at the gSOL held in the owner's token account."
       ],
       "accounts": [
         {
           "name": "state",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "beam",
+          "name": "attribution",
           "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "gsolMint",
-          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "gsolTokenAccount",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The owner's gSOL token account, whose balance caps the attributed balance."
+          ]
         }
       ],
-      "args": [
-        {
-          "name": "recipientBeam",
-          "type": "publicKey"
-        },
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+      "args": [],
+      "returns": {
+        "defined": "AttributedYield"
+      }
     },
     {
-      "name": "removeBeam",
+      "name": "registerImpactProject",
       "docs": [
-        "Removes a beam from the state.",
+        "Registers an [ImpactProject] funded by the state's yield, to be credited by",
+        "[ImpactCertificate]s.",
         "",
-        "Errors if the beam's allocation is not set to zero."
+        "Errors if the name is longer than [ImpactProject::MAX_NAME_LEN] bytes."
       ],
       "accounts": [
         {
-          "name": "state",
+          "name": "payer",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "updateAuthority",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "project",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "beam",
-          "type": "publicKey"
+          "name": "name",
+          "type": "string"
         }
       ]
     },
     {
-      "name": "exportMintAuthority",
+      "name": "claimImpactCertificate",
       "docs": [
-        "Exports the gsol mint authority to a new account."
+        "Issues the `owner` an [ImpactCertificate] for the yield attributed to them since",
+        "their last certificate, crediting the [ImpactProject] of their choice,",
+        "along with a non-transferable certificate token.",
+        "",
+        "The attributed balance is first capped at the gSOL held in the owner's token account.",
+        "",
+        "Errors if `period` is not the current period, if a certificate was already claimed",
+        "in it, or if no yield has been attributed since the last certificate."
       ],
       "accounts": [
         {
-          "name": "updateAuthority",
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
           "isMut": false,
           "isSigner": true
         },
@@ -1132,175 +2286,453 @@ export const IDL: SunriseCore = {
           "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "attribution",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "gsolMintAuthority",
+          "name": "gsolTokenAccount",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The owner's gSOL token account, whose balance caps the attributed balance."
+          ]
         },
         {
-          "name": "newAuthority",
+          "name": "project",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "certificate",
+          "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "updateEpochReport",
-      "docs": [
-        "Updates the Epoch Report Account, which stores the amount of yield extracted or extractable over time"
-      ],
-      "accounts": [
+        },
         {
-          "name": "state",
+          "name": "certificateMint",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "beam",
+          "name": "certificateTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
-          "isSigner": true,
-          "docs": [
-            "The beam updating its epoch report.",
-            "This is verified in the handler to be a beam attached to this state."
-          ]
+          "isSigner": false
         },
         {
-          "name": "gsolMint",
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sysvarInstructions",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "extractableYield",
+          "name": "period",
           "type": "u64"
         }
       ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "state",
+      "docs": [
+        "The state for the Sunrise beam controller program."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "updateAuthority",
+            "docs": [
+              "Update authority for this state."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "gsolMint",
+            "docs": [
+              "The Sunrise gSol mint."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "preSupply",
+            "docs": [
+              "The gSol mint supply when this program started",
+              "monitoring it."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "gsolMintAuthorityBump",
+            "docs": [
+              "Bump of the gSol mint authority PDA."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "yieldAccount",
+            "docs": [
+              "The Sunrise yield account."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "docs": [
+              "The account receiving the protocol fee on extracted yield."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "protocolFeeBps",
+            "docs": [
+              "The share of extracted yield sent to the treasury, in basis points."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "yieldPerGsol",
+            "docs": [
+              "The cumulative yield extracted per gSOL issued through the beams, net of the protocol fee,",
+              "scaled by [YIELD_PER_GSOL_SCALE].",
+              "Used to attribute the extracted yield to depositors with an [Attribution]."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "beamRouter",
+            "docs": [
+              "The beam router program, trusted to call beams on behalf of users as the top-level program.",
+              "The default key trusts no router."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "reservedSpace",
+            "docs": [
+              "Reserved space for adding future fields.",
+              "New fields are carved out of this space, so that existing state accounts keep their layout."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                46
+              ]
+            }
+          },
+          {
+            "name": "allocations",
+            "docs": [
+              "Holds [BeamDetails] for all supported beams."
+            ],
+            "type": {
+              "vec": {
+                "defined": "BeamDetails"
+              }
+            }
+          },
+          {
+            "name": "epochReport",
+            "type": {
+              "defined": "EpochReport"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "attribution",
+      "docs": [
+        "A depositor's opt-in record of the gSOL minted to and burned from them by the beams,",
+        "which attributes to them a share of the yield extracted while they hold it.",
+        "",
+        "The beams update the attribution when it is passed after the accounts of a deposit",
+        "or withdrawal. gSOL moved between token accounts is not followed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The state this attribution is recorded against."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "The depositor."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump of the attribution PDA."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "gsolBalance",
+            "docs": [
+              "The gSOL attributed to the owner."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timeWeightedBalance",
+            "docs": [
+              "The gSOL balance summed over each epoch it was held for, in gSOL-epochs."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "attributedYield",
+            "docs": [
+              "The yield attributed to the owner as of the last update, in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "updatedEpoch",
+            "docs": [
+              "The epoch of the last update."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "yieldPerGsol",
+            "docs": [
+              "The state's [yield_per_gsol][State::yield_per_gsol] as of the last update."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "certifiedEpoch",
+            "docs": [
+              "The epoch that the last [ImpactCertificate] runs up to, or the registration epoch."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "certifiedYield",
+            "docs": [
+              "The attributed yield already covered by impact certificates, in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "certifiedTimeWeightedBalance",
+            "docs": [
+              "The time-weighted balance already covered by impact certificates, in gSOL-epochs."
+            ],
+            "type": "u128"
+          }
+        ]
+      }
     },
     {
-      "name": "extractYield",
+      "name": "impactCertificate",
       "docs": [
-        "CPI request from a beam program to extract yield from Sunrise"
-      ],
-      "accounts": [
-        {
-          "name": "state",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "The core sunrise state - will have its epoch report updated."
-          ]
-        },
-        {
-          "name": "beam",
-          "isMut": false,
-          "isSigner": true,
-          "docs": [
-            "The beam contributing the extracted yield.",
-            "This is verified in the handler to be a beam attached to this state."
-          ]
-        },
-        {
-          "name": "sysvarInstructions",
-          "isMut": false,
-          "isSigner": false
-        }
+        "A record of the yield that a depositor funded over a range of epochs, claimed from",
+        "their [Attribution] at most once per period, crediting an [ImpactProject].",
+        "",
+        "The certificate is issued to the owner as the only token of its `mint`, which is",
+        "frozen in the owner's token account so that it can't be transferred.",
+        "",
+        "The token carries no Metaplex or Token-2022 metadata: this account is its metadata.",
+        "The mint is a PDA of the certificate (seeds `[certificate, \"impact_certificate_mint\"]`),",
+        "and the certificate remains its freeze authority, so a certificate can be found from its",
+        "token and checked with [ImpactCertificate::verify]."
       ],
-      "args": [
-        {
-          "name": "amountInLamports",
-          "type": "u64"
-        }
-      ]
-    }
-  ],
-  "accounts": [
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The state this certificate is issued against."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "The depositor the certificate is issued to."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "docs": [
+              "The mint of the certificate token."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump of the certificate PDA."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "period",
+            "docs": [
+              "The period the certificate was claimed in. See [ImpactCertificate::period]."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "startEpoch",
+            "docs": [
+              "The first epoch covered by the certificate."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "endEpoch",
+            "docs": [
+              "The epoch the certificate was claimed in, which ends the range it covers."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "attributedYield",
+            "docs": [
+              "The yield attributed to the owner over the range, in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timeWeightedBalance",
+            "docs": [
+              "The owner's gSOL balance summed over each epoch in the range, in gSOL-epochs."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "project",
+            "docs": [
+              "The [ImpactProject] that the owner credited the yield to."
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
     {
-      "name": "state",
+      "name": "impactProject",
       "docs": [
-        "The state for the Sunrise beam controller program."
+        "A project funded by the yield of a state, which depositors can credit the yield attributed",
+        "to them to in their [ImpactCertificate]s. Registered by the state's update authority."
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "updateAuthority",
+            "name": "state",
             "docs": [
-              "Update authority for this state."
+              "The state whose yield funds the project."
             ],
             "type": "publicKey"
           },
           {
-            "name": "gsolMint",
+            "name": "recipient",
             "docs": [
-              "The Sunrise gSol mint."
+              "The account of the project that receives its share of the yield."
             ],
             "type": "publicKey"
           },
           {
-            "name": "preSupply",
+            "name": "bump",
             "docs": [
-              "The gSol mint supply when this program started",
-              "monitoring it."
+              "Bump of the project PDA."
             ],
-            "type": "u64"
+            "type": "u8"
           },
           {
-            "name": "gsolMintAuthorityBump",
+            "name": "name",
             "docs": [
-              "Bump of the gSol mint authority PDA."
+              "The project's name, of at most [ImpactProject::MAX_NAME_LEN] bytes."
+            ],
+            "type": "string"
+          }
+        ]
+      }
+    },
+    {
+      "name": "backingOracle",
+      "docs": [
+        "The value backing gSOL, as last reported by the beams.",
+        "Updated whenever a beam updates its epoch report."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "state",
+            "docs": [
+              "The state this oracle reports on."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump of the oracle PDA."
             ],
             "type": "u8"
           },
           {
-            "name": "yieldAccount",
+            "name": "updatedSlot",
             "docs": [
-              "The Sunrise yield account."
+              "The slot at which the oracle was last updated."
             ],
-            "type": "publicKey"
+            "type": "u64"
           },
           {
-            "name": "reservedSpace",
+            "name": "gsolSupply",
             "docs": [
-              "Reserved space for adding future fields."
+              "The gSOL supply issued through the beams."
             ],
-            "type": {
-              "array": [
-                "u32",
-                32
-              ]
-            }
+            "type": "u64"
           },
           {
-            "name": "allocations",
+            "name": "totalBacking",
             "docs": [
-              "Holds [BeamDetails] for all supported beams."
+              "The total value backing the gSOL supply, in lamports."
             ],
-            "type": {
-              "vec": {
-                "defined": "BeamDetails"
-              }
-            }
+            "type": "u64"
           },
           {
-            "name": "epochReport",
+            "name": "backingPerGsol",
+            "docs": [
+              "The value backing [ONE_GSOL], in lamports."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "beamBacking",
+            "docs": [
+              "Holds the [BeamBacking] of each beam, in the same order as the state's allocations."
+            ],
             "type": {
-              "defined": "EpochReport"
+              "vec": {
+                "defined": "BeamBacking"
+              }
             }
           }
         ]
@@ -1344,6 +2776,20 @@ export const IDL: SunriseCore = {
             ],
             "type": "bool"
           },
+          {
+            "name": "deficit",
+            "docs": [
+              "The amount by which the beam's value fell short of its partial gSOL supply when it last reported."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "docs": [
+              "The share of the yield extracted by this beam that was sent to the treasury."
+            ],
+            "type": "u64"
+          },
           {
             "name": "reservedSpace",
             "docs": [
@@ -1352,7 +2798,7 @@ export const IDL: SunriseCore = {
             "type": {
               "array": [
                 "u32",
-                32
+                28
               ]
             }
           }
@@ -1413,6 +2859,24 @@ export const IDL: SunriseCore = {
             "type": {
               "option": "u8"
             }
+          },
+          {
+            "name": "newTreasury",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "newProtocolFeeBps",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "newBeamRouter",
+            "type": {
+              "option": "publicKey"
+            }
           }
         ]
       }
@@ -1481,6 +2945,60 @@ export const IDL: SunriseCore = {
           }
         ]
       }
+    },
+    {
+      "name": "AttributedYield",
+      "docs": [
+        "The yield attributed to a depositor, as returned by the `get_attributed_yield` view."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "gsolBalance",
+            "docs": [
+              "The gSOL attributed to the depositor."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timeWeightedBalance",
+            "docs": [
+              "The gSOL balance summed over each epoch it was held for, in gSOL-epochs."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "attributedYield",
+            "docs": [
+              "The cumulative yield attributed to the depositor, in lamports."
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BeamBacking",
+      "docs": [
+        "The value backing the gSOL issued by a beam."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "beam",
+            "type": "publicKey"
+          },
+          {
+            "name": "backing",
+            "docs": [
+              "The value held by the beam, net of any exit fees, in lamports."
+            ],
+            "type": "u64"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -1548,6 +3066,66 @@ export const IDL: SunriseCore = {
       "code": 6012,
       "name": "Overflow",
       "msg": "Overflow"
+    },
+    {
+      "code": 6013,
+      "name": "OutstandingDeficit",
+      "msg": "A beam has an outstanding deficit"
+    },
+    {
+      "code": 6014,
+      "name": "DeficitExceeded",
+      "msg": "Amount exceeds the beam's deficit"
+    },
+    {
+      "code": 6015,
+      "name": "InsufficientSurplus",
+      "msg": "Insufficient surplus in the covering beam"
+    },
+    {
+      "code": 6016,
+      "name": "BackingOracleTooSmall",
+      "msg": "The backing oracle must be resized"
+    },
+    {
+      "code": 6017,
+      "name": "StaleBackingOracle",
+      "msg": "The backing oracle is stale"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidAttribution",
+      "msg": "The attribution does not belong to this depositor"
+    },
+    {
+      "code": 6019,
+      "name": "InvalidImpactPeriod",
+      "msg": "Impact certificates can only be claimed for the current period"
+    },
+    {
+      "code": 6020,
+      "name": "NoImpactToCertify",
+      "msg": "No yield has been attributed since the last impact certificate"
+    },
+    {
+      "code": 6021,
+      "name": "MissingTreasury",
+      "msg": "The treasury must be passed while a protocol fee is charged"
+    },
+    {
+      "code": 6022,
+      "name": "MissingBeamSignature",
+      "msg": "The beam must sign the request"
+    },
+    {
+      "code": 6023,
+      "name": "InvalidImpactCertificate",
+      "msg": "The token account does not hold the impact certificate's token"
+    },
+    {
+      "code": 6024,
+      "name": "ImpactProjectNameTooLong",
+      "msg": "The impact project's name is too long"
     }
   ]
 };
@@ -6,3 +6,7 @@ export const SUNRISE_PROGRAM_ID = new PublicKey(
 );
 /** The constant seed of the GSOL mint authority PDA. */
 export const GSOL_AUTHORITY_SEED = "gsol_mint_authority";
+/** The constant seed of a depositor's attribution PDA. */
+export const ATTRIBUTION_SEED = "attribution";
+/** The constant seed of the state's backing oracle PDA. */
+export const BACKING_ORACLE_SEED = "backing_oracle";
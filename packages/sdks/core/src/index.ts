@@ -1,5 +1,6 @@
 import { type AnchorProvider, Program } from "@coral-xyz/anchor";
 import {
+  type AccountMeta,
   PublicKey,
   type Keypair,
   type Transaction,
@@ -13,7 +14,12 @@ import {
 } from "@solana/spl-token";
 import { sendAndConfirmChecked, SunriseCore } from "@sunrisestake/beams-common";
 import { StateAccount } from "./state.js";
-import { GSOL_AUTHORITY_SEED, SUNRISE_PROGRAM_ID } from "./constants.js";
+import {
+  ATTRIBUTION_SEED,
+  BACKING_ORACLE_SEED,
+  GSOL_AUTHORITY_SEED,
+  SUNRISE_PROGRAM_ID,
+} from "./constants.js";
 
 /** An instance of the Sunrise program that checks the validity of other
  * beams and regulates the minting and burning of GSOL.
@@ -82,6 +88,10 @@ export class SunriseClient {
         gsolMintAuthority: SunriseClient.deriveGsolMintAuthority(
           state.publicKey,
         )[0],
+        backingOracle: SunriseClient.deriveBackingOracle(
+          state.publicKey,
+          programId,
+        )[0],
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
       })
@@ -161,6 +171,7 @@ export class SunriseClient {
         updateAuthority: this.state.updateAuthority,
         payer: this.program.provider.publicKey,
         state: this.stateAddress,
+        backingOracle: this.backingOracle[0],
         systemProgram: SystemProgram.programId,
       })
       .transaction();
@@ -218,6 +229,21 @@ export class SunriseClient {
     };
   }
 
+  /** Return the attribution account of `owner`, which can be passed as the last remaining
+   * account of a beam instruction that mints or burns gSOL, to record it once it is registered.
+   */
+  public attributionAccount(owner: PublicKey): AccountMeta {
+    const [pubkey] = PublicKey.findProgramAddressSync(
+      [
+        this.stateAddress.toBuffer(),
+        Buffer.from(ATTRIBUTION_SEED),
+        owner.toBuffer(),
+      ],
+      this.program.programId,
+    );
+    return { pubkey, isSigner: false, isWritable: true };
+  }
+
   private static deriveGsolMintAuthority(
     stateAddress: PublicKey,
     programId = SUNRISE_PROGRAM_ID,
@@ -228,6 +254,34 @@ export class SunriseClient {
     );
   }
 
+  /** Derive the address of the backing oracle of a state. */
+  public static deriveBackingOracle(
+    stateAddress: PublicKey,
+    programId = SUNRISE_PROGRAM_ID,
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [stateAddress.toBuffer(), Buffer.from(BACKING_ORACLE_SEED)],
+      programId,
+    );
+  }
+
+  /** Get the address of the backing oracle. */
+  public get backingOracle(): [PublicKey, number] {
+    return SunriseClient.deriveBackingOracle(
+      this.stateAddress,
+      this.program.programId,
+    );
+  }
+
+  /** Get the treasury that receives the protocol fee on extracted yield,
+   * or null if none is set on the state.
+   */
+  public get treasury(): PublicKey | null {
+    return this.state.treasury.equals(PublicKey.default)
+      ? null
+      : this.state.treasury;
+  }
+
   /** Get the address of the gsol mint authority. */
   public get gsolMintAuthority(): [PublicKey, number] {
     return SunriseClient.deriveGsolMintAuthority(
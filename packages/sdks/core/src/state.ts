@@ -19,6 +19,8 @@ export class StateAccount {
   public readonly preSupply: BN;
   public readonly gsolAuthBump: number;
   public readonly yieldAccount: PublicKey;
+  public readonly treasury: PublicKey;
+  public readonly protocolFeeBps: number;
   public readonly beams: BeamDetails[];
   public readonly epochReport: EpochReport;
 
@@ -32,6 +34,8 @@ export class StateAccount {
     this.preSupply = account.preSupply;
     this.gsolAuthBump = account.gsolMintAuthorityBump;
     this.yieldAccount = account.yieldAccount;
+    this.treasury = account.treasury;
+    this.protocolFeeBps = account.protocolFeeBps;
     this.beams = account.allocations;
     this.epochReport = account.epochReport;
   }
@@ -59,6 +63,8 @@ export class StateAccount {
       preSupply: this.preSupply.toString(),
       gsolAuthBump: this.gsolAuthBump.toString(),
       yieldAccount: this.yieldAccount.toBase58(),
+      treasury: this.treasury.toBase58(),
+      protocolFeeBps: this.protocolFeeBps.toString(),
       beams: this.beams.map((beam) => printBeamDetails(beam)),
       epochReport: printEpochReport(this.epochReport),
     };
@@ -8,3 +8,5 @@ export const MARINADE_BEAM_PROGRAM_ID = new PublicKey(
 export const MARINADE_FINANCE_PROGRAM_ID = new PublicKey(
   "MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD",
 );
+/** The default target size of the liquidity buffer, as a share of the gSOL supply in basis points. */
+export const DEFAULT_TARGET_LIQUIDITY_BPS = 1000;
@@ -22,6 +22,7 @@ import {
 } from "@sunrisestake/beams-common";
 import { StateAccount } from "./state.js";
 import {
+  DEFAULT_TARGET_LIQUIDITY_BPS,
   MARINADE_BEAM_PROGRAM_ID,
   MARINADE_FINANCE_PROGRAM_ID,
 } from "./constants.js";
@@ -38,6 +39,8 @@ export class MarinadeLpClient extends BeamInterface<
 > {
   /** The address of the authority of this beam's token vaults*/
   readonly vaultAuthority: [PublicKey, number];
+  /** The address of this beam's yield breakdown account */
+  readonly yieldBreakdown: PublicKey;
 
   private constructor(
     program: Program<MarinadeLpBeam.MarinadeLpBeam>,
@@ -54,6 +57,10 @@ export class MarinadeLpClient extends BeamInterface<
       program.programId,
       stateAddress,
     );
+    this.yieldBreakdown = Utils.deriveYieldBreakdownAddress(
+      program.programId,
+      stateAddress,
+    )[0];
   }
 
   /** Register a new state.*/
@@ -63,6 +70,7 @@ export class MarinadeLpClient extends BeamInterface<
     sunriseState: PublicKey,
     msolRecipientBeam: PublicKey,
     msolTokenAccount: PublicKey,
+    targetLiquidityBps = DEFAULT_TARGET_LIQUIDITY_BPS,
     programId = MARINADE_BEAM_PROGRAM_ID,
   ): Promise<MarinadeLpClient> {
     const program = new Program<MarinadeLpBeam.MarinadeLpBeam>(
@@ -89,10 +97,15 @@ export class MarinadeLpClient extends BeamInterface<
         vaultAuthorityBump,
         msolRecipientBeam,
         msolTokenAccount,
+        targetLiquidityBps,
       })
       .accounts({
         payer: provider.publicKey,
         state: stateAddress,
+        yieldBreakdown: Utils.deriveYieldBreakdownAddress(
+          programId,
+          stateAddress,
+        )[0],
         liqPoolMint: marinadeLpClientParams.marinade.lpMint.address,
         liqPoolVault: marinadeLpClientParams.beamVault,
         vaultAuthority,
@@ -214,7 +227,9 @@ export class MarinadeLpClient extends BeamInterface<
         tokenProgram: TOKEN_PROGRAM_ID,
         sunriseProgram: this.sunrise.program.programId,
         marinadeProgram: MARINADE_FINANCE_PROGRAM_ID,
+        yieldBreakdown: this.yieldBreakdown,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(gsolOwner)])
       .instruction();
 
     return transaction.add(instruction);
@@ -226,10 +241,13 @@ export class MarinadeLpClient extends BeamInterface<
     );
   }
 
-  /** Return a transaction to withdraw from a marinade liquidity-pool. */
+  /** Return a transaction to withdraw from a marinade liquidity-pool, paying out at least
+   * `minLamportsOut` lamports.
+   */
   public async withdraw(
     lamports: BN,
     gsolTokenAccount?: PublicKey,
+    minLamportsOut = new BN(0),
   ): Promise<Transaction> {
     const withdrawer = this.provider.publicKey;
     const { gsolMint, sysvarInstructions, burnGsolFrom } =
@@ -240,7 +258,7 @@ export class MarinadeLpClient extends BeamInterface<
       );
 
     const instruction = await this.program.methods
-      .withdraw(lamports)
+      .withdraw(lamports, minLamportsOut)
       .accounts({
         state: this.stateAddress,
         marinadeState: this.state.proxyState,
@@ -261,7 +279,9 @@ export class MarinadeLpClient extends BeamInterface<
         sysvarInstructions,
         sunriseProgram: this.sunrise.program.programId,
         marinadeProgram: MARINADE_FINANCE_PROGRAM_ID,
+        yieldBreakdown: this.yieldBreakdown,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(withdrawer)])
       .instruction();
 
     return new Transaction().add(requestIncreasedCUsIx(400_000), instruction);
@@ -309,6 +329,7 @@ export class MarinadeLpClient extends BeamInterface<
         sysvarInstructions,
         sunriseProgram: this.sunrise.program.programId,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(burner)])
       .instruction();
 
     return new Transaction().add(instruction);
@@ -331,8 +352,10 @@ export class MarinadeLpClient extends BeamInterface<
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       gsolMint: this.sunrise.state.gsolMint,
+      backingOracle: this.sunrise.backingOracle[0],
       sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
       sunriseProgram: this.sunrise.program.programId,
+      yieldBreakdown: this.yieldBreakdown,
     };
     const instruction = await this.program.methods
       .updateEpochReport()
@@ -351,6 +374,7 @@ export class MarinadeLpClient extends BeamInterface<
       marinadeState: this.state.proxyState,
       sunriseState: this.state.sunriseState,
       yieldAccount: this.sunrise.state.yieldAccount,
+      treasury: this.sunrise.treasury,
       liqPoolMint: this.marinadeLp.marinade.lpMint.address,
       liqPoolTokenVault: this.marinadeLp.beamVault,
       vaultAuthority: this.vaultAuthority[0],
@@ -364,6 +388,7 @@ export class MarinadeLpClient extends BeamInterface<
       marinadeProgram: MARINADE_FINANCE_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
+      yieldBreakdown: this.yieldBreakdown,
     };
     const instruction = await this.program.methods
       .extractYield()
@@ -11,6 +11,7 @@ export class StateAccount implements BeamState {
   public readonly vaultAuthorityBump: number;
   public readonly msolRecipientBeam: PublicKey;
   public readonly msolTokenAccount: PublicKey;
+  public readonly targetLiquidityBps: number;
 
   private constructor(
     _address: PublicKey,
@@ -23,6 +24,7 @@ export class StateAccount implements BeamState {
     this.vaultAuthorityBump = account.vaultAuthorityBump;
     this.msolRecipientBeam = account.msolRecipientBeam;
     this.msolTokenAccount = account.msolTokenAccount;
+    this.targetLiquidityBps = account.targetLiquidityBps;
   }
 
   /** Create a new instance from an anchor-deserialized account. */
@@ -45,6 +47,7 @@ export class StateAccount implements BeamState {
       vaultAuthorityBump: this.vaultAuthorityBump.toString(),
       msolRecipientBeam: this.msolRecipientBeam.toBase58(),
       msolTokenAccount: this.msolTokenAccount.toBase58(),
+      targetLiquidityBps: this.targetLiquidityBps.toString(),
     };
   }
 }
@@ -18,6 +18,7 @@ export type MarinadeLpClientParams = {
 /** All the constant seeds used for the PDAs of the on-chain program. */
 const enum Seeds {
   STATE = "sunrise-marinade-lp",
+  YIELD_BREAKDOWN = "yield-breakdown",
 }
 
 export type Balance = {
@@ -38,6 +39,17 @@ export class Utils {
     );
   }
 
+  /** Derive the address of the yield breakdown account of a beam state. */
+  public static deriveYieldBreakdownAddress(
+    pid: PublicKey,
+    state: PublicKey,
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [state.toBuffer(), Buffer.from(Seeds.YIELD_BREAKDOWN)],
+      pid,
+    );
+  }
+
   public static async getMarinadeLpClientParams(
     provider: AnchorProvider,
     beamProgramId: PublicKey,
@@ -219,15 +219,19 @@ export class MarinadeClient extends BeamInterface<
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(gsolOwner)])
       .instruction();
 
     return transaction.add(instruction);
   }
 
-  /** Return a transaction to withdraw from a marinade stake-pool. */
+  /** Return a transaction to withdraw from a marinade stake-pool, paying out at least
+   * `minLamportsOut` lamports after the liquid unstake fee.
+   */
   public async withdraw(
     amount: BN,
     gsolTokenAccount?: PublicKey,
+    minLamportsOut = new BN(0),
   ): Promise<Transaction> {
     const withdrawer = this.provider.publicKey;
     const { gsolMint, sysvarInstructions, burnGsolFrom } =
@@ -257,8 +261,9 @@ export class MarinadeClient extends BeamInterface<
       tokenProgram: TOKEN_PROGRAM_ID,
     };
     const instruction = await this.program.methods
-      .withdraw(amount)
+      .withdraw(amount, minLamportsOut)
       .accounts(accounts)
+      .remainingAccounts([this.sunrise.attributionAccount(withdrawer)])
       .instruction();
 
     return new Transaction().add(instruction);
@@ -324,6 +329,7 @@ export class MarinadeClient extends BeamInterface<
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .preInstructions([initMarinadeTicket])
+      .remainingAccounts([this.sunrise.attributionAccount(withdrawer)])
       .instruction();
 
     return {
@@ -359,6 +365,7 @@ export class MarinadeClient extends BeamInterface<
         sysvarInstructions,
         sunriseProgram: this.sunrise.program.programId,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(burner)])
       .instruction();
 
     return new Transaction().add(instruction);
@@ -451,6 +458,7 @@ export class MarinadeClient extends BeamInterface<
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(gsolOwner)])
       .instruction();
 
     return transaction.add(instruction);
@@ -490,6 +498,7 @@ export class MarinadeClient extends BeamInterface<
       msolMint: this.marinade.state.mSolMint.address,
       msolVault: this.marinade.beamMsolVault,
       gsolMint: this.sunrise.state.gsolMint,
+      backingOracle: this.sunrise.backingOracle[0],
       vaultAuthority: this.vaultAuthority[0],
       sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
       sunriseProgram: this.sunrise.program.programId,
@@ -517,6 +526,7 @@ export class MarinadeClient extends BeamInterface<
       liqPoolMsolLeg: this.marinade.state.mSolLeg,
       treasuryMsolAccount: this.marinade.state.treasuryMsolAccount,
       yieldAccount: this.sunrise.state.yieldAccount,
+      treasury: this.sunrise.treasury,
       sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
       sunriseProgram: this.sunrise.program.programId,
       marinadeProgram: MARINADE_FINANCE_PROGRAM_ID,
@@ -47,6 +47,7 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
       { kind: "sol-deposit" },
       { kind: "stake-deposit" }, // unimplemented.
       { kind: "liquid-unstake" },
+      { kind: "order-unstake" },
       { kind: "stake-withdrawal" },
     ]);
     this.vaultAuthority = Utils.deriveAuthorityAddress(
@@ -224,6 +225,7 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(gsolOwner)])
       .instruction();
 
     return transaction.add(instruction);
@@ -231,10 +233,12 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
 
   /**
    * Return a transaction to withdraw from an SPL stake-pool.
+   * The withdrawal fails if the withdrawer receives fewer than `minLamportsOut` lamports.
    */
   public async withdraw(
     amount: BN,
     gsolTokenAccount?: PublicKey,
+    minLamportsOut = new BN(0),
   ): Promise<Transaction> {
     const withdrawer = this.provider.publicKey;
     const { gsolMint, sysvarInstructions, burnGsolFrom } =
@@ -245,7 +249,7 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
       );
 
     const instruction = await this.program.methods
-      .withdraw(amount)
+      .withdraw(amount, minLamportsOut)
       .accounts({
         state: this.stateAddress,
         stakePool: this.spl.stakePoolAddress,
@@ -268,6 +272,7 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(withdrawer)])
       .instruction();
 
     return new Transaction().add(instruction);
@@ -288,10 +293,15 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
       this.provider,
       stakeAccount,
     );
-    const validatorStakeAccount = stakeAccountInfo.voterAddress;
-    if (!validatorStakeAccount) {
+    const voteAccount = stakeAccountInfo.voterAddress;
+    if (!voteAccount) {
       throw new Error("Invalid validator account!");
     }
+    const validatorStakeAccount = await Utils.findValidatorStakeAccount(
+      this.provider,
+      this.spl,
+      voteAccount,
+    );
 
     const transaction = new Transaction();
     const gsolOwner = recipient ?? stakeOwner;
@@ -330,20 +340,26 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(gsolOwner)])
       .instruction();
 
     return transaction.add(instruction);
   }
 
   /**
-   * Returns a transaction to withdraw from an SPL stake-pool into a new stake account
+   * Returns a transaction to withdraw from an SPL stake-pool into a new stake account,
+   * which must also sign the transaction. The withdrawer is its stake and withdraw authority.
+   * The stake is split from the pool's stake account for the validator with `voteAccount`,
+   * or its preferred withdrawal validator, falling back to the pool's reserve.
    */
   public async withdrawStake(
     amount: BN,
     newStakeAccount: PublicKey,
     gsolTokenAccount?: PublicKey,
+    voteAccount?: PublicKey,
   ): Promise<Transaction> {
     const withdrawer = this.provider.publicKey;
+    const stakeAccountToSplit = await this.stakeAccountToSplit(voteAccount);
     const { gsolMint, sysvarInstructions, burnGsolFrom } =
       this.sunrise.burnGsolAccounts(
         this.stateAddress,
@@ -352,7 +368,7 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
       );
 
     const instruction = await this.program.methods
-      .withdrawStake(amount)
+      .withdrawStake(amount, voteAccount ?? null)
       .accounts({
         state: this.stateAddress,
         stakePool: this.spl.stakePoolAddress,
@@ -365,7 +381,7 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
         vaultAuthority: this.vaultAuthority[0],
         stakePoolWithdrawAuthority: this.spl.withdrawAuthority,
         validatorStakeList: this.spl.stakePoolState.validatorList,
-        stakeAccountToSplit: this.spl.stakePoolState.reserveStake,
+        stakeAccountToSplit,
         managerFeeAccount: this.spl.stakePoolState.managerFeeAccount,
         sysvarClock: SYSVAR_CLOCK_PUBKEY,
         sysvarStakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
@@ -377,23 +393,79 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(withdrawer)])
       .instruction();
 
     return new Transaction().add(instruction);
   }
 
   /**
-   * Return a transaction to order a withdrawal from a spl stake-pool..
-   * This is not a supported feature for SPL beams and will throw an error.
+   * Return a transaction to order a delayed withdrawal from an SPL stake-pool,
+   * avoiding the pool's SOL withdrawal fee and reserve liquidity limits.
+   * The stake is withdrawn into a new stake account, which must also sign the transaction,
+   * and is deactivated unless it was split from the pool's reserve.
+   * The new stake account is returned as both tickets, and is passed to `redeemTicket`
+   * once its stake has cooled down.
    */
-  public orderWithdraw(lamports: BN): Promise<{
+  public async orderWithdraw(
+    lamports: BN,
+    gsolTokenAccount?: PublicKey,
+    voteAccount?: PublicKey,
+  ): Promise<{
     tx: Transaction;
     sunriseTicket: Keypair;
     proxyTicket: Keypair;
   }> {
-    throw new Error(
-      `Delayed withdrawals are unimplemented for SPL beam. Requested: ${lamports}`,
-    ); // TODO
+    const withdrawer = this.provider.publicKey;
+    const stakeAccountToSplit = await this.stakeAccountToSplit(voteAccount);
+    const { gsolMint, sysvarInstructions, burnGsolFrom } =
+      this.sunrise.burnGsolAccounts(
+        this.stateAddress,
+        withdrawer,
+        gsolTokenAccount,
+      );
+
+    const newStakeAccount = Keypair.generate();
+    const [withdrawalTicket] = Utils.deriveWithdrawalTicket(
+      this.program.programId,
+      this.stateAddress,
+      newStakeAccount.publicKey,
+    );
+
+    const instruction = await this.program.methods
+      .orderWithdrawal(lamports)
+      .accounts({
+        state: this.stateAddress,
+        sunriseState: this.state.sunriseState,
+        withdrawer,
+        gsolTokenAccount: burnGsolFrom,
+        gsolMint,
+        sysvarInstructions,
+        sunriseProgram: this.sunrise.program.programId,
+        stakePool: this.spl.stakePoolAddress,
+        newStakeAccount: newStakeAccount.publicKey,
+        withdrawalTicket,
+        poolMint: this.spl.stakePoolState.poolMint,
+        poolTokenVault: this.spl.beamVault,
+        vaultAuthority: this.vaultAuthority[0],
+        stakePoolWithdrawAuthority: this.spl.withdrawAuthority,
+        validatorStakeList: this.spl.stakePoolState.validatorList,
+        stakeAccountToSplit,
+        managerFeeAccount: this.spl.stakePoolState.managerFeeAccount,
+        sysvarClock: SYSVAR_CLOCK_PUBKEY,
+        nativeStakeProgram: StakeProgram.programId,
+        splStakePoolProgram: SPL_STAKE_POOL_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([this.sunrise.attributionAccount(withdrawer)])
+      .instruction();
+
+    return {
+      tx: new Transaction().add(instruction),
+      sunriseTicket: newStakeAccount,
+      proxyTicket: newStakeAccount,
+    };
   }
 
   public async burnGSol(
@@ -422,6 +494,7 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
         sysvarInstructions,
         sunriseProgram: this.sunrise.program.programId,
       })
+      .remainingAccounts([this.sunrise.attributionAccount(burner)])
       .instruction();
 
     return new Transaction().add(instruction);
@@ -439,6 +512,7 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
       vaultAuthority: this.vaultAuthority[0],
       poolTokenVault: this.spl.beamVault,
       gsolMint: this.sunrise.state.gsolMint,
+      backingOracle: this.sunrise.backingOracle[0],
       sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
       sunriseProgram: this.sunrise.program.programId,
     };
@@ -465,6 +539,7 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
       sunriseState: this.state.sunriseState,
       poolMint: this.spl.stakePoolState.poolMint,
       yieldAccount: this.sunrise.state.yieldAccount,
+      treasury: this.sunrise.treasury,
       newStakeAccount,
       vaultAuthority: this.vaultAuthority[0],
       poolTokenVault: this.spl.beamVault,
@@ -490,13 +565,50 @@ export class SplClient extends BeamInterface<SplBeam.SplBeam, StateAccount> {
   }
 
   /**
-   * Return a transaction to redeem a ticket received from ordering a withdrawal.
-   * This is not a supported feature for SPL beams and will throw an error.
+   * Return a transaction to redeem the ticket of the withdrawal ordered into `stakeAccount`,
+   * sending its lamports to the beneficiary of the ticket, who must sign the transaction.
+   * The redemption fails until the stake account has cooled down.
+   */
+  public async redeemTicket(stakeAccount: PublicKey): Promise<Transaction> {
+    const [withdrawalTicket] = Utils.deriveWithdrawalTicket(
+      this.program.programId,
+      this.stateAddress,
+      stakeAccount,
+    );
+    const ticket =
+      await this.program.account.withdrawalTicket.fetch(withdrawalTicket);
+
+    return this.program.methods
+      .redeemTicket()
+      .accounts({
+        state: this.stateAddress,
+        beneficiary: ticket.beneficiary,
+        withdrawalTicket,
+        stakeAccount: ticket.stakeAccount,
+        vaultAuthority: this.vaultAuthority[0],
+        sysvarClock: SYSVAR_CLOCK_PUBKEY,
+        sysvarStakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
+        nativeStakeProgram: StakeProgram.programId,
+      })
+      .transaction();
+  }
+
+  /** The pool's stake account for the validator with `voteAccount`, or its preferred
+   * withdrawal validator, falling back to the pool's reserve.
    */
-  public redeemTicket(sunriseTicket: PublicKey): Promise<Transaction> {
-    throw new Error(
-      `Delayed withdrawals are unimplemented for SPL beam. Requested: ${sunriseTicket}`,
-    ); // TODO
+  private async stakeAccountToSplit(
+    voteAccount?: PublicKey,
+  ): Promise<PublicKey> {
+    const splitFromVoteAccount =
+      voteAccount ??
+      this.spl.stakePoolState.preferredWithdrawValidatorVoteAddress;
+    return splitFromVoteAccount
+      ? Utils.findValidatorStakeAccount(
+          this.provider,
+          this.spl,
+          splitFromVoteAccount,
+        )
+      : this.spl.stakePoolState.reserveStake;
   }
 
   /**
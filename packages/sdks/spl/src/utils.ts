@@ -24,8 +24,18 @@ const enum Seeds {
   STATE = "sunrise_spl",
   VAULT_AUTHORITY = "vault_authority",
   EXTRACT_YIELD_STAKE_ACCOUNT = "extract_yield_stake_account",
+  WITHDRAWAL_TICKET = "withdrawal_ticket",
 }
 
+/** The size of the validator list header: account type, max validators and vec length. */
+const VALIDATOR_LIST_HEADER_SIZE = 1 + 4 + 4;
+/** The size of a validator list entry. */
+const VALIDATOR_STAKE_INFO_SIZE = 8 + 8 + 8 + 8 + 4 + 4 + 1 + 32;
+/** The offset of the validator seed suffix in a validator list entry. */
+const VALIDATOR_SEED_SUFFIX_OFFSET = 8 + 8 + 8 + 8 + 4;
+/** The offset of the vote account in a validator list entry. */
+const VOTE_ACCOUNT_OFFSET = VALIDATOR_SEED_SUFFIX_OFFSET + 4 + 1;
+
 /**
  * A utility class containing methods for PDA-derivation.
  */
@@ -63,6 +73,63 @@ export class Utils {
     );
   }
 
+  /** Derive the address of the ticket of a withdrawal ordered into `stakeAccount`. */
+  public static deriveWithdrawalTicket(
+    pid: PublicKey,
+    state: PublicKey,
+    stakeAccount: PublicKey,
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [
+        state.toBuffer(),
+        Buffer.from(Seeds.WITHDRAWAL_TICKET),
+        stakeAccount.toBuffer(),
+      ],
+      pid,
+    );
+  }
+
+  /** Derive the address of the stake pool's stake account for the validator with
+   * `voteAccount`, reading its seed from the pool's validator list.
+   */
+  public static async findValidatorStakeAccount(
+    provider: AnchorProvider,
+    spl: SplClientParams,
+    voteAccount: PublicKey,
+  ): Promise<PublicKey> {
+    const validatorList = await provider.connection.getAccountInfo(
+      spl.stakePoolState.validatorList,
+    );
+    if (!validatorList) {
+      throw new Error("Validator list not found");
+    }
+    const data = validatorList.data;
+    const count = data.readUInt32LE(VALIDATOR_LIST_HEADER_SIZE - 4);
+    for (let i = 0; i < count; i++) {
+      const entry = VALIDATOR_LIST_HEADER_SIZE + i * VALIDATOR_STAKE_INFO_SIZE;
+      const voteAccountOffset = entry + VOTE_ACCOUNT_OFFSET;
+      const entryVoteAccount = new PublicKey(
+        data.subarray(voteAccountOffset, voteAccountOffset + 32),
+      );
+      if (!entryVoteAccount.equals(voteAccount)) continue;
+
+      const seedSuffix = data.readUInt32LE(
+        entry + VALIDATOR_SEED_SUFFIX_OFFSET,
+      );
+      const seeds = [voteAccount.toBuffer(), spl.stakePoolAddress.toBuffer()];
+      if (seedSuffix !== 0) {
+        const suffix = Buffer.alloc(4);
+        suffix.writeUInt32LE(seedSuffix);
+        seeds.push(suffix);
+      }
+      return PublicKey.findProgramAddressSync(
+        seeds,
+        SPL_STAKE_POOL_PROGRAM_ID,
+      )[0];
+    }
+    throw new Error("The validator is not in the stake pool's validator list");
+  }
+
   public static getSplClientParams = async (
     provider: AnchorProvider,
     beamProgramId: PublicKey,
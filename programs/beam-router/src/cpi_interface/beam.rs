@@ -0,0 +1,77 @@
+use crate::{Deposit, Withdraw};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use sunrise_beam_interface::instruction::{BeamInstruction, BurnGsolAccounts, DepositAccounts};
+
+impl<'a> From<&Deposit<'a>> for DepositAccounts {
+    fn from(accounts: &Deposit<'a>) -> Self {
+        Self {
+            state: accounts.beam.key(),
+            sunrise_state: accounts.sunrise_state.key(),
+            depositor: accounts.depositor.key(),
+            mint_gsol_to: accounts.mint_gsol_to.key(),
+            gsol_mint: accounts.gsol_mint.key(),
+            gsol_mint_authority: accounts.gsol_mint_authority.key(),
+        }
+    }
+}
+
+impl<'a> From<&Withdraw<'a>> for BurnGsolAccounts {
+    fn from(accounts: &Withdraw<'a>) -> Self {
+        Self {
+            state: accounts.beam.key(),
+            sunrise_state: accounts.sunrise_state.key(),
+            owner: accounts.withdrawer.key(),
+            gsol_token_account: accounts.gsol_token_account.key(),
+            gsol_mint: accounts.gsol_mint.key(),
+        }
+    }
+}
+
+pub fn deposit<'info>(
+    accounts: &Deposit<'info>,
+    beam_accounts: &[AccountInfo<'info>],
+    lamports: u64,
+) -> Result<()> {
+    let instruction = BeamInstruction::Deposit { lamports }.to_instruction(
+        accounts.beam_program.key(),
+        &DepositAccounts::from(accounts),
+        beam_account_metas(beam_accounts),
+    );
+    invoke_beam(&instruction, accounts.to_account_infos(), beam_accounts)
+}
+
+pub fn withdraw<'info>(
+    accounts: &Withdraw<'info>,
+    beam_accounts: &[AccountInfo<'info>],
+    lamports: u64,
+    min_lamports_out: u64,
+) -> Result<()> {
+    let instruction = BeamInstruction::Withdraw {
+        lamports,
+        min_lamports_out,
+    }
+    .to_instruction(
+        accounts.beam_program.key(),
+        &BurnGsolAccounts::from(accounts),
+        beam_account_metas(beam_accounts),
+    );
+    invoke_beam(&instruction, accounts.to_account_infos(), beam_accounts)
+}
+
+/// The beam-specific accounts are forwarded with the privileges they were passed to the router with.
+fn beam_account_metas(beam_accounts: &[AccountInfo]) -> Vec<AccountMeta> {
+    beam_accounts
+        .iter()
+        .flat_map(|account| account.to_account_metas(None))
+        .collect()
+}
+
+fn invoke_beam<'info>(
+    instruction: &anchor_lang::solana_program::instruction::Instruction,
+    mut account_infos: Vec<AccountInfo<'info>>,
+    beam_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    account_infos.extend_from_slice(beam_accounts);
+    invoke(instruction, &account_infos).map_err(Into::into)
+}
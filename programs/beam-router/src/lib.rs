@@ -0,0 +1,236 @@
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token::{Mint, TokenAccount};
+use cpi_interface::beam as beam_interface;
+use seeds::*;
+use state::{ExitRoute, State, StateEntry, MAX_EXITS};
+
+use sunrise_core as sunrise_core_cpi;
+
+mod cpi_interface;
+mod seeds;
+mod state;
+mod utils;
+
+declare_id!("CNHv6nQDkAx3uxDE8Etqrpy9ebbLAMpBbYmHUoovqGM1");
+
+/// A single entry point for deposits and withdrawals, which routes each one to a beam
+/// according to the Sunrise allocations and the cost of withdrawing through each beam.
+#[program]
+pub mod beam_router {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, input: StateEntry) -> Result<()> {
+        ctx.accounts.state.set_inner(input.into());
+        Ok(())
+    }
+
+    pub fn update(ctx: Context<Update>, update_input: StateEntry) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.update_authority = update_input.update_authority;
+        state.sunrise_state = update_input.sunrise_state;
+        Ok(())
+    }
+
+    /// Adds or updates the exit route through `beam`, with an estimated withdrawal fee of `fee_bps`.
+    pub fn set_exit(ctx: Context<Update>, beam: Pubkey, fee_bps: u16) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        match state.get_exit_index(&beam) {
+            Some(index) => state.exits[index].fee_bps = fee_bps,
+            None => {
+                require_gt!(MAX_EXITS, state.exits.len(), BeamRouterError::ExitsFull);
+                state.exits.push(ExitRoute { beam, fee_bps });
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the exit route through `beam`.
+    pub fn remove_exit(ctx: Context<Update>, beam: Pubkey) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        let index = state
+            .get_exit_index(&beam)
+            .ok_or(BeamRouterError::ExitNotFound)?;
+        state.exits.remove(index);
+        Ok(())
+    }
+
+    /// Deposits `lamports` into the most under-allocated beam.
+    ///
+    /// The remaining accounts are the beam-specific accounts of the beam's `deposit` instruction,
+    /// optionally followed by the depositor's attribution.
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        let sunrise_state = &ctx.accounts.sunrise_state;
+        let effective_supply = ctx
+            .accounts
+            .gsol_mint
+            .supply
+            .saturating_sub(sunrise_state.pre_supply);
+        let target_index =
+            utils::most_underallocated_beam(&sunrise_state.allocations, effective_supply)
+                .ok_or(BeamRouterError::NoDepositableBeam)?;
+        require_keys_eq!(
+            ctx.accounts.beam.key(),
+            sunrise_state.allocations[target_index].key,
+            BeamRouterError::NotMostUnderallocatedBeam
+        );
+
+        // CPI: Deposit through the beam, which mints the gSOL to the depositor.
+        beam_interface::deposit(ctx.accounts, ctx.remaining_accounts, lamports)
+    }
+
+    /// Withdraws `lamports` through the cheapest exit with a fee of at most `max_fee_bps`.
+    ///
+    /// Errors if the withdrawer receives fewer than `lamports` minus `max_fee_bps`.
+    /// The remaining accounts are the beam-specific accounts of the beam's `withdraw` instruction,
+    /// optionally followed by the withdrawer's attribution.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        lamports: u64,
+        max_fee_bps: u16,
+    ) -> Result<()> {
+        let exit_index = utils::cheapest_exit(
+            &ctx.accounts.state.exits,
+            &ctx.accounts.sunrise_state.allocations,
+            lamports,
+            max_fee_bps,
+        )
+        .ok_or(BeamRouterError::NoExitWithinMaxFee)?;
+        require_keys_eq!(
+            ctx.accounts.beam.key(),
+            ctx.accounts.state.exits[exit_index].beam,
+            BeamRouterError::NotCheapestExit
+        );
+
+        // The fee is only an estimate, so the bound is enforced by the beam on the actual withdrawal.
+        let min_lamports_out = beam_math::net_of_fee_bps(lamports, max_fee_bps)?;
+
+        // CPI: Withdraw through the beam, which burns the gSOL from the withdrawer.
+        beam_interface::withdraw(
+            ctx.accounts,
+            ctx.remaining_accounts,
+            lamports,
+            min_lamports_out,
+        )
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(input: StateEntry)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        space = State::SPACE,
+        payer = payer,
+        seeds = [STATE, input.sunrise_state.as_ref()],
+        bump
+    )]
+    pub state: Account<'info, State>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Account<'info, State>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    /// CHECK: The beam state, checked against the Sunrise allocations.
+    pub beam: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = gsol_mint)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub mint_gsol_to: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub gsol_mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to Sunrise.
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    /// CHECK: The program that owns the beam state.
+    #[account(executable, address = *beam.owner @ BeamRouterError::InvalidBeamProgram)]
+    pub beam_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    /// CHECK: The beam state, checked against the exit routes.
+    pub beam: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = gsol_mint)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    /// CHECK: The program that owns the beam state.
+    #[account(executable, address = *beam.owner @ BeamRouterError::InvalidBeamProgram)]
+    pub beam_program: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum BeamRouterError {
+    #[msg("The maximum number of exit routes has been reached")]
+    ExitsFull,
+    #[msg("There is no exit route through this beam")]
+    ExitNotFound,
+    #[msg("No beam is accepting deposits")]
+    NoDepositableBeam,
+    #[msg("Deposits must go to the most under-allocated beam")]
+    NotMostUnderallocatedBeam,
+    #[msg("No exit route can withdraw the requested amount within the max fee")]
+    NoExitWithinMaxFee,
+    #[msg("Withdrawals must go through the cheapest exit route")]
+    NotCheapestExit,
+    #[msg("The beam program does not own the beam state")]
+    InvalidBeamProgram,
+}
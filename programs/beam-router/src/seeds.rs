@@ -0,0 +1,2 @@
+/// Seed of this program's state address.
+pub const STATE: &[u8] = b"sunrise_beam_router";
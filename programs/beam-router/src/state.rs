@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+/// The maximum number of exit routes.
+pub const MAX_EXITS: usize = 8;
+
+#[account]
+#[derive(Debug, Default)]
+pub struct State {
+    /// The update authority of the state.
+    pub update_authority: Pubkey,
+
+    /// The state of the main sunrise beam.
+    pub sunrise_state: Pubkey,
+
+    /// The beams that withdrawals can be routed through, and their estimated fees.
+    pub exits: Vec<ExitRoute>,
+}
+
+impl State {
+    pub const SPACE: usize = 8 +  /*discriminator*/
+        32 + /*update_authority*/
+        32 + /*sunrise_state*/
+        4 + MAX_EXITS * ExitRoute::SPACE; /*exits*/
+
+    /// Gets the index of a beam in the exit routes.
+    pub fn get_exit_index(&self, beam: &Pubkey) -> Option<usize> {
+        self.exits.iter().position(|exit| exit.beam == *beam)
+    }
+}
+
+/// A beam that supports immediate withdrawals, and the estimated fee of withdrawing through it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct ExitRoute {
+    /// The beam state.
+    pub beam: Pubkey,
+    /// The estimated withdrawal fee of the beam, in basis points.
+    pub fee_bps: u16,
+}
+
+impl ExitRoute {
+    pub const SPACE: usize = 32 + /*beam*/
+        2; /*fee_bps*/
+}
+
+// Anchor-ts only supports deserialization(in instruction arguments) for types
+// that explicitly derive AnchorSerialize & AnchorDeserialize.
+// https://github.com/coral-xyz/anchor/issues/2545
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StateEntry {
+    pub update_authority: Pubkey,
+    pub sunrise_state: Pubkey,
+}
+
+impl From<StateEntry> for State {
+    fn from(se: StateEntry) -> Self {
+        State {
+            update_authority: se.update_authority,
+            sunrise_state: se.sunrise_state,
+            exits: vec![],
+        }
+    }
+}
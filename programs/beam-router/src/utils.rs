@@ -0,0 +1,120 @@
+use crate::state::ExitRoute;
+use std::cmp::Reverse;
+use sunrise_core::BeamDetails;
+
+/// The gSOL a beam is short of its allocation of `effective_supply`. Negative if it is over-allocated.
+fn allocation_deficit(details: &BeamDetails, effective_supply: u64) -> i128 {
+    let target = (details.allocation as i128) * (effective_supply as i128) / 100;
+    target - details.partial_gsol_supply as i128
+}
+
+/// Finds the beam whose gSOL supply is furthest below its allocation of the effective gSOL supply.
+/// Beams with allocation 0 or in draining mode are never chosen. Ties are resolved to the first beam.
+pub fn most_underallocated_beam(
+    allocations: &[BeamDetails],
+    effective_supply: u64,
+) -> Option<usize> {
+    allocations
+        .iter()
+        .enumerate()
+        .filter(|(_, details)| details.allocation > 0 && !details.draining_mode)
+        .min_by_key(|(_, details)| Reverse(allocation_deficit(details, effective_supply)))
+        .map(|(index, _)| index)
+}
+
+/// Finds the cheapest exit with a fee of at most `max_fee_bps`,
+/// through a beam that has issued at least `lamports` gSOL.
+/// Exits through beams that are not registered with Sunrise are never chosen.
+/// Ties are resolved to the first exit.
+pub fn cheapest_exit(
+    exits: &[ExitRoute],
+    allocations: &[BeamDetails],
+    lamports: u64,
+    max_fee_bps: u16,
+) -> Option<usize> {
+    exits
+        .iter()
+        .enumerate()
+        .filter(|(_, exit)| exit.fee_bps <= max_fee_bps)
+        .filter(|(_, exit)| {
+            allocations
+                .iter()
+                .any(|details| details.key == exit.beam && details.partial_gsol_supply >= lamports)
+        })
+        .min_by_key(|(_, exit)| exit.fee_bps)
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod utils_tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+
+    fn beam(allocation: u8, partial_gsol_supply: u64) -> BeamDetails {
+        let mut details = BeamDetails::new(Pubkey::new_unique(), allocation);
+        details.partial_gsol_supply = partial_gsol_supply;
+        details
+    }
+
+    fn exit(beam: &BeamDetails, fee_bps: u16) -> ExitRoute {
+        ExitRoute {
+            beam: beam.key,
+            fee_bps,
+        }
+    }
+
+    #[test]
+    fn test_most_underallocated_beam() {
+        // No beams
+        assert_eq!(most_underallocated_beam(&[], 100), None);
+        // Target 50/50 of 100 - the second beam holds 30 of a target 50
+        assert_eq!(
+            most_underallocated_beam(&[beam(50, 70), beam(50, 30)], 100),
+            Some(1)
+        );
+        // Target 80/20 of 100 - the first beam holds 70 of a target 80
+        assert_eq!(
+            most_underallocated_beam(&[beam(80, 70), beam(20, 30)], 100),
+            Some(0)
+        );
+        // Ties go to the first beam
+        assert_eq!(
+            most_underallocated_beam(&[beam(50, 0), beam(50, 0)], 0),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_most_underallocated_beam_ignores_closed_beams() {
+        let mut draining = beam(50, 0);
+        draining.draining_mode = true;
+        assert_eq!(
+            most_underallocated_beam(&[draining, beam(50, 100)], 100),
+            Some(1)
+        );
+        assert_eq!(
+            most_underallocated_beam(&[beam(0, 0), beam(100, 100)], 100),
+            Some(1)
+        );
+        assert_eq!(most_underallocated_beam(&[beam(0, 0)], 100), None);
+    }
+
+    #[test]
+    fn test_cheapest_exit() {
+        let (lp, marinade) = (beam(50, 100), beam(50, 100));
+        let allocations = [lp.clone(), marinade.clone()];
+        let exits = [exit(&marinade, 30), exit(&lp, 0)];
+
+        // The cheapest exit
+        assert_eq!(cheapest_exit(&exits, &allocations, 100, 30), Some(1));
+        // Exits above the max fee are skipped
+        let exits = [exit(&marinade, 30), exit(&lp, 50)];
+        assert_eq!(cheapest_exit(&exits, &allocations, 100, 30), Some(0));
+        assert_eq!(cheapest_exit(&exits, &allocations, 100, 10), None);
+        // Exits through beams without enough gSOL are skipped
+        assert_eq!(cheapest_exit(&exits, &allocations, 101, 50), None);
+        // Exits through unregistered beams are skipped
+        let exits = [exit(&beam(0, 100), 0), exit(&lp, 10)];
+        assert_eq!(cheapest_exit(&exits, &allocations, 100, 10), Some(1));
+    }
+}
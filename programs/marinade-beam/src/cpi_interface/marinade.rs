@@ -1,15 +1,15 @@
 use crate::cpi_interface::program::Marinade;
 use crate::state::State;
-use crate::{ExtractYield, OrderWithdrawal, Withdraw};
+use crate::{ExtractYield, FundLiquidityBeam, OrderWithdrawal, Withdraw};
 use anchor_lang::prelude::*;
 use marinade_common::vault_authority_seed::VaultAuthoritySeed;
 use marinade_cpi::cpi::{
     accounts::{
-        Claim as MarinadeClaim, Deposit as MarinadeDeposit,
+        AddLiquidity as MarinadeAddLiquidity, Claim as MarinadeClaim, Deposit as MarinadeDeposit,
         DepositStakeAccount as MarinadeDepositStakeAccount, LiquidUnstake as MarinadeLiquidUnstake,
         OrderUnstake as MarinadeOrderUnstake,
     },
-    claim as cpi_marinade_claim, deposit as cpi_deposit,
+    add_liquidity as cpi_add_liquidity, claim as cpi_marinade_claim, deposit as cpi_deposit,
     deposit_stake_account as cpi_deposit_stake_account, liquid_unstake as cpi_liquid_unstake,
     order_unstake as cpi_order_unstake,
 };
@@ -41,6 +41,22 @@ pub fn liquid_unstake<'info>(
     cpi_liquid_unstake(cpi_ctx.with_signer(&[&seeds[..]]), msol_lamports)
 }
 
+/// Adds liquidity to the Marinade liq_pool from lamports held by the vault authority.
+pub fn add_liquidity_from_vault_authority<'info>(
+    program: &Program<'info, Marinade>,
+    state: &Account<State>,
+    accounts: MarinadeAddLiquidity<'info>,
+    lamports: u64,
+) -> Result<()> {
+    let cpi_program = program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, accounts);
+
+    let seed_data = VaultAuthoritySeed::new(state);
+    let seeds = seed_data.as_slices();
+
+    cpi_add_liquidity(cpi_ctx.with_signer(&[&seeds[..]]), lamports)
+}
+
 pub fn order_unstake<'info>(
     program: &Program<'info, Marinade>,
     state: &Account<State>,
@@ -148,6 +164,39 @@ impl<'a> From<&ExtractYield<'a>> for MarinadeLiquidUnstake<'a> {
     }
 }
 
+impl<'a> From<&FundLiquidityBeam<'a>> for MarinadeLiquidUnstake<'a> {
+    fn from(accounts: &FundLiquidityBeam<'a>) -> Self {
+        Self {
+            state: accounts.marinade_state.to_account_info(),
+            msol_mint: accounts.msol_mint.to_account_info(),
+            liq_pool_sol_leg_pda: accounts.liq_pool_sol_leg_pda.to_account_info(),
+            liq_pool_msol_leg: accounts.liq_pool_msol_leg.to_account_info(),
+            treasury_msol_account: accounts.treasury_msol_account.to_account_info(),
+            get_msol_from: accounts.msol_vault.to_account_info(),
+            get_msol_from_authority: accounts.vault_authority.to_account_info(),
+            transfer_sol_to: accounts.vault_authority.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        }
+    }
+}
+
+impl<'a> From<&FundLiquidityBeam<'a>> for MarinadeAddLiquidity<'a> {
+    fn from(accounts: &FundLiquidityBeam<'a>) -> Self {
+        Self {
+            state: accounts.marinade_state.to_account_info(),
+            lp_mint: accounts.liq_pool_mint.to_account_info(),
+            lp_mint_authority: accounts.liq_pool_mint_authority.to_account_info(),
+            liq_pool_msol_leg: accounts.liq_pool_msol_leg.to_account_info(),
+            liq_pool_sol_leg_pda: accounts.liq_pool_sol_leg_pda.to_account_info(),
+            transfer_from: accounts.vault_authority.to_account_info(),
+            mint_to: accounts.liquidity_beam_vault.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        }
+    }
+}
+
 impl<'a> From<&OrderWithdrawal<'a>> for MarinadeOrderUnstake<'a> {
     fn from(accounts: &OrderWithdrawal<'a>) -> Self {
         Self {
@@ -1,3 +1,4 @@
 pub mod marinade;
 pub mod program;
 pub mod sunrise;
+pub mod token;
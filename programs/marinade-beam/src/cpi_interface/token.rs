@@ -0,0 +1,30 @@
+use crate::state::State;
+use crate::WithdrawAsLst;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer as cpi_transfer, Token, Transfer};
+use marinade_common::vault_authority_seed::VaultAuthoritySeed;
+
+pub fn transfer_from_vault<'info>(
+    program: &Program<'info, Token>,
+    state: &Account<State>,
+    accounts: Transfer<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_program = program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, accounts);
+
+    let seed_data = VaultAuthoritySeed::new(state);
+    let seeds = seed_data.as_slices();
+
+    cpi_transfer(cpi_ctx.with_signer(&[&seeds[..]]), amount)
+}
+
+impl<'a> From<&WithdrawAsLst<'a>> for Transfer<'a> {
+    fn from(accounts: &WithdrawAsLst<'a>) -> Self {
+        Self {
+            from: accounts.msol_vault.to_account_info(),
+            to: accounts.transfer_msol_to.to_account_info(),
+            authority: accounts.vault_authority.to_account_info(),
+        }
+    }
+}
@@ -6,17 +6,21 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
 use anchor_spl::associated_token::{AssociatedToken, Create};
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use marinade_common::liquidity_beam::LiquidityBeamState;
 use marinade_cpi::{State as MarinadeState, TicketAccountData as MarinadeTicketAccount};
 use std::ops::Deref;
+use sunrise_beam_interface::core_cpi;
+use sunrise_beam_interface::view::{BeamValue, DepositQuote, WithdrawQuote};
 
 mod cpi_interface;
-mod state;
+pub mod state;
 mod system;
 
 use cpi_interface::marinade as marinade_interface;
 use cpi_interface::sunrise as sunrise_interface;
+use cpi_interface::token as token_interface;
 use state::{State, StateEntry};
-use system::accounts::ProxyTicket;
+pub use system::accounts::ProxyTicket;
 use system::utils;
 
 use crate::cpi_interface::program::Marinade;
@@ -24,11 +28,13 @@ use sunrise_core as sunrise_core_cpi;
 
 declare_id!("G9nMA5HvMa1HLXy1DBA3biH445Zxb2dkqsG4eDfcvgjm");
 
-mod constants {
+pub mod constants {
     /// Seed of the PDA that can authorize spending from the vault that holds pool tokens.
     pub const VAULT_AUTHORITY: &[u8] = b"vault-authority";
     /// Seed of this program's state address.
     pub const STATE: &[u8] = b"sunrise-marinade";
+    /// Seed of the Marinade liq_pool SOL leg PDA.
+    pub const LIQ_POOL_SOL_LEG: &[u8] = b"liq_sol";
     // TODO: RECOVERED_MARGIN is needed because, for some reason, the claim tickets have a couple of lamports less than they should,
     // probably due to a rounding error converting to and from marinade.
     // Figure this out and then remove this margin
@@ -39,7 +45,12 @@ mod constants {
 pub mod marinade_beam {
     use super::*;
     use crate::cpi_interface::marinade;
-    use marinade_common::calc_msol_from_lamports;
+    use beam_math::{proportional, Rounding};
+    use marinade_common::{
+        calc_lamports_from_msol_amount, calc_liquid_unstake_lamports, calc_msol_from_lamports,
+        calc_msol_from_lamports_with_rounding,
+    };
+    use marinade_cpi::cpi::accounts::LiquidUnstake as MarinadeLiquidUnstake;
 
     pub fn initialize(ctx: Context<Initialize>, input: StateEntry) -> Result<()> {
         ctx.accounts.state.set_inner(input.into());
@@ -61,7 +72,10 @@ pub mod marinade_beam {
         Ok(())
     }
 
-    pub fn deposit(ctx: Context<Deposit>, lamports: u64) -> Result<()> {
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
         // CPI: Deposit SOL to Marinade pool. The msol tokens are minted into a vault controlled
         // by a PDA of this program.
         marinade_interface::deposit(ctx.accounts, lamports)?;
@@ -73,13 +87,17 @@ pub mod marinade_beam {
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
         Ok(())
     }
 
-    pub fn deposit_stake_account(ctx: Context<DepositStake>, validator_index: u32) -> Result<()> {
+    pub fn deposit_stake_account<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositStake<'info>>,
+        validator_index: u32,
+    ) -> Result<()> {
         // Get the stake value in lamports of the stake account.
         let lamports = utils::get_delegated_stake_amount(&ctx.accounts.stake_account)?;
 
@@ -93,15 +111,27 @@ pub mod marinade_beam {
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
         Ok(())
     }
 
-    pub fn withdraw(ctx: Context<Withdraw>, lamports: u64) -> Result<()> {
+    /// Withdraws `lamports` via a liquid unstake.
+    ///
+    /// Errors if the withdrawer receives fewer than `min_lamports_out` lamports
+    /// after the liquid unstake fee.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
         // Calculate how much msol_lamports need to be deposited to unstake `lamports` lamports.
-        let msol_lamports = calc_msol_from_lamports(ctx.accounts.marinade_state.as_ref(), lamports);
+        let msol_lamports =
+            calc_msol_from_lamports(ctx.accounts.marinade_state.as_ref(), lamports)?;
+
+        let withdrawer_balance_before = ctx.accounts.withdrawer.lamports();
 
         msg!("Liquid Unstake {} msol", msol_lamports);
         // CPI: Liquid unstake.
@@ -113,6 +143,54 @@ pub mod marinade_beam {
             msol_lamports,
         )?;
 
+        let withdrawer_balance_after = ctx.accounts.withdrawer.lamports();
+        let withdrawn_lamports = withdrawer_balance_after.saturating_sub(withdrawer_balance_before);
+
+        msg!("Withdrawn {} lamports", withdrawn_lamports);
+        require_gte!(
+            withdrawn_lamports,
+            min_lamports_out,
+            MarinadeBeamError::WithdrawalBelowMinimum
+        );
+
+        msg!("Burn {} lamports", lamports);
+        let bump = ctx.bumps.state;
+        // CPI: Burn GSOL of the same proportion as the number of lamports withdrawn.
+        sunrise_interface::burn_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            bump,
+            ctx.remaining_accounts,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// Withdrawing as an LST burns gSOL and transfers the equivalent amount of mSOL
+    /// out of the beam's vault, rather than unstaking it. This incurs no unstake fee.
+    pub fn withdraw_as_lst<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawAsLst<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        // Calculate how much msol_lamports are worth `lamports` lamports.
+        let msol_lamports = calc_msol_from_lamports_with_rounding(
+            ctx.accounts.marinade_state.as_ref(),
+            lamports,
+            Rounding::Up,
+        )?;
+
+        msg!("Transfer {} msol", msol_lamports);
+        // CPI: Transfer mSOL from the vault to the withdrawer.
+        let accounts = ctx.accounts.deref().into();
+        token_interface::transfer_from_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.state,
+            accounts,
+            msol_lamports,
+        )?;
+
         msg!("Burn {} lamports", lamports);
         let bump = ctx.bumps.state;
         // CPI: Burn GSOL of the same proportion as the number of lamports withdrawn.
@@ -121,15 +199,20 @@ pub mod marinade_beam {
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
         Ok(())
     }
 
-    pub fn order_withdrawal(ctx: Context<OrderWithdrawal>, lamports: u64) -> Result<()> {
+    pub fn order_withdrawal<'info>(
+        ctx: Context<'_, '_, '_, 'info, OrderWithdrawal<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
         // Calculate how much msol_lamports need to be deposited to unstake `lamports` lamports.
-        let msol_lamports = calc_msol_from_lamports(ctx.accounts.marinade_state.as_ref(), lamports);
+        let msol_lamports =
+            calc_msol_from_lamports(ctx.accounts.marinade_state.as_ref(), lamports)?;
 
         // CPI: Order unstake and receive a Marinade unstake ticket.
         let accounts = ctx.accounts.deref().into();
@@ -153,6 +236,7 @@ pub mod marinade_beam {
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
@@ -162,13 +246,14 @@ pub mod marinade_beam {
     /// Burning is withdrawing without redeeming the pool tokens. The result is a beam that is "worth more"
     /// than the SOL that has been staked into it, i.e. the pool tokens are more valuable than the SOL.
     /// This allows yield extraction and can be seen as a form of "donation".
-    pub fn burn(ctx: Context<Burn>, lamports: u64) -> Result<()> {
+    pub fn burn<'info>(ctx: Context<'_, '_, '_, 'info, Burn<'info>>, lamports: u64) -> Result<()> {
         let state_bump = ctx.bumps.state;
         sunrise_interface::burn_gsol(
             ctx.accounts.deref(),
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             state_bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
@@ -209,24 +294,46 @@ pub mod marinade_beam {
             &ctx.accounts.marinade_state,
             &ctx.accounts.msol_vault,
         )?;
-        let yield_msol = calc_msol_from_lamports(&ctx.accounts.marinade_state, yield_lamports);
+        let yield_msol = calc_msol_from_lamports(&ctx.accounts.marinade_state, yield_lamports)?;
+        let protocol_fee_msol = ctx.accounts.sunrise_state.protocol_fee(yield_msol)?;
+        let treasury = core_cpi::fee_treasury(ctx.accounts.treasury.as_ref(), protocol_fee_msol)?;
+        let treasury_balance = || treasury.map(|t| t.lamports()).unwrap_or_default();
 
         let yield_account_balance_before = ctx.accounts.yield_account.lamports();
+        let treasury_balance_before = treasury_balance();
 
         // TODO: Change to use delayed unstake so as not to incur fees.
+        // The protocol fee share is unstaked to the treasury, and the rest to the yield account.
+        if let Some(treasury) = treasury {
+            let accounts = MarinadeLiquidUnstake {
+                transfer_sol_to: treasury.to_account_info(),
+                ..MarinadeLiquidUnstake::from(ctx.accounts.deref())
+            };
+            marinade::liquid_unstake(
+                &ctx.accounts.marinade_program,
+                &ctx.accounts.state,
+                accounts,
+                protocol_fee_msol,
+            )?;
+        }
         let accounts = ctx.accounts.deref().into();
         marinade::liquid_unstake(
             &ctx.accounts.marinade_program,
             &ctx.accounts.state,
             accounts,
-            yield_msol,
+            yield_msol.saturating_sub(protocol_fee_msol),
         )?;
 
         let yield_account_balance_after = ctx.accounts.yield_account.lamports();
-        let withdrawn_lamports =
-            yield_account_balance_after.saturating_sub(yield_account_balance_before);
-
-        msg!("Withdrawn {} lamports to yield account", withdrawn_lamports);
+        let treasury_balance_after = treasury_balance();
+        let withdrawn_lamports = yield_account_balance_after
+            .saturating_sub(yield_account_balance_before)
+            .saturating_add(treasury_balance_after.saturating_sub(treasury_balance_before));
+
+        msg!(
+            "Withdrawn {} lamports to yield account and treasury",
+            withdrawn_lamports
+        );
 
         // CPI: update the epoch report with the extracted yield.
         let state_bump = ctx.bumps.state;
@@ -241,17 +348,97 @@ pub mod marinade_beam {
         Ok(())
     }
 
+    /// Permissionless crank that tops up the Marinade LP beam's liquidity buffer to its target size.
+    ///
+    /// If the gSOL the liquidity beam is responsible for is below its target share of the gSOL supply,
+    /// mSOL worth the shortfall, grossed up for the liquid unstake fee, is liquid-unstaked from this beam's
+    /// vault, and the SOL received is added as liquidity to the Marinade liq_pool on behalf of the liquidity beam.
+    /// The gSOL backed by the added liquidity is transferred to the liquidity beam.
+    pub fn fund_liquidity_beam(ctx: Context<FundLiquidityBeam>) -> Result<()> {
+        let liquidity_beam = &ctx.accounts.liquidity_beam;
+        let details = ctx
+            .accounts
+            .sunrise_state
+            .get_beam_details(&liquidity_beam.key())
+            .ok_or(sunrise_core::BeamError::UnidentifiedBeam)?;
+        let target_liquidity = liquidity_beam.target_liquidity(ctx.accounts.gsol_mint.supply)?;
+        let shortfall = target_liquidity.saturating_sub(details.partial_gsol_supply);
+        msg!(
+            "Liquidity: {}, target: {}, shortfall: {}",
+            details.partial_gsol_supply,
+            target_liquidity,
+            shortfall
+        );
+        require_gt!(shortfall, 0, MarinadeBeamError::LiquidityWithinTarget);
+
+        // CPI: Liquid unstake mSOL into the vault authority. The liquid unstake fee is taken from
+        // the mSOL, so the mSOL worth the shortfall is grossed up by the fee charged on it.
+        let marinade_state = ctx.accounts.marinade_state.as_ref();
+        let liq_pool_lamports = ctx
+            .accounts
+            .liq_pool_sol_leg_pda
+            .lamports()
+            .saturating_sub(marinade_state.rent_exempt_for_token_acc);
+        let shortfall_msol = calc_msol_from_lamports(marinade_state, shortfall)?;
+        let shortfall_lamports_out =
+            calc_liquid_unstake_lamports(marinade_state, liq_pool_lamports, shortfall_msol)?;
+        require_gt!(
+            shortfall_lamports_out,
+            0,
+            MarinadeBeamError::LiquidityWithinTarget
+        );
+        let msol_lamports = proportional(shortfall_msol, shortfall, shortfall_lamports_out)?
+            .min(ctx.accounts.msol_vault.amount);
+        let vault_authority_balance_before = ctx.accounts.vault_authority.lamports();
+        let accounts = ctx.accounts.deref().into();
+        marinade::liquid_unstake(
+            &ctx.accounts.marinade_program,
+            &ctx.accounts.state,
+            accounts,
+            msol_lamports,
+        )?;
+        let unstaked_lamports = ctx
+            .accounts
+            .vault_authority
+            .lamports()
+            .saturating_sub(vault_authority_balance_before);
+
+        // CPI: Add the unstaked SOL as liquidity, minting the liq_pool tokens to the liquidity beam's vault.
+        let accounts = ctx.accounts.deref().into();
+        marinade::add_liquidity_from_vault_authority(
+            &ctx.accounts.marinade_program,
+            &ctx.accounts.state,
+            accounts,
+            unstaked_lamports,
+        )?;
+
+        // CPI: Transfer the gSOL backed by the added liquidity to the liquidity beam.
+        // The liquid unstake fee is borne by this beam.
+        let bump = ctx.bumps.state;
+        sunrise_interface::transfer_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            bump,
+            liquidity_beam.key(),
+            unstaked_lamports,
+        )?;
+
+        Ok(())
+    }
+
     pub fn update_epoch_report(ctx: Context<UpdateEpochReport>) -> Result<()> {
-        let yield_lamports = utils::calculate_extractable_yield(
+        let surplus_lamports = utils::calculate_surplus(
             &ctx.accounts.sunrise_state,
             &ctx.accounts.state,
             &ctx.accounts.marinade_state,
             &ctx.accounts.msol_vault,
         )?;
 
-        // Reduce by fee
+        // Reduce any surplus by fee. A deficit is reported as-is.
         // TODO can we do better than an estimate?
-        let extractable_lamports = (yield_lamports as f64) * 0.997; // estimated 0.3% unstake fee
+        let surplus_lamports =
+            beam_math::net_surplus_of_fee_bps(surplus_lamports, utils::ESTIMATED_UNSTAKE_FEE_BPS)?;
 
         // CPI: update the epoch report with the extracted yield.
         let state_bump = ctx.bumps.state;
@@ -260,11 +447,69 @@ pub mod marinade_beam {
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             state_bump,
-            extractable_lamports as u64,
+            surplus_lamports,
         )?;
 
         Ok(())
     }
+
+    /// View: quotes a [deposit] of `lamports`, returning the gSOL that would be minted
+    /// and the mSOL that the beam would receive.
+    pub fn quote_deposit(ctx: Context<View>, lamports: u64) -> Result<DepositQuote> {
+        Ok(DepositQuote {
+            gsol_out: lamports,
+            beam_tokens_out: calc_msol_from_lamports(&ctx.accounts.marinade_state, lamports)?,
+        })
+    }
+
+    /// View: quotes a [withdraw] of `lamports`, returning the lamports that would be received
+    /// after the liquid unstake fee, and the mSOL that the beam would unstake.
+    pub fn quote_withdraw(ctx: Context<View>, lamports: u64) -> Result<WithdrawQuote> {
+        let marinade_state = &ctx.accounts.marinade_state;
+        let msol_lamports = calc_msol_from_lamports(marinade_state, lamports)?;
+        let liq_pool_lamports = ctx
+            .accounts
+            .liq_pool_sol_leg_pda
+            .lamports()
+            .saturating_sub(marinade_state.rent_exempt_for_token_acc);
+
+        Ok(WithdrawQuote {
+            lamports_out: calc_liquid_unstake_lamports(
+                marinade_state,
+                liq_pool_lamports,
+                msol_lamports,
+            )?,
+            beam_tokens_in: msol_lamports,
+        })
+    }
+
+    /// View: returns the value of the mSOL held by the beam, the surplus that [update_epoch_report]
+    /// would report and the yield that [extract_yield] would extract, before the liquid unstake fee.
+    pub fn get_beam_value(ctx: Context<View>) -> Result<BeamValue> {
+        let value = calc_lamports_from_msol_amount(
+            &ctx.accounts.marinade_state,
+            ctx.accounts.msol_vault.amount,
+        )?;
+        let gsol_supply = ctx
+            .accounts
+            .sunrise_state
+            .get_beam_details(&ctx.accounts.state.key())
+            .ok_or(sunrise_core::BeamError::UnidentifiedBeam)?
+            .partial_gsol_supply;
+        let surplus = utils::calculate_surplus(
+            &ctx.accounts.sunrise_state,
+            &ctx.accounts.state,
+            &ctx.accounts.marinade_state,
+            &ctx.accounts.msol_vault,
+        )?;
+
+        Ok(BeamValue {
+            value,
+            gsol_supply,
+            surplus: beam_math::net_surplus_of_fee_bps(surplus, utils::ESTIMATED_UNSTAKE_FEE_BPS)?,
+            extractable_yield: surplus.max(0) as u64,
+        })
+    }
 }
 
 #[derive(Accounts)]
@@ -321,9 +566,6 @@ pub struct Deposit<'info> {
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
-    /// CHECK: The registered marinade state.
-    pub marinade_state: UncheckedAccount<'info>,
-    #[account(mut)]
     /// CHECK: The registered sunrise state.
     pub sunrise_state: UncheckedAccount<'info>,
 
@@ -332,6 +574,20 @@ pub struct Deposit<'info> {
     #[account(mut,token::mint = gsol_mint)]
     pub mint_gsol_to: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
+    // Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by Sunrise CPI.
+    pub gsol_mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    /// CHECK: The registered marinade state.
+    pub marinade_state: UncheckedAccount<'info>,
+
     #[account(mut)]
     // Checked by Marinade CPI.
     pub msol_mint: Box<Account<'info, Mint>>,
@@ -351,14 +607,6 @@ pub struct Deposit<'info> {
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    // Verified in CPI to Sunrise program.
-    pub gsol_mint: Box<Account<'info, Mint>>,
-    /// CHECK: Checked by Sunrise CPI.
-    pub gsol_mint_authority: UncheckedAccount<'info>,
-    /// CHECK: Checked by Sunrise CPI.
-    pub sysvar_instructions: UncheckedAccount<'info>,
-
     #[account(mut)]
     /// CHECK: Checked by Marinade CPI.
     pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
@@ -372,8 +620,6 @@ pub struct Deposit<'info> {
     #[account(mut)]
     /// CHECK: Checked by Marinade CPI.
     pub reserve_pda: UncheckedAccount<'info>,
-
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
     pub marinade_program: Program<'info, Marinade>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -464,8 +710,6 @@ pub struct Withdraw<'info> {
     )]
     pub state: Account<'info, State>,
     #[account(mut)]
-    pub marinade_state: Box<Account<'info, MarinadeState>>,
-    #[account(mut)]
     /// CHECK: The main Sunrise beam state.
     pub sunrise_state: UncheckedAccount<'info>,
 
@@ -477,6 +721,14 @@ pub struct Withdraw<'info> {
     /// Verified in CPI to Sunrise program.
     pub gsol_mint: Box<Account<'info, Mint>>,
 
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+
     #[account(mut)]
     // Checked by Marinade CPI.
     pub msol_mint: Box<Account<'info, Mint>>,
@@ -505,13 +757,58 @@ pub struct Withdraw<'info> {
     #[account(mut)]
     /// CHECK: Checked by Marinade CPI.
     pub treasury_msol_account: UncheckedAccount<'info>,
+    pub marinade_program: Program<'info, Marinade>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAsLst<'info> {
+    #[account(
+        has_one = sunrise_state,
+        has_one = marinade_state,
+        seeds = [constants::STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Account<'info, State>,
+    #[account(has_one = msol_mint)]
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+    #[account(mut)]
+    /// CHECK: The main Sunrise beam state.
+    pub sunrise_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = msol_mint,
+        token::authority = vault_authority,
+    )]
+    pub msol_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Seeds of the MSOL vault authority.
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            constants::VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    /// The token account that receives the withdrawn mSOL.
+    #[account(mut, token::mint = msol_mint)]
+    pub transfer_msol_to: Box<Account<'info, TokenAccount>>,
 
     /// CHECK: Checked by Sunrise CPI.
     pub sysvar_instructions: UncheckedAccount<'info>,
 
     pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
-    pub marinade_program: Program<'info, Marinade>,
-    pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
@@ -526,8 +823,6 @@ pub struct OrderWithdrawal<'info> {
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
-    pub marinade_state: Box<Account<'info, MarinadeState>>,
-    #[account(mut)]
     /// CHECK: The main Sunrise beam state.
     pub sunrise_state: UncheckedAccount<'info>,
 
@@ -539,6 +834,14 @@ pub struct OrderWithdrawal<'info> {
     /// Verified in CPI to Sunrise program.
     pub gsol_mint: Box<Account<'info, Mint>>,
 
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+
     #[account(mut)]
     // Checked by Marinade CPI.
     pub msol_mint: Box<Account<'info, Mint>>,
@@ -558,9 +861,6 @@ pub struct OrderWithdrawal<'info> {
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    /// CHECK: Checked by Sunrise CPI.
-    pub sysvar_instructions: UncheckedAccount<'info>,
-
     /// CHECK: Checked by Marinade CPI.
     #[account(mut)]
     pub new_ticket_account: UncheckedAccount<'info>,
@@ -570,8 +870,6 @@ pub struct OrderWithdrawal<'info> {
         payer = withdrawer
     )]
     pub proxy_ticket_account: Box<Account<'info, ProxyTicket>>,
-
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
     pub marinade_program: Program<'info, Marinade>,
     pub clock: Sysvar<'info, Clock>,
     pub rent: Sysvar<'info, Rent>,
@@ -583,11 +881,12 @@ pub struct OrderWithdrawal<'info> {
 pub struct RedeemTicket<'info> {
     #[account(mut, has_one = marinade_state)]
     pub state: Account<'info, State>,
+
+    pub beneficiary: Signer<'info>,
+
     #[account(mut)]
     /// CHECK: The registered Marinade state.
     pub marinade_state: UncheckedAccount<'info>,
-
-    pub beneficiary: Signer<'info>,
     #[account(
         mut,
         has_one = marinade_ticket_account,
@@ -635,6 +934,14 @@ pub struct Burn<'info> {
     #[account(mut, token::mint = gsol_mint)]
     pub gsol_token_account: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
     #[account(
     seeds = [
     state.key().as_ref(),
@@ -649,14 +956,6 @@ pub struct Burn<'info> {
     pub system_program: UncheckedAccount<'info>,
     /// CHECK: Checked by Marinade CPI.
     pub token_program: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    /// Verified in CPI to Sunrise program.
-    pub gsol_mint: Box<Account<'info, Mint>>,
-    /// CHECK: Checked by Sunrise CPI.
-    pub sysvar_instructions: UncheckedAccount<'info>,
-
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
 }
 
 #[derive(Accounts, Clone)]
@@ -673,6 +972,20 @@ pub struct ExtractYield<'info> {
         has_one = yield_account
     )]
     pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    /// CHECK: Matches the yield account key stored in the state.
+    pub yield_account: UncheckedAccount<'info>,
+    #[account(mut, address = sunrise_state.treasury)]
+    /// CHECK: Matches the treasury key stored in the state.
+    /// Only needed while the state charges a protocol fee.
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
     #[account(mut)]
     pub marinade_state: Box<Account<'info, MarinadeState>>,
 
@@ -698,10 +1011,6 @@ pub struct ExtractYield<'info> {
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    /// CHECK: Matches the yield account key stored in the state.
-    pub yield_account: UncheckedAccount<'info>,
-
     #[account(mut)]
     /// CHECK: Checked by Marinade CPI.
     pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
@@ -711,11 +1020,6 @@ pub struct ExtractYield<'info> {
     #[account(mut)]
     /// CHECK: Checked by Marinade CPI.
     pub treasury_msol_account: UncheckedAccount<'info>,
-
-    /// CHECK: Checked by Sunrise CPI.
-    pub sysvar_instructions: UncheckedAccount<'info>,
-
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
     pub marinade_program: Program<'info, Marinade>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -734,6 +1038,19 @@ pub struct UpdateEpochReport<'info> {
     mut, // Update the extractable yield on the state's epoch report.
     )]
     pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    /// Required to update the core state epoch report
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Account<'info, Mint>,
+    #[account(mut)]
+    /// CHECK: Updated with the epoch report - verified in CPI to Sunrise program.
+    pub backing_oracle: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
     #[account(
         has_one = msol_mint,
     )]
@@ -754,15 +1071,134 @@ pub struct UpdateEpochReport<'info> {
     bump = state.vault_authority_bump
     )]
     pub vault_authority: UncheckedAccount<'info>,
+}
 
-    /// Required to update the core state epoch report
-    /// Verified in CPI to Sunrise program.
-    pub gsol_mint: Account<'info, Mint>,
+#[derive(Accounts)]
+pub struct View<'info> {
+    #[account(
+        has_one = marinade_state,
+        has_one = sunrise_state,
+        seeds = [constants::STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(
+        has_one = msol_mint,
+    )]
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+    #[account(
+        token::mint = msol_mint,
+        token::authority = vault_authority,
+    )]
+    pub msol_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Seeds of the MSOL vault authority.
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            constants::VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [marinade_state.key().as_ref(), constants::LIQ_POOL_SOL_LEG],
+        seeds::program = marinade_cpi::ID,
+        bump = marinade_state.liq_pool.sol_leg_bump_seed
+    )]
+    /// CHECK: The Marinade liq_pool SOL leg PDA with verified seeds.
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundLiquidityBeam<'info> {
+    #[account(
+        has_one = sunrise_state,
+        has_one = marinade_state,
+        seeds = [constants::STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+    #[account(
+        mut, // Transfer gSOL between beams.
+        has_one = gsol_mint
+    )]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+    /// The gSOL mint, whose supply determines the target liquidity.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+
+    /// The Marinade LP beam funded by this beam. Its withdrawn mSOL must be sent to this beam's vault.
+    #[account(
+        has_one = sunrise_state,
+        has_one = marinade_state,
+        constraint = liquidity_beam.msol_recipient_beam == state.key() @ MarinadeBeamError::UnpairedLiquidityBeam,
+        constraint = liquidity_beam.msol_token_account == msol_vault.key() @ MarinadeBeamError::UnpairedLiquidityBeam,
+    )]
+    pub liquidity_beam: Box<Account<'info, LiquidityBeamState>>,
+    #[account(
+        mut,
+        token::mint = liq_pool_mint,
+        token::authority = liquidity_beam_vault_authority,
+    )]
+    pub liquidity_beam_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            liquidity_beam.key().as_ref(),
+            constants::VAULT_AUTHORITY
+        ],
+        bump = liquidity_beam.vault_authority_bump,
+        seeds::program = marinade_common::liquidity_beam::ID
+    )]
+    /// CHECK: The liquidity beam's vault authority PDA with verified seeds.
+    pub liquidity_beam_vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub msol_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = msol_mint,
+        token::authority = vault_authority,
+    )]
+    pub msol_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut, // Temporarily holds the unstaked SOL.
+        seeds = [
+            state.key().as_ref(),
+            constants::VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: Seeds of the MSOL vault authority.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by Marinade CPI.
+    pub liq_pool_mint: UncheckedAccount<'info>,
+    /// CHECK: Checked by Marinade CPI.
+    pub liq_pool_mint_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by Marinade CPI.
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by Marinade CPI.
+    pub liq_pool_msol_leg: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by Marinade CPI.
+    pub treasury_msol_account: UncheckedAccount<'info>,
 
     /// CHECK: Checked by Sunrise CPI.
     pub sysvar_instructions: UncheckedAccount<'info>,
 
     pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+    pub marinade_program: Program<'info, Marinade>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[error_code]
@@ -777,4 +1213,10 @@ pub enum MarinadeBeamError {
     DelayedUnstakeTicketsNotYetClaimable,
     #[msg("The amount of delayed unstake tickets requested to be recovered exceeds the amount in the report")]
     TooManyTicketsClaimed,
+    #[msg("The withdrawal paid out fewer lamports than the requested minimum")]
+    WithdrawalBelowMinimum,
+    #[msg("The liquidity beam's liquidity is not below its target")]
+    LiquidityWithinTarget,
+    #[msg("The liquidity beam does not send its withdrawn mSOL to this beam")]
+    UnpairedLiquidityBeam,
 }
@@ -8,19 +8,34 @@ use marinade_common::calc_lamports_from_msol_amount;
 use marinade_cpi::state::State as MarinadeState;
 use sunrise_core::BeamError;
 
-/// Calculates the amount that can be extracted as yield, in lamports.
-pub fn calculate_extractable_yield(
+/// The estimated fee charged by Marinade to liquid unstake the yield, in basis points (0.3%).
+pub const ESTIMATED_UNSTAKE_FEE_BPS: u16 = 30;
+
+/// Calculates the value of the msol vault less the gSOL issued by this beam, in lamports.
+/// This is negative if the beam is in deficit.
+pub fn calculate_surplus(
     sunrise_state: &sunrise_core::State,
     beam_state: &Account<State>,
     marinade_state: &MarinadeState,
     msol_vault: &TokenAccount,
-) -> Result<u64> {
-    let staked_value = calc_lamports_from_msol_amount(marinade_state, msol_vault.amount);
+) -> Result<i64> {
+    let staked_value = calc_lamports_from_msol_amount(marinade_state, msol_vault.amount)?;
     let details = sunrise_state
         .get_beam_details(&beam_state.key())
         .ok_or(BeamError::UnidentifiedBeam)?;
     let staked_sol = details.partial_gsol_supply;
-    Ok(staked_value.saturating_sub(staked_sol))
+    Ok(beam_math::surplus(staked_value, staked_sol)?)
+}
+
+/// Calculates the amount that can be extracted as yield, in lamports.
+pub fn calculate_extractable_yield(
+    sunrise_state: &sunrise_core::State,
+    beam_state: &Account<State>,
+    marinade_state: &MarinadeState,
+    msol_vault: &TokenAccount,
+) -> Result<u64> {
+    let surplus = calculate_surplus(sunrise_state, beam_state, marinade_state, msol_vault)?;
+    Ok(surplus.max(0) as u64)
 }
 
 pub fn get_delegated_stake_amount(stake_account: &AccountInfo) -> Result<u64> {
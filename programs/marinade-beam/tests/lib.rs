@@ -0,0 +1,202 @@
+//! Integration tests of the Marinade beam against the Marinade state in `packages/tests/fixtures`.
+
+use beam_test_utils::beams::initialize_marinade_beam;
+use beam_test_utils::{add_marinade, beam_program_test, suite, BeamTestContext};
+use solana_program_test::{tokio, ProgramTest};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use sunrise_client::marinade::TicketAccountData;
+use sunrise_client::marinade_sp::{self, ProxyTicket};
+use sunrise_client::sunrise::BackingOracle;
+use sunrise_client::{BeamValue, MarinadeClient, WithdrawQuote};
+
+const DEPOSIT: u64 = 10 * LAMPORTS_PER_SOL;
+const WITHDRAWAL: u64 = 2 * LAMPORTS_PER_SOL;
+const DELAYED_WITHDRAWAL: u64 = 3 * LAMPORTS_PER_SOL;
+const BURN: u64 = LAMPORTS_PER_SOL;
+
+fn program_test() -> ProgramTest {
+    let mut program_test = beam_program_test!(marinade_beam, marinade_sp::ID);
+    add_marinade(&mut program_test);
+    program_test
+}
+
+/// Start with an initialized beam that is not yet registered,
+/// and a funded staker with a gSOL token account.
+async fn setup() -> (BeamTestContext, MarinadeClient, Keypair, Pubkey) {
+    setup_in(BeamTestContext::start(program_test()).await).await
+}
+
+/// As `setup`, in an already started `context`.
+async fn setup_in(
+    mut context: BeamTestContext,
+) -> (BeamTestContext, MarinadeClient, Keypair, Pubkey) {
+    let beam = initialize_marinade_beam(&mut context).await;
+    let (staker, gsol_account) = context.create_staker().await;
+    (context, beam, staker, gsol_account)
+}
+
+#[tokio::test]
+async fn cannot_deposit_before_registration() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    suite::cannot_deposit_before_registration(&mut context, &beam, &staker, &gsol_account, DEPOSIT)
+        .await;
+}
+
+#[tokio::test]
+async fn can_deposit_and_withdraw() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    suite::can_deposit_and_withdraw(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        WITHDRAWAL,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn can_order_a_withdrawal_and_redeem_the_ticket() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    let quote: WithdrawQuote = context.view(beam.quote_withdraw(DELAYED_WITHDRAWAL)).await;
+    let vault_balance = context.token_balance(&beam.msol_vault()).await;
+    let marinade_ticket = Keypair::new();
+    let proxy_ticket = Keypair::new();
+    let rent = context.rent().await;
+    let instructions = [
+        beam.create_ticket_account(&context.payer(), &marinade_ticket.pubkey(), &rent),
+        beam.order_withdrawal(
+            &staker.pubkey(),
+            &gsol_account,
+            &marinade_ticket.pubkey(),
+            &proxy_ticket.pubkey(),
+            DELAYED_WITHDRAWAL,
+        ),
+    ];
+    context
+        .send(&instructions, &[&staker, &marinade_ticket, &proxy_ticket])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        context.token_balance(&gsol_account).await,
+        DEPOSIT - DELAYED_WITHDRAWAL
+    );
+    let vault_decrease = vault_balance - context.token_balance(&beam.msol_vault()).await;
+    assert!(vault_decrease.abs_diff(quote.beam_tokens_in) <= 1);
+
+    let proxy: ProxyTicket = context.fetch(&proxy_ticket.pubkey()).await;
+    assert_eq!(proxy.state, beam.state_address);
+    assert_eq!(proxy.marinade_ticket_account, marinade_ticket.pubkey());
+    assert_eq!(proxy.beneficiary, staker.pubkey());
+    let ticket: TicketAccountData = context.fetch(&marinade_ticket.pubkey()).await;
+    assert!(ticket.lamports_amount.abs_diff(DELAYED_WITHDRAWAL) < 100);
+
+    // The ticket can't be redeemed until Marinade has unstaked the lamports.
+    let redeem = beam.redeem_ticket(&proxy_ticket.pubkey(), &proxy);
+    assert!(context.send(&[redeem.clone()], &[&staker]).await.is_err());
+
+    context.warp_to_epoch(ticket.created_epoch + 2).await;
+    let staker_balance = context.balance(&staker.pubkey()).await;
+    let proxy_rent = context.balance(&proxy_ticket.pubkey()).await;
+    context.send(&[redeem], &[&staker]).await.unwrap();
+
+    // The proxy ticket is closed to the staker, who paid for it.
+    assert_eq!(
+        context.balance(&staker.pubkey()).await,
+        staker_balance + ticket.lamports_amount + proxy_rent
+    );
+    assert!(context.account(&proxy_ticket.pubkey()).await.is_none());
+}
+
+#[tokio::test]
+async fn can_extract_the_yield_of_burned_gsol() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    // Burning gSOL leaves the beam holding more value than its gSOL supply.
+    let vault_balance = context.token_balance(&beam.msol_vault()).await;
+    let burn = beam.burn(&staker.pubkey(), &gsol_account, BURN);
+    context.send(&[burn], &[&staker]).await.unwrap();
+    assert_eq!(context.token_balance(&gsol_account).await, DEPOSIT - BURN);
+    assert_eq!(
+        context.token_balance(&beam.msol_vault()).await,
+        vault_balance
+    );
+
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert_eq!(value.gsol_supply, DEPOSIT - BURN);
+    assert!(value.surplus > 0);
+
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    context.refresh_sunrise().await;
+    let epoch = context.epoch().await;
+    let report = &context.sunrise.state.epoch_report.beam_epoch_details[0];
+    assert_eq!(report.epoch, epoch);
+    // The reported yield is net of the estimated liquid unstake fee.
+    assert_eq!(report.extractable_yield, value.surplus as u64);
+    let extractable_yield = report.extractable_yield;
+
+    let yield_account = context.sunrise.state.yield_account;
+    let yield_balance = context.balance(&yield_account).await;
+    let extract_yield = beam.extract_yield(&context.payer());
+    context.send(&[extract_yield], &[]).await.unwrap();
+
+    context.refresh_sunrise().await;
+    let report = &context.sunrise.state.epoch_report.beam_epoch_details[0];
+    let extracted_yield = report.extracted_yield;
+    assert_eq!(
+        context.balance(&yield_account).await,
+        yield_balance + extracted_yield
+    );
+    assert!(extracted_yield.abs_diff(extractable_yield) <= 1);
+}
+
+#[tokio::test]
+async fn can_extract_yield_without_a_treasury() {
+    let context = BeamTestContext::start_without_treasury(program_test()).await;
+    let (mut context, beam, staker, gsol_account) = setup_in(context).await;
+    context.register_beam(&beam.state_address).await;
+    suite::can_extract_yield_without_a_treasury(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        BURN,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn can_report_after_resizing_the_allocations() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    // The backing oracle is created with the state and grows with its allocations.
+    let capacity = context.sunrise.state.allocations.len();
+    let oracle: BackingOracle = context.fetch(&context.sunrise.backing_oracle()).await;
+    assert_eq!(oracle.beam_backing.len(), capacity);
+
+    let resize_allocations = context.sunrise.resize_allocations(&context.payer(), 2);
+    context.send(&[resize_allocations], &[]).await.unwrap();
+    context.refresh_sunrise().await;
+    assert_eq!(context.sunrise.state.allocations.len(), capacity + 2);
+
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    let oracle: BackingOracle = context.fetch(&context.sunrise.backing_oracle()).await;
+    assert_eq!(oracle.beam_backing.len(), capacity + 2);
+    assert_eq!(oracle.beam_backing[0].beam, beam.state_address);
+    assert_eq!(oracle.gsol_supply, DEPOSIT);
+}
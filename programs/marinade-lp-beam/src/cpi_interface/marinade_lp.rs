@@ -3,8 +3,12 @@ use crate::state::State;
 use anchor_lang::prelude::*;
 use marinade_common::vault_authority_seed::VaultAuthoritySeed;
 use marinade_cpi::cpi::{
-    accounts::{AddLiquidity as MarinadeAddLiquidity, RemoveLiquidity as MarinadeRemoveLiquidity},
-    add_liquidity as marinade_add_liquidity, remove_liquidity as marinade_remove_liquidity,
+    accounts::{
+        AddLiquidity as MarinadeAddLiquidity, Deposit as MarinadeDeposit,
+        RemoveLiquidity as MarinadeRemoveLiquidity,
+    },
+    add_liquidity as marinade_add_liquidity, deposit as marinade_deposit,
+    remove_liquidity as marinade_remove_liquidity,
 };
 
 pub fn add_liquidity(
@@ -39,6 +43,25 @@ pub fn remove_liquidity<'info>(
     Ok(())
 }
 
+/// Deposits lamports held by the vault authority into Marinade, minting mSOL to the
+/// designated mSOL token account.
+pub fn deposit_from_vault_authority<'info>(
+    program: &Program<'info, Marinade>,
+    state: &Account<State>,
+    accounts: MarinadeDeposit<'info>,
+    lamports: u64,
+) -> Result<()> {
+    let cpi_program = program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, accounts);
+
+    let seed_data = VaultAuthoritySeed::new(state);
+    let seeds = seed_data.as_slices();
+
+    marinade_deposit(cpi_ctx.with_signer(&[&seeds[..]]), lamports)?;
+
+    Ok(())
+}
+
 impl<'a> From<&crate::Deposit<'a>> for MarinadeAddLiquidity<'a> {
     fn from(accounts: &crate::Deposit<'a>) -> MarinadeAddLiquidity<'a> {
         Self {
@@ -90,3 +113,39 @@ impl<'a> From<&crate::ExtractYield<'a>> for MarinadeRemoveLiquidity<'a> {
         }
     }
 }
+
+impl<'a> From<&crate::RebalanceLiquidity<'a>> for MarinadeRemoveLiquidity<'a> {
+    fn from(accounts: &crate::RebalanceLiquidity<'a>) -> MarinadeRemoveLiquidity<'a> {
+        Self {
+            state: accounts.marinade_state.to_account_info(),
+            lp_mint: accounts.liq_pool_mint.to_account_info(),
+            burn_from: accounts.liq_pool_token_vault.to_account_info(),
+            burn_from_authority: accounts.vault_authority.to_account_info(),
+            transfer_sol_to: accounts.vault_authority.to_account_info(),
+            transfer_msol_to: accounts.transfer_msol_to.to_account_info(),
+            liq_pool_sol_leg_pda: accounts.liq_pool_sol_leg_pda.to_account_info(),
+            liq_pool_msol_leg: accounts.liq_pool_msol_leg.to_account_info(),
+            liq_pool_msol_leg_authority: accounts.liq_pool_msol_leg_authority.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        }
+    }
+}
+
+impl<'a> From<&crate::RebalanceLiquidity<'a>> for MarinadeDeposit<'a> {
+    fn from(accounts: &crate::RebalanceLiquidity<'a>) -> MarinadeDeposit<'a> {
+        Self {
+            state: accounts.marinade_state.to_account_info(),
+            msol_mint: accounts.msol_mint.to_account_info(),
+            liq_pool_sol_leg_pda: accounts.liq_pool_sol_leg_pda.to_account_info(),
+            liq_pool_msol_leg: accounts.liq_pool_msol_leg.to_account_info(),
+            liq_pool_msol_leg_authority: accounts.liq_pool_msol_leg_authority.to_account_info(),
+            reserve_pda: accounts.reserve_pda.to_account_info(),
+            transfer_from: accounts.vault_authority.to_account_info(),
+            mint_to: accounts.transfer_msol_to.to_account_info(),
+            msol_mint_authority: accounts.msol_mint_authority.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        }
+    }
+}
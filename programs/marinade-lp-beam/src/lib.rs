@@ -4,39 +4,55 @@
 
 use crate::cpi_interface::program::Marinade;
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 use anchor_spl::associated_token::{AssociatedToken, Create};
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use beam_math::MAX_BPS;
 use cpi_interface::marinade_lp as marinade_lp_interface;
 use cpi_interface::sunrise as sunrise_interface;
 use marinade_cpi::State as MarinadeState;
-use state::{State, StateEntry};
+use state::{State, StateEntry, YieldBreakdown};
 use std::cmp::max;
 use std::ops::Deref;
+use sunrise_beam_interface::core_cpi;
+use sunrise_beam_interface::unsupported::unsupported;
+use sunrise_beam_interface::view::{BeamValue, DepositQuote, WithdrawQuote};
 use sunrise_core as sunrise_core_cpi;
+use sunrise_core::BeamError;
 use system::utils;
 
 mod cpi_interface;
-mod state;
+pub mod state;
 mod system;
 
 declare_id!("9Xek4q2hsdPm4yaRt4giQnVTTgRGwGhXQ1HBXbinuPTP");
 
-mod constants {
+pub mod constants {
     /// Seed of the PDA that can authorize spending from the vault that holds pool tokens.
     pub const VAULT_AUTHORITY: &[u8] = b"vault-authority";
     /// Seed of this program's state address.
     pub const STATE: &[u8] = b"sunrise-marinade-lp";
+    /// Seed of the yield breakdown address of a beam.
+    pub const YIELD_BREAKDOWN: &[u8] = b"yield-breakdown";
+    /// Seed of the Marinade liq_pool SOL leg PDA, owned by the Marinade program.
+    pub const LIQ_POOL_SOL_LEG: &[u8] = b"liq_sol";
 }
 
 #[program]
 pub mod marinade_lp_beam {
     use super::*;
     use crate::cpi_interface::marinade_lp;
-    use crate::system::utils::get_extractable_yield_from_excess_balance;
     use marinade_common::calc_lamports_from_msol_amount;
+    use marinade_cpi::cpi::accounts::RemoveLiquidity as MarinadeRemoveLiquidity;
 
     pub fn initialize(ctx: Context<Initialize>, input: StateEntry) -> Result<()> {
+        require_gte!(
+            MAX_BPS,
+            input.target_liquidity_bps,
+            MarinadeLpBeamError::InvalidTargetLiquidity
+        );
         ctx.accounts.state.set_inner(input.into());
+        ctx.accounts.yield_breakdown.state = ctx.accounts.state.key();
         let cpi_program = ctx.accounts.associated_token_program.to_account_info();
         let cpi_accounts = Create {
             payer: ctx.accounts.payer.to_account_info(),
@@ -51,14 +67,75 @@ pub mod marinade_lp_beam {
     }
 
     pub fn update(ctx: Context<Update>, update_input: StateEntry) -> Result<()> {
+        require_gte!(
+            MAX_BPS,
+            update_input.target_liquidity_bps,
+            MarinadeLpBeamError::InvalidTargetLiquidity
+        );
         ctx.accounts.state.set_inner(update_input.into());
         Ok(())
     }
 
-    pub fn deposit(ctx: Context<Deposit>, lamports: u64) -> Result<()> {
+    /// Resizes a state created before `target_liquidity_bps` was added, which cannot be
+    /// deserialized (and so updated) until it has room for it, and sets its target liquidity.
+    pub fn resize_state(ctx: Context<ResizeState>, target_liquidity_bps: u16) -> Result<()> {
+        require_gte!(
+            MAX_BPS,
+            target_liquidity_bps,
+            MarinadeLpBeamError::InvalidTargetLiquidity
+        );
+        let state = ctx.accounts.state.to_account_info();
+        require_eq!(
+            state.data_len(),
+            State::SPACE_WITHOUT_TARGET_LIQUIDITY,
+            MarinadeLpBeamError::StateAlreadyResized
+        );
+        {
+            let data = state.try_borrow_data()?;
+            require!(
+                data[..8] == State::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            // The update authority is the first field after the discriminator.
+            let update_authority =
+                Pubkey::try_from(&data[8..40]).map_err(|_| ProgramError::InvalidAccountData)?;
+            require_keys_eq!(
+                update_authority,
+                ctx.accounts.update_authority.key(),
+                ErrorCode::ConstraintHasOne
+            );
+        }
+
+        utils::resize_account(
+            &state,
+            &ctx.accounts.update_authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            State::SPACE,
+        )?;
+        state.try_borrow_mut_data()?[State::SPACE_WITHOUT_TARGET_LIQUIDITY..]
+            .copy_from_slice(&target_liquidity_bps.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
         // CPI: Add liquidity to Marinade liq_pool. The liq_pool tokens are minted into a
         // vault controlled by a PDA of this program.
+        let liq_pool_tokens_before = ctx.accounts.liq_pool_token_vault.amount;
         marinade_lp_interface::add_liquidity(ctx.accounts, lamports, None)?;
+        ctx.accounts.liq_pool_token_vault.reload()?;
+        let liq_pool_tokens = ctx
+            .accounts
+            .liq_pool_token_vault
+            .amount
+            .saturating_sub(liq_pool_tokens_before);
+
+        // Add the deposit to the cost basis, so that it is not counted as yield.
+        ctx.accounts
+            .yield_breakdown
+            .record_deposit(liq_pool_tokens, lamports)?;
 
         let state_bump = ctx.bumps.state;
         // CPI: Mint GSOL of the same proportion as the lamports deposited to the depositor.
@@ -67,13 +144,21 @@ pub mod marinade_lp_beam {
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             state_bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
         Ok(())
     }
 
-    pub fn withdraw(ctx: Context<Withdraw>, lamports: u64) -> Result<()> {
+    /// Withdraws `lamports` by removing liquidity from the Marinade liq_pool.
+    ///
+    /// Errors if the withdrawer receives fewer than `min_lamports_out` lamports.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
         // Calculate the number of liq_pool tokens that would be needed to withdraw `lamports`
         let liq_pool_balance_to_withdraw =
             utils::calculate_liq_pool_balance_required_to_withdraw_lamports(
@@ -97,6 +182,16 @@ pub mod marinade_lp_beam {
         // NOTE: This results in an asymmetry in the amount of gSOL each beam is responsible for
         // The lamport amount is burned, and the mSOL amount has been effectively moved from
         // the beam to the Marinade-SP (or whichever beam owns the msol token account)
+        let withdrawer_balance_before = ctx.accounts.withdrawer.lamports();
+
+        // Remove the withdrawn liquidity from the cost basis, so that it is not counted as a loss.
+        let withdrawn_value =
+            liq_pool_balance_to_withdraw.sol_value(&ctx.accounts.marinade_state)?;
+        ctx.accounts.yield_breakdown.record_withdrawal(
+            liq_pool_balance_to_withdraw.liq_pool_token as u64,
+            withdrawn_value,
+        );
+
         let accounts = ctx.accounts.deref().into();
         marinade_lp::remove_liquidity(
             &ctx.accounts.marinade_program,
@@ -106,6 +201,16 @@ pub mod marinade_lp_beam {
             liq_pool_balance_to_withdraw.liq_pool_token as u64,
         )?;
 
+        let withdrawer_balance_after = ctx.accounts.withdrawer.lamports();
+        let withdrawn_lamports = withdrawer_balance_after.saturating_sub(withdrawer_balance_before);
+
+        msg!("Withdrawn {} lamports", withdrawn_lamports);
+        require_gte!(
+            withdrawn_lamports,
+            min_lamports_out,
+            MarinadeLpBeamError::WithdrawalBelowMinimum
+        );
+
         let state_bump = ctx.bumps.state;
         // CPI: Burn GSOL of the same proportion as the lamports withdrawn from the depositor.
         sunrise_interface::burn_gsol(
@@ -113,13 +218,14 @@ pub mod marinade_lp_beam {
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             state_bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
         let lamport_value_of_msol = calc_lamports_from_msol_amount(
             &ctx.accounts.marinade_state,
             liq_pool_balance_to_withdraw.msol as u64,
-        );
+        )?;
         sunrise_interface::transfer_gsol(
             ctx.accounts.deref(),
             ctx.accounts.sunrise_program.to_account_info(),
@@ -135,27 +241,28 @@ pub mod marinade_lp_beam {
     /// Burning is withdrawing without redeeming the pool tokens. The result is a beam that is "worth more"
     /// than the SOL that has been staked into it, i.e. the pool tokens are more valuable than the SOL.
     /// This allows yield extraction and can be seen as a form of "donation".
-    pub fn burn(ctx: Context<Burn>, lamports: u64) -> Result<()> {
+    pub fn burn<'info>(ctx: Context<'_, '_, '_, 'info, Burn<'info>>, lamports: u64) -> Result<()> {
         let state_bump = ctx.bumps.state;
         sunrise_interface::burn_gsol(
             ctx.accounts.deref(),
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             state_bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
         Ok(())
     }
 
-    pub fn order_withdrawal(_ctx: Context<Noop>) -> Result<()> {
+    pub fn order_withdrawal(_ctx: Context<Unsupported>, _lamports: u64) -> Result<()> {
         // Marinade liq_pool only supports immediate withdrawals.
-        Err(MarinadeLpBeamError::Unimplemented.into())
+        unsupported()
     }
 
-    pub fn redeem_ticket(_ctx: Context<Noop>) -> Result<()> {
+    pub fn redeem_ticket(_ctx: Context<Unsupported>) -> Result<()> {
         // Marinade liq_pool only supports immediate withdrawals.
-        Err(MarinadeLpBeamError::Unimplemented.into())
+        unsupported()
     }
 
     pub fn extract_yield(ctx: Context<ExtractYield>) -> Result<()> {
@@ -178,22 +285,62 @@ pub mod marinade_lp_beam {
             MarinadeLpBeamError::InsufficientYieldBalance
         );
 
-        let yield_account_balance_before = ctx.accounts.yield_account.lamports();
+        // checked by the assert above - guaranteed to be positive
+        let liq_pool_tokens = yield_balance.liq_pool_token as u64;
+
+        // Attribute the yield earned up to now, then remove the extracted liquidity from the cost basis.
+        utils::checkpoint_yield_breakdown(
+            &mut ctx.accounts.yield_breakdown,
+            &ctx.accounts.marinade_state,
+            &ctx.accounts.liq_pool_mint,
+            &ctx.accounts.liq_pool_token_vault,
+            &ctx.accounts.liq_pool_sol_leg_pda,
+            &ctx.accounts.liq_pool_msol_leg,
+        )?;
+        let extracted_value = yield_balance.sol_value(&ctx.accounts.marinade_state)?;
+        ctx.accounts
+            .yield_breakdown
+            .record_withdrawal(liq_pool_tokens, extracted_value);
+        let protocol_fee_liq_pool_tokens =
+            ctx.accounts.sunrise_state.protocol_fee(liq_pool_tokens)?;
+        let treasury =
+            core_cpi::fee_treasury(ctx.accounts.treasury.as_ref(), protocol_fee_liq_pool_tokens)?;
+        let treasury_balance = || treasury.map(|t| t.lamports()).unwrap_or_default();
 
+        let yield_account_balance_before = ctx.accounts.yield_account.lamports();
+        let treasury_balance_before = treasury_balance();
+
+        // The SOL leg of the protocol fee share is sent to the treasury, and the rest to the yield account.
+        if let Some(treasury) = treasury {
+            let accounts = MarinadeRemoveLiquidity {
+                transfer_sol_to: treasury.to_account_info(),
+                ..MarinadeRemoveLiquidity::from(ctx.accounts.deref())
+            };
+            marinade_lp::remove_liquidity(
+                &ctx.accounts.marinade_program,
+                &ctx.accounts.state,
+                accounts,
+                protocol_fee_liq_pool_tokens,
+            )?;
+        }
         let accounts = ctx.accounts.deref().into();
         marinade_lp::remove_liquidity(
             &ctx.accounts.marinade_program,
             &ctx.accounts.state,
             accounts,
-            // checked by the assert above - guaranteed to be positive
-            yield_balance.liq_pool_token as u64,
+            liq_pool_tokens.saturating_sub(protocol_fee_liq_pool_tokens),
         )?;
 
         let yield_account_balance_after = ctx.accounts.yield_account.lamports();
-        let withdrawn_lamports =
-            yield_account_balance_after.saturating_sub(yield_account_balance_before);
+        let treasury_balance_after = treasury_balance();
+        let withdrawn_lamports = yield_account_balance_after
+            .saturating_sub(yield_account_balance_before)
+            .saturating_add(treasury_balance_after.saturating_sub(treasury_balance_before));
 
-        msg!("Withdrawn {} lamports to yield account", withdrawn_lamports);
+        msg!(
+            "Withdrawn {} lamports to yield account and treasury",
+            withdrawn_lamports
+        );
 
         // CPI: update the epoch report with the extracted yield.
         let state_bump = ctx.bumps.state;
@@ -208,7 +355,114 @@ pub mod marinade_lp_beam {
         Ok(())
     }
 
+    /// Permissionless crank that keeps the liquidity buffer at its target size.
+    ///
+    /// If the gSOL this beam is responsible for exceeds its target share of the gSOL supply,
+    /// the surplus liquidity is removed and moved to the mSOL recipient beam (typically the Marinade-SP beam).
+    /// The mSOL leg is sent to the recipient's mSOL token account, and the SOL leg is staked
+    /// with Marinade into the same account. The gSOL backed by the surplus is transferred to the recipient beam.
+    ///
+    /// A shortfall is filled from the Marinade-SP beam by its `fund_liquidity_beam` crank.
+    pub fn rebalance_liquidity(ctx: Context<RebalanceLiquidity>) -> Result<()> {
+        let details = ctx
+            .accounts
+            .sunrise_state
+            .get_beam_details(&ctx.accounts.state.key())
+            .ok_or(BeamError::UnidentifiedBeam)?;
+        let target_liquidity = ctx
+            .accounts
+            .state
+            .target_liquidity(ctx.accounts.gsol_mint.supply)?;
+        let surplus = details.partial_gsol_supply.saturating_sub(target_liquidity);
+        msg!(
+            "Liquidity: {}, target: {}, surplus: {}",
+            details.partial_gsol_supply,
+            target_liquidity,
+            surplus
+        );
+        require_gt!(surplus, 0, MarinadeLpBeamError::LiquidityWithinTarget);
+
+        // Calculate the number of liq_pool tokens worth the surplus, up to the tokens in the vault.
+        let surplus_tokens = utils::calculate_liq_pool_token_value_of_lamports(
+            &ctx.accounts.marinade_state,
+            &ctx.accounts.liq_pool_mint,
+            &ctx.accounts.liq_pool_sol_leg_pda,
+            &ctx.accounts.liq_pool_msol_leg,
+            surplus,
+        )?;
+        let liq_pool_tokens = surplus_tokens.min(ctx.accounts.liq_pool_token_vault.amount);
+
+        // Remove the surplus liquidity from the cost basis, so that it is not counted as a loss.
+        let surplus_value = utils::liq_pool_balance_for_tokens(
+            liq_pool_tokens,
+            &ctx.accounts.marinade_state,
+            &ctx.accounts.liq_pool_mint,
+            &ctx.accounts.liq_pool_sol_leg_pda,
+            &ctx.accounts.liq_pool_msol_leg,
+        )?
+        .sol_value(&ctx.accounts.marinade_state)?;
+        ctx.accounts
+            .yield_breakdown
+            .record_withdrawal(liq_pool_tokens, surplus_value);
+
+        // CPI: Remove the surplus liquidity. The mSOL goes to the recipient beam's mSOL token account,
+        // and the SOL to the vault authority, to be staked below.
+        let vault_authority_balance_before = ctx.accounts.vault_authority.lamports();
+        let accounts = ctx.accounts.deref().into();
+        marinade_lp::remove_liquidity(
+            &ctx.accounts.marinade_program,
+            &ctx.accounts.state,
+            accounts,
+            liq_pool_tokens,
+        )?;
+        let removed_lamports = ctx
+            .accounts
+            .vault_authority
+            .lamports()
+            .saturating_sub(vault_authority_balance_before);
+
+        // CPI: Stake the SOL leg with Marinade, minting mSOL to the recipient beam's mSOL token account.
+        if removed_lamports > 0 {
+            let accounts = ctx.accounts.deref().into();
+            marinade_lp::deposit_from_vault_authority(
+                &ctx.accounts.marinade_program,
+                &ctx.accounts.state,
+                accounts,
+                removed_lamports,
+            )?;
+        }
+
+        // CPI: Transfer the gSOL backed by the removed liquidity to the recipient beam,
+        // which is only the value removed if the vault held less than the surplus is worth.
+        let transferred_gsol = if liq_pool_tokens < surplus_tokens {
+            surplus_value
+        } else {
+            surplus
+        };
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::transfer_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            ctx.accounts.state.msol_recipient_beam,
+            transferred_gsol,
+        )?;
+
+        Ok(())
+    }
+
+    /// Reports the beam's surplus to sunrise, and checkpoints its yield breakdown.
     pub fn update_epoch_report(ctx: Context<UpdateEpochReport>) -> Result<()> {
+        utils::checkpoint_yield_breakdown(
+            &mut ctx.accounts.yield_breakdown,
+            &ctx.accounts.marinade_state,
+            &ctx.accounts.liq_pool_mint,
+            &ctx.accounts.liq_pool_token_vault,
+            &ctx.accounts.liq_pool_sol_leg_pda,
+            &ctx.accounts.liq_pool_msol_leg,
+        )?;
+
         let yield_balance = utils::calculate_extractable_yield(
             &ctx.accounts.sunrise_state,
             &ctx.accounts.state,
@@ -219,8 +473,7 @@ pub mod marinade_lp_beam {
             &ctx.accounts.liq_pool_msol_leg,
         )?;
 
-        let excess_lamports = max(0, yield_balance.lamports) as u64;
-        let extractable_lamports = get_extractable_yield_from_excess_balance(excess_lamports);
+        let surplus_lamports = utils::get_surplus_from_balance(yield_balance.lamports)?;
 
         // CPI: update the epoch report with the extracted yield.
         let state_bump = ctx.bumps.state;
@@ -229,11 +482,93 @@ pub mod marinade_lp_beam {
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             state_bump,
-            extractable_lamports as u64,
+            surplus_lamports,
         )?;
 
         Ok(())
     }
+
+    /// Creates the yield breakdown account of a beam initialized without one,
+    /// using the current value of the beam's liquidity pool position as its cost basis.
+    pub fn init_yield_breakdown(ctx: Context<InitYieldBreakdown>) -> Result<()> {
+        ctx.accounts.yield_breakdown.state = ctx.accounts.state.key();
+        utils::checkpoint_yield_breakdown(
+            &mut ctx.accounts.yield_breakdown,
+            &ctx.accounts.marinade_state,
+            &ctx.accounts.liq_pool_mint,
+            &ctx.accounts.liq_pool_token_vault,
+            &ctx.accounts.liq_pool_sol_leg_pda,
+            &ctx.accounts.liq_pool_msol_leg,
+        )
+    }
+
+    /// View: quotes a [deposit] of `lamports`, returning the gSOL that would be minted
+    /// and the liq_pool tokens that the beam would receive.
+    pub fn quote_deposit(ctx: Context<View>, lamports: u64) -> Result<DepositQuote> {
+        let liq_pool_tokens = utils::calculate_liq_pool_token_value_of_lamports(
+            &ctx.accounts.marinade_state,
+            &ctx.accounts.liq_pool_mint,
+            &ctx.accounts.liq_pool_sol_leg_pda,
+            &ctx.accounts.liq_pool_msol_leg,
+            lamports,
+        )?;
+        Ok(DepositQuote {
+            gsol_out: lamports,
+            beam_tokens_out: liq_pool_tokens,
+        })
+    }
+
+    /// View: quotes a [withdraw] of `lamports`, returning the lamports that would be received
+    /// from the SOL leg of the pool, and the liq_pool tokens that the beam would burn.
+    /// The mSOL leg goes to the mSOL recipient beam, not the withdrawer.
+    pub fn quote_withdraw(ctx: Context<View>, lamports: u64) -> Result<WithdrawQuote> {
+        let liq_pool_balance_to_withdraw =
+            utils::calculate_liq_pool_balance_required_to_withdraw_lamports(
+                &ctx.accounts.marinade_state,
+                &ctx.accounts.liq_pool_mint,
+                &ctx.accounts.liq_pool_sol_leg_pda,
+                &ctx.accounts.liq_pool_msol_leg,
+                lamports,
+            )?;
+        Ok(WithdrawQuote {
+            lamports_out: max(0, liq_pool_balance_to_withdraw.lamports) as u64,
+            beam_tokens_in: max(0, liq_pool_balance_to_withdraw.liq_pool_token) as u64,
+        })
+    }
+
+    /// View: returns the value of the beam's liquidity pool position, the surplus that
+    /// [update_epoch_report] would report and the SOL leg that [extract_yield] would extract.
+    pub fn get_beam_value(ctx: Context<View>) -> Result<BeamValue> {
+        let balance = utils::current_liq_pool_balance(
+            &ctx.accounts.marinade_state,
+            &ctx.accounts.liq_pool_mint,
+            &ctx.accounts.liq_pool_token_vault,
+            &ctx.accounts.liq_pool_sol_leg_pda,
+            &ctx.accounts.liq_pool_msol_leg,
+        )?;
+        let gsol_supply = ctx
+            .accounts
+            .sunrise_state
+            .get_beam_details(&ctx.accounts.state.key())
+            .ok_or(BeamError::UnidentifiedBeam)?
+            .partial_gsol_supply;
+        let yield_balance = utils::calculate_extractable_yield(
+            &ctx.accounts.sunrise_state,
+            &ctx.accounts.state,
+            &ctx.accounts.marinade_state,
+            &ctx.accounts.liq_pool_mint,
+            &ctx.accounts.liq_pool_token_vault,
+            &ctx.accounts.liq_pool_sol_leg_pda,
+            &ctx.accounts.liq_pool_msol_leg,
+        )?;
+
+        Ok(BeamValue {
+            value: balance.sol_value(&ctx.accounts.marinade_state)?,
+            gsol_supply,
+            surplus: utils::get_surplus_from_balance(yield_balance.lamports)?,
+            extractable_yield: max(0, yield_balance.lamports) as u64,
+        })
+    }
 }
 
 #[derive(Accounts)]
@@ -249,6 +584,14 @@ pub struct Initialize<'info> {
         bump
     )]
     pub state: Account<'info, State>,
+    #[account(
+        init,
+        space = YieldBreakdown::SPACE,
+        payer = payer,
+        seeds = [state.key().as_ref(), constants::YIELD_BREAKDOWN],
+        bump
+    )]
+    pub yield_breakdown: Box<Account<'info, YieldBreakdown>>,
     /// CHECK: The liquidity pool token mint.
     pub liq_pool_mint: UncheckedAccount<'info>,
     #[account(mut)]
@@ -281,6 +624,24 @@ pub struct Update<'info> {
     pub state: Account<'info, State>,
 }
 
+#[derive(Accounts)]
+pub struct ResizeState<'info> {
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [constants::STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: A state that cannot be deserialized until it is resized.
+    /// Its discriminator and update authority are checked in the handler.
+    pub state: UncheckedAccount<'info>,
+    /// CHECK: The main Sunrise beam state the beam state is derived from.
+    pub sunrise_state: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(
@@ -291,9 +652,6 @@ pub struct Deposit<'info> {
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
-    /// CHECK: The registered Marinade state.
-    pub marinade_state: UncheckedAccount<'info>,
-    #[account(mut)]
     pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
 
     #[account(mut)]
@@ -301,6 +659,20 @@ pub struct Deposit<'info> {
     #[account(mut, token::mint = gsol_mint)]
     pub mint_gsol_to: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by Sunrise CPI.
+    pub gsol_mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    /// CHECK: The registered Marinade state.
+    pub marinade_state: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub liq_pool_mint: Box<Account<'info, Mint>>,
     #[account(
@@ -319,14 +691,6 @@ pub struct Deposit<'info> {
     /// CHECK: The vault authority PDA with verified seeds.
     pub vault_authority: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    /// Verified in CPI to Sunrise program.
-    pub gsol_mint: Box<Account<'info, Mint>>,
-    /// CHECK: Checked by Sunrise CPI.
-    pub gsol_mint_authority: UncheckedAccount<'info>,
-    /// CHECK: Checked by Sunrise CPI.
-    pub sysvar_instructions: UncheckedAccount<'info>,
-
     #[account(mut)]
     /// CHECK: Checked by Marinade CPI.
     pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
@@ -340,9 +704,16 @@ pub struct Deposit<'info> {
     pub system_program: UncheckedAccount<'info>,
     /// CHECK: Checked by Marinade CPI.
     pub token_program: UncheckedAccount<'info>,
-
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
     pub marinade_program: Program<'info, Marinade>,
+
+    /// The beam's yield breakdown, updated with the liquidity added.
+    #[account(
+        mut,
+        has_one = state,
+        seeds = [state.key().as_ref(), constants::YIELD_BREAKDOWN],
+        bump
+    )]
+    pub yield_breakdown: Box<Account<'info, YieldBreakdown>>,
 }
 
 #[derive(Accounts)]
@@ -355,8 +726,6 @@ pub struct Withdraw<'info> {
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
-    pub marinade_state: Box<Account<'info, MarinadeState>>,
-    #[account(mut)]
     /// CHECK: The main Sunrise beam state.
     pub sunrise_state: UncheckedAccount<'info>,
 
@@ -365,6 +734,17 @@ pub struct Withdraw<'info> {
     #[account(mut, token::mint = gsol_mint)]
     pub gsol_token_account: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+
     #[account(mut)]
     pub liq_pool_mint: Box<Account<'info, Mint>>,
     #[account(
@@ -401,15 +781,16 @@ pub struct Withdraw<'info> {
     pub system_program: UncheckedAccount<'info>,
     /// CHECK: Checked by Marinade CPI.
     pub token_program: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    /// Verified in CPI to Sunrise program.
-    pub gsol_mint: Box<Account<'info, Mint>>,
-    /// CHECK: Checked by Sunrise CPI.
-    pub sysvar_instructions: UncheckedAccount<'info>,
-
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
     pub marinade_program: Program<'info, Marinade>,
+
+    /// The beam's yield breakdown, updated with the liquidity removed.
+    #[account(
+        mut,
+        has_one = state,
+        seeds = [state.key().as_ref(), constants::YIELD_BREAKDOWN],
+        bump
+    )]
+    pub yield_breakdown: Box<Account<'info, YieldBreakdown>>,
 }
 
 #[derive(Accounts)]
@@ -429,11 +810,6 @@ pub struct Burn<'info> {
     #[account(mut, token::mint = gsol_mint)]
     pub gsol_token_account: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: Checked by Marinade CPI.
-    pub system_program: UncheckedAccount<'info>,
-    /// CHECK: Checked by Marinade CPI.
-    pub token_program: UncheckedAccount<'info>,
-
     #[account(mut)]
     /// Verified in CPI to Sunrise program.
     pub gsol_mint: Box<Account<'info, Mint>>,
@@ -441,6 +817,11 @@ pub struct Burn<'info> {
     pub sysvar_instructions: UncheckedAccount<'info>,
 
     pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    /// CHECK: Checked by Marinade CPI.
+    pub system_program: UncheckedAccount<'info>,
+    /// CHECK: Checked by Marinade CPI.
+    pub token_program: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -452,20 +833,30 @@ pub struct ExtractYield<'info> {
     bump
     )]
     pub state: Box<Account<'info, State>>,
-    #[account(mut)]
-    pub marinade_state: Box<Account<'info, MarinadeState>>,
     #[account(
         mut, // Update the extracted yield on the state's epoch report.
         has_one = yield_account
     )]
     pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
-
     #[account(mut)]
     /// CHECK: Matches the yield account key stored in the state.
     pub yield_account: UncheckedAccount<'info>,
+    #[account(mut, address = sunrise_state.treasury)]
+    /// CHECK: Matches the treasury key stored in the state.
+    /// Only needed while the state charges a protocol fee.
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
 
     #[account(mut)]
     pub liq_pool_mint: Box<Account<'info, Mint>>,
@@ -503,12 +894,16 @@ pub struct ExtractYield<'info> {
     pub system_program: UncheckedAccount<'info>,
     /// CHECK: Checked by Marinade CPI.
     pub token_program: UncheckedAccount<'info>,
-
-    /// CHECK: Checked by Sunrise CPI.
-    pub sysvar_instructions: UncheckedAccount<'info>,
-
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
     pub marinade_program: Program<'info, Marinade>,
+
+    /// The beam's yield breakdown, updated with the liquidity extracted.
+    #[account(
+        mut,
+        has_one = state,
+        seeds = [state.key().as_ref(), constants::YIELD_BREAKDOWN],
+        bump
+    )]
+    pub yield_breakdown: Box<Account<'info, YieldBreakdown>>,
 }
 
 #[derive(Accounts)]
@@ -520,7 +915,6 @@ pub struct UpdateEpochReport<'info> {
     bump
     )]
     pub state: Box<Account<'info, State>>,
-    pub marinade_state: Box<Account<'info, MarinadeState>>,
     #[account(
     mut, // Update the extracted yield on the state's epoch report.
     )]
@@ -529,6 +923,16 @@ pub struct UpdateEpochReport<'info> {
     /// Required to update the core state epoch report
     /// Verified in CPI to Sunrise program.
     pub gsol_mint: Account<'info, Mint>,
+    #[account(mut)]
+    /// CHECK: Updated with the epoch report - verified in CPI to Sunrise program.
+    pub backing_oracle: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by Sunrise CPI.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
 
     #[account(mut)]
     pub liq_pool_mint: Box<Account<'info, Mint>>,
@@ -558,14 +962,186 @@ pub struct UpdateEpochReport<'info> {
     /// CHECK: Checked by Marinade CPI.
     pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
 
+    /// The beam's yield breakdown, updated with the liquidity held.
+    #[account(
+        mut,
+        has_one = state,
+        seeds = [state.key().as_ref(), constants::YIELD_BREAKDOWN],
+        bump
+    )]
+    pub yield_breakdown: Box<Account<'info, YieldBreakdown>>,
+}
+
+#[derive(Accounts)]
+pub struct InitYieldBreakdown<'info> {
+    #[account(has_one = update_authority, has_one = marinade_state)]
+    pub state: Box<Account<'info, State>>,
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+
+    #[account(
+        init,
+        space = YieldBreakdown::SPACE,
+        payer = update_authority,
+        seeds = [state.key().as_ref(), constants::YIELD_BREAKDOWN],
+        bump
+    )]
+    pub yield_breakdown: Box<Account<'info, YieldBreakdown>>,
+
+    #[account(address = marinade_state.liq_pool.lp_mint)]
+    pub liq_pool_mint: Box<Account<'info, Mint>>,
+    #[account(
+        token::mint = liq_pool_mint,
+        token::authority = vault_authority,
+    )]
+    pub liq_pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            constants::VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [marinade_state.key().as_ref(), constants::LIQ_POOL_SOL_LEG],
+        seeds::program = marinade_cpi::ID,
+        bump = marinade_state.liq_pool.sol_leg_bump_seed
+    )]
+    /// CHECK: The Marinade liq_pool SOL leg PDA with verified seeds.
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+    #[account(address = marinade_state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct View<'info> {
+    #[account(
+        has_one = sunrise_state,
+        has_one = marinade_state,
+        seeds = [constants::STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+
+    #[account(address = marinade_state.liq_pool.lp_mint)]
+    pub liq_pool_mint: Box<Account<'info, Mint>>,
+    #[account(
+        token::mint = liq_pool_mint,
+        token::authority = vault_authority,
+    )]
+    pub liq_pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            constants::VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [marinade_state.key().as_ref(), constants::LIQ_POOL_SOL_LEG],
+        seeds::program = marinade_cpi::ID,
+        bump = marinade_state.liq_pool.sol_leg_bump_seed
+    )]
+    /// CHECK: The Marinade liq_pool SOL leg PDA with verified seeds.
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+    #[account(address = marinade_state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RebalanceLiquidity<'info> {
+    #[account(
+        has_one = sunrise_state,
+        has_one = marinade_state,
+        seeds = [constants::STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub marinade_state: Box<Account<'info, MarinadeState>>,
+    #[account(
+        mut, // Transfer gSOL between beams.
+        has_one = gsol_mint
+    )]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    /// The gSOL mint, whose supply determines the target liquidity.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub liq_pool_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = liq_pool_mint,
+        token::authority = vault_authority,
+    )]
+    pub liq_pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut, // Temporarily holds the SOL leg of the removed liquidity.
+        seeds = [
+            state.key().as_ref(),
+            constants::VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Receives the mSOL leg of the removed liquidity, and the mSOL minted for the SOL leg.
+    #[account(mut, address = state.msol_token_account)]
+    pub transfer_msol_to: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    /// CHECK: Checked by Marinade CPI.
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Checked by Marinade CPI.
+    pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by Marinade CPI.
+    pub msol_mint: UncheckedAccount<'info>,
+    /// CHECK: Checked by Marinade CPI.
+    pub msol_mint_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by Marinade CPI.
+    pub reserve_pda: UncheckedAccount<'info>,
+    /// CHECK: Checked by Marinade CPI.
+    pub system_program: UncheckedAccount<'info>,
+    /// CHECK: Checked by Marinade CPI.
+    pub token_program: UncheckedAccount<'info>,
+
     /// CHECK: Checked by Sunrise CPI.
     pub sysvar_instructions: UncheckedAccount<'info>,
 
     pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+    pub marinade_program: Program<'info, Marinade>,
+
+    /// The beam's yield breakdown, updated with the liquidity removed.
+    #[account(
+        mut,
+        has_one = state,
+        seeds = [state.key().as_ref(), constants::YIELD_BREAKDOWN],
+        bump
+    )]
+    pub yield_breakdown: Box<Account<'info, YieldBreakdown>>,
 }
 
+/// The accounts of the instructions of the standard interface that the beam does not support.
 #[derive(Accounts)]
-pub struct Noop {}
+pub struct Unsupported {}
 
 #[error_code]
 pub enum MarinadeLpBeamError {
@@ -575,4 +1151,12 @@ pub enum MarinadeLpBeamError {
     Unimplemented,
     #[msg("The yield balance is insufficient to extract yield")]
     InsufficientYieldBalance,
+    #[msg("The withdrawal paid out fewer lamports than the requested minimum")]
+    WithdrawalBelowMinimum,
+    #[msg("The liquidity is not above its target")]
+    LiquidityWithinTarget,
+    #[msg("The target liquidity must not exceed 10000 basis points")]
+    InvalidTargetLiquidity,
+    #[msg("The state has already been resized")]
+    StateAlreadyResized,
 }
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use beam_math::{proportional_i128, MathError, MathResult, Rounding};
+use marinade_common::liquidity_beam;
 use marinade_common::vault_authority_seed::HasVaultAuthority;
 
 #[account]
@@ -22,6 +25,9 @@ pub struct State {
 
     /// The token-account that receives msol when withdrawing liquidity.
     pub msol_token_account: Pubkey,
+
+    /// The target size of the liquidity buffer, as a share of the gSOL supply in basis points.
+    pub target_liquidity_bps: u16,
 }
 
 impl HasVaultAuthority for State {
@@ -37,7 +43,16 @@ impl State {
         32 + /*sunrise_state*/
         1  + /*vault_authority_bump*/
         32 + /*msol_recipient_beam*/
-        32; /*msol_token_account*/
+        32 + /*msol_token_account*/
+        2; /*target_liquidity_bps*/
+
+    /// The space of a state created before `target_liquidity_bps` was added.
+    pub const SPACE_WITHOUT_TARGET_LIQUIDITY: usize = State::SPACE - 2;
+
+    /// The target liquidity in lamports for a gSOL supply of `gsol_supply`.
+    pub fn target_liquidity(&self, gsol_supply: u64) -> MathResult<u64> {
+        liquidity_beam::target_liquidity(gsol_supply, self.target_liquidity_bps)
+    }
 }
 
 // Anchor-ts only supports deserialization(in instruction arguments) for types
@@ -51,6 +66,7 @@ pub struct StateEntry {
     pub vault_authority_bump: u8,
     pub msol_recipient_beam: Pubkey,
     pub msol_token_account: Pubkey,
+    pub target_liquidity_bps: u16,
 }
 
 impl From<StateEntry> for State {
@@ -62,6 +78,297 @@ impl From<StateEntry> for State {
             vault_authority_bump: se.vault_authority_bump,
             msol_recipient_beam: se.msol_recipient_beam,
             msol_token_account: se.msol_token_account,
+            target_liquidity_bps: se.target_liquidity_bps,
         }
     }
 }
+
+/// Splits the yield earned on the beam's liquidity pool position into
+/// liquidity pool fee income and mSOL staking appreciation.
+///
+/// The account tracks the cost basis of the position: the SOL value of the liq_pool tokens held,
+/// as of the last checkpoint, adjusted by the value of liquidity deposited and withdrawn since.
+/// Flows are recorded at the value they move, so they are never counted as yield.
+/// A checkpoint, taken when reporting and extracting yield, attributes the change in value
+/// over the basis and moves the basis to the current value of the position.
+#[account]
+pub struct YieldBreakdown {
+    /// The beam state this breakdown belongs to.
+    pub state: Pubkey,
+
+    /// The liq_pool tokens held by the beam, as recorded by the beam.
+    pub liq_pool_tokens: u64,
+
+    /// The cost basis of the liq_pool tokens held, in lamports.
+    pub lamports_value: u64,
+
+    /// The value of one mSOL in lamports at the last checkpoint.
+    pub msol_price: u64,
+
+    /// The epoch of the last checkpoint.
+    pub epoch: u64,
+
+    /// The total yield earned from liquidity pool swap fees, in lamports.
+    pub fee_income: i64,
+
+    /// The total yield earned from the appreciation of the mSOL leg, in lamports.
+    pub staking_appreciation: i64,
+}
+
+impl YieldBreakdown {
+    pub const SPACE: usize = 8 +  /*discriminator*/
+        32 + /*state*/
+        8 +  /*liq_pool_tokens*/
+        8 +  /*lamports_value*/
+        8 +  /*msol_price*/
+        8 +  /*epoch*/
+        8 +  /*fee_income*/
+        8; /*staking_appreciation*/
+
+    /// Adds `liq_pool_tokens` bought for `lamports` to the position.
+    pub fn record_deposit(&mut self, liq_pool_tokens: u64, lamports: u64) -> MathResult<()> {
+        self.liq_pool_tokens = self
+            .liq_pool_tokens
+            .checked_add(liq_pool_tokens)
+            .ok_or(MathError::Overflow)?;
+        self.lamports_value = self
+            .lamports_value
+            .checked_add(lamports)
+            .ok_or(MathError::Overflow)?;
+        Ok(())
+    }
+
+    /// Removes `liq_pool_tokens` worth `lamports` from the position.
+    ///
+    /// The yield earned on the remaining tokens stays in the position, to be attributed at the next checkpoint.
+    pub fn record_withdrawal(&mut self, liq_pool_tokens: u64, lamports: u64) {
+        self.liq_pool_tokens = self.liq_pool_tokens.saturating_sub(liq_pool_tokens);
+        self.lamports_value = self.lamports_value.saturating_sub(lamports);
+    }
+
+    /// Attributes the yield earned since the last checkpoint and moves the basis to the current position.
+    ///
+    /// The staking appreciation is the change in the mSOL price applied to the current mSOL leg,
+    /// and the fee income is the remaining change in value over the basis.
+    /// Liquidity added to or removed from the position without being recorded
+    /// (e.g. by the Marinade-SP beam's `fund_liquidity_beam` crank) is valued at the current price.
+    /// Returns the fee income and staking appreciation earned since the last checkpoint.
+    pub fn checkpoint(
+        &mut self,
+        liq_pool_tokens: u64,
+        lamports_value: u64,
+        msol_leg: u64,
+        msol_price: u64,
+        epoch: u64,
+    ) -> MathResult<(i64, i64)> {
+        let basis_value = if liq_pool_tokens >= self.liq_pool_tokens {
+            let unrecorded_value = proportional_i128(
+                lamports_value as i128,
+                (liq_pool_tokens - self.liq_pool_tokens) as i128,
+                liq_pool_tokens as i128,
+                Rounding::Up,
+            )?;
+            (self.lamports_value as i128) + unrecorded_value
+        } else {
+            proportional_i128(
+                self.lamports_value as i128,
+                liq_pool_tokens as i128,
+                self.liq_pool_tokens as i128,
+                Rounding::Up,
+            )?
+        };
+        let total_yield = (lamports_value as i128) - basis_value;
+        let staking_appreciation = if self.msol_price == 0 {
+            // No mSOL price to compare to (e.g. the first checkpoint).
+            0
+        } else {
+            proportional_i128(
+                msol_leg as i128,
+                (msol_price as i128) - (self.msol_price as i128),
+                LAMPORTS_PER_SOL as i128,
+                Rounding::Down,
+            )?
+        };
+        let fee_income =
+            i64::try_from(total_yield - staking_appreciation).map_err(|_| MathError::Overflow)?;
+        let staking_appreciation =
+            i64::try_from(staking_appreciation).map_err(|_| MathError::Overflow)?;
+
+        self.fee_income = self.fee_income.saturating_add(fee_income);
+        self.staking_appreciation = self
+            .staking_appreciation
+            .saturating_add(staking_appreciation);
+        self.liq_pool_tokens = liq_pool_tokens;
+        self.lamports_value = lamports_value;
+        self.msol_price = msol_price;
+        self.epoch = epoch;
+
+        Ok((fee_income, staking_appreciation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+    use liquidity_beam::LiquidityBeamState;
+
+    const EPOCH: u64 = 100;
+
+    fn breakdown() -> YieldBreakdown {
+        YieldBreakdown {
+            state: Pubkey::new_unique(),
+            liq_pool_tokens: 0,
+            lamports_value: 0,
+            msol_price: 0,
+            epoch: 0,
+            fee_income: 0,
+            staking_appreciation: 0,
+        }
+    }
+
+    #[test]
+    fn test_target_liquidity() {
+        let mut state = State::from(StateEntry {
+            update_authority: Pubkey::new_unique(),
+            marinade_state: Pubkey::new_unique(),
+            sunrise_state: Pubkey::new_unique(),
+            vault_authority_bump: 255,
+            msol_recipient_beam: Pubkey::new_unique(),
+            msol_token_account: Pubkey::new_unique(),
+            target_liquidity_bps: 1_000,
+        });
+        assert_eq!(state.target_liquidity(1_005), Ok(100));
+
+        state.target_liquidity_bps = beam_math::MAX_BPS;
+        assert_eq!(state.target_liquidity(u64::MAX), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_liquidity_beam_state_mirrors_state() {
+        let state = State::from(StateEntry {
+            update_authority: Pubkey::new_unique(),
+            marinade_state: Pubkey::new_unique(),
+            sunrise_state: Pubkey::new_unique(),
+            vault_authority_bump: 254,
+            msol_recipient_beam: Pubkey::new_unique(),
+            msol_token_account: Pubkey::new_unique(),
+            target_liquidity_bps: 1_000,
+        });
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), State::SPACE);
+
+        let mirror = LiquidityBeamState::try_deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(LiquidityBeamState::DISCRIMINATOR, State::DISCRIMINATOR);
+        assert_eq!(LiquidityBeamState::owner(), crate::ID);
+        assert_eq!(
+            mirror,
+            LiquidityBeamState {
+                update_authority: state.update_authority,
+                marinade_state: state.marinade_state,
+                sunrise_state: state.sunrise_state,
+                vault_authority_bump: state.vault_authority_bump,
+                msol_recipient_beam: state.msol_recipient_beam,
+                msol_token_account: state.msol_token_account,
+                target_liquidity_bps: state.target_liquidity_bps,
+            }
+        );
+    }
+
+    #[test]
+    fn test_first_checkpoint_attributes_no_staking_appreciation() {
+        let mut breakdown = breakdown();
+        breakdown.record_deposit(1_000, 1_000).unwrap();
+
+        // 10 lamports of yield, with a 500 mSOL leg
+        let result = breakdown.checkpoint(1_000, 1_010, 500, LAMPORTS_PER_SOL, EPOCH);
+
+        assert_eq!(result, Ok((10, 0)));
+        assert_eq!(breakdown.lamports_value, 1_010);
+        assert_eq!(breakdown.msol_price, LAMPORTS_PER_SOL);
+        assert_eq!(breakdown.epoch, EPOCH);
+    }
+
+    #[test]
+    fn test_checkpoint_splits_fee_income_and_staking_appreciation() {
+        let mut breakdown = breakdown();
+        breakdown.record_deposit(1_000, 1_000).unwrap();
+        breakdown
+            .checkpoint(1_000, 1_000, 500, LAMPORTS_PER_SOL, EPOCH)
+            .unwrap();
+
+        // mSOL appreciates by 2%: 500 mSOL are worth 10 lamports more. The other 5 lamports are fees.
+        let result =
+            breakdown.checkpoint(1_000, 1_015, 500, LAMPORTS_PER_SOL * 102 / 100, EPOCH + 1);
+
+        assert_eq!(result, Ok((5, 10)));
+        assert_eq!(breakdown.fee_income, 5);
+        assert_eq!(breakdown.staking_appreciation, 10);
+    }
+
+    #[test]
+    fn test_deposits_between_checkpoints_are_not_yield() {
+        let mut breakdown = breakdown();
+        breakdown.record_deposit(1_000, 1_000).unwrap();
+        breakdown
+            .checkpoint(1_000, 1_000, 0, LAMPORTS_PER_SOL, EPOCH)
+            .unwrap();
+
+        // The pool has earned 10% in fees, so a deposit of 1_100 lamports buys 1_000 tokens.
+        breakdown.record_deposit(1_000, 1_100).unwrap();
+        let result = breakdown.checkpoint(2_000, 2_200, 0, LAMPORTS_PER_SOL, EPOCH + 1);
+
+        // Only the yield earned on the first 1_000 tokens is attributed.
+        assert_eq!(result, Ok((100, 0)));
+        assert_eq!(breakdown.liq_pool_tokens, 2_000);
+        assert_eq!(breakdown.lamports_value, 2_200);
+    }
+
+    #[test]
+    fn test_withdrawals_between_checkpoints_are_not_yield() {
+        let mut breakdown = breakdown();
+        breakdown.record_deposit(1_000, 1_000).unwrap();
+        breakdown
+            .checkpoint(1_000, 1_000, 0, LAMPORTS_PER_SOL, EPOCH)
+            .unwrap();
+
+        // Half of the tokens are withdrawn after the pool has earned 10% in fees.
+        breakdown.record_withdrawal(500, 550);
+        let result = breakdown.checkpoint(500, 550, 0, LAMPORTS_PER_SOL, EPOCH + 1);
+
+        // The yield earned on the withdrawn tokens stays attributed.
+        assert_eq!(result, Ok((100, 0)));
+        assert_eq!(breakdown.lamports_value, 550);
+    }
+
+    #[test]
+    fn test_unrecorded_liquidity_is_valued_at_the_current_price() {
+        let mut breakdown = breakdown();
+        breakdown.record_deposit(1_000, 1_000).unwrap();
+        breakdown
+            .checkpoint(1_000, 1_000, 0, LAMPORTS_PER_SOL, EPOCH)
+            .unwrap();
+
+        // 1_000 tokens are added directly to the vault after the pool has earned 10% in fees.
+        let result = breakdown.checkpoint(2_000, 2_200, 0, LAMPORTS_PER_SOL, EPOCH + 1);
+
+        assert_eq!(result, Ok((100, 0)));
+    }
+
+    #[test]
+    fn test_loss_is_negative_yield() {
+        let mut breakdown = breakdown();
+        breakdown.record_deposit(1_000, 1_000).unwrap();
+        breakdown
+            .checkpoint(1_000, 1_000, 1_000, LAMPORTS_PER_SOL, EPOCH)
+            .unwrap();
+
+        // mSOL depegs by 1%, and the pool earns 2 lamports in fees.
+        let result =
+            breakdown.checkpoint(1_000, 992, 1_000, LAMPORTS_PER_SOL * 99 / 100, EPOCH + 1);
+
+        assert_eq!(result, Ok((2, -10)));
+    }
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use marinade_common::{calc_lamports_from_msol_amount, proportional};
+use beam_math::{proportional_i128, MathError, Rounding};
+use marinade_common::calc_lamports_from_msol_amount;
 use marinade_cpi::State as MarinadeState;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -18,8 +19,8 @@ impl LiquidityPoolBalance {
     }
 
     pub fn value_of(&self, liq_pool_token: u64) -> Result<Self> {
-        let lamports = proportional(self.lamports, liq_pool_token as i128, self.liq_pool_token);
-        let msol = proportional(self.msol, liq_pool_token as i128, self.liq_pool_token);
+        let lamports = proportional(self.lamports, liq_pool_token as i128, self.liq_pool_token)?;
+        let msol = proportional(self.msol, liq_pool_token as i128, self.liq_pool_token)?;
         Ok(LiquidityPoolBalance {
             lamports,
             msol,
@@ -28,27 +29,31 @@ impl LiquidityPoolBalance {
     }
 
     // The value of both legs of the liquidity pool balance in SOL
-    pub fn sol_value(&self, marinade_state: &MarinadeState) -> u64 {
+    pub fn sol_value(&self, marinade_state: &MarinadeState) -> Result<u64> {
         let lamports = self.lamports;
-        let msol = calc_lamports_from_msol_amount(marinade_state, self.msol as u64);
-        lamports.checked_add(msol as i128).expect("sol_value") as u64
+        let msol = calc_lamports_from_msol_amount(marinade_state, self.msol as u64)?;
+        let sol_value = lamports
+            .checked_add(msol as i128)
+            .ok_or(MathError::Overflow)?;
+        Ok(sol_value as u64)
     }
 
     // if this balance in lamports is smaller than other_lamports, return this,
     // otherwise return a liquidity pool balance with lamports = other_lamports
     // and liq_pool_token = the amount of liq_pool_token that would be needed to withdraw
     // other_lamports from the liquidity pool
-    pub fn min_lamports(&self, other_lamports: i128) -> Self {
+    pub fn min_lamports(&self, other_lamports: i128) -> Result<Self> {
         if self.lamports < other_lamports {
-            return *self;
+            return Ok(*self);
         }
-        let other_liq_pool_token = proportional(self.liq_pool_token, other_lamports, self.lamports);
-        let other_msol = proportional(self.msol, other_lamports, self.lamports);
-        Self {
+        let other_liq_pool_token =
+            proportional(self.liq_pool_token, other_lamports, self.lamports)?;
+        let other_msol = proportional(self.msol, other_lamports, self.lamports)?;
+        Ok(Self {
             lamports: other_lamports,
             msol: other_msol,
             liq_pool_token: other_liq_pool_token,
-        }
+        })
     }
 
     // returns a new balance that is the result of subtracting other_lamports from this balance
@@ -56,10 +61,10 @@ impl LiquidityPoolBalance {
         let new_lamports = self
             .lamports
             .checked_sub(other_lamports)
-            .expect("checked_sub_lamports");
-        let new_liq_pool_token = proportional(self.liq_pool_token, new_lamports, self.lamports);
+            .ok_or(MathError::Overflow)?;
+        let new_liq_pool_token = proportional(self.liq_pool_token, new_lamports, self.lamports)?;
 
-        let new_msol = proportional(self.msol, new_lamports, self.lamports);
+        let new_msol = proportional(self.msol, new_lamports, self.lamports)?;
         Ok(Self {
             lamports: new_lamports,
             msol: new_msol,
@@ -78,3 +83,12 @@ impl LiquidityPoolBalance {
         }
     }
 }
+
+fn proportional(amount: i128, numerator: i128, denominator: i128) -> Result<i128> {
+    Ok(proportional_i128(
+        amount,
+        numerator,
+        denominator,
+        Rounding::Down,
+    )?)
+}
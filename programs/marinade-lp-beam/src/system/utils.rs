@@ -1,16 +1,21 @@
 use super::balance::LiquidityPoolBalance;
-use crate::state::State;
+use crate::state::{State, YieldBreakdown};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
 use anchor_spl::token::{Mint, TokenAccount};
-use marinade_common::{
-    calc_lamports_from_msol_amount, proportional, proportional_with_rounding, RoundingMode,
-};
+use beam_math::{proportional, proportional_with_rounding, MathError, Rounding};
+use marinade_common::calc_lamports_from_msol_amount;
 use marinade_cpi::State as MarinadeState;
+use std::cmp::max;
 use sunrise_core::BeamError;
 
-// estimated 0.3% unstake fee
-// const WITHDRAWAL_FRACTION : f64 =  0.997;
-const WITHDRAWAL_FRACTION: f64 = 1.0;
+/// The unstake fee deducted from the excess balance when estimating the extractable yield.
+const WITHDRAWAL_FEE_BPS: u16 = 0;
+
+/// Shortfalls up to this size are attributed to rounding in the liquidity pool maths,
+/// and are not reported as a deficit.
+const ROUNDING_TOLERANCE_LAMPORTS: i128 = 2;
 
 /// Calculates the amount that can be extracted as yield, in lamports.
 pub fn calculate_extractable_yield(
@@ -46,7 +51,7 @@ pub fn calculate_extractable_yield(
         liq_pool_sol_leg_pda,
         liq_pool_msol_leg,
         staked_sol,
-    );
+    )?;
 
     msg!("staked_balance: {:?}", staked_balance);
 
@@ -75,11 +80,88 @@ pub fn calculate_extractable_yield(
 // (However, in reality, this beam should rarely be extracted from, as it is
 // included as a buffer to allow for fee-less gSOL withdrawals)
 // Subtract fee TODO can we do better than an estimate?
-pub fn get_extractable_yield_from_excess_balance(excess_balance: u64) -> u64 {
-    let extractable_lamports = (excess_balance as f64) * WITHDRAWAL_FRACTION;
+pub fn get_extractable_yield_from_excess_balance(excess_balance: u64) -> Result<u64> {
+    let extractable_lamports = beam_math::net_of_fee_bps(excess_balance, WITHDRAWAL_FEE_BPS)?;
     msg!("Excess balance: {:?}", excess_balance);
     msg!("Extractable yield: {}", extractable_lamports);
-    extractable_lamports as u64
+    Ok(extractable_lamports)
+}
+
+/// Converts the SOL leg of the beam's surplus liquidity pool balance into the surplus reported to sunrise.
+/// A positive balance is reduced by fee, and a negative balance beyond the rounding tolerance is a deficit.
+pub fn get_surplus_from_balance(balance_lamports: i128) -> Result<i64> {
+    if balance_lamports >= 0 {
+        let extractable_lamports = get_extractable_yield_from_excess_balance(
+            u64::try_from(balance_lamports).map_err(|_| MathError::Overflow)?,
+        )?;
+        return i64::try_from(extractable_lamports).map_err(|_| MathError::Overflow.into());
+    }
+    if balance_lamports >= -ROUNDING_TOLERANCE_LAMPORTS {
+        return Ok(0);
+    }
+    msg!("Deficit: {}", -balance_lamports);
+    i64::try_from(balance_lamports).map_err(|_| MathError::Overflow.into())
+}
+
+// https://solanacookbook.com/references/programs.html#how-to-change-account-size
+pub fn resize_account<'a>(
+    target_account: &AccountInfo<'a>,
+    funding_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    new_size: usize,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let new_minimum_balance = rent.minimum_balance(new_size);
+
+    let lamports_diff = new_minimum_balance.saturating_sub(target_account.lamports());
+    invoke(
+        &system_instruction::transfer(funding_account.key, target_account.key, lamports_diff),
+        &[
+            funding_account.clone(),
+            target_account.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    target_account.realloc(new_size, false)?;
+
+    Ok(())
+}
+
+/// Checkpoints the beam's liquidity pool position in its yield breakdown,
+/// attributing the yield earned since the last checkpoint.
+pub fn checkpoint_yield_breakdown(
+    yield_breakdown: &mut YieldBreakdown,
+    marinade_state: &MarinadeState,
+    liq_pool_mint: &Mint,
+    liq_pool_token_account: &TokenAccount,
+    liq_pool_sol_leg_pda: &AccountInfo,
+    liq_pool_msol_leg: &TokenAccount,
+) -> Result<()> {
+    let balance = current_liq_pool_balance(
+        marinade_state,
+        liq_pool_mint,
+        liq_pool_token_account,
+        liq_pool_sol_leg_pda,
+        liq_pool_msol_leg,
+    )?;
+    let lamports_value = balance.sol_value(marinade_state)?;
+    let msol_price = calc_lamports_from_msol_amount(marinade_state, LAMPORTS_PER_SOL)?;
+
+    let (fee_income, staking_appreciation) = yield_breakdown.checkpoint(
+        liq_pool_token_account.amount,
+        lamports_value,
+        max(0, balance.msol) as u64,
+        msol_price,
+        Clock::get()?.epoch,
+    )?;
+
+    msg!(
+        "Fee income: {}, staking appreciation: {}",
+        fee_income,
+        staking_appreciation
+    );
+    Ok(())
 }
 
 // Prevent the compiler from enlarging the stack and potentially triggering an Access violation
@@ -104,7 +186,7 @@ pub fn current_liq_pool_balance(
 // Prevent the compiler from enlarging the stack and potentially triggering an Access violation
 #[inline(never)]
 /// Returns the liquidity pool balance for a given amount of lp tokens
-pub(super) fn liq_pool_balance_for_tokens(
+pub fn liq_pool_balance_for_tokens(
     tokens: u64,
     marinade_state: &MarinadeState,
     liq_pool_mint: &Mint,
@@ -127,11 +209,11 @@ pub(super) fn liq_pool_balance_for_tokens(
     msg!("LP for token: {:?}", sunrise_liq_pool_balance);
     msg!(
         "Total LP value: {:?}",
-        total_balance.sol_value(marinade_state)
+        total_balance.sol_value(marinade_state)?
     );
     msg!(
         "LP value: {:?}",
-        sunrise_liq_pool_balance.sol_value(marinade_state)
+        sunrise_liq_pool_balance.sol_value(marinade_state)?
     );
 
     Ok(sunrise_liq_pool_balance)
@@ -143,16 +225,22 @@ pub fn calculate_liq_pool_token_value_of_lamports(
     liq_pool_sol_leg_pda: &AccountInfo,
     liq_pool_msol_leg: &TokenAccount,
     lamports: u64,
-) -> u64 {
+) -> Result<u64> {
     let total_lamports = liq_pool_sol_leg_pda
         .lamports()
         .checked_sub(marinade_state.rent_exempt_for_token_acc)
         .unwrap();
     let total_msol = liq_pool_msol_leg.amount;
-    let lamports_value_of_msol = calc_lamports_from_msol_amount(marinade_state, total_msol);
-    let total_value_of_pool = total_lamports.checked_add(lamports_value_of_msol).unwrap();
+    let lamports_value_of_msol = calc_lamports_from_msol_amount(marinade_state, total_msol)?;
+    let total_value_of_pool = total_lamports
+        .checked_add(lamports_value_of_msol)
+        .ok_or(MathError::Overflow)?;
 
-    proportional(liq_pool_mint.supply, lamports, total_value_of_pool)
+    Ok(proportional(
+        liq_pool_mint.supply,
+        lamports,
+        total_value_of_pool,
+    )?)
 }
 
 fn total_liq_pool(
@@ -191,8 +279,8 @@ pub fn calculate_liq_pool_balance_required_to_withdraw_lamports(
         liq_pool_mint_supply,
         lamports,
         liq_pool_lamports,
-        RoundingMode::Up,
-    );
+        Rounding::Up,
+    )?;
 
     liq_pool_balance_for_tokens(
         liq_pool_tokens,
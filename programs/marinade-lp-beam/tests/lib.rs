@@ -0,0 +1,382 @@
+//! Integration tests of the Marinade liquidity pool beam against the Marinade state
+//! in `packages/tests/fixtures`.
+//!
+//! The beam sends the mSOL leg of its withdrawals to a Marinade beam, so one is registered
+//! alongside it, with no allocation of its own.
+
+use anchor_spl::token::spl_token;
+use beam_math::MAX_BPS;
+use beam_test_utils::beams::{initialize_marinade_beam, initialize_marinade_lp_beam};
+use beam_test_utils::{add_marinade, beam_program_test, error_code, suite, BeamTestContext};
+use marinade_lp_beam::MarinadeLpBeamError;
+use solana_program_test::{processor, tokio, ProgramTest};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use sunrise_client::{marinade_lp, marinade_sp, BeamValue, MarinadeClient, MarinadeLpClient};
+
+const DEPOSIT: u64 = 10 * LAMPORTS_PER_SOL;
+const WITHDRAWAL: u64 = LAMPORTS_PER_SOL;
+const BURN: u64 = LAMPORTS_PER_SOL;
+const TARGET_LIQUIDITY_BPS: u16 = 1_000;
+
+fn program_test() -> ProgramTest {
+    let mut program_test = beam_program_test!(marinade_lp_beam, marinade_lp::ID);
+    program_test.add_program(
+        "marinade_beam",
+        marinade_sp::ID,
+        processor!(marinade_beam::entry),
+    );
+    add_marinade(&mut program_test);
+    program_test
+}
+
+/// Start with an initialized beam that is not yet registered, the Marinade beam that receives
+/// its mSOL, and a funded staker with a gSOL token account.
+async fn setup() -> (
+    BeamTestContext,
+    MarinadeLpClient,
+    MarinadeClient,
+    Keypair,
+    Pubkey,
+) {
+    setup_in(BeamTestContext::start(program_test()).await).await
+}
+
+/// As `setup`, in an already started `context`.
+async fn setup_in(
+    mut context: BeamTestContext,
+) -> (
+    BeamTestContext,
+    MarinadeLpClient,
+    MarinadeClient,
+    Keypair,
+    Pubkey,
+) {
+    let msol_beam = initialize_marinade_beam(&mut context).await;
+    let beam = initialize_marinade_lp_beam(&mut context, &msol_beam, TARGET_LIQUIDITY_BPS).await;
+    let (staker, gsol_account) = context.create_staker().await;
+    (context, beam, msol_beam, staker, gsol_account)
+}
+
+/// Register the beam with the whole allocation, after the Marinade beam.
+async fn register(
+    context: &mut BeamTestContext,
+    beam: &MarinadeLpClient,
+    msol_beam: &MarinadeClient,
+) {
+    context
+        .register_beams(&[(msol_beam.state_address, 0), (beam.state_address, 100)])
+        .await;
+}
+
+/// The input that updates `state` to target `target_liquidity_bps`.
+fn state_entry(state: &marinade_lp::State, target_liquidity_bps: u16) -> marinade_lp::StateEntry {
+    marinade_lp::StateEntry {
+        update_authority: state.update_authority,
+        marinade_state: state.marinade_state,
+        sunrise_state: state.sunrise_state,
+        vault_authority_bump: state.vault_authority_bump,
+        msol_recipient_beam: state.msol_recipient_beam,
+        msol_token_account: state.msol_token_account,
+        target_liquidity_bps,
+    }
+}
+
+/// The target liquidity of a beam for a gSOL supply of `gsol_supply`.
+fn target_liquidity(gsol_supply: u64) -> u64 {
+    gsol_supply * TARGET_LIQUIDITY_BPS as u64 / MAX_BPS as u64
+}
+
+#[tokio::test]
+async fn cannot_deposit_before_registration() {
+    let (mut context, beam, _, staker, gsol_account) = setup().await;
+    suite::cannot_deposit_before_registration(&mut context, &beam, &staker, &gsol_account, DEPOSIT)
+        .await;
+}
+
+#[tokio::test]
+async fn can_deposit_and_withdraw() {
+    let (mut context, beam, msol_beam, staker, gsol_account) = setup().await;
+    register(&mut context, &beam, &msol_beam).await;
+    suite::can_deposit_and_withdraw(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        WITHDRAWAL,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn withdrawals_send_the_msol_leg_to_the_marinade_beam() {
+    let (mut context, beam, msol_beam, staker, gsol_account) = setup().await;
+    register(&mut context, &beam, &msol_beam).await;
+    suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    // A fresh deposit is fully backed, so there is no yield to report.
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    context.refresh_sunrise().await;
+    assert_eq!(
+        context.sunrise.state.epoch_report.beam_epoch_details[1].extractable_yield,
+        0
+    );
+
+    let msol_vault_balance = context.token_balance(&msol_beam.msol_vault()).await;
+    let withdraw = beam.withdraw(&staker.pubkey(), &gsol_account, WITHDRAWAL, 0);
+    context.send(&[withdraw], &[&staker]).await.unwrap();
+    assert!(context.token_balance(&msol_beam.msol_vault()).await > msol_vault_balance);
+
+    // The gSOL backed by the mSOL leg moves to the Marinade beam.
+    context.refresh_sunrise().await;
+    let allocations = &context.sunrise.state.allocations;
+    assert!(allocations[0].partial_gsol_supply > 0);
+    assert_eq!(
+        allocations[0].partial_gsol_supply + allocations[1].partial_gsol_supply,
+        DEPOSIT - WITHDRAWAL
+    );
+}
+
+#[tokio::test]
+async fn can_extract_the_yield_of_burned_gsol() {
+    let (mut context, beam, msol_beam, staker, gsol_account) = setup().await;
+    register(&mut context, &beam, &msol_beam).await;
+    suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    // Burning gSOL leaves the beam holding more value than its gSOL supply.
+    let vault_balance = context.token_balance(&beam.liq_pool_token_vault()).await;
+    let burn = beam.burn(&staker.pubkey(), &gsol_account, BURN);
+    context.send(&[burn], &[&staker]).await.unwrap();
+    assert_eq!(context.token_balance(&gsol_account).await, DEPOSIT - BURN);
+    assert_eq!(
+        context.token_balance(&beam.liq_pool_token_vault()).await,
+        vault_balance
+    );
+
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert_eq!(value.gsol_supply, DEPOSIT - BURN);
+    assert!(value.surplus > 0);
+
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    context.refresh_sunrise().await;
+    let epoch = context.epoch().await;
+    let report = &context.sunrise.state.epoch_report.beam_epoch_details[1];
+    assert_eq!(report.epoch, epoch);
+    assert_eq!(report.extractable_yield, value.surplus as u64);
+
+    let yield_account = context.sunrise.state.yield_account;
+    let yield_balance = context.balance(&yield_account).await;
+    let msol_vault_balance = context.token_balance(&msol_beam.msol_vault()).await;
+    let extract_yield = beam.extract_yield(&context.payer());
+    context.send(&[extract_yield], &[]).await.unwrap();
+
+    // The SOL leg of the surplus is extracted, and the mSOL leg goes to the Marinade beam.
+    context.refresh_sunrise().await;
+    let report = &context.sunrise.state.epoch_report.beam_epoch_details[1];
+    let extracted_yield = report.extracted_yield;
+    assert!(extracted_yield > 0);
+    assert_eq!(
+        context.balance(&yield_account).await,
+        yield_balance + extracted_yield
+    );
+    assert!(context.token_balance(&msol_beam.msol_vault()).await > msol_vault_balance);
+}
+
+#[tokio::test]
+async fn can_extract_yield_without_a_treasury() {
+    let context = BeamTestContext::start_without_treasury(program_test()).await;
+    let (mut context, beam, msol_beam, staker, gsol_account) = setup_in(context).await;
+    register(&mut context, &beam, &msol_beam).await;
+    suite::can_extract_yield_without_a_treasury(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        BURN,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn tracks_the_cost_basis_of_deposits_and_withdrawals() {
+    let (mut context, beam, msol_beam, staker, gsol_account) = setup().await;
+    register(&mut context, &beam, &msol_beam).await;
+
+    suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+    let breakdown: marinade_lp::YieldBreakdown = context.fetch(&beam.yield_breakdown()).await;
+    assert_eq!(breakdown.state, beam.state_address);
+    assert_eq!(
+        breakdown.liq_pool_tokens,
+        context.token_balance(&beam.liq_pool_token_vault()).await
+    );
+    assert_eq!(breakdown.lamports_value, DEPOSIT);
+
+    let withdraw = beam.withdraw(&staker.pubkey(), &gsol_account, WITHDRAWAL, 0);
+    context.send(&[withdraw], &[&staker]).await.unwrap();
+    let breakdown: marinade_lp::YieldBreakdown = context.fetch(&beam.yield_breakdown()).await;
+    assert_eq!(
+        breakdown.liq_pool_tokens,
+        context.token_balance(&beam.liq_pool_token_vault()).await
+    );
+
+    // Neither the deposit nor the withdrawal is yield, up to rounding in the liquidity pool maths.
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    let breakdown: marinade_lp::YieldBreakdown = context.fetch(&beam.yield_breakdown()).await;
+    assert_eq!(breakdown.epoch, context.epoch().await);
+    assert!(breakdown.fee_income.abs() <= 2);
+    assert_eq!(breakdown.staking_appreciation, 0);
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert_eq!(breakdown.lamports_value, value.value);
+}
+
+#[tokio::test]
+async fn cannot_target_more_liquidity_than_the_gsol_supply() {
+    let (mut context, beam, ..) = setup().await;
+
+    let update = beam.update(state_entry(&beam.state, MAX_BPS + 1));
+    let error = context.send(&[update], &[]).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(MarinadeLpBeamError::InvalidTargetLiquidity))
+    );
+}
+
+#[tokio::test]
+async fn can_resize_a_state_created_without_a_target_liquidity() {
+    let (mut context, beam, ..) = setup().await;
+    let rent = context.rent().await;
+
+    // Shrink the state to its size before `target_liquidity_bps` was added.
+    let mut account = context.account(&beam.state_address).await.unwrap();
+    account
+        .data
+        .truncate(marinade_lp::State::SPACE_WITHOUT_TARGET_LIQUIDITY);
+    account.lamports = rent.minimum_balance(account.data.len());
+    context
+        .ctx
+        .set_account(&beam.state_address, &account.into());
+
+    // It cannot be deserialized, so cannot be updated.
+    let update = beam.update(state_entry(&beam.state, 500));
+    assert!(context.send(&[update.clone()], &[]).await.is_err());
+
+    let sunrise_state = beam.state.sunrise_state;
+    let resize = marinade_lp::resize_state(&marinade_lp::ID, &context.payer(), &sunrise_state, 400);
+    context.send(&[resize], &[]).await.unwrap();
+
+    let state: marinade_lp::State = context.fetch(&beam.state_address).await;
+    assert_eq!(state.target_liquidity_bps, 400);
+    assert_eq!(state.msol_token_account, beam.state.msol_token_account);
+    assert_eq!(
+        context.balance(&beam.state_address).await,
+        rent.minimum_balance(marinade_lp::State::SPACE)
+    );
+    context.send(&[update], &[]).await.unwrap();
+    let state: marinade_lp::State = context.fetch(&beam.state_address).await;
+    assert_eq!(state.target_liquidity_bps, 500);
+
+    let resize = marinade_lp::resize_state(&marinade_lp::ID, &context.payer(), &sunrise_state, 600);
+    let error = context.send(&[resize], &[]).await.unwrap_err();
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(MarinadeLpBeamError::StateAlreadyResized))
+    );
+}
+
+#[tokio::test]
+async fn can_rebalance_surplus_liquidity_to_the_marinade_beam() {
+    let (mut context, beam, msol_beam, staker, gsol_account) = setup().await;
+    register(&mut context, &beam, &msol_beam).await;
+    suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+    let vault_balance = context.token_balance(&beam.liq_pool_token_vault()).await;
+    let msol_vault_balance = context.token_balance(&msol_beam.msol_vault()).await;
+
+    let rebalance = beam.rebalance_liquidity();
+    context.send(&[rebalance.clone()], &[]).await.unwrap();
+
+    // The beam keeps its target share of the gSOL supply, and the surplus moves to the Marinade beam.
+    context.refresh_sunrise().await;
+    let allocations = &context.sunrise.state.allocations;
+    assert_eq!(
+        allocations[1].partial_gsol_supply,
+        target_liquidity(DEPOSIT)
+    );
+    assert_eq!(
+        allocations[0].partial_gsol_supply,
+        DEPOSIT - target_liquidity(DEPOSIT)
+    );
+    let vault_balance_after = context.token_balance(&beam.liq_pool_token_vault()).await;
+    assert!(vault_balance_after < vault_balance);
+    assert!(context.token_balance(&msol_beam.msol_vault()).await > msol_vault_balance);
+    let breakdown: marinade_lp::YieldBreakdown = context.fetch(&beam.yield_breakdown()).await;
+    assert_eq!(breakdown.liq_pool_tokens, vault_balance_after);
+
+    // There is no surplus left to rebalance.
+    let error = context.send(&[rebalance], &[]).await.unwrap_err();
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(MarinadeLpBeamError::LiquidityWithinTarget))
+    );
+}
+
+#[tokio::test]
+async fn rebalancing_transfers_only_the_gsol_backed_by_the_liquidity_removed() {
+    let (mut context, beam, msol_beam, staker, gsol_account) = setup().await;
+    register(&mut context, &beam, &msol_beam).await;
+    suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    // Leave the vault with a quarter of its liquidity, worth less than the surplus.
+    let vault = beam.liq_pool_token_vault();
+    let mut account = context.account(&vault).await.unwrap();
+    let mut token_account = spl_token::state::Account::unpack(&account.data).unwrap();
+    token_account.amount /= 4;
+    spl_token::state::Account::pack(token_account, &mut account.data).unwrap();
+    context.ctx.set_account(&vault, &account.into());
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert!(value.value < DEPOSIT - target_liquidity(DEPOSIT));
+
+    let rebalance = beam.rebalance_liquidity();
+    context.send(&[rebalance], &[]).await.unwrap();
+
+    // All of the liquidity is removed, and only the gSOL backed by it moves to the Marinade beam.
+    assert_eq!(context.token_balance(&vault).await, 0);
+    context.refresh_sunrise().await;
+    let allocations = &context.sunrise.state.allocations;
+    assert_eq!(allocations[0].partial_gsol_supply, value.value);
+    assert_eq!(allocations[1].partial_gsol_supply, DEPOSIT - value.value);
+}
+
+#[tokio::test]
+async fn can_fund_the_liquidity_from_the_marinade_beam() {
+    let (mut context, beam, msol_beam, staker, gsol_account) = setup().await;
+    context
+        .register_beams(&[(msol_beam.state_address, 100), (beam.state_address, 0)])
+        .await;
+    let deposit = msol_beam.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+    let msol_vault_balance = context.token_balance(&msol_beam.msol_vault()).await;
+
+    let fund = msol_beam.fund_liquidity_beam(&beam);
+    context.send(&[fund], &[]).await.unwrap();
+
+    // mSOL worth the shortfall, grossed up for the unstake fee, is liquid-unstaked into liquidity
+    // for the beam, which is responsible for the gSOL backed by it. The fee is borne by the Marinade beam.
+    context.refresh_sunrise().await;
+    let allocations = &context.sunrise.state.allocations;
+    let funded = allocations[1].partial_gsol_supply;
+    assert!(funded <= target_liquidity(DEPOSIT));
+    assert!(target_liquidity(DEPOSIT) - funded <= target_liquidity(DEPOSIT) / 1_000);
+    assert_eq!(allocations[0].partial_gsol_supply, DEPOSIT - funded);
+    assert!(context.token_balance(&msol_beam.msol_vault()).await < msol_vault_balance);
+    assert!(context.token_balance(&beam.liq_pool_token_vault()).await > 0);
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert_eq!(value.gsol_supply, funded);
+}
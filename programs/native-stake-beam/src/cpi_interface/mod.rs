@@ -0,0 +1,2 @@
+pub mod stake;
+pub mod sunrise;
@@ -0,0 +1,207 @@
+use crate::seeds::VAULT_AUTHORITY;
+use crate::state::State;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::stake::instruction as stake_instruction;
+use anchor_lang::solana_program::stake::state::{Authorized, Lockup, StakeStateV2};
+use anchor_lang::solana_program::{stake, system_instruction};
+
+/// Invokes `instruction` signed by the vault authority of `state`.
+fn invoke_as_vault_authority(
+    state: &Account<State>,
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+) -> Result<()> {
+    let bump = &[state.vault_authority_bump][..];
+    let state_address = state.key();
+    let seeds = &[state_address.as_ref(), VAULT_AUTHORITY, bump][..];
+
+    invoke_signed(instruction, account_infos, &[seeds]).map_err(Into::into)
+}
+
+/// Transfers `lamports` of undelegated SOL out of the vault.
+pub fn transfer_from_vault<'info>(
+    state: &Account<'info, State>,
+    vault_authority: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    invoke_as_vault_authority(
+        state,
+        &system_instruction::transfer(vault_authority.key, to.key, lamports),
+        &[vault_authority.clone(), to.clone(), system_program.clone()],
+    )
+}
+
+/// Creates a stake account funded with `lamports` from the vault,
+/// with the vault authority as its stake and withdraw authority.
+pub fn create_stake_account<'info>(
+    state: &Account<'info, State>,
+    vault_authority: &AccountInfo<'info>,
+    new_stake_account: &AccountInfo<'info>,
+    sysvar_rent: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    invoke_as_vault_authority(
+        state,
+        &system_instruction::create_account(
+            vault_authority.key,
+            new_stake_account.key,
+            lamports,
+            StakeStateV2::size_of() as u64,
+            &stake::program::ID,
+        ),
+        &[
+            vault_authority.clone(),
+            new_stake_account.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    invoke_as_vault_authority(
+        state,
+        &stake_instruction::initialize(
+            new_stake_account.key,
+            &Authorized {
+                staker: *vault_authority.key,
+                withdrawer: *vault_authority.key,
+            },
+            &Lockup::default(),
+        ),
+        &[new_stake_account.clone(), sysvar_rent.clone()],
+    )
+}
+
+pub fn delegate<'info>(
+    state: &Account<'info, State>,
+    vault_authority: &AccountInfo<'info>,
+    stake_account: &AccountInfo<'info>,
+    vote_account: &AccountInfo<'info>,
+    sysvar_clock: &AccountInfo<'info>,
+    sysvar_stake_history: &AccountInfo<'info>,
+    stake_config: &AccountInfo<'info>,
+) -> Result<()> {
+    invoke_as_vault_authority(
+        state,
+        &stake_instruction::delegate_stake(
+            stake_account.key,
+            vault_authority.key,
+            vote_account.key,
+        ),
+        &[
+            stake_account.clone(),
+            vote_account.clone(),
+            sysvar_clock.clone(),
+            sysvar_stake_history.clone(),
+            stake_config.clone(),
+            vault_authority.clone(),
+        ],
+    )
+}
+
+/// Splits `lamports` from `stake_account` into `split_stake_account`,
+/// which must already be allocated, owned by the stake program and rent-exempt.
+pub fn split<'info>(
+    state: &Account<'info, State>,
+    vault_authority: &AccountInfo<'info>,
+    stake_account: &AccountInfo<'info>,
+    split_stake_account: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    // The split instruction alone, without the allocate and assign instructions
+    // that `stake_instruction::split` prepends for an uncreated account.
+    let instruction = Instruction::new_with_bincode(
+        stake::program::ID,
+        &stake_instruction::StakeInstruction::Split(lamports),
+        vec![
+            AccountMeta::new(*stake_account.key, false),
+            AccountMeta::new(*split_stake_account.key, false),
+            AccountMeta::new_readonly(*vault_authority.key, true),
+        ],
+    );
+    invoke_as_vault_authority(
+        state,
+        &instruction,
+        &[
+            stake_account.clone(),
+            split_stake_account.clone(),
+            vault_authority.clone(),
+        ],
+    )
+}
+
+pub fn deactivate<'info>(
+    state: &Account<'info, State>,
+    vault_authority: &AccountInfo<'info>,
+    stake_account: &AccountInfo<'info>,
+    sysvar_clock: &AccountInfo<'info>,
+) -> Result<()> {
+    invoke_as_vault_authority(
+        state,
+        &stake_instruction::deactivate_stake(stake_account.key, vault_authority.key),
+        &[
+            stake_account.clone(),
+            sysvar_clock.clone(),
+            vault_authority.clone(),
+        ],
+    )
+}
+
+/// Merges `source_stake_account` into `destination_stake_account`, closing the source.
+pub fn merge<'info>(
+    state: &Account<'info, State>,
+    vault_authority: &AccountInfo<'info>,
+    destination_stake_account: &AccountInfo<'info>,
+    source_stake_account: &AccountInfo<'info>,
+    sysvar_clock: &AccountInfo<'info>,
+    sysvar_stake_history: &AccountInfo<'info>,
+) -> Result<()> {
+    let instructions = stake_instruction::merge(
+        destination_stake_account.key,
+        source_stake_account.key,
+        vault_authority.key,
+    );
+    invoke_as_vault_authority(
+        state,
+        &instructions[0],
+        &[
+            destination_stake_account.clone(),
+            source_stake_account.clone(),
+            sysvar_clock.clone(),
+            sysvar_stake_history.clone(),
+            vault_authority.clone(),
+        ],
+    )
+}
+
+/// Withdraws `lamports` from an inactive stake account.
+pub fn withdraw<'info>(
+    state: &Account<'info, State>,
+    vault_authority: &AccountInfo<'info>,
+    stake_account: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    sysvar_clock: &AccountInfo<'info>,
+    sysvar_stake_history: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    invoke_as_vault_authority(
+        state,
+        &stake_instruction::withdraw(
+            stake_account.key,
+            vault_authority.key,
+            to.key,
+            lamports,
+            None,
+        ),
+        &[
+            stake_account.clone(),
+            to.clone(),
+            sysvar_clock.clone(),
+            sysvar_stake_history.clone(),
+            vault_authority.clone(),
+        ],
+    )
+}
@@ -0,0 +1,1002 @@
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{stake, sysvar};
+use anchor_lang::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use cpi_interface::{stake as stake_interface, sunrise as sunrise_interface};
+use seeds::*;
+use spl_beam::cpi_interface::{program::NativeStakeProgram, stake_account::StakeAccount};
+use state::{State, StateEntry, WithdrawalTicket, MAX_STAKE_ACCOUNTS, MAX_VALIDATORS};
+use std::ops::Deref;
+use sunrise_beam_interface::core_cpi;
+use sunrise_beam_interface::view::{BeamValue, DepositQuote, WithdrawQuote};
+
+use sunrise_core as sunrise_core_cpi;
+
+mod cpi_interface;
+pub mod seeds;
+pub mod state;
+mod utils;
+
+declare_id!("3Jh7vGeNsUwArCTnSVQYSbaC73G629iNR4cBkTvydXGQ");
+
+/// A beam that stakes directly with a set of validators, holding its own stake accounts.
+///
+/// Deposits are held as undelegated SOL in the vault until the update authority delegates them.
+#[program]
+pub mod native_stake_beam {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, input: StateEntry) -> Result<()> {
+        ctx.accounts.state.set_inner(input.into());
+
+        // Fund the vault so that it is rent-exempt before the first deposit.
+        let rent = Rent::get()?.minimum_balance(0);
+        let lamports = rent.saturating_sub(ctx.accounts.vault_authority.lamports());
+        if lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: ctx.accounts.vault_authority.to_account_info(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn update(ctx: Context<Update>, update_input: StateEntry) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.update_authority = update_input.update_authority;
+        state.sunrise_state = update_input.sunrise_state;
+        state.vault_authority_bump = update_input.vault_authority_bump;
+        Ok(())
+    }
+
+    /// Adds a validator that the beam may delegate to.
+    pub fn add_validator(ctx: Context<Update>, vote_account: Pubkey) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        require!(
+            state.get_validator_index(&vote_account).is_none(),
+            NativeStakeBeamError::ValidatorAlreadyAdded
+        );
+        require_gt!(
+            MAX_VALIDATORS,
+            state.validators.len(),
+            NativeStakeBeamError::TooManyValidators
+        );
+
+        state.validators.push(vote_account);
+        Ok(())
+    }
+
+    /// Removes a validator, so that no new stake is delegated to it.
+    /// Stake already delegated to the validator is unaffected, and can be deactivated separately.
+    pub fn remove_validator(ctx: Context<Update>, vote_account: Pubkey) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        let index = state
+            .get_validator_index(&vote_account)
+            .ok_or(NativeStakeBeamError::ValidatorNotFound)?;
+        state.validators.remove(index);
+        Ok(())
+    }
+
+    /// Delegates `lamports` from the vault to a validator in the validator list,
+    /// in a new stake account. `lamports` includes the rent of the stake account.
+    pub fn delegate(ctx: Context<Delegate>, lamports: u64) -> Result<()> {
+        require_gte!(
+            utils::vault_balance(&ctx.accounts.vault_authority)?,
+            lamports,
+            NativeStakeBeamError::InsufficientVaultBalance
+        );
+        require_gt!(
+            MAX_STAKE_ACCOUNTS,
+            ctx.accounts.state.stake_accounts.len(),
+            NativeStakeBeamError::TooManyStakeAccounts
+        );
+
+        let accounts = &ctx.accounts;
+        let vault_authority = accounts.vault_authority.to_account_info();
+        let new_stake_account = accounts.new_stake_account.to_account_info();
+
+        // CPI: Create and initialize the stake account, funded from the vault.
+        stake_interface::create_stake_account(
+            &accounts.state,
+            &vault_authority,
+            &new_stake_account,
+            &accounts.sysvar_rent.to_account_info(),
+            &accounts.system_program.to_account_info(),
+            lamports,
+        )?;
+
+        // CPI: Delegate the stake account to the validator.
+        stake_interface::delegate(
+            &accounts.state,
+            &vault_authority,
+            &new_stake_account,
+            &accounts.vote_account.to_account_info(),
+            &accounts.sysvar_clock.to_account_info(),
+            &accounts.sysvar_stake_history.to_account_info(),
+            &accounts.stake_config.to_account_info(),
+        )?;
+
+        ctx.accounts
+            .state
+            .stake_accounts
+            .push(ctx.accounts.new_stake_account.key());
+        Ok(())
+    }
+
+    /// Deactivates a stake account held by the beam, so that it can be withdrawn to the vault once cooled down.
+    pub fn deactivate_stake(ctx: Context<DeactivateStake>) -> Result<()> {
+        stake_interface::deactivate(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.to_account_info(),
+            &ctx.accounts.stake_account.to_account_info(),
+            &ctx.accounts.sysvar_clock.to_account_info(),
+        )
+    }
+
+    /// Merges two of the beam's stake accounts, delegated to the same validator, into one.
+    ///
+    /// Anyone can merge stake accounts, as this only reduces the number of accounts held.
+    pub fn merge_stake(ctx: Context<MergeStake>) -> Result<()> {
+        stake_interface::merge(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.to_account_info(),
+            &ctx.accounts.destination_stake_account.to_account_info(),
+            &ctx.accounts.source_stake_account.to_account_info(),
+            &ctx.accounts.sysvar_clock.to_account_info(),
+            &ctx.accounts.sysvar_stake_history.to_account_info(),
+        )?;
+
+        let source = ctx.accounts.source_stake_account.key();
+        let state = &mut ctx.accounts.state;
+        let index = state
+            .get_stake_account_index(&source)
+            .ok_or(NativeStakeBeamError::StakeAccountNotFound)?;
+        state.stake_accounts.remove(index);
+        Ok(())
+    }
+
+    /// Withdraws an inactive stake account held by the beam into the vault, including its rewards.
+    ///
+    /// Anyone can withdraw an inactive stake account, as the lamports stay in the beam.
+    pub fn withdraw_inactive_stake(ctx: Context<WithdrawInactiveStake>) -> Result<()> {
+        let stake_account = &ctx.accounts.stake_account;
+        require!(
+            stake_account.can_be_withdrawn(&ctx.accounts.sysvar_clock.epoch),
+            NativeStakeBeamError::StakeAccountNotInactive
+        );
+
+        // CPI: Withdraw all lamports from the stake account to the vault.
+        let lamports = stake_account.to_account_info().lamports();
+        stake_interface::withdraw(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.to_account_info(),
+            &ctx.accounts.stake_account.to_account_info(),
+            &ctx.accounts.vault_authority.to_account_info(),
+            &ctx.accounts.sysvar_clock.to_account_info(),
+            &ctx.accounts.sysvar_stake_history.to_account_info(),
+            lamports,
+        )?;
+
+        let stake_account = ctx.accounts.stake_account.key();
+        let state = &mut ctx.accounts.state;
+        let index = state
+            .get_stake_account_index(&stake_account)
+            .ok_or(NativeStakeBeamError::StakeAccountNotFound)?;
+        state.stake_accounts.remove(index);
+        Ok(())
+    }
+
+    /// Deposits `lamports` into the vault, to be delegated by the update authority.
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        // Transfer the SOL to the vault.
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.vault_authority.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+
+        // CPI: Mint GSOL of the same proportion as the lamports deposited to depositor.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::mint_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// Withdraws `lamports` of undelegated SOL from the vault.
+    ///
+    /// Errors if the vault does not hold enough undelegated SOL.
+    /// The beam charges no withdrawal fee, so `min_lamports_out` is met whenever it is at most `lamports`.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
+        require_gte!(
+            lamports,
+            min_lamports_out,
+            NativeStakeBeamError::WithdrawalBelowMinimum
+        );
+        require_gte!(
+            utils::vault_balance(&ctx.accounts.vault_authority)?,
+            lamports,
+            NativeStakeBeamError::InsufficientVaultBalance
+        );
+
+        // Transfer the SOL from the vault to the withdrawer.
+        stake_interface::transfer_from_vault(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.to_account_info(),
+            &ctx.accounts.withdrawer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            lamports,
+        )?;
+
+        // CPI: Burn GSOL of the same proportion as the lamports withdrawn from depositor.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::burn_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// Orders a delayed withdrawal of `lamports` of delegated stake.
+    ///
+    /// The lamports are split from one of the beam's stake accounts into a new stake account,
+    /// which is deactivated. A ticket is created mapping the new stake account to the withdrawer,
+    /// which can be redeemed once the stake account has cooled down.
+    pub fn order_withdrawal<'info>(
+        ctx: Context<'_, '_, '_, 'info, OrderWithdrawal<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        let vault_authority = ctx.accounts.vault_authority.to_account_info();
+        let stake_account = ctx.accounts.stake_account.to_account_info();
+        let new_stake_account = ctx.accounts.new_stake_account.to_account_info();
+
+        // CPI: Split the lamports into the new stake account.
+        stake_interface::split(
+            &ctx.accounts.state,
+            &vault_authority,
+            &stake_account,
+            &new_stake_account,
+            lamports,
+        )?;
+
+        // CPI: Deactivate the new stake account so that it can be claimed after cooldown.
+        stake_interface::deactivate(
+            &ctx.accounts.state,
+            &vault_authority,
+            &new_stake_account,
+            &ctx.accounts.sysvar_clock.to_account_info(),
+        )?;
+
+        // Splitting the whole balance of a stake account closes it.
+        if stake_account.lamports() == 0 {
+            let state = &mut ctx.accounts.state;
+            let index = state
+                .get_stake_account_index(stake_account.key)
+                .ok_or(NativeStakeBeamError::StakeAccountNotFound)?;
+            state.stake_accounts.remove(index);
+        }
+
+        // Create a program-owned account mapping the stake account to the beneficiary that ordered it.
+        let ticket_account = &mut ctx.accounts.withdrawal_ticket;
+        ticket_account.state = ctx.accounts.state.key();
+        ticket_account.stake_account = ctx.accounts.new_stake_account.key();
+        ticket_account.beneficiary = ctx.accounts.withdrawer.key();
+
+        // CPI: Burn GSOL of the same proportion as the lamports withdrawn.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::burn_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// Redeems a withdrawal ticket, sending the lamports in its stake account to the beneficiary.
+    ///
+    /// Errors if the stake account has not yet cooled down.
+    pub fn redeem_ticket(ctx: Context<RedeemTicket>) -> Result<()> {
+        let stake_account = &ctx.accounts.stake_account;
+        require!(
+            stake_account.can_be_withdrawn(&ctx.accounts.sysvar_clock.epoch),
+            NativeStakeBeamError::WithdrawalTicketNotRedeemable
+        );
+
+        // CPI: Withdraw the lamports from the stake account to the beneficiary.
+        let lamports = stake_account.to_account_info().lamports();
+        stake_interface::withdraw(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.to_account_info(),
+            &ctx.accounts.stake_account.to_account_info(),
+            &ctx.accounts.beneficiary.to_account_info(),
+            &ctx.accounts.sysvar_clock.to_account_info(),
+            &ctx.accounts.sysvar_stake_history.to_account_info(),
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// Burning is withdrawing without redeeming any SOL. The result is a beam that is "worth more"
+    /// than the gSOL issued by it, which allows yield extraction and can be seen as a form of "donation".
+    pub fn burn<'info>(ctx: Context<'_, '_, '_, 'info, Burn<'info>>, lamports: u64) -> Result<()> {
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::burn_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// Reports the surplus of the beam, or its deficit if a validator has been slashed.
+    ///
+    /// The remaining accounts must contain every stake account held by the beam, in state order.
+    pub fn update_epoch_report(ctx: Context<UpdateEpochReport>) -> Result<()> {
+        let staked_lamports = utils::staked_lamports(&ctx.accounts.state, ctx.remaining_accounts)?;
+        let surplus = utils::calculate_surplus(
+            &ctx.accounts.sunrise_state,
+            &ctx.accounts.state.key(),
+            utils::vault_balance(&ctx.accounts.vault_authority)?,
+            staked_lamports,
+        )?;
+
+        // CPI: update the epoch report with the surplus.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::update_epoch_report(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            surplus,
+        )?;
+
+        Ok(())
+    }
+
+    /// Extracts the yield of the beam, up to the undelegated SOL in the vault, to the yield account,
+    /// less the protocol fee, which is sent to the treasury.
+    /// Stake rewards reach the vault when the update authority deactivates stake and it is withdrawn.
+    ///
+    /// The remaining accounts must contain every stake account held by the beam, in state order.
+    pub fn extract_yield(ctx: Context<ExtractYield>) -> Result<()> {
+        let staked_lamports = utils::staked_lamports(&ctx.accounts.state, ctx.remaining_accounts)?;
+        let vault_lamports = utils::vault_balance(&ctx.accounts.vault_authority)?;
+        let extractable_yield = utils::calculate_extractable_yield(
+            &ctx.accounts.sunrise_state,
+            &ctx.accounts.state.key(),
+            vault_lamports,
+            staked_lamports,
+        )?;
+        let lamports = extractable_yield.min(vault_lamports);
+        require_gt!(
+            lamports,
+            0,
+            NativeStakeBeamError::InsufficientYieldToExtract
+        );
+
+        // Transfer the protocol fee from the vault to the treasury,
+        // and the rest of the yield to the yield account.
+        let protocol_fee = ctx.accounts.sunrise_state.protocol_fee(lamports)?;
+        let treasury = core_cpi::fee_treasury(ctx.accounts.treasury.as_ref(), protocol_fee)?;
+        if let Some(treasury) = treasury {
+            stake_interface::transfer_from_vault(
+                &ctx.accounts.state,
+                &ctx.accounts.vault_authority.to_account_info(),
+                &treasury.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                protocol_fee,
+            )?;
+        }
+        stake_interface::transfer_from_vault(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.to_account_info(),
+            &ctx.accounts.yield_account.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            lamports.saturating_sub(protocol_fee),
+        )?;
+
+        // CPI: update the epoch report with the extracted yield.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::extract_yield(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// View: quotes a [deposit] of `lamports`. The beam charges no fees, so the gSOL minted is `lamports`.
+    pub fn quote_deposit(_ctx: Context<View>, lamports: u64) -> Result<DepositQuote> {
+        Ok(DepositQuote {
+            gsol_out: lamports,
+            beam_tokens_out: 0,
+        })
+    }
+
+    /// View: quotes a [withdraw] of `lamports`.
+    ///
+    /// Errors if the vault does not hold enough undelegated SOL, as the withdrawal would.
+    pub fn quote_withdraw(ctx: Context<View>, lamports: u64) -> Result<WithdrawQuote> {
+        require_gte!(
+            utils::vault_balance(&ctx.accounts.vault_authority)?,
+            lamports,
+            NativeStakeBeamError::InsufficientVaultBalance
+        );
+        Ok(WithdrawQuote {
+            lamports_out: lamports,
+            beam_tokens_in: 0,
+        })
+    }
+
+    /// View: returns the SOL held by the beam, the surplus that [update_epoch_report] would report
+    /// and the yield that [extract_yield] would extract.
+    ///
+    /// The remaining accounts must contain every stake account held by the beam, in state order.
+    pub fn get_beam_value(ctx: Context<View>) -> Result<BeamValue> {
+        let staked_lamports = utils::staked_lamports(&ctx.accounts.state, ctx.remaining_accounts)?;
+        let vault_lamports = utils::vault_balance(&ctx.accounts.vault_authority)?;
+        let surplus = utils::calculate_surplus(
+            &ctx.accounts.sunrise_state,
+            &ctx.accounts.state.key(),
+            vault_lamports,
+            staked_lamports,
+        )?;
+        let gsol_supply = ctx
+            .accounts
+            .sunrise_state
+            .get_beam_details(&ctx.accounts.state.key())
+            .ok_or(sunrise_core::BeamError::UnidentifiedBeam)?
+            .partial_gsol_supply;
+
+        Ok(BeamValue {
+            value: vault_lamports.saturating_add(staked_lamports),
+            gsol_supply,
+            surplus,
+            extractable_yield: (surplus.max(0) as u64).min(vault_lamports),
+        })
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(input: StateEntry)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        space = State::SPACE,
+        payer = payer,
+        seeds = [STATE, input.sunrise_state.as_ref()],
+        bump
+    )]
+    pub state: Account<'info, State>,
+    /// CHECK: PDA vault and stake authority.
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = input.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Account<'info, State>,
+}
+
+#[derive(Accounts)]
+pub struct Delegate<'info> {
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// The uncreated new stake account. Will be created and initialised by CPI to the stake program.
+    pub new_stake_account: Signer<'info>,
+    #[account(
+        constraint = state.get_validator_index(&vote_account.key()).is_some() @ NativeStakeBeamError::ValidatorNotFound
+    )]
+    /// CHECK: A vote account in the validator list. Checked by CPI to the stake program.
+    pub vote_account: UncheckedAccount<'info>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    pub sysvar_rent: Sysvar<'info, Rent>,
+    #[account(address = sysvar::stake_history::ID)]
+    /// CHECK: The stake history sysvar.
+    pub sysvar_stake_history: UncheckedAccount<'info>,
+    #[account(address = stake::config::ID)]
+    /// CHECK: The stake config account.
+    pub stake_config: UncheckedAccount<'info>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateStake<'info> {
+    pub update_authority: Signer<'info>,
+    #[account(
+        has_one = update_authority,
+        constraint = state.get_stake_account_index(&stake_account.key()).is_some() @ NativeStakeBeamError::StakeAccountNotFound
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: A stake account held by the beam.
+    pub stake_account: UncheckedAccount<'info>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+}
+
+#[derive(Accounts)]
+pub struct MergeStake<'info> {
+    #[account(
+        mut,
+        constraint = state.get_stake_account_index(&destination_stake_account.key()).is_some() @ NativeStakeBeamError::StakeAccountNotFound,
+        constraint = state.get_stake_account_index(&source_stake_account.key()).is_some() @ NativeStakeBeamError::StakeAccountNotFound
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: A stake account held by the beam.
+    pub destination_stake_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = source_stake_account.key() != destination_stake_account.key()
+    )]
+    /// CHECK: A stake account held by the beam, closed by the merge.
+    pub source_stake_account: UncheckedAccount<'info>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    #[account(address = sysvar::stake_history::ID)]
+    /// CHECK: The stake history sysvar.
+    pub sysvar_stake_history: UncheckedAccount<'info>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawInactiveStake<'info> {
+    #[account(
+        mut,
+        constraint = state.get_stake_account_index(&stake_account.key()).is_some() @ NativeStakeBeamError::StakeAccountNotFound
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    #[account(address = sysvar::stake_history::ID)]
+    /// CHECK: The stake history sysvar.
+    pub sysvar_stake_history: UncheckedAccount<'info>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub mint_gsol_to: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub gsol_mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct OrderWithdrawal<'info> {
+    #[account(
+        mut,
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump,
+        constraint = state.get_stake_account_index(&stake_account.key()).is_some() @ NativeStakeBeamError::StakeAccountNotFound
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    /// CHECK: The stake account held by the beam to split the withdrawal from.
+    pub stake_account: UncheckedAccount<'info>,
+    #[account(
+        init,
+        space = stake::state::StakeStateV2::size_of(),
+        payer = withdrawer,
+        owner = stake::program::ID,
+    )]
+    /// The uninitialized new stake account. Will be initialised by the split.
+    pub new_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        space = WithdrawalTicket::SPACE,
+        payer = withdrawer,
+        seeds = [
+            state.key().as_ref(),
+            WITHDRAWAL_TICKET,
+            new_stake_account.key().as_ref()
+        ],
+        bump
+    )]
+    pub withdrawal_ticket: Box<Account<'info, WithdrawalTicket>>,
+
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemTicket<'info> {
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = stake_account,
+        has_one = beneficiary,
+        close = beneficiary,
+    )]
+    pub withdrawal_ticket: Box<Account<'info, WithdrawalTicket>>,
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    #[account(address = sysvar::stake_history::ID)]
+    /// CHECK: The stake history sysvar.
+    pub sysvar_stake_history: UncheckedAccount<'info>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+}
+
+#[derive(Accounts)]
+pub struct Burn<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub burner: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEpochReport<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+    mut, // Update the extractable yield on the state's epoch report.
+    )]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    /// Required to update the core state epoch report
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Account<'info, Mint>,
+    #[account(mut)]
+    /// CHECK: Updated with the epoch report - verified in CPI to Sunrise program.
+    pub backing_oracle: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct View<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExtractYield<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut, // Update the extracted yield on the state's epoch report.
+        has_one = yield_account
+    )]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    /// CHECK: Matches the yield key stored in the state.
+    pub yield_account: UncheckedAccount<'info>,
+    #[account(mut, address = sunrise_state.treasury)]
+    /// CHECK: Matches the treasury key stored in the state.
+    /// Only needed while the state charges a protocol fee.
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[error_code]
+pub enum NativeStakeBeamError {
+    #[msg("An error occurred during calculation")]
+    CalculationFailure,
+    #[msg("The validator is already in the validator list")]
+    ValidatorAlreadyAdded,
+    #[msg("The validator list already contains the maximum number of validators")]
+    TooManyValidators,
+    #[msg("The validator is not in the validator list")]
+    ValidatorNotFound,
+    #[msg("The beam already holds the maximum number of stake accounts")]
+    TooManyStakeAccounts,
+    #[msg("The stake account is not held by the beam")]
+    StakeAccountNotFound,
+    #[msg("Every stake account held by the beam must be passed in order")]
+    IncompleteStakeAccounts,
+    #[msg("The vault does not hold enough undelegated SOL")]
+    InsufficientVaultBalance,
+    #[msg("The withdrawal paid out fewer lamports than the requested minimum")]
+    WithdrawalBelowMinimum,
+    #[msg("The stake account has not yet been deactivated and cooled down")]
+    StakeAccountNotInactive,
+    #[msg("The stake account of this withdrawal ticket cannot yet be claimed")]
+    WithdrawalTicketNotRedeemable,
+    #[msg("There is no yield to extract from the vault")]
+    InsufficientYieldToExtract,
+}
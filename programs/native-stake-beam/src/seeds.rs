@@ -0,0 +1,7 @@
+/// Seed of the PDA that holds the beam's undelegated SOL, and is the stake and withdraw authority
+/// of its stake accounts.
+pub const VAULT_AUTHORITY: &[u8] = b"vault_authority";
+/// Seed of this program's state address.
+pub const STATE: &[u8] = b"sunrise_native_stake";
+/// Seed of a ticket mapping an ordered withdrawal to its beneficiary.
+pub const WITHDRAWAL_TICKET: &[u8] = b"withdrawal_ticket";
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+/// The maximum number of validators that the beam delegates to.
+pub const MAX_VALIDATORS: usize = 8;
+/// The maximum number of stake accounts held by the beam.
+pub const MAX_STAKE_ACCOUNTS: usize = 16;
+
+#[account]
+#[derive(Debug, Default)]
+pub struct State {
+    /// The update authority of the state.
+    pub update_authority: Pubkey,
+
+    /// The state of the main sunrise beam.
+    pub sunrise_state: Pubkey,
+
+    /// The bump of the PDA that holds the undelegated SOL and
+    /// can authorize the beam's stake accounts.
+    pub vault_authority_bump: u8,
+
+    /// The vote accounts of the validators that the beam may delegate to.
+    pub validators: Vec<Pubkey>,
+
+    /// The stake accounts held by the beam.
+    pub stake_accounts: Vec<Pubkey>,
+}
+
+impl State {
+    pub const SPACE: usize = 8 +  /*discriminator*/
+        32 + /*update_authority*/
+        32 + /*sunrise_state*/
+        1 + /*vault_authority_bump*/
+        4 + MAX_VALIDATORS * 32 + /*validators*/
+        4 + MAX_STAKE_ACCOUNTS * 32; /*stake_accounts*/
+
+    /// Gets the index of a validator vote account in the validator list.
+    pub fn get_validator_index(&self, vote_account: &Pubkey) -> Option<usize> {
+        self.validators.iter().position(|v| v == vote_account)
+    }
+
+    /// Gets the index of a stake account held by the beam.
+    pub fn get_stake_account_index(&self, stake_account: &Pubkey) -> Option<usize> {
+        self.stake_accounts.iter().position(|s| s == stake_account)
+    }
+}
+
+// Anchor-ts only supports deserialization(in instruction arguments) for types
+// that explicitly derive AnchorSerialize & AnchorDeserialize.
+// https://github.com/coral-xyz/anchor/issues/2545
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StateEntry {
+    pub update_authority: Pubkey,
+    pub sunrise_state: Pubkey,
+    pub vault_authority_bump: u8,
+}
+
+impl From<StateEntry> for State {
+    fn from(se: StateEntry) -> Self {
+        State {
+            update_authority: se.update_authority,
+            sunrise_state: se.sunrise_state,
+            vault_authority_bump: se.vault_authority_bump,
+            validators: vec![],
+            stake_accounts: vec![],
+        }
+    }
+}
+
+#[account]
+pub struct WithdrawalTicket {
+    /// The beam state that the withdrawal was ordered from.
+    pub state: Pubkey,
+
+    /// The stake account holding the withdrawn lamports.
+    pub stake_account: Pubkey,
+
+    /// The account that can redeem the ticket.
+    pub beneficiary: Pubkey,
+}
+
+impl WithdrawalTicket {
+    pub const SPACE: usize = 8 +  /*discriminator*/
+        32 + /*state*/
+        32 + /*stake_account*/
+        32; /*beneficiary*/
+}
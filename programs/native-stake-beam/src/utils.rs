@@ -0,0 +1,119 @@
+use crate::state::State;
+use crate::NativeStakeBeamError;
+use anchor_lang::prelude::*;
+use sunrise_core::BeamError;
+
+/// The undelegated SOL in the vault that can be spent, keeping the vault rent-exempt.
+pub fn vault_balance(vault_authority: &AccountInfo) -> Result<u64> {
+    let rent = Rent::get()?.minimum_balance(0);
+    Ok(vault_authority.lamports().saturating_sub(rent))
+}
+
+/// Sums the lamports in the beam's stake accounts, which must be passed
+/// as `accounts` in the order they are held in the state.
+pub fn staked_lamports(state: &State, accounts: &[AccountInfo]) -> Result<u64> {
+    require_eq!(
+        accounts.len(),
+        state.stake_accounts.len(),
+        NativeStakeBeamError::IncompleteStakeAccounts
+    );
+
+    let mut lamports: u64 = 0;
+    for (stake_account, account_info) in state.stake_accounts.iter().zip(accounts) {
+        require_keys_eq!(
+            account_info.key(),
+            *stake_account,
+            NativeStakeBeamError::IncompleteStakeAccounts
+        );
+        lamports = lamports
+            .checked_add(account_info.lamports())
+            .ok_or(NativeStakeBeamError::CalculationFailure)?;
+    }
+    Ok(lamports)
+}
+
+/// Calculates the amount of yield that can be extracted from the beam.
+/// This is the [surplus][calculate_surplus] of the beam, or zero if it is in deficit.
+pub fn calculate_extractable_yield(
+    sunrise_state: &sunrise_core::State,
+    beam: &Pubkey,
+    vault_lamports: u64,
+    staked_lamports: u64,
+) -> Result<u64> {
+    let surplus = calculate_surplus(sunrise_state, beam, vault_lamports, staked_lamports)?;
+    Ok(surplus.max(0) as u64)
+}
+
+/// Calculates the surplus of the beam, which is negative if the beam is in deficit, e.g. after a slashing.
+/// This is calculated as:
+/// The SOL in the vault and the stake accounts minus the amount of SOL staked in the beam.
+/// The stake accounts include the stake rewards earned, and their rent.
+pub fn calculate_surplus(
+    sunrise_state: &sunrise_core::State,
+    beam: &Pubkey,
+    vault_lamports: u64,
+    staked_lamports: u64,
+) -> Result<i64> {
+    let owned_value = vault_lamports
+        .checked_add(staked_lamports)
+        .ok_or(NativeStakeBeamError::CalculationFailure)?;
+
+    // Calculate the amount of SOL staked in the beam
+    let details = sunrise_state
+        .get_beam_details(beam)
+        .ok_or(BeamError::UnidentifiedBeam)?;
+    let staked_sol = details.partial_gsol_supply;
+
+    msg!("owned_value: {}, staked_sol: {}", owned_value, staked_sol);
+
+    Ok(beam_math::surplus(owned_value, staked_sol)?)
+}
+
+#[cfg(test)]
+mod utils_tests {
+    use super::*;
+    use sunrise_core::BeamDetails;
+
+    #[test]
+    fn test_calculate_extractable_yield() {
+        let beam = Pubkey::new_unique();
+        let mut details = BeamDetails::new(beam, 100);
+        details.partial_gsol_supply = 1_000;
+        let sunrise_state = sunrise_core::State {
+            allocations: vec![details],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            calculate_extractable_yield(&sunrise_state, &beam, 100, 950).unwrap(),
+            50
+        );
+        // Slashed below the gSOL issued - no yield
+        assert_eq!(
+            calculate_extractable_yield(&sunrise_state, &beam, 0, 900).unwrap(),
+            0
+        );
+        assert!(calculate_extractable_yield(&sunrise_state, &Pubkey::new_unique(), 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_calculate_surplus() {
+        let beam = Pubkey::new_unique();
+        let mut details = BeamDetails::new(beam, 100);
+        details.partial_gsol_supply = 1_000;
+        let sunrise_state = sunrise_core::State {
+            allocations: vec![details],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            calculate_surplus(&sunrise_state, &beam, 100, 950).unwrap(),
+            50
+        );
+        // Slashed below the gSOL issued - a deficit
+        assert_eq!(
+            calculate_surplus(&sunrise_state, &beam, 0, 900).unwrap(),
+            -100
+        );
+    }
+}
@@ -0,0 +1,394 @@
+//! Integration tests of the native stake beam, delegating to a validator that does not vote.
+//!
+//! As the validator earns no rewards, rewards are simulated by transferring lamports
+//! to the beam's stake accounts.
+
+use anchor_lang::error::ErrorCode;
+use beam_test_utils::beams::initialize_native_stake_beam;
+use beam_test_utils::{add_vote_account, beam_program_test, error_code, suite, BeamTestContext};
+use native_stake_beam::NativeStakeBeamError;
+use solana_program_test::tokio;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::stake::state::StakeStateV2;
+use sunrise_client::native_stake::{self, NativeStakeClient, State, WithdrawalTicket};
+use sunrise_client::{BeamValue, DepositQuote, WithdrawQuote};
+
+const DEPOSIT: u64 = 10 * LAMPORTS_PER_SOL;
+const WITHDRAWAL: u64 = LAMPORTS_PER_SOL;
+const DELEGATION: u64 = 5 * LAMPORTS_PER_SOL;
+const DELAYED_WITHDRAWAL: u64 = 2 * LAMPORTS_PER_SOL;
+const REWARDS: u64 = LAMPORTS_PER_SOL / 10;
+
+/// Start with an initialized beam that is not yet registered, a validator that is not yet
+/// in its validator list, and a funded staker with a gSOL token account.
+async fn setup() -> (BeamTestContext, NativeStakeClient, Pubkey, Keypair, Pubkey) {
+    let mut program_test = beam_program_test!(native_stake_beam, native_stake::ID);
+    let vote_account = add_vote_account(&mut program_test);
+    let mut context = BeamTestContext::start(program_test).await;
+    let beam = initialize_native_stake_beam(&mut context).await;
+    let (staker, gsol_account) = context.create_staker().await;
+    (context, beam, vote_account, staker, gsol_account)
+}
+
+/// As `setup`, with the beam registered, the validator added,
+/// and `DEPOSIT` deposited by the staker.
+async fn setup_with_deposit() -> (BeamTestContext, NativeStakeClient, Pubkey, Keypair, Pubkey) {
+    let (mut context, beam, vote_account, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    let add_validator = beam.add_validator(&vote_account);
+    let deposit = beam.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context
+        .send(&[add_validator, deposit], &[&staker])
+        .await
+        .unwrap();
+    let beam = refresh(&mut context, &beam.state_address).await;
+    (context, beam, vote_account, staker, gsol_account)
+}
+
+/// As `setup_with_deposit`, with `DELEGATION` delegated to the validator and activated.
+/// Returns the stake account.
+async fn setup_with_delegation() -> (BeamTestContext, NativeStakeClient, Keypair, Pubkey, Pubkey) {
+    let (mut context, beam, vote_account, staker, gsol_account) = setup_with_deposit().await;
+    let stake_account = Keypair::new();
+    let delegate = beam.delegate(&stake_account.pubkey(), &vote_account, DELEGATION);
+    context.send(&[delegate], &[&stake_account]).await.unwrap();
+
+    let epoch = context.epoch().await;
+    context.warp_to_epoch(epoch + 1).await;
+    let beam = refresh(&mut context, &beam.state_address).await;
+    (context, beam, staker, gsol_account, stake_account.pubkey())
+}
+
+/// A client for the beam, with its stake accounts as they are now.
+async fn refresh(context: &mut BeamTestContext, state_address: &Pubkey) -> NativeStakeClient {
+    let state: State = context.fetch(state_address).await;
+    NativeStakeClient::new(
+        native_stake::ID,
+        *state_address,
+        state,
+        context.sunrise.clone(),
+    )
+}
+
+async fn stake_state(context: &mut BeamTestContext, stake_account: &Pubkey) -> StakeStateV2 {
+    let account = context.account(stake_account).await.unwrap();
+    account.deserialize_data().unwrap()
+}
+
+#[tokio::test]
+async fn cannot_deposit_before_registration() {
+    let (mut context, beam, _, staker, gsol_account) = setup().await;
+    suite::cannot_deposit_before_registration(&mut context, &beam, &staker, &gsol_account, DEPOSIT)
+        .await;
+}
+
+#[tokio::test]
+async fn can_deposit_and_withdraw_from_the_vault() {
+    let (mut context, beam, _, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    suite::can_deposit_and_withdraw(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        WITHDRAWAL,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn quotes_deposits_and_withdrawals_from_the_vault_one_to_one() {
+    let (mut context, beam, _, _, _) = setup_with_deposit().await;
+
+    let quote: DepositQuote = context.view(beam.quote_deposit(DEPOSIT)).await;
+    assert_eq!(quote.gsol_out, DEPOSIT);
+    let quote: WithdrawQuote = context.view(beam.quote_withdraw(WITHDRAWAL)).await;
+    assert_eq!(quote.lamports_out, WITHDRAWAL);
+}
+
+#[tokio::test]
+async fn cannot_withdraw_below_the_minimum_out() {
+    let (mut context, beam, _, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    let error = suite::cannot_withdraw_below_the_minimum_out(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        WITHDRAWAL,
+    )
+    .await;
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(NativeStakeBeamError::WithdrawalBelowMinimum))
+    );
+}
+
+#[tokio::test]
+async fn can_delegate_to_a_validator_in_the_list() {
+    let (mut context, beam, vote_account, _, _) = setup_with_deposit().await;
+    let vault_balance = context.balance(&beam.vault_authority()).await;
+
+    let stake_account = Keypair::new();
+    let delegate = beam.delegate(&stake_account.pubkey(), &vote_account, DELEGATION);
+    context.send(&[delegate], &[&stake_account]).await.unwrap();
+
+    let beam = refresh(&mut context, &beam.state_address).await;
+    assert_eq!(beam.state.stake_accounts, vec![stake_account.pubkey()]);
+    assert_eq!(
+        context.balance(&beam.vault_authority()).await,
+        vault_balance - DELEGATION
+    );
+
+    // The stake account holds the delegation, including its rent,
+    // and is authorized by the vault.
+    assert_eq!(context.balance(&stake_account.pubkey()).await, DELEGATION);
+    let stake_state = stake_state(&mut context, &stake_account.pubkey()).await;
+    let authorized = stake_state.authorized().unwrap();
+    assert_eq!(authorized.staker, beam.vault_authority());
+    assert_eq!(authorized.withdrawer, beam.vault_authority());
+    let delegation = stake_state.delegation().unwrap();
+    assert_eq!(delegation.voter_pubkey, vote_account);
+    let rent = context
+        .rent()
+        .await
+        .minimum_balance(StakeStateV2::size_of());
+    assert_eq!(delegation.stake, DELEGATION - rent);
+
+    // The stake is still valued by the beam.
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert_eq!(value.value, DEPOSIT);
+    assert_eq!(value.surplus, 0);
+}
+
+#[tokio::test]
+async fn cannot_delegate_to_a_validator_not_in_the_list() {
+    let (mut context, beam, _, _, _) = setup_with_deposit().await;
+
+    let stake_account = Keypair::new();
+    let delegate = beam.delegate(&stake_account.pubkey(), &Pubkey::new_unique(), DELEGATION);
+    let error = context
+        .send(&[delegate], &[&stake_account])
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(NativeStakeBeamError::ValidatorNotFound))
+    );
+}
+
+#[tokio::test]
+async fn cannot_delegate_more_than_the_vault_holds() {
+    let (mut context, beam, vote_account, _, _) = setup_with_deposit().await;
+
+    let stake_account = Keypair::new();
+    let delegate = beam.delegate(&stake_account.pubkey(), &vote_account, DEPOSIT + 1);
+    let error = context
+        .send(&[delegate], &[&stake_account])
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(NativeStakeBeamError::InsufficientVaultBalance))
+    );
+}
+
+#[tokio::test]
+async fn cannot_withdraw_delegated_stake_from_the_vault() {
+    let (mut context, beam, staker, gsol_account, _) = setup_with_delegation().await;
+
+    let withdraw = beam.withdraw(&staker.pubkey(), &gsol_account, DEPOSIT - DELEGATION + 1, 0);
+    let error = context.send(&[withdraw], &[&staker]).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(NativeStakeBeamError::InsufficientVaultBalance))
+    );
+    assert_eq!(context.token_balance(&gsol_account).await, DEPOSIT);
+}
+
+#[tokio::test]
+async fn can_deactivate_and_withdraw_stake_with_its_rewards() {
+    let (mut context, beam, _, _, stake_account) = setup_with_delegation().await;
+    context.fund(&stake_account, REWARDS).await;
+
+    let deactivate_stake = beam.deactivate_stake(&stake_account);
+    context.send(&[deactivate_stake], &[]).await.unwrap();
+    let epoch = context.epoch().await;
+    let stake_state = stake_state(&mut context, &stake_account).await;
+    assert_eq!(stake_state.delegation().unwrap().deactivation_epoch, epoch);
+
+    // The stake is still cooling down in the epoch it was deactivated in.
+    let withdraw_inactive_stake = beam.withdraw_inactive_stake(&stake_account);
+    assert!(context
+        .send(&[withdraw_inactive_stake.clone()], &[])
+        .await
+        .is_err());
+
+    context.warp_to_epoch(epoch + 1).await;
+    let vault_balance = context.balance(&beam.vault_authority()).await;
+    context.send(&[withdraw_inactive_stake], &[]).await.unwrap();
+
+    // The stake account is closed, and the stake and rewards are back in the vault.
+    assert!(context.account(&stake_account).await.is_none());
+    assert_eq!(
+        context.balance(&beam.vault_authority()).await,
+        vault_balance + DELEGATION + REWARDS
+    );
+    let beam = refresh(&mut context, &beam.state_address).await;
+    assert!(beam.state.stake_accounts.is_empty());
+}
+
+#[tokio::test]
+async fn only_the_update_authority_can_deactivate_stake() {
+    let (mut context, beam, staker, _, stake_account) = setup_with_delegation().await;
+
+    let mut deactivate_stake = beam.deactivate_stake(&stake_account);
+    deactivate_stake.accounts[0].pubkey = staker.pubkey();
+    let error = context
+        .send(&[deactivate_stake], &[&staker])
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(ErrorCode::ConstraintHasOne))
+    );
+}
+
+#[tokio::test]
+async fn can_extract_the_yield_of_stake_rewards() {
+    let (mut context, beam, _, _, stake_account) = setup_with_delegation().await;
+    context.fund(&stake_account, REWARDS).await;
+
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert_eq!(value.value, DEPOSIT + REWARDS);
+    assert_eq!(value.gsol_supply, DEPOSIT);
+    assert_eq!(value.surplus, REWARDS as i64);
+    assert_eq!(value.extractable_yield, REWARDS);
+
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    context.refresh_sunrise().await;
+    let report = &context.sunrise.state.epoch_report.beam_epoch_details[0];
+    assert_eq!(report.extractable_yield, REWARDS);
+
+    // The yield is paid from the undelegated SOL in the vault.
+    let yield_account = context.sunrise.state.yield_account;
+    let treasury = context.sunrise.treasury().unwrap();
+    let yield_balance = context.balance(&yield_account).await;
+    let treasury_balance = context.balance(&treasury).await;
+    let vault_balance = context.balance(&beam.vault_authority()).await;
+    let extract_yield = beam.extract_yield();
+    context.send(&[extract_yield], &[]).await.unwrap();
+
+    let protocol_fee = context.sunrise.state.protocol_fee(REWARDS).unwrap();
+    assert_eq!(
+        context.balance(&yield_account).await,
+        yield_balance + REWARDS - protocol_fee
+    );
+    assert_eq!(
+        context.balance(&treasury).await,
+        treasury_balance + protocol_fee
+    );
+    assert_eq!(
+        context.balance(&beam.vault_authority()).await,
+        vault_balance - REWARDS
+    );
+    context.refresh_sunrise().await;
+    let report = &context.sunrise.state.epoch_report.beam_epoch_details[0];
+    assert_eq!(report.extracted_yield, REWARDS);
+
+    // Nothing is left to extract.
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert_eq!(value.surplus, 0);
+}
+
+#[tokio::test]
+async fn cannot_extract_yield_without_a_surplus() {
+    let (mut context, beam, _, _, _) = setup_with_delegation().await;
+
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    let extract_yield = beam.extract_yield();
+    let error = context.send(&[extract_yield], &[]).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(NativeStakeBeamError::InsufficientYieldToExtract))
+    );
+}
+
+#[tokio::test]
+async fn cannot_value_the_beam_without_its_stake_accounts() {
+    let (mut context, beam, _, _, _) = setup_with_delegation().await;
+
+    let mut update_epoch_report = beam.update_epoch_report();
+    update_epoch_report.accounts.pop();
+    let error = context.send(&[update_epoch_report], &[]).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(NativeStakeBeamError::IncompleteStakeAccounts))
+    );
+}
+
+#[tokio::test]
+async fn can_order_a_withdrawal_and_redeem_the_ticket() {
+    let (mut context, beam, staker, gsol_account, stake_account) = setup_with_delegation().await;
+
+    let new_stake_account = Keypair::new();
+    let order_withdrawal = beam.order_withdrawal(
+        &staker.pubkey(),
+        &gsol_account,
+        &stake_account,
+        &new_stake_account.pubkey(),
+        DELAYED_WITHDRAWAL,
+    );
+    context
+        .send(&[order_withdrawal], &[&staker, &new_stake_account])
+        .await
+        .unwrap();
+    assert_eq!(
+        context.token_balance(&gsol_account).await,
+        DEPOSIT - DELAYED_WITHDRAWAL
+    );
+
+    let ticket_address = native_stake::derive_withdrawal_ticket(
+        &native_stake::ID,
+        &beam.state_address,
+        &new_stake_account.pubkey(),
+    )
+    .0;
+    let ticket: WithdrawalTicket = context.fetch(&ticket_address).await;
+    assert_eq!(ticket.state, beam.state_address);
+    assert_eq!(ticket.stake_account, new_stake_account.pubkey());
+    assert_eq!(ticket.beneficiary, staker.pubkey());
+
+    // The stake is still deactivating in the epoch it was ordered in.
+    let redeem_ticket = beam.redeem_ticket(&ticket);
+    assert!(context
+        .send(&[redeem_ticket.clone()], &[&staker])
+        .await
+        .is_err());
+
+    let epoch = context.epoch().await;
+    context.warp_to_epoch(epoch + 1).await;
+    let staker_balance = context.balance(&staker.pubkey()).await;
+    let stake_balance = context.balance(&new_stake_account.pubkey()).await;
+    let ticket_balance = context.balance(&ticket_address).await;
+    context.send(&[redeem_ticket], &[&staker]).await.unwrap();
+
+    assert_eq!(
+        context.balance(&staker.pubkey()).await,
+        staker_balance + stake_balance + ticket_balance
+    );
+    assert!(context.account(&ticket_address).await.is_none());
+}
@@ -0,0 +1,390 @@
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use cpi_interface::sunrise as sunrise_interface;
+use seeds::*;
+use state::{State, StateEntry};
+use std::ops::Deref;
+use sunrise_beam_interface::unsupported::unsupported;
+use sunrise_beam_interface::view::{BeamValue, DepositQuote, WithdrawQuote};
+
+use sunrise_core as sunrise_core_cpi;
+
+mod cpi_interface;
+pub mod seeds;
+pub mod state;
+
+declare_id!("E4nx9gv9wJQCWnCU3cE1LgbgXwAWfNLMwVRJkGHkebPK");
+
+/// A beam that holds plain SOL in a vault, as an instant, fee-free withdrawal buffer.
+/// gSOL is minted and burned one-to-one with the SOL in the vault, so the beam earns no yield.
+#[program]
+pub mod reserve_beam {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, input: StateEntry) -> Result<()> {
+        ctx.accounts.state.set_inner(input.into());
+
+        // Fund the vault so that it is rent-exempt before the first deposit.
+        let rent = Rent::get()?.minimum_balance(0);
+        let lamports = rent.saturating_sub(ctx.accounts.vault_authority.lamports());
+        if lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: ctx.accounts.vault_authority.to_account_info(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn update(ctx: Context<Update>, update_input: StateEntry) -> Result<()> {
+        let updated_state: State = update_input.into();
+        ctx.accounts.state.set_inner(updated_state);
+        Ok(())
+    }
+
+    /// Deposits `lamports` into the vault, minting the same amount of gSOL.
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        // Transfer the SOL to the vault.
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.vault_authority.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+
+        // CPI: Mint GSOL of the same proportion as the lamports deposited to depositor.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::mint_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// Withdraws `lamports` from the vault, burning the same amount of gSOL.
+    ///
+    /// The reserve charges no fee, so `min_lamports_out` is met whenever it is at most `lamports`.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
+        require_gte!(
+            lamports,
+            min_lamports_out,
+            ReserveBeamError::WithdrawalBelowMinimum
+        );
+        let rent = Rent::get()?.minimum_balance(0);
+        let vault_balance = ctx.accounts.vault_authority.lamports().saturating_sub(rent);
+        require_gte!(
+            vault_balance,
+            lamports,
+            ReserveBeamError::InsufficientReserve
+        );
+
+        // Transfer the SOL from the vault to the withdrawer.
+        let bump = &[ctx.accounts.state.vault_authority_bump][..];
+        let state_address = ctx.accounts.state.key();
+        let seeds = &[state_address.as_ref(), VAULT_AUTHORITY, bump][..];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.vault_authority.to_account_info(),
+                    to: ctx.accounts.withdrawer.to_account_info(),
+                },
+                &[seeds],
+            ),
+            lamports,
+        )?;
+
+        // CPI: Burn GSOL of the same proportion as the lamports withdrawn from depositor.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::burn_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn order_withdrawal(_ctx: Context<Unsupported>, _lamports: u64) -> Result<()> {
+        // The reserve only supports immediate withdrawals.
+        unsupported()
+    }
+
+    pub fn redeem_ticket(_ctx: Context<Unsupported>) -> Result<()> {
+        // The reserve only supports immediate withdrawals.
+        unsupported()
+    }
+
+    pub fn burn(_ctx: Context<Unsupported>, _lamports: u64) -> Result<()> {
+        // The reserve earns no yield, so a donation could never be extracted.
+        unsupported()
+    }
+
+    /// Reports the yield of the reserve, which is always zero.
+    pub fn update_epoch_report(ctx: Context<UpdateEpochReport>) -> Result<()> {
+        // CPI: update the epoch report with the extractable yield.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::update_epoch_report(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            0,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn extract_yield(_ctx: Context<Unsupported>) -> Result<()> {
+        // The reserve earns no yield.
+        unsupported()
+    }
+
+    /// View: quotes a [deposit] of `lamports`, which mints the same amount of gSOL.
+    pub fn quote_deposit(_ctx: Context<View>, lamports: u64) -> Result<DepositQuote> {
+        Ok(DepositQuote {
+            gsol_out: lamports,
+            beam_tokens_out: 0,
+        })
+    }
+
+    /// View: quotes a [withdraw] of `lamports`, which pays out the same amount of SOL.
+    ///
+    /// Errors if the reserve does not hold enough SOL, as the withdrawal would.
+    pub fn quote_withdraw(ctx: Context<View>, lamports: u64) -> Result<WithdrawQuote> {
+        let rent = Rent::get()?.minimum_balance(0);
+        let vault_balance = ctx.accounts.vault_authority.lamports().saturating_sub(rent);
+        require_gte!(
+            vault_balance,
+            lamports,
+            ReserveBeamError::InsufficientReserve
+        );
+        Ok(WithdrawQuote {
+            lamports_out: lamports,
+            beam_tokens_in: 0,
+        })
+    }
+
+    /// View: returns the SOL held in the vault. The reserve earns no yield,
+    /// so it always reports a surplus of zero.
+    pub fn get_beam_value(ctx: Context<View>) -> Result<BeamValue> {
+        let rent = Rent::get()?.minimum_balance(0);
+        let vault_balance = ctx.accounts.vault_authority.lamports().saturating_sub(rent);
+        let gsol_supply = ctx
+            .accounts
+            .sunrise_state
+            .get_beam_details(&ctx.accounts.state.key())
+            .ok_or(sunrise_core::BeamError::UnidentifiedBeam)?
+            .partial_gsol_supply;
+
+        Ok(BeamValue {
+            value: vault_balance,
+            gsol_supply,
+            surplus: 0,
+            extractable_yield: 0,
+        })
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(input: StateEntry)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        space = State::SPACE,
+        payer = payer,
+        seeds = [STATE, input.sunrise_state.as_ref()],
+        bump
+    )]
+    pub state: Account<'info, State>,
+    /// CHECK: PDA vault of the reserve.
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = input.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Account<'info, State>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub mint_gsol_to: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub gsol_mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEpochReport<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+    mut, // Update the extractable yield on the state's epoch report.
+    )]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    /// Required to update the core state epoch report
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Account<'info, Mint>,
+    #[account(mut)]
+    /// CHECK: Updated with the epoch report - verified in CPI to Sunrise program.
+    pub backing_oracle: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+}
+
+#[derive(Accounts)]
+pub struct View<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+}
+
+/// The accounts of the instructions of the standard interface that the reserve does not support.
+#[derive(Accounts)]
+pub struct Unsupported {}
+
+#[error_code]
+pub enum ReserveBeamError {
+    #[msg("The withdrawal paid out fewer lamports than the requested minimum")]
+    WithdrawalBelowMinimum,
+    #[msg("The reserve does not hold enough SOL for this withdrawal")]
+    InsufficientReserve,
+}
@@ -0,0 +1,4 @@
+/// Seed of the PDA that holds the reserve's SOL.
+pub const VAULT_AUTHORITY: &[u8] = b"vault_authority";
+/// Seed of this program's state address.
+pub const STATE: &[u8] = b"sunrise_reserve";
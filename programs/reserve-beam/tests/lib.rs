@@ -0,0 +1,172 @@
+//! Integration tests of the reserve beam.
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use beam_test_utils::beams::initialize_reserve_beam;
+use beam_test_utils::{beam_program_test, error_code, suite, BeamTestContext};
+use reserve_beam::{accounts, instruction as ix, ReserveBeamError};
+use solana_program_test::tokio;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use sunrise_beam_interface::unsupported::BeamInterfaceError;
+use sunrise_client::reserve::{self, ReserveClient};
+use sunrise_client::{BeamValue, DepositQuote, WithdrawQuote};
+
+const DEPOSIT: u64 = 10 * LAMPORTS_PER_SOL;
+const WITHDRAWAL: u64 = 4 * LAMPORTS_PER_SOL;
+
+/// Start with an initialized beam that is not yet registered,
+/// and a funded staker with a gSOL token account.
+async fn setup() -> (BeamTestContext, ReserveClient, Keypair, Pubkey) {
+    let mut context = BeamTestContext::start(beam_program_test!(reserve_beam, reserve::ID)).await;
+    let beam = initialize_reserve_beam(&mut context).await;
+    let (staker, gsol_account) = context.create_staker().await;
+    (context, beam, staker, gsol_account)
+}
+
+/// As `setup`, with the beam registered and `DEPOSIT` deposited by the staker.
+async fn setup_with_deposit() -> (BeamTestContext, ReserveClient, Keypair, Pubkey) {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    let deposit = beam.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+    (context, beam, staker, gsol_account)
+}
+
+/// An instruction of the standard interface that the reserve does not support.
+fn unsupported(data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: reserve::ID,
+        accounts: accounts::Unsupported {}.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+#[tokio::test]
+async fn initializes_a_rent_exempt_vault() {
+    let (mut context, beam, _, _) = setup().await;
+
+    let rent = context.rent().await.minimum_balance(0);
+    assert_eq!(context.balance(&beam.vault_authority()).await, rent);
+}
+
+#[tokio::test]
+async fn cannot_deposit_before_registration() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    suite::cannot_deposit_before_registration(&mut context, &beam, &staker, &gsol_account, DEPOSIT)
+        .await;
+}
+
+#[tokio::test]
+async fn can_deposit_and_withdraw() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    suite::can_deposit_and_withdraw(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        WITHDRAWAL,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn quotes_deposits_and_withdrawals_one_to_one() {
+    let (mut context, beam, _, _) = setup_with_deposit().await;
+
+    let quote: DepositQuote = context.view(beam.quote_deposit(DEPOSIT)).await;
+    assert_eq!(quote.gsol_out, DEPOSIT);
+    let quote: WithdrawQuote = context.view(beam.quote_withdraw(WITHDRAWAL)).await;
+    assert_eq!(quote.lamports_out, WITHDRAWAL);
+}
+
+#[tokio::test]
+async fn can_withdraw_the_whole_reserve() {
+    let (mut context, beam, staker, gsol_account) = setup_with_deposit().await;
+
+    let withdraw = beam.withdraw(&staker.pubkey(), &gsol_account, DEPOSIT, DEPOSIT);
+    context.send(&[withdraw], &[&staker]).await.unwrap();
+
+    // The vault is left rent-exempt.
+    let rent = context.rent().await.minimum_balance(0);
+    assert_eq!(context.balance(&beam.vault_authority()).await, rent);
+    assert_eq!(context.token_balance(&gsol_account).await, 0);
+}
+
+#[tokio::test]
+async fn cannot_withdraw_below_the_minimum_out() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    let error = suite::cannot_withdraw_below_the_minimum_out(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        WITHDRAWAL,
+    )
+    .await;
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(ReserveBeamError::WithdrawalBelowMinimum))
+    );
+}
+
+#[tokio::test]
+async fn cannot_withdraw_more_than_the_reserve_holds() {
+    let (mut context, beam, staker, gsol_account) = setup_with_deposit().await;
+
+    let withdraw = beam.withdraw(&staker.pubkey(), &gsol_account, DEPOSIT + 1, 0);
+    let error = context.send(&[withdraw], &[&staker]).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(ReserveBeamError::InsufficientReserve))
+    );
+    assert_eq!(context.token_balance(&gsol_account).await, DEPOSIT);
+}
+
+#[tokio::test]
+async fn reports_no_yield() {
+    let (mut context, beam, _, _) = setup_with_deposit().await;
+
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert_eq!(value.value, DEPOSIT);
+    assert_eq!(value.gsol_supply, DEPOSIT);
+    assert_eq!(value.surplus, 0);
+    assert_eq!(value.extractable_yield, 0);
+
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    context.refresh_sunrise().await;
+    assert_eq!(
+        context.sunrise.state.epoch_report.beam_epoch_details[0].extractable_yield,
+        0
+    );
+}
+
+#[tokio::test]
+async fn does_not_support_delayed_withdrawals_burns_or_yield_extraction() {
+    let (mut context, _, _, _) = setup_with_deposit().await;
+
+    for instruction in [
+        unsupported(ix::OrderWithdrawal {
+            _lamports: WITHDRAWAL,
+        }),
+        unsupported(ix::RedeemTicket {}),
+        unsupported(ix::Burn {
+            _lamports: WITHDRAWAL,
+        }),
+        unsupported(ix::ExtractYield {}),
+    ] {
+        let error = context.send(&[instruction], &[]).await.unwrap_err();
+        assert_eq!(
+            error_code(error),
+            Some(u32::from(BeamInterfaceError::UnsupportedInstruction))
+        );
+    }
+}
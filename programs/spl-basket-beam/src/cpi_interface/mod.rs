@@ -0,0 +1,2 @@
+pub mod spl;
+pub mod sunrise;
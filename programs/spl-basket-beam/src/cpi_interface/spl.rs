@@ -0,0 +1,138 @@
+use crate::seeds::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::program::{invoke, invoke_signed},
+};
+
+pub fn deposit(accounts: &crate::Deposit, lamports: u64) -> Result<()> {
+    invoke(
+        &spl_stake_pool::instruction::deposit_sol(
+            &spl_stake_pool::ID,
+            &accounts.stake_pool.key(),
+            accounts.stake_pool_withdraw_authority.key,
+            accounts.reserve_stake_account.key,
+            accounts.depositor.key,
+            &accounts.pool_token_vault.key(),
+            accounts.manager_fee_account.key,
+            &accounts.pool_token_vault.key(),
+            &accounts.pool_mint.key(),
+            accounts.token_program.key,
+            lamports,
+        ),
+        &[
+            accounts.spl_stake_pool_program.to_account_info(),
+            accounts.stake_pool.to_account_info(),
+            accounts.stake_pool_withdraw_authority.to_account_info(),
+            accounts.reserve_stake_account.to_account_info(),
+            accounts.depositor.to_account_info(),
+            accounts.manager_fee_account.to_account_info(),
+            accounts.pool_token_vault.to_account_info(),
+            accounts.pool_mint.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.token_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Accounts required by the WithdrawSol instruction in the Stake Pool program,
+/// burning pool tokens from one of the basket's vaults.
+pub struct WithdrawSol<'info> {
+    pub state: Box<Account<'info, crate::state::State>>,
+    pub stake_pool: AccountInfo<'info>,
+    pub stake_pool_withdraw_authority: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub pool_token_vault: AccountInfo<'info>,
+    pub reserve_stake_account: AccountInfo<'info>,
+    pub to: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub sysvar_clock: AccountInfo<'info>,
+    pub sysvar_stake_history: AccountInfo<'info>,
+    pub native_stake_program: AccountInfo<'info>,
+    pub spl_stake_pool_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'a> From<&crate::Withdraw<'a>> for WithdrawSol<'a> {
+    fn from(accounts: &crate::Withdraw<'a>) -> Self {
+        Self {
+            state: accounts.state.clone(),
+            stake_pool: accounts.stake_pool.to_account_info(),
+            stake_pool_withdraw_authority: accounts.stake_pool_withdraw_authority.to_account_info(),
+            vault_authority: accounts.vault_authority.to_account_info(),
+            pool_token_vault: accounts.pool_token_vault.to_account_info(),
+            reserve_stake_account: accounts.reserve_stake_account.to_account_info(),
+            to: accounts.withdrawer.to_account_info(),
+            manager_fee_account: accounts.manager_fee_account.to_account_info(),
+            pool_mint: accounts.pool_mint.to_account_info(),
+            sysvar_clock: accounts.sysvar_clock.to_account_info(),
+            sysvar_stake_history: accounts.sysvar_stake_history.to_account_info(),
+            native_stake_program: accounts.native_stake_program.to_account_info(),
+            spl_stake_pool_program: accounts.spl_stake_pool_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        }
+    }
+}
+
+impl<'a> From<&crate::ExtractYield<'a>> for WithdrawSol<'a> {
+    fn from(accounts: &crate::ExtractYield<'a>) -> Self {
+        Self {
+            state: accounts.state.clone(),
+            stake_pool: accounts.stake_pool.to_account_info(),
+            stake_pool_withdraw_authority: accounts.stake_pool_withdraw_authority.to_account_info(),
+            vault_authority: accounts.vault_authority.to_account_info(),
+            pool_token_vault: accounts.pool_token_vault.to_account_info(),
+            reserve_stake_account: accounts.reserve_stake_account.to_account_info(),
+            to: accounts.yield_account.to_account_info(),
+            manager_fee_account: accounts.manager_fee_account.to_account_info(),
+            pool_mint: accounts.pool_mint.to_account_info(),
+            sysvar_clock: accounts.sysvar_clock.to_account_info(),
+            sysvar_stake_history: accounts.sysvar_stake_history.to_account_info(),
+            native_stake_program: accounts.native_stake_program.to_account_info(),
+            spl_stake_pool_program: accounts.spl_stake_pool_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+        }
+    }
+}
+
+pub fn withdraw(accounts: &WithdrawSol, pool_tokens: u64) -> Result<()> {
+    let bump = &[accounts.state.vault_authority_bump][..];
+    let state_address = accounts.state.key();
+    let seeds = &[state_address.as_ref(), VAULT_AUTHORITY, bump][..];
+
+    invoke_signed(
+        &spl_stake_pool::instruction::withdraw_sol(
+            &spl_stake_pool::ID,
+            accounts.stake_pool.key,
+            accounts.stake_pool_withdraw_authority.key,
+            accounts.vault_authority.key,
+            accounts.pool_token_vault.key,
+            accounts.reserve_stake_account.key,
+            accounts.to.key,
+            accounts.manager_fee_account.key,
+            accounts.pool_mint.key,
+            accounts.token_program.key,
+            pool_tokens,
+        ),
+        &[
+            accounts.spl_stake_pool_program.clone(),
+            accounts.stake_pool.clone(),
+            accounts.stake_pool_withdraw_authority.clone(),
+            accounts.vault_authority.clone(),
+            accounts.pool_token_vault.clone(),
+            accounts.reserve_stake_account.clone(),
+            accounts.to.clone(),
+            accounts.manager_fee_account.clone(),
+            accounts.pool_mint.clone(),
+            accounts.sysvar_clock.clone(),
+            accounts.sysvar_stake_history.clone(),
+            accounts.native_stake_program.clone(),
+            accounts.token_program.clone(),
+        ],
+        &[seeds],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::seeds::*;
+use crate::{Deposit, ExtractYield, UpdateEpochReport, Withdraw};
+use anchor_lang::prelude::*;
+use sunrise_beam_interface::core_cpi;
+use sunrise_beam_interface::{
+    impl_burn_gsol, impl_extract_yield, impl_mint_gsol, impl_update_epoch_report,
+};
+
+pub fn mint_gsol<'a>(
+    accounts: impl Into<core_cpi::MintGsol<'a>>,
+    cpi_program: AccountInfo<'a>,
+    sunrise_key: Pubkey,
+    state_bump: u8,
+    remaining_accounts: &[AccountInfo<'a>],
+    lamports: u64,
+) -> Result<()> {
+    let seeds = [STATE, sunrise_key.as_ref(), &[state_bump]];
+    core_cpi::mint_gsol(accounts, cpi_program, &seeds, remaining_accounts, lamports)
+}
+
+impl_mint_gsol!(Deposit);
+
+pub fn burn_gsol<'a>(
+    accounts: impl Into<core_cpi::BurnGsol<'a>>,
+    cpi_program: AccountInfo<'a>,
+    sunrise_key: Pubkey,
+    state_bump: u8,
+    remaining_accounts: &[AccountInfo<'a>],
+    lamports: u64,
+) -> Result<()> {
+    let seeds = [STATE, sunrise_key.as_ref(), &[state_bump]];
+    core_cpi::burn_gsol(accounts, cpi_program, &seeds, remaining_accounts, lamports)
+}
+
+impl_burn_gsol!(Withdraw => withdrawer);
+
+pub fn extract_yield<'a>(
+    accounts: impl Into<core_cpi::ExtractYield<'a>>,
+    cpi_program: AccountInfo<'a>,
+    sunrise_key: Pubkey,
+    state_bump: u8,
+    lamports: u64,
+) -> Result<()> {
+    let seeds = [STATE, sunrise_key.as_ref(), &[state_bump]];
+    core_cpi::extract_yield(accounts, cpi_program, &seeds, lamports)
+}
+
+impl_extract_yield!(ExtractYield);
+
+pub fn update_epoch_report<'a>(
+    accounts: impl Into<core_cpi::UpdateEpochReport<'a>>,
+    cpi_program: AccountInfo<'a>,
+    sunrise_key: Pubkey,
+    state_bump: u8,
+    surplus: i64,
+) -> Result<()> {
+    let seeds = [STATE, sunrise_key.as_ref(), &[state_bump]];
+    core_cpi::update_epoch_report(accounts, cpi_program, &seeds, surplus)
+}
+
+impl_update_epoch_report!(UpdateEpochReport);
@@ -0,0 +1,776 @@
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use cpi_interface::{spl as spl_interface, sunrise as sunrise_interface};
+use seeds::*;
+use spl_beam::cpi_interface::{
+    program::{NativeStakeProgram, SplStakePool},
+    stake_pool::StakePool,
+};
+use state::{PoolAllocation, State, StateEntry, MAX_POOLS};
+use std::ops::Deref;
+use sunrise_beam_interface::core_cpi;
+use sunrise_beam_interface::unsupported::unsupported;
+use sunrise_beam_interface::view::{BeamValue, DepositQuote, WithdrawQuote};
+
+use sunrise_core as sunrise_core_cpi;
+
+mod cpi_interface;
+mod seeds;
+mod state;
+mod utils;
+
+declare_id!("BWFJsqmPwMgj2v6xiXRSa1rKRejJkTbWpQJsGfBdMmmA");
+
+/// A beam backed by a weighted basket of SPL stake pools, with a pool token vault per pool.
+#[program]
+pub mod spl_basket_beam {
+    use super::*;
+    use spl_beam::utils::{
+        calculate_deposited_value, check_pool_updated, pool_tokens_from_lamports,
+    };
+
+    pub fn initialize(ctx: Context<Initialize>, input: StateEntry) -> Result<()> {
+        ctx.accounts.state.set_inner(input.into());
+        Ok(())
+    }
+
+    pub fn update(ctx: Context<Update>, update_input: StateEntry) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.update_authority = update_input.update_authority;
+        state.sunrise_state = update_input.sunrise_state;
+        state.vault_authority_bump = update_input.vault_authority_bump;
+        Ok(())
+    }
+
+    /// Adds a stake pool to the basket with target weight `weight`, creating its pool token vault.
+    pub fn add_pool(ctx: Context<AddPool>, weight: u32) -> Result<()> {
+        let stake_pool = ctx.accounts.stake_pool.key();
+        let state = &mut ctx.accounts.state;
+        require!(
+            state.get_pool_index(&stake_pool).is_none(),
+            SplBasketBeamError::PoolAlreadyInBasket
+        );
+        require_gt!(MAX_POOLS, state.pools.len(), SplBasketBeamError::BasketFull);
+
+        state.pools.push(PoolAllocation { stake_pool, weight });
+        Ok(())
+    }
+
+    /// Removes a stake pool from the basket.
+    ///
+    /// Errors if the beam's pool token vault for the pool still holds pool tokens:
+    /// set the pool's weight to 0 and withdraw from it until it is empty first.
+    pub fn remove_pool(ctx: Context<RemovePool>) -> Result<()> {
+        require_eq!(
+            ctx.accounts.pool_token_vault.amount,
+            0,
+            SplBasketBeamError::PoolNotEmpty
+        );
+        let state = &mut ctx.accounts.state;
+        let index = state
+            .get_pool_index(&ctx.accounts.stake_pool.key())
+            .ok_or(SplBasketBeamError::PoolNotInBasket)?;
+        state.pools.remove(index);
+        Ok(())
+    }
+
+    /// Sets the target weight of a stake pool in the basket.
+    /// Setting the weight to 0 stops deposits into the pool.
+    pub fn set_pool_weight(ctx: Context<Update>, stake_pool: Pubkey, weight: u32) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        let index = state
+            .get_pool_index(&stake_pool)
+            .ok_or(SplBasketBeamError::PoolNotInBasket)?;
+        state.pools[index].weight = weight;
+        Ok(())
+    }
+
+    /// Deposits `lamports` into the most under-weight stake pool in the basket.
+    ///
+    /// The remaining accounts must contain a (stake pool, pool token vault) pair
+    /// for every pool in the basket, in basket order, optionally followed by the
+    /// depositor's attribution.
+    /// The gSOL minted is the value of the pool tokens actually received by the beam.
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        // Check that the deposit is being routed to the most under-weight pool.
+        let (basket_accounts, _) = core_cpi::split_attribution(ctx.remaining_accounts);
+        let holdings = utils::load_basket_holdings(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.key(),
+            basket_accounts,
+        )?;
+        let values = holdings
+            .iter()
+            .map(|holding| holding.value())
+            .collect::<Result<Vec<_>>>()?;
+        let target_index = utils::most_underweight_pool(&ctx.accounts.state.weights(), &values)
+            .ok_or(SplBasketBeamError::NoDepositablePool)?;
+        require_keys_eq!(
+            ctx.accounts.stake_pool.key(),
+            ctx.accounts.state.pools[target_index].stake_pool,
+            SplBasketBeamError::NotMostUnderweightPool
+        );
+
+        let pool_tokens_before = ctx.accounts.pool_token_vault.amount;
+
+        // CPI: Deposit SOL to SPL stake pool.
+        spl_interface::deposit(ctx.accounts, lamports)?;
+
+        // Calculate the value of the pool tokens received, net of fees.
+        let deposited_lamports = calculate_deposited_value(
+            &mut ctx.accounts.stake_pool,
+            &mut ctx.accounts.pool_token_vault,
+            pool_tokens_before,
+        )?;
+
+        let state_bump = ctx.bumps.state;
+        // CPI: Mint GSOL of the same proportion as the value deposited to depositor.
+        sunrise_interface::mint_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            deposited_lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// Withdraws `lamports` from the reserve of a stake pool in the basket.
+    ///
+    /// Errors if the withdrawer receives fewer than `min_lamports_out` lamports
+    /// after the pool's SOL withdrawal fee.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
+        check_pool_updated(&ctx.accounts.stake_pool, ctx.accounts.sysvar_clock.epoch)?;
+
+        // Calculate the number of pool tokens needed to be burnt to withdraw `lamports` lamports.
+        let pool = &ctx.accounts.stake_pool;
+        let pool_tokens_amount = pool_tokens_from_lamports(&pool.clone().into_inner(), lamports)?;
+
+        let withdrawer_balance_before = ctx.accounts.withdrawer.lamports();
+
+        // CPI: Withdraw SOL from SPL stake pool.
+        let withdraw_sol_accounts = ctx.accounts.deref().into();
+        spl_interface::withdraw(&withdraw_sol_accounts, pool_tokens_amount)?;
+
+        let withdrawer_balance_after = ctx.accounts.withdrawer.lamports();
+        let withdrawn_lamports = withdrawer_balance_after.saturating_sub(withdrawer_balance_before);
+        require_gte!(
+            withdrawn_lamports,
+            min_lamports_out,
+            SplBasketBeamError::WithdrawalBelowMinimum
+        );
+
+        // CPI: Burn GSOL of the same proportion as the lamports withdrawn from depositor.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::burn_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn order_withdrawal(_ctx: Context<Unsupported>, _lamports: u64) -> Result<()> {
+        // The basket only supports immediate withdrawals from the reserve of a pool.
+        unsupported()
+    }
+
+    pub fn redeem_ticket(_ctx: Context<Unsupported>) -> Result<()> {
+        // The basket only supports immediate withdrawals from the reserve of a pool.
+        unsupported()
+    }
+
+    pub fn burn(_ctx: Context<Unsupported>, _lamports: u64) -> Result<()> {
+        // Donations to the basket are not supported.
+        unsupported()
+    }
+
+    /// Reports the surplus (or deficit) of the whole basket.
+    ///
+    /// The remaining accounts must contain a (stake pool, pool token vault) pair
+    /// for every pool in the basket, in basket order.
+    pub fn update_epoch_report(ctx: Context<UpdateEpochReport>) -> Result<()> {
+        let holdings = utils::load_basket_holdings(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.key(),
+            ctx.remaining_accounts,
+        )?;
+        let current_epoch = Clock::get()?.epoch;
+        for holding in holdings.iter() {
+            check_pool_updated(&holding.stake_pool, current_epoch)?;
+        }
+
+        // Calculate the surplus (or deficit) of the basket.
+        let surplus = utils::calculate_surplus(
+            &ctx.accounts.sunrise_state,
+            &ctx.accounts.state.key(),
+            &holdings,
+        )?;
+
+        // CPI: update the epoch report with the surplus.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::update_epoch_report(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            surplus,
+        )?;
+
+        Ok(())
+    }
+
+    /// Extracts the yield of the whole basket, up to the value held in one of its pools,
+    /// by withdrawing SOL from that pool to the yield account.
+    /// The protocol fee share of the yield is withdrawn to the treasury.
+    ///
+    /// The remaining accounts must contain a (stake pool, pool token vault) pair
+    /// for every pool in the basket, in basket order.
+    pub fn extract_yield(ctx: Context<ExtractYield>) -> Result<()> {
+        let holdings = utils::load_basket_holdings(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.key(),
+            ctx.remaining_accounts,
+        )?;
+        let current_epoch = ctx.accounts.sysvar_clock.epoch;
+        for holding in holdings.iter() {
+            check_pool_updated(&holding.stake_pool, current_epoch)?;
+        }
+
+        // Calculate how much yield can be extracted from the basket, and from this pool.
+        let extractable_yield = utils::calculate_extractable_yield(
+            &ctx.accounts.sunrise_state,
+            &ctx.accounts.state.key(),
+            &holdings,
+        )?;
+        let pool_index = ctx
+            .accounts
+            .state
+            .get_pool_index(&ctx.accounts.stake_pool.key())
+            .ok_or(SplBasketBeamError::PoolNotInBasket)?;
+        let lamports = extractable_yield.min(holdings[pool_index].value()?);
+        require_gt!(lamports, 0, SplBasketBeamError::InsufficientYieldToExtract);
+
+        let pool = &ctx.accounts.stake_pool;
+        let pool_tokens_amount = pool_tokens_from_lamports(&pool.clone().into_inner(), lamports)?;
+        let protocol_fee_pool_tokens = ctx
+            .accounts
+            .sunrise_state
+            .protocol_fee(pool_tokens_amount)?;
+        let treasury =
+            core_cpi::fee_treasury(ctx.accounts.treasury.as_ref(), protocol_fee_pool_tokens)?;
+        let treasury_balance = || treasury.map(|t| t.lamports()).unwrap_or_default();
+
+        // CPI: Withdraw the protocol fee share of the yield from the SPL stake pool to the treasury,
+        // and the rest to the yield account.
+        let yield_balance_before = ctx.accounts.yield_account.lamports();
+        let treasury_balance_before = treasury_balance();
+        if let Some(treasury) = treasury {
+            let withdraw_sol_accounts = spl_interface::WithdrawSol {
+                to: treasury.to_account_info(),
+                ..spl_interface::WithdrawSol::from(ctx.accounts.deref())
+            };
+            spl_interface::withdraw(&withdraw_sol_accounts, protocol_fee_pool_tokens)?;
+        }
+        let withdraw_sol_accounts = ctx.accounts.deref().into();
+        spl_interface::withdraw(
+            &withdraw_sol_accounts,
+            pool_tokens_amount.saturating_sub(protocol_fee_pool_tokens),
+        )?;
+        let extracted_lamports = ctx
+            .accounts
+            .yield_account
+            .lamports()
+            .saturating_sub(yield_balance_before)
+            .saturating_add(treasury_balance().saturating_sub(treasury_balance_before));
+
+        // CPI: update the epoch report with the extracted yield.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::extract_yield(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            state_bump,
+            extracted_lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// View: quotes a [deposit] of `lamports` into the most under-weight stake pool in the basket,
+    /// returning the gSOL that would be minted and the pool tokens that the beam would receive.
+    ///
+    /// The remaining accounts must contain a (stake pool, pool token vault) pair
+    /// for every pool in the basket, in basket order.
+    pub fn quote_deposit(ctx: Context<View>, lamports: u64) -> Result<DepositQuote> {
+        let holdings = utils::load_basket_holdings(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.key(),
+            ctx.remaining_accounts,
+        )?;
+        let values = holdings
+            .iter()
+            .map(|holding| holding.value())
+            .collect::<Result<Vec<_>>>()?;
+        let target_index = utils::most_underweight_pool(&ctx.accounts.state.weights(), &values)
+            .ok_or(SplBasketBeamError::NoDepositablePool)?;
+
+        let stake_pool = &holdings[target_index].stake_pool;
+        check_pool_updated(stake_pool, Clock::get()?.epoch)?;
+        spl_beam::utils::quote_deposit(stake_pool, lamports)
+    }
+
+    /// View: quotes a [withdraw] of `lamports` from the reserve of the given stake pool,
+    /// returning the lamports that would be received after the pool's SOL withdrawal fee,
+    /// and the pool tokens that the beam would spend.
+    pub fn quote_withdraw(ctx: Context<QuoteWithdraw>, lamports: u64) -> Result<WithdrawQuote> {
+        check_pool_updated(&ctx.accounts.stake_pool, Clock::get()?.epoch)?;
+        spl_beam::utils::quote_withdraw(&ctx.accounts.stake_pool, lamports)
+    }
+
+    /// View: returns the value of the pool tokens in every vault of the basket, the surplus that
+    /// [update_epoch_report] would report and the yield of the whole basket. [extract_yield]
+    /// extracts this yield up to the value held in the pool it withdraws from.
+    ///
+    /// The remaining accounts must contain a (stake pool, pool token vault) pair
+    /// for every pool in the basket, in basket order.
+    pub fn get_beam_value(ctx: Context<View>) -> Result<BeamValue> {
+        let holdings = utils::load_basket_holdings(
+            &ctx.accounts.state,
+            &ctx.accounts.vault_authority.key(),
+            ctx.remaining_accounts,
+        )?;
+        let current_epoch = Clock::get()?.epoch;
+        let mut value: u64 = 0;
+        for holding in holdings.iter() {
+            check_pool_updated(&holding.stake_pool, current_epoch)?;
+            value = value
+                .checked_add(holding.value()?)
+                .ok_or(SplBasketBeamError::CalculationFailure)?;
+        }
+
+        let gsol_supply = ctx
+            .accounts
+            .sunrise_state
+            .get_beam_details(&ctx.accounts.state.key())
+            .ok_or(sunrise_core::BeamError::UnidentifiedBeam)?
+            .partial_gsol_supply;
+        let surplus = utils::calculate_surplus(
+            &ctx.accounts.sunrise_state,
+            &ctx.accounts.state.key(),
+            &holdings,
+        )?;
+
+        Ok(BeamValue {
+            value,
+            gsol_supply,
+            surplus,
+            extractable_yield: surplus.max(0) as u64,
+        })
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(input: StateEntry)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        space = State::SPACE,
+        payer = payer,
+        seeds = [STATE, input.sunrise_state.as_ref()],
+        bump
+    )]
+    pub state: Account<'info, State>,
+    /// CHECK: PDA authority of the pool tokens.
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = input.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Account<'info, State>,
+}
+
+#[derive(Accounts)]
+pub struct AddPool<'info> {
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(has_one = pool_mint)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+    pub pool_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = update_authority,
+        associated_token::mint = pool_mint,
+        associated_token::authority = vault_authority
+    )]
+    pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct RemovePool<'info> {
+    pub update_authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(has_one = pool_mint)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+    pub pool_mint: Box<Account<'info, Mint>>,
+    #[account(
+        associated_token::mint = pool_mint,
+        associated_token::authority = vault_authority
+    )]
+    pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(mut,token::mint = gsol_mint)]
+    pub mint_gsol_to: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub gsol_mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut, has_one = pool_mint)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = vault_authority
+    )]
+    pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub reserve_stake_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub manager_fee_account: UncheckedAccount<'info>,
+    pub spl_stake_pool_program: Program<'info, SplStakePool>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump,
+        constraint = state.get_pool_index(&stake_pool.key()).is_some() @ SplBasketBeamError::PoolNotInBasket
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut, has_one = pool_mint)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = vault_authority
+    )]
+    pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub reserve_stake_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub manager_fee_account: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub sysvar_stake_history: UncheckedAccount<'info>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+    pub spl_stake_pool_program: Program<'info, SplStakePool>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEpochReport<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+    mut, // Update the extractable yield on the state's epoch report.
+    )]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    /// Required to update the core state epoch report
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Account<'info, Mint>,
+    #[account(mut)]
+    /// CHECK: Updated with the epoch report - verified in CPI to Sunrise program.
+    pub backing_oracle: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct View<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteWithdraw<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump,
+        constraint = state.get_pool_index(&stake_pool.key()).is_some() @ SplBasketBeamError::PoolNotInBasket
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    pub stake_pool: Box<Account<'info, StakePool>>,
+}
+
+#[derive(Accounts)]
+pub struct ExtractYield<'info> {
+    #[account(
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut, // Update the extracted yield on the state's epoch report.
+        has_one = yield_account
+    )]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    /// CHECK: Matches the yield key stored in the state.
+    pub yield_account: UncheckedAccount<'info>,
+    #[account(mut, address = sunrise_state.treasury)]
+    /// CHECK: Matches the treasury key stored in the state.
+    /// Only needed while the state charges a protocol fee.
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut, has_one = pool_mint)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = vault_authority
+    )]
+    pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub reserve_stake_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub manager_fee_account: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub sysvar_stake_history: UncheckedAccount<'info>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+    pub spl_stake_pool_program: Program<'info, SplStakePool>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// The accounts of the instructions of the standard interface that the basket does not support.
+#[derive(Accounts)]
+pub struct Unsupported {}
+
+#[error_code]
+pub enum SplBasketBeamError {
+    #[msg("An error occurred during calculation")]
+    CalculationFailure,
+    #[msg("The stake pool is already in the basket")]
+    PoolAlreadyInBasket,
+    #[msg("The basket already contains the maximum number of stake pools")]
+    BasketFull,
+    #[msg("The stake pool is not in the basket")]
+    PoolNotInBasket,
+    #[msg("The stake pool and vault accounts of every pool in the basket must be passed in order")]
+    IncompleteBasket,
+    #[msg("No pool in the basket has a non-zero weight")]
+    NoDepositablePool,
+    #[msg("Deposits must go to the most under-weight pool in the basket")]
+    NotMostUnderweightPool,
+    #[msg("The withdrawal paid out fewer lamports than the requested minimum")]
+    WithdrawalBelowMinimum,
+    #[msg("There is no yield to extract from this pool")]
+    InsufficientYieldToExtract,
+    #[msg("The beam's vault for the stake pool still holds pool tokens")]
+    PoolNotEmpty,
+}
@@ -0,0 +1,4 @@
+/// Seed of the PDA that can authorize spending from the vaults that hold pool tokens.
+pub const VAULT_AUTHORITY: &[u8] = b"vault_authority";
+/// Seed of this program's state address.
+pub const STATE: &[u8] = b"sunrise_spl_basket";
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+/// The maximum number of stake pools in a basket.
+pub const MAX_POOLS: usize = 8;
+
+#[account]
+#[derive(Debug, Default)]
+pub struct State {
+    /// The update authority of the state.
+    pub update_authority: Pubkey,
+
+    /// The state of the main sunrise beam.
+    pub sunrise_state: Pubkey,
+
+    /// The bump of the PDA that can authorize spending from the vaults
+    /// that hold pool tokens.
+    pub vault_authority_bump: u8,
+
+    /// The stake pools in the basket, and their target weights.
+    pub pools: Vec<PoolAllocation>,
+}
+
+impl State {
+    pub const SPACE: usize = 8 +  /*discriminator*/
+        32 + /*update_authority*/
+        32 + /*sunrise_state*/
+        1 + /*vault_authority_bump*/
+        4 + MAX_POOLS * PoolAllocation::SPACE; /*pools*/
+
+    /// Gets the index of a stake pool in the basket.
+    pub fn get_pool_index(&self, stake_pool: &Pubkey) -> Option<usize> {
+        self.pools
+            .iter()
+            .position(|allocation| allocation.stake_pool == *stake_pool)
+    }
+
+    /// Gets the target weights of the pools in the basket.
+    pub fn weights(&self) -> Vec<u32> {
+        self.pools
+            .iter()
+            .map(|allocation| allocation.weight)
+            .collect()
+    }
+}
+
+/// A stake pool in the basket, and its target weight relative to the other pools.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct PoolAllocation {
+    /// The state account of the spl pool.
+    pub stake_pool: Pubkey,
+    /// The target weight of the pool. A pool with weight 0 receives no deposits.
+    pub weight: u32,
+}
+
+impl PoolAllocation {
+    pub const SPACE: usize = 32 + /*stake_pool*/
+        4; /*weight*/
+}
+
+// Anchor-ts only supports deserialization(in instruction arguments) for types
+// that explicitly derive AnchorSerialize & AnchorDeserialize.
+// https://github.com/coral-xyz/anchor/issues/2545
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StateEntry {
+    pub update_authority: Pubkey,
+    pub sunrise_state: Pubkey,
+    pub vault_authority_bump: u8,
+}
+
+impl From<StateEntry> for State {
+    fn from(se: StateEntry) -> Self {
+        State {
+            update_authority: se.update_authority,
+            sunrise_state: se.sunrise_state,
+            vault_authority_bump: se.vault_authority_bump,
+            pools: vec![],
+        }
+    }
+}
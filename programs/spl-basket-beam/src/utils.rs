@@ -0,0 +1,148 @@
+use crate::state::State;
+use crate::SplBasketBeamError;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::TokenAccount;
+use spl_beam::cpi_interface::stake_pool::StakePool;
+use spl_beam::utils::lamports_from_pool_tokens;
+use sunrise_core::BeamError;
+
+/// A stake pool in the basket, and the pool tokens held by the beam in its vault.
+pub struct PoolHolding {
+    pub stake_pool: StakePool,
+    pub pool_tokens: u64,
+}
+
+impl PoolHolding {
+    /// The value in lamports of the pool tokens held by the beam.
+    pub fn value(&self) -> Result<u64> {
+        lamports_from_pool_tokens(&self.stake_pool, self.pool_tokens)
+    }
+}
+
+/// Loads the holdings of every pool in the basket from `accounts`, which must contain
+/// a (stake pool, pool token vault) pair for each pool, in basket order.
+pub fn load_basket_holdings(
+    state: &State,
+    vault_authority: &Pubkey,
+    accounts: &[AccountInfo],
+) -> Result<Vec<PoolHolding>> {
+    require_eq!(
+        accounts.len(),
+        state.pools.len() * 2,
+        SplBasketBeamError::IncompleteBasket
+    );
+
+    state
+        .pools
+        .iter()
+        .zip(accounts.chunks(2))
+        .map(|(allocation, pool_accounts)| {
+            let (stake_pool_info, vault_info) = (&pool_accounts[0], &pool_accounts[1]);
+            require_keys_eq!(
+                stake_pool_info.key(),
+                allocation.stake_pool,
+                SplBasketBeamError::IncompleteBasket
+            );
+            require_keys_eq!(
+                *stake_pool_info.owner,
+                StakePool::owner(),
+                SplBasketBeamError::IncompleteBasket
+            );
+            let stake_pool =
+                StakePool::try_deserialize(&mut &stake_pool_info.try_borrow_data()?[..])?;
+
+            require_keys_eq!(
+                vault_info.key(),
+                get_associated_token_address(vault_authority, &stake_pool.pool_mint),
+                SplBasketBeamError::IncompleteBasket
+            );
+            let vault = TokenAccount::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+
+            Ok(PoolHolding {
+                stake_pool,
+                pool_tokens: vault.amount,
+            })
+        })
+        .collect()
+}
+
+/// Finds the pool whose value is furthest below its target weight, relative to the other pools.
+/// This is the pool with the lowest value per unit of weight.
+/// Pools with weight 0 are never chosen. Ties are resolved to the first pool in the basket.
+pub fn most_underweight_pool(weights: &[u32], values: &[u64]) -> Option<usize> {
+    weights
+        .iter()
+        .zip(values)
+        .enumerate()
+        .filter(|(_, (weight, _))| **weight > 0)
+        .min_by(|(_, (weight_a, value_a)), (_, (weight_b, value_b))| {
+            // Compare value_a / weight_a with value_b / weight_b without dividing.
+            let a = (**value_a as u128) * (**weight_b as u128);
+            let b = (**value_b as u128) * (**weight_a as u128);
+            a.cmp(&b)
+        })
+        .map(|(index, _)| index)
+}
+
+/// Calculates the amount of yield that can be extracted from the basket.
+/// This is the [surplus][calculate_surplus] of the basket, or zero if it is in deficit.
+pub fn calculate_extractable_yield(
+    sunrise_state: &sunrise_core::State,
+    beam: &Pubkey,
+    holdings: &[PoolHolding],
+) -> Result<u64> {
+    let surplus = calculate_surplus(sunrise_state, beam, holdings)?;
+    Ok(surplus.max(0) as u64)
+}
+
+/// Calculates the surplus of the basket, which is negative if the basket is in deficit.
+/// This is calculated as:
+/// The value of the pool tokens in all vaults minus the amount of SOL staked in the beam
+pub fn calculate_surplus(
+    sunrise_state: &sunrise_core::State,
+    beam: &Pubkey,
+    holdings: &[PoolHolding],
+) -> Result<i64> {
+    let mut owned_value: u64 = 0;
+    for holding in holdings {
+        owned_value = owned_value
+            .checked_add(holding.value()?)
+            .ok_or(SplBasketBeamError::CalculationFailure)?;
+    }
+
+    // Calculate the amount of SOL staked in the beam
+    let details = sunrise_state
+        .get_beam_details(beam)
+        .ok_or(BeamError::UnidentifiedBeam)?;
+    let staked_sol = details.partial_gsol_supply;
+
+    msg!("owned_value: {}, staked_sol: {}", owned_value, staked_sol);
+
+    Ok(beam_math::surplus(owned_value, staked_sol)?)
+}
+
+#[cfg(test)]
+mod utils_tests {
+    use super::*;
+
+    #[test]
+    fn test_most_underweight_pool() {
+        // Empty basket
+        assert_eq!(most_underweight_pool(&[], &[]), None);
+        // Equal weights - the pool with the lowest value
+        assert_eq!(most_underweight_pool(&[1, 1, 1], &[30, 10, 20]), Some(1));
+        // Target 75%/25% of 100 - the second pool holds 20 of a target 25
+        assert_eq!(most_underweight_pool(&[3, 1], &[80, 20]), Some(1));
+        // Target 75%/25% of 100 - the first pool holds 70 of a target 75
+        assert_eq!(most_underweight_pool(&[3, 1], &[70, 30]), Some(0));
+        // Ties go to the first pool
+        assert_eq!(most_underweight_pool(&[1, 1], &[0, 0]), Some(0));
+    }
+
+    #[test]
+    fn test_most_underweight_pool_ignores_zero_weights() {
+        assert_eq!(most_underweight_pool(&[0, 1], &[0, 100]), Some(1));
+        assert_eq!(most_underweight_pool(&[0, 0], &[0, 0]), None);
+    }
+}
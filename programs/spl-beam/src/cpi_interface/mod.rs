@@ -3,3 +3,4 @@ pub mod spl;
 pub mod stake_account;
 pub mod stake_pool;
 pub mod sunrise;
+pub mod token;
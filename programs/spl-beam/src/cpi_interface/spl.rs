@@ -2,11 +2,16 @@ use crate::constants::STAKE_ACCOUNT_SIZE;
 use crate::cpi_interface::stake_pool::StakePool;
 use crate::seeds::*;
 use crate::state::State;
-use crate::{ExtractYield, SplBeamError, WithdrawStake};
+use crate::{ExtractYield, OrderWithdrawal, SplBeamError, WithdrawStake};
 use anchor_lang::{
     prelude::*,
-    solana_program::program::{invoke, invoke_signed},
+    solana_program::{
+        instruction::Instruction,
+        program::{invoke, invoke_signed},
+    },
 };
+use borsh::BorshSerialize;
+use spl_stake_pool::instruction::StakePoolInstruction;
 
 pub fn deposit(accounts: &crate::Deposit, lamports: u64) -> Result<()> {
     invoke(
@@ -47,7 +52,7 @@ pub fn deposit_stake(accounts: &crate::DepositStake) -> Result<()> {
     // transfer these permissions.
     let deposit_stake_instructions = &spl_stake_pool::instruction::deposit_stake(
         &spl_stake_pool::ID,
-        accounts.stake_pool.key,
+        &accounts.stake_pool.key(),
         accounts.validator_list.key,
         accounts.stake_pool_withdraw_authority.key,
         accounts.stake_account.key,
@@ -137,6 +142,97 @@ pub fn withdraw(accounts: &crate::Withdraw, pool_token_lamports: u64) -> Result<
     Ok(())
 }
 
+/// Updates the balances of the validators in the pool's validator list from `start_index`.
+/// `validator_stake_accounts` are pairs of each validator's stake account and transient stake account.
+pub fn update_validator_list_balance<'info>(
+    accounts: &crate::RefreshPool<'info>,
+    validator_stake_accounts: &[AccountInfo<'info>],
+    start_index: u32,
+) -> Result<()> {
+    let mut account_metas = vec![
+        AccountMeta::new_readonly(accounts.stake_pool.key(), false),
+        AccountMeta::new_readonly(accounts.stake_pool_withdraw_authority.key(), false),
+        AccountMeta::new(accounts.validator_list.key(), false),
+        AccountMeta::new(accounts.reserve_stake_account.key(), false),
+        AccountMeta::new_readonly(accounts.sysvar_clock.key(), false),
+        AccountMeta::new_readonly(accounts.sysvar_stake_history.key(), false),
+        AccountMeta::new_readonly(accounts.native_stake_program.key(), false),
+    ];
+    account_metas.extend(
+        validator_stake_accounts
+            .iter()
+            .map(|account| AccountMeta::new(account.key(), false)),
+    );
+
+    let instruction = Instruction {
+        program_id: spl_stake_pool::ID,
+        accounts: account_metas,
+        data: StakePoolInstruction::UpdateValidatorListBalance {
+            start_index,
+            no_merge: false,
+        }
+        .try_to_vec()?,
+    };
+
+    let mut account_infos = vec![
+        accounts.spl_stake_pool_program.to_account_info(),
+        accounts.stake_pool.to_account_info(),
+        accounts.stake_pool_withdraw_authority.to_account_info(),
+        accounts.validator_list.to_account_info(),
+        accounts.reserve_stake_account.to_account_info(),
+        accounts.sysvar_clock.to_account_info(),
+        accounts.sysvar_stake_history.to_account_info(),
+        accounts.native_stake_program.to_account_info(),
+    ];
+    account_infos.extend_from_slice(validator_stake_accounts);
+
+    invoke(&instruction, &account_infos)?;
+
+    Ok(())
+}
+
+/// Updates the pool's total balance from its validator list, and removes any
+/// validators that were marked for removal.
+pub fn update_stake_pool_balance(accounts: &crate::RefreshPool) -> Result<()> {
+    invoke(
+        &spl_stake_pool::instruction::update_stake_pool_balance(
+            &spl_stake_pool::ID,
+            &accounts.stake_pool.key(),
+            accounts.stake_pool_withdraw_authority.key,
+            accounts.validator_list.key,
+            accounts.reserve_stake_account.key,
+            accounts.manager_fee_account.key,
+            &accounts.pool_mint.key(),
+            accounts.token_program.key,
+        ),
+        &[
+            accounts.spl_stake_pool_program.to_account_info(),
+            accounts.stake_pool.to_account_info(),
+            accounts.stake_pool_withdraw_authority.to_account_info(),
+            accounts.validator_list.to_account_info(),
+            accounts.reserve_stake_account.to_account_info(),
+            accounts.manager_fee_account.to_account_info(),
+            accounts.pool_mint.to_account_info(),
+            accounts.token_program.to_account_info(),
+        ],
+    )?;
+
+    invoke(
+        &spl_stake_pool::instruction::cleanup_removed_validator_entries(
+            &spl_stake_pool::ID,
+            &accounts.stake_pool.key(),
+            accounts.validator_list.key,
+        ),
+        &[
+            accounts.spl_stake_pool_program.to_account_info(),
+            accounts.stake_pool.to_account_info(),
+            accounts.validator_list.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
 /// Accounts required by the WithdrawStake program in the Stake Pool program
 pub struct ExtractStakeAccount<'info> {
     pub state: Box<Account<'info, State>>,
@@ -201,7 +297,8 @@ impl<'a> From<WithdrawStake<'a>> for ExtractStakeAccount<'a> {
                 .to_account_info(),
             stake_to_split: withdraw_stake.stake_account_to_split.to_account_info(),
             stake_to_receive: withdraw_stake.new_stake_account.to_account_info(),
-            user_stake_authority: withdraw_stake.vault_authority.to_account_info(),
+            // The withdrawer is made the authority of the new stake account.
+            user_stake_authority: withdraw_stake.withdrawer.to_account_info(),
             user_transfer_authority: withdraw_stake.vault_authority.to_account_info(),
             user_pool_token_account: withdraw_stake.pool_token_vault.to_account_info(),
             manager_fee_account: withdraw_stake.manager_fee_account.to_account_info(),
@@ -217,8 +314,42 @@ impl<'a> From<&WithdrawStake<'a>> for ExtractStakeAccount<'a> {
         withdraw_stake.to_owned().into()
     }
 }
+impl<'a> From<OrderWithdrawal<'a>> for ExtractStakeAccount<'a> {
+    /// Convert the OrderWithdrawal beam instruction accounts to the ExtractStakeAccount accounts
+    fn from(order_withdrawal: OrderWithdrawal<'a>) -> Self {
+        Self {
+            state: order_withdrawal.state,
+            sunrise_state: order_withdrawal.sunrise_state,
+            stake_pool_program: order_withdrawal.spl_stake_pool_program.to_account_info(),
+            stake_pool: order_withdrawal.stake_pool,
+            validator_list_storage: order_withdrawal.validator_stake_list.to_account_info(),
+            stake_pool_withdraw: order_withdrawal
+                .stake_pool_withdraw_authority
+                .to_account_info(),
+            stake_to_split: order_withdrawal.stake_account_to_split.to_account_info(),
+            stake_to_receive: order_withdrawal.new_stake_account.to_account_info(),
+            user_stake_authority: order_withdrawal.vault_authority.to_account_info(),
+            user_transfer_authority: order_withdrawal.vault_authority.to_account_info(),
+            user_pool_token_account: order_withdrawal.pool_token_vault.to_account_info(),
+            manager_fee_account: order_withdrawal.manager_fee_account.to_account_info(),
+            pool_mint: order_withdrawal.pool_mint.to_account_info(),
+            token_program: order_withdrawal.token_program.to_account_info(),
+            native_stake_program: order_withdrawal.native_stake_program.to_account_info(),
+            sysvar_clock: order_withdrawal.sysvar_clock.to_account_info(),
+        }
+    }
+}
+impl<'a> From<&OrderWithdrawal<'a>> for ExtractStakeAccount<'a> {
+    fn from(order_withdrawal: &OrderWithdrawal<'a>) -> Self {
+        order_withdrawal.to_owned().into()
+    }
+}
 
-pub fn extract_stake(accounts: &ExtractStakeAccount, lamports: u64) -> Result<()> {
+/// Withdraws up to `lamports` from the pool into the new stake account, less the rent of a
+/// stake account and capped at the lamports of the stake account split from.
+///
+/// Returns the lamports extracted.
+pub fn extract_stake(accounts: &ExtractStakeAccount, lamports: u64) -> Result<u64> {
     let bump = &[accounts.state.vault_authority_bump][..];
     let state_address = accounts.state.key();
     let seeds = &[state_address.as_ref(), VAULT_AUTHORITY, bump][..];
@@ -275,5 +406,5 @@ pub fn extract_stake(accounts: &ExtractStakeAccount, lamports: u64) -> Result<()
         &[seeds],
     )?;
 
-    Ok(())
+    Ok(total_extractable_lamports)
 }
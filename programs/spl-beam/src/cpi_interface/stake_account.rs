@@ -1,6 +1,6 @@
 use crate::seeds::VAULT_AUTHORITY;
 use crate::state::State;
-use crate::ExtractYield;
+use crate::{ExtractYield, OrderWithdrawal, RedeemTicket};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::clock::Epoch;
 use anchor_lang::solana_program::program::invoke_signed;
@@ -52,6 +52,13 @@ impl StakeAccount {
             _ => false,
         }
     }
+
+    /// Whether the stake account in `account_info` is delegated, reading its current data.
+    /// Stake split from a stake pool's reserve is initialized but not delegated.
+    pub fn is_delegated(account_info: &AccountInfo) -> Result<bool> {
+        let stake_account = Self::try_deserialize(&mut &account_info.data.borrow()[..])?;
+        Ok(matches!(stake_account.0, StakeStateV2::Stake(..)))
+    }
 }
 
 pub struct ClaimStakeAccount<'info> {
@@ -83,6 +90,72 @@ impl<'a> From<&ExtractYield<'a>> for ClaimStakeAccount<'a> {
     }
 }
 
+impl<'a> From<RedeemTicket<'a>> for ClaimStakeAccount<'a> {
+    /// Convert the RedeemTicket beam instruction accounts to the ClaimStakeAccount accounts
+    fn from(redeem_ticket: RedeemTicket<'a>) -> Self {
+        Self {
+            state: redeem_ticket.state,
+            stake_account: redeem_ticket.stake_account,
+            withdrawer: redeem_ticket.vault_authority.to_account_info(),
+            to: redeem_ticket.beneficiary.to_account_info(),
+            native_stake_program: redeem_ticket.native_stake_program.to_account_info(),
+            sysvar_clock: redeem_ticket.sysvar_clock.to_account_info(),
+            sysvar_stake_history: redeem_ticket.sysvar_stake_history.to_account_info(),
+        }
+    }
+}
+impl<'a> From<&RedeemTicket<'a>> for ClaimStakeAccount<'a> {
+    fn from(redeem_ticket: &RedeemTicket<'a>) -> Self {
+        redeem_ticket.to_owned().into()
+    }
+}
+
+pub struct DeactivateStakeAccount<'info> {
+    pub state: Box<Account<'info, State>>,
+    pub stake_account: AccountInfo<'info>,
+    pub stake_authority: AccountInfo<'info>,
+    pub native_stake_program: AccountInfo<'info>,
+    pub sysvar_clock: AccountInfo<'info>,
+}
+impl<'a> From<OrderWithdrawal<'a>> for DeactivateStakeAccount<'a> {
+    /// Convert the OrderWithdrawal beam instruction accounts to the DeactivateStakeAccount accounts
+    fn from(order_withdrawal: OrderWithdrawal<'a>) -> Self {
+        Self {
+            state: order_withdrawal.state,
+            stake_account: order_withdrawal.new_stake_account.to_account_info(),
+            stake_authority: order_withdrawal.vault_authority.to_account_info(),
+            native_stake_program: order_withdrawal.native_stake_program.to_account_info(),
+            sysvar_clock: order_withdrawal.sysvar_clock.to_account_info(),
+        }
+    }
+}
+impl<'a> From<&OrderWithdrawal<'a>> for DeactivateStakeAccount<'a> {
+    fn from(order_withdrawal: &OrderWithdrawal<'a>) -> Self {
+        order_withdrawal.to_owned().into()
+    }
+}
+
+pub fn deactivate_stake_account(accounts: &DeactivateStakeAccount) -> Result<()> {
+    let bump = &[accounts.state.vault_authority_bump][..];
+    let state_address = accounts.state.key();
+    let seeds = &[state_address.as_ref(), VAULT_AUTHORITY, bump][..];
+
+    invoke_signed(
+        &solana_program::stake::instruction::deactivate_stake(
+            accounts.stake_account.key,
+            accounts.stake_authority.key,
+        ),
+        &[
+            accounts.stake_account.clone(),
+            accounts.sysvar_clock.clone(),
+            accounts.stake_authority.clone(),
+        ],
+        &[seeds],
+    )?;
+
+    Ok(())
+}
+
 pub fn claim_stake_account(accounts: &ClaimStakeAccount, lamports: u64) -> Result<()> {
     let bump = &[accounts.state.vault_authority_bump][..];
     let state_address = accounts.state.key();
@@ -0,0 +1,21 @@
+use crate::seeds::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer as cpi_transfer, Transfer};
+
+pub fn transfer_from_vault(accounts: &crate::WithdrawAsLst, pool_tokens: u64) -> Result<()> {
+    let bump = &[accounts.state.vault_authority_bump][..];
+    let state_address = accounts.state.key();
+    let seeds = &[state_address.as_ref(), VAULT_AUTHORITY, bump][..];
+
+    let cpi_accounts = Transfer {
+        from: accounts.pool_token_vault.to_account_info(),
+        to: accounts.transfer_pool_tokens_to.to_account_info(),
+        authority: accounts.vault_authority.to_account_info(),
+    };
+
+    cpi_transfer(
+        CpiContext::new(accounts.token_program.to_account_info(), cpi_accounts)
+            .with_signer(&[seeds]),
+        pool_tokens,
+    )
+}
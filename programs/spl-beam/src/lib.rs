@@ -9,28 +9,31 @@ use cpi_interface::{
     program::{NativeStakeProgram, SplStakePool},
     spl as spl_interface,
     stake_pool::StakePool,
-    sunrise as sunrise_interface,
+    sunrise as sunrise_interface, token as token_interface,
 };
 use seeds::*;
-use state::{State, StateEntry};
+use state::{State, StateEntry, WithdrawalTicket};
 use std::ops::Deref;
+use sunrise_beam_interface::core_cpi;
+use sunrise_beam_interface::view::{BeamValue, DepositQuote, WithdrawQuote};
 
 use crate::cpi_interface::stake_account::StakeAccount;
 use sunrise_core as sunrise_core_cpi;
 
 mod constants;
-mod cpi_interface;
-mod seeds;
-mod state;
-mod utils;
+pub mod cpi_interface;
+pub mod seeds;
+pub mod state;
+pub mod utils;
 
 declare_id!("EUZfY4LePXSZVMvRuiVzbxazw9yBDYU99DpGJKCthxbS");
 
 #[program]
 pub mod spl_beam {
     use super::*;
-    use crate::cpi_interface::stake_account::claim_stake_account;
-    use crate::utils::proportional;
+    use crate::cpi_interface::stake_account::{
+        claim_stake_account, deactivate_stake_account, ClaimStakeAccount,
+    };
 
     pub fn initialize(ctx: Context<Initialize>, input: StateEntry) -> Result<()> {
         ctx.accounts.state.set_inner(input.into());
@@ -53,53 +56,172 @@ pub mod spl_beam {
         Ok(())
     }
 
-    pub fn deposit(ctx: Context<Deposit>, lamports: u64) -> Result<()> {
+    /// Deposits `lamports` into the stake pool.
+    ///
+    /// The gSOL minted is the value of the pool tokens actually received by the beam,
+    /// which is less than `lamports` if the pool charges deposit fees.
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        let pool_tokens_before = ctx.accounts.pool_token_vault.amount;
+
         // CPI: Deposit SOL to SPL stake pool.
         spl_interface::deposit(ctx.accounts, lamports)?;
 
+        // Calculate the value of the pool tokens received, net of fees.
+        let deposited_lamports = utils::calculate_deposited_value(
+            &mut ctx.accounts.stake_pool,
+            &mut ctx.accounts.pool_token_vault,
+            pool_tokens_before,
+        )?;
+
         let state_bump = ctx.bumps.state;
-        // CPI: Mint GSOL of the same proportion as the lamports deposited to depositor.
+        // CPI: Mint GSOL of the same proportion as the value deposited to depositor.
         sunrise_interface::mint_gsol(
             ctx.accounts.deref(),
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             ctx.accounts.stake_pool.key(),
             state_bump,
-            lamports,
+            ctx.remaining_accounts,
+            deposited_lamports,
         )?;
 
         Ok(())
     }
 
-    pub fn deposit_stake(ctx: Context<DepositStake>) -> Result<()> {
+    /// Deposits a stake account into the stake pool.
+    ///
+    /// The gSOL minted is the value of the pool tokens actually received by the beam,
+    /// which is less than the delegated stake if the pool charges deposit fees.
+    pub fn deposit_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositStake<'info>>,
+    ) -> Result<()> {
         // Get the stake value in lamports of the stake account.
         let lamports = utils::get_delegated_stake_amount(&ctx.accounts.stake_account)?;
+        msg!("Depositing stake account of {} lamports", lamports);
+
+        // Check the stake account is delegated to a validator the pool accepts deposits for,
+        // and that it is being merged into the pool's stake account for that validator.
+        let vote_account = utils::get_delegated_vote_account(&ctx.accounts.stake_account)?;
+        if let Some(preferred_vote_account) = ctx
+            .accounts
+            .stake_pool
+            .preferred_deposit_validator_vote_address
+        {
+            require_keys_eq!(
+                vote_account,
+                preferred_vote_account,
+                SplBeamError::NotPreferredDepositValidator
+            );
+        }
+        require_keys_eq!(
+            ctx.accounts.validator_list.key(),
+            ctx.accounts.stake_pool.validator_list,
+            SplBeamError::InvalidValidatorList
+        );
+        let validator_stake_account = utils::find_validator_stake_account(
+            &ctx.accounts.stake_pool.key(),
+            &ctx.accounts.validator_list,
+            &vote_account,
+        )?;
+        require_keys_eq!(
+            ctx.accounts.validator_stake_account.key(),
+            validator_stake_account,
+            SplBeamError::InvalidValidatorStakeAccount
+        );
+        msg!("Depositing stake delegated to validator {}", vote_account);
+
+        let pool_tokens_before = ctx.accounts.pool_token_vault.amount;
+
         // CPI: Deposit staked SOL to SPL stake pool.
         spl_interface::deposit_stake(ctx.accounts)?;
 
+        // Calculate the value of the pool tokens received, net of fees.
+        let deposited_lamports = utils::calculate_deposited_value(
+            &mut ctx.accounts.stake_pool,
+            &mut ctx.accounts.pool_token_vault,
+            pool_tokens_before,
+        )?;
+
         let state_bump = ctx.bumps.state;
-        // CPI: Mint Gsol of the same proportion as the stake amount.
+        // CPI: Mint Gsol of the same proportion as the value deposited.
         sunrise_interface::mint_gsol(
             ctx.accounts.deref(),
             ctx.accounts.sunrise_program.to_account_info(),
             ctx.accounts.sunrise_state.key(),
             ctx.accounts.stake_pool.key(),
             state_bump,
-            lamports,
+            ctx.remaining_accounts,
+            deposited_lamports,
         )?;
 
         Ok(())
     }
 
-    pub fn withdraw(ctx: Context<Withdraw>, lamports: u64) -> Result<()> {
+    /// Withdraws `lamports` from the stake pool's reserve.
+    ///
+    /// Errors if the withdrawer receives fewer than `min_lamports_out` lamports
+    /// after the pool's SOL withdrawal fee.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
+        utils::check_pool_updated(&ctx.accounts.stake_pool, Clock::get()?.epoch)?;
+
         // Calculate the number of pool tokens needed to be burnt to withdraw `lamports` lamports.
         let pool = &ctx.accounts.stake_pool;
         let pool_tokens_amount =
             utils::pool_tokens_from_lamports(&pool.clone().into_inner(), lamports)?;
 
+        let withdrawer_balance_before = ctx.accounts.withdrawer.lamports();
+
         // CPI: Withdraw SOL from SPL stake pool.
         spl_interface::withdraw(ctx.accounts.deref(), pool_tokens_amount)?;
 
+        let withdrawer_balance_after = ctx.accounts.withdrawer.lamports();
+        let withdrawn_lamports = withdrawer_balance_after.saturating_sub(withdrawer_balance_before);
+
+        msg!("Withdrawn {} lamports", withdrawn_lamports);
+        require_gte!(
+            withdrawn_lamports,
+            min_lamports_out,
+            SplBeamError::WithdrawalBelowMinimum
+        );
+
+        // CPI: Burn GSOL of the same proportion as the lamports withdrawn.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::burn_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            pool.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            lamports,
+        )?;
+
+        Ok(())
+    }
+
+    /// Withdrawing as an LST burns gSOL and transfers the equivalent amount of pool tokens
+    /// out of the beam's vault, rather than redeeming them. This incurs no withdrawal fee.
+    pub fn withdraw_as_lst<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawAsLst<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        utils::check_pool_updated(&ctx.accounts.stake_pool, Clock::get()?.epoch)?;
+
+        // Calculate the number of pool tokens worth `lamports` lamports.
+        let pool = &ctx.accounts.stake_pool;
+        let pool_tokens_amount =
+            utils::pool_tokens_from_lamports_round_up(&pool.clone().into_inner(), lamports)?;
+
+        // CPI: Transfer pool tokens from the vault to the withdrawer.
+        token_interface::transfer_from_vault(ctx.accounts.deref(), pool_tokens_amount)?;
+
         // CPI: Burn GSOL of the same proportion as the lamports withdrawn.
         let state_bump = ctx.bumps.state;
         sunrise_interface::burn_gsol(
@@ -108,6 +230,7 @@ pub mod spl_beam {
             ctx.accounts.sunrise_state.key(),
             pool.key(),
             state_bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
@@ -117,7 +240,7 @@ pub mod spl_beam {
     /// Burning is withdrawing without redeeming the pool tokens. The result is a beam that is "worth more"
     /// than the SOL that has been staked into it, i.e. the pool tokens are more valuable than the SOL.
     /// This allows yield extraction and can be seen as a form of "donation".
-    pub fn burn(ctx: Context<Burn>, lamports: u64) -> Result<()> {
+    pub fn burn<'info>(ctx: Context<'_, '_, '_, 'info, Burn<'info>>, lamports: u64) -> Result<()> {
         let pool = &ctx.accounts.stake_pool;
 
         let state_bump = ctx.bumps.state;
@@ -127,21 +250,56 @@ pub mod spl_beam {
             ctx.accounts.sunrise_state.key(),
             pool.key(),
             state_bump,
+            ctx.remaining_accounts,
             lamports,
         )?;
 
         Ok(())
     }
 
-    pub fn withdraw_stake(ctx: Context<WithdrawStake>, lamports: u64) -> Result<()> {
-        // Calculate the number of pool tokens needed to be burnt to withdraw `lamports` worth of stake.
-        let pool = &ctx.accounts.stake_pool;
-        let pool_tokens_amount =
-            utils::pool_tokens_from_lamports(&pool.clone().into_inner(), lamports)?;
+    /// Withdraws `lamports` from the stake pool into `new_stake_account`, with the withdrawer as
+    /// its stake and withdraw authority.
+    /// The rent of the stake account is deducted from the withdrawal, and only the lamports
+    /// actually withdrawn are burned in gSOL.
+    ///
+    /// The stake is split from the pool's stake account for the validator with vote account
+    /// `vote_account`, falling back to the pool's preferred withdrawal validator if none is given.
+    /// If neither is set, the stake is split from the account passed as `stake_account_to_split`.
+    pub fn withdraw_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawStake<'info>>,
+        lamports: u64,
+        vote_account: Option<Pubkey>,
+    ) -> Result<()> {
+        utils::check_pool_updated(&ctx.accounts.stake_pool, ctx.accounts.sysvar_clock.epoch)?;
+
+        let preferred_vote_account = ctx
+            .accounts
+            .stake_pool
+            .preferred_withdraw_validator_vote_address;
+        let vote_account = vote_account.or(preferred_vote_account);
+        if let Some(vote_account) = vote_account {
+            require_keys_eq!(
+                ctx.accounts.validator_stake_list.key(),
+                ctx.accounts.stake_pool.validator_list,
+                SplBeamError::InvalidValidatorList
+            );
+            let validator_stake_account = utils::find_validator_stake_account(
+                &ctx.accounts.stake_pool.key(),
+                &ctx.accounts.validator_stake_list,
+                &vote_account,
+            )?;
+            require_keys_eq!(
+                ctx.accounts.stake_account_to_split.key(),
+                validator_stake_account,
+                SplBeamError::InvalidValidatorStakeAccount
+            );
+            msg!("Withdrawing stake delegated to validator {}", vote_account);
+        }
 
         // CPI: Withdraw SOL from SPL stake pool into a stake account.
         let extract_stake_account_accounts = ctx.accounts.deref().into();
-        spl_interface::extract_stake(&extract_stake_account_accounts, pool_tokens_amount)?;
+        let extracted_lamports =
+            spl_interface::extract_stake(&extract_stake_account_accounts, lamports)?;
 
         // CPI: Burn GSOL of the same proportion as the lamports withdrawn.
         let state_bump = ctx.bumps.state;
@@ -151,38 +309,92 @@ pub mod spl_beam {
             ctx.accounts.sunrise_state.key(),
             ctx.accounts.stake_pool.key(),
             state_bump,
-            lamports,
+            ctx.remaining_accounts,
+            extracted_lamports,
         )?;
 
         Ok(())
     }
 
-    pub fn order_withdrawal(_ctx: Context<Noop>) -> Result<()> {
-        // spl stake pools only support immediate withdrawals.
-        Err(SplBeamError::Unimplemented.into())
+    /// Orders a delayed withdrawal of `lamports`, avoiding the pool's SOL withdrawal fee
+    /// and reserve liquidity limits.
+    ///
+    /// The lamports are withdrawn from the pool as a stake account, which is deactivated.
+    /// As with `withdraw_stake`, the rent of the stake account is deducted,
+    /// and only the lamports withdrawn are burned in gSOL.
+    /// A ticket is created mapping the stake account to the withdrawer, which can be
+    /// redeemed once the stake account has cooled down.
+    /// Stake split from the pool's reserve is not delegated, so its ticket can be redeemed at once.
+    pub fn order_withdrawal<'info>(
+        ctx: Context<'_, '_, '_, 'info, OrderWithdrawal<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        utils::check_pool_updated(&ctx.accounts.stake_pool, ctx.accounts.sysvar_clock.epoch)?;
+
+        // CPI: Withdraw SOL from SPL stake pool into a stake account.
+        let extract_stake_account_accounts = ctx.accounts.deref().into();
+        let extracted_lamports =
+            spl_interface::extract_stake(&extract_stake_account_accounts, lamports)?;
+
+        // CPI: Deactivate the stake account so that it can be claimed after cooldown.
+        // Stake split from the pool's reserve is not delegated, and needs no deactivation.
+        if StakeAccount::is_delegated(&ctx.accounts.new_stake_account.to_account_info())? {
+            let deactivate_stake_account_accounts = ctx.accounts.deref().into();
+            deactivate_stake_account(&deactivate_stake_account_accounts)?;
+        }
+
+        // Create a program-owned account mapping the stake account to the beneficiary that ordered it.
+        let ticket_account = &mut ctx.accounts.withdrawal_ticket;
+        ticket_account.state = ctx.accounts.state.key();
+        ticket_account.stake_account = ctx.accounts.new_stake_account.key();
+        ticket_account.beneficiary = ctx.accounts.withdrawer.key();
+
+        // CPI: Burn GSOL of the same proportion as the lamports withdrawn.
+        let state_bump = ctx.bumps.state;
+        sunrise_interface::burn_gsol(
+            ctx.accounts.deref(),
+            ctx.accounts.sunrise_program.to_account_info(),
+            ctx.accounts.sunrise_state.key(),
+            ctx.accounts.stake_pool.key(),
+            state_bump,
+            ctx.remaining_accounts,
+            extracted_lamports,
+        )?;
+
+        Ok(())
     }
 
-    pub fn redeem_ticket(_ctx: Context<Noop>) -> Result<()> {
-        // spl stake pools only support immediate withdrawals.
-        Err(SplBeamError::Unimplemented.into())
+    /// Redeems a withdrawal ticket, sending the lamports in its stake account to the beneficiary.
+    ///
+    /// Errors if the stake account has not yet cooled down.
+    pub fn redeem_ticket(ctx: Context<RedeemTicket>) -> Result<()> {
+        let stake_account = &ctx.accounts.stake_account;
+        require!(
+            stake_account.can_be_withdrawn(&ctx.accounts.sysvar_clock.epoch),
+            SplBeamError::WithdrawalTicketNotRedeemable
+        );
+
+        // CPI: Withdraw the lamports from the stake account to the beneficiary.
+        let lamports = stake_account.to_account_info().lamports();
+        let claim_stake_account_accounts = ctx.accounts.deref().into();
+        claim_stake_account(&claim_stake_account_accounts, lamports)?;
+
+        Ok(())
     }
 
     pub fn update_epoch_report(ctx: Context<UpdateEpochReport>) -> Result<()> {
-        // Calculate how much yield can be extracted from the pool.
-        let gross_extractable_yield = utils::calculate_extractable_yield(
+        utils::check_pool_updated(&ctx.accounts.stake_pool, Clock::get()?.epoch)?;
+
+        // Calculate the surplus (or deficit) of the beam in the pool.
+        let gross_surplus = utils::calculate_surplus(
             &ctx.accounts.sunrise_state,
             &ctx.accounts.state,
             &ctx.accounts.stake_pool,
             &ctx.accounts.pool_token_vault,
         )?;
 
-        // Reduce by fee
-        let fee = proportional(
-            gross_extractable_yield,
-            ctx.accounts.stake_pool.stake_withdrawal_fee.numerator,
-            ctx.accounts.stake_pool.stake_withdrawal_fee.denominator,
-        )?;
-        let net_extractable_yield = gross_extractable_yield.saturating_sub(fee);
+        // Reduce any surplus by fee. A deficit is reported as-is.
+        let net_surplus = utils::calculate_net_surplus(&ctx.accounts.stake_pool, gross_surplus)?;
 
         // CPI: update the epoch report with the extracted yield.
         let state_bump = ctx.bumps.state;
@@ -192,13 +404,15 @@ pub mod spl_beam {
             ctx.accounts.sunrise_state.key(),
             ctx.accounts.stake_pool.key(),
             state_bump,
-            net_extractable_yield,
+            net_surplus,
         )?;
 
         Ok(())
     }
 
     pub fn extract_yield(ctx: Context<ExtractYield>) -> Result<()> {
+        utils::check_pool_updated(&ctx.accounts.stake_pool, ctx.accounts.sysvar_clock.epoch)?;
+
         // Calculate how much yield can be extracted from the pool.
         let extractable_yield = utils::calculate_extractable_yield(
             &ctx.accounts.sunrise_state,
@@ -216,9 +430,22 @@ pub mod spl_beam {
         stake_account.reload()?;
         let lamports = stake_account.to_account_info().lamports();
 
-        // CPI: Withdraw the lamports from the stake account.
+        // CPI: Withdraw the protocol fee from the stake account to the treasury,
+        // and the rest of the lamports to the yield account.
+        let protocol_fee = ctx.accounts.sunrise_state.protocol_fee(lamports)?;
+        let treasury = core_cpi::fee_treasury(ctx.accounts.treasury.as_ref(), protocol_fee)?;
+        if let Some(treasury) = treasury {
+            let claim_stake_account_accounts = ClaimStakeAccount {
+                to: treasury.to_account_info(),
+                ..ClaimStakeAccount::from(ctx.accounts.deref())
+            };
+            claim_stake_account(&claim_stake_account_accounts, protocol_fee)?;
+        }
         let claim_stake_account_accounts = ctx.accounts.deref().into();
-        claim_stake_account(&claim_stake_account_accounts, lamports)?;
+        claim_stake_account(
+            &claim_stake_account_accounts,
+            lamports.saturating_sub(protocol_fee),
+        )?;
 
         // CPI: update the epoch report with the extracted yield.
         let state_bump = ctx.bumps.state;
@@ -233,6 +460,80 @@ pub mod spl_beam {
 
         Ok(())
     }
+
+    /// Permissionless wrapper around the stake pool's update crank.
+    ///
+    /// Updates the balances of the validators in the pool's validator list from `start_index`,
+    /// using the validator and transient stake account pairs passed as remaining accounts.
+    /// If this reaches the end of the validator list, the pool's total balance is also updated,
+    /// allowing the pool to be priced again in the current epoch.
+    pub fn refresh_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, RefreshPool<'info>>,
+        start_index: u32,
+    ) -> Result<()> {
+        let validator_stake_accounts = ctx.remaining_accounts;
+        let validator_count = utils::validator_list_len(&ctx.accounts.validator_list)?;
+
+        // CPI: Update the validator balances.
+        if !validator_stake_accounts.is_empty() {
+            spl_interface::update_validator_list_balance(
+                ctx.accounts,
+                validator_stake_accounts,
+                start_index,
+            )?;
+        }
+
+        // CPI: Update the pool balance once all validators are updated.
+        let updated_count = start_index as usize + validator_stake_accounts.len() / 2;
+        if updated_count >= validator_count {
+            spl_interface::update_stake_pool_balance(ctx.accounts)?;
+        }
+
+        Ok(())
+    }
+
+    /// View: quotes a [deposit] of `lamports`, returning the gSOL that would be minted
+    /// and the pool tokens that the beam would receive.
+    pub fn quote_deposit(ctx: Context<View>, lamports: u64) -> Result<DepositQuote> {
+        utils::check_pool_updated(&ctx.accounts.stake_pool, Clock::get()?.epoch)?;
+        utils::quote_deposit(&ctx.accounts.stake_pool, lamports)
+    }
+
+    /// View: quotes a [withdraw] of `lamports`, returning the lamports that would be received
+    /// after the pool's SOL withdrawal fee, and the pool tokens that the beam would spend.
+    pub fn quote_withdraw(ctx: Context<View>, lamports: u64) -> Result<WithdrawQuote> {
+        utils::check_pool_updated(&ctx.accounts.stake_pool, Clock::get()?.epoch)?;
+        utils::quote_withdraw(&ctx.accounts.stake_pool, lamports)
+    }
+
+    /// View: returns the value of the pool tokens held by the beam, the surplus that
+    /// [update_epoch_report] would report and the yield that [extract_yield] would extract.
+    pub fn get_beam_value(ctx: Context<View>) -> Result<BeamValue> {
+        let stake_pool = &ctx.accounts.stake_pool;
+        utils::check_pool_updated(stake_pool, Clock::get()?.epoch)?;
+
+        let value =
+            utils::lamports_from_pool_tokens(stake_pool, ctx.accounts.pool_token_vault.amount)?;
+        let gsol_supply = ctx
+            .accounts
+            .sunrise_state
+            .get_beam_details(&ctx.accounts.state.key())
+            .ok_or(sunrise_core::BeamError::UnidentifiedBeam)?
+            .partial_gsol_supply;
+        let gross_surplus = utils::calculate_surplus(
+            &ctx.accounts.sunrise_state,
+            &ctx.accounts.state,
+            stake_pool,
+            &ctx.accounts.pool_token_vault,
+        )?;
+
+        Ok(BeamValue {
+            value,
+            gsol_supply,
+            surplus: utils::calculate_net_surplus(stake_pool, gross_surplus)?,
+            extractable_yield: gross_surplus.max(0) as u64,
+        })
+    }
 }
 
 #[derive(Accounts)]
@@ -288,9 +589,6 @@ pub struct Deposit<'info> {
     )]
     pub state: Box<Account<'info, State>>,
 
-    #[account(mut)]
-    pub stake_pool: Box<Account<'info, StakePool>>,
-
     #[account(mut)]
     pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
 
@@ -299,6 +597,19 @@ pub struct Deposit<'info> {
     #[account(mut,token::mint = gsol_mint)]
     pub mint_gsol_to: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub gsol_mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
     #[account(mut)]
     pub pool_mint: Box<Account<'info, Mint>>,
     #[account(
@@ -326,16 +637,6 @@ pub struct Deposit<'info> {
     #[account(mut)]
     /// CHECK: Checked by CPI to SPL StakePool Program.
     pub manager_fee_account: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    /// Verified in CPI to Sunrise program.
-    pub gsol_mint: Box<Account<'info, Mint>>,
-    /// CHECK: Checked by CPI to Sunrise.
-    pub gsol_mint_authority: UncheckedAccount<'info>,
-    /// CHECK: Checked by CPI to Sunrise.
-    pub sysvar_instructions: UncheckedAccount<'info>,
-
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
     pub spl_stake_pool_program: Program<'info, SplStakePool>,
 
     pub system_program: Program<'info, System>,
@@ -347,13 +648,12 @@ pub struct DepositStake<'info> {
     #[account(
         has_one = sunrise_state,
         has_one = stake_pool,
-        seeds = [STATE, sunrise_state.key().as_ref()],
+        seeds = [STATE, sunrise_state.key().as_ref(), stake_pool.key().as_ref()],
         bump
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
-    /// CHECK: The registered SPL stake pool.
-    pub stake_pool: UncheckedAccount<'info>,
+    pub stake_pool: Box<Account<'info, StakePool>>,
     #[account(mut)]
     /// CHECK: The main Sunrise beam state.
     pub sunrise_state: UncheckedAccount<'info>,
@@ -430,8 +730,6 @@ pub struct Withdraw<'info> {
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
-    pub stake_pool: Box<Account<'info, StakePool>>,
-    #[account(mut)]
     pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
 
     #[account(mut)]
@@ -439,6 +737,17 @@ pub struct Withdraw<'info> {
     #[account(mut, token::mint = gsol_mint)]
     pub gsol_token_account: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Account<'info, Mint>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
     #[account(mut)]
     pub pool_mint: Box<Account<'info, Mint>>,
     #[account(
@@ -472,6 +781,50 @@ pub struct Withdraw<'info> {
     pub sysvar_clock: UncheckedAccount<'info>,
     /// CHECK: Checked by CPI to SPL StakePool Program.
     pub native_stake_program: UncheckedAccount<'info>,
+    pub spl_stake_pool_program: Program<'info, SplStakePool>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAsLst<'info> {
+    #[account(
+        has_one = sunrise_state,
+        has_one = stake_pool,
+        seeds = [STATE, sunrise_state.key().as_ref(), stake_pool.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(has_one = pool_mint)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub pool_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = pool_mint,
+        token::authority = vault_authority
+    )]
+    pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+    /// The token account that receives the withdrawn pool tokens.
+    #[account(mut, token::mint = pool_mint)]
+    pub transfer_pool_tokens_to: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
     /// Verified in CPI to Sunrise program.
@@ -480,9 +833,7 @@ pub struct Withdraw<'info> {
     pub sysvar_instructions: UncheckedAccount<'info>,
 
     pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
-    pub spl_stake_pool_program: Program<'info, SplStakePool>,
 
-    pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
@@ -491,7 +842,7 @@ pub struct WithdrawStake<'info> {
     #[account(
         has_one = sunrise_state,
         has_one = stake_pool,
-        seeds = [STATE, sunrise_state.key().as_ref()],
+        seeds = [STATE, sunrise_state.key().as_ref(), stake_pool.key().as_ref()],
         bump
     )]
     pub state: Box<Account<'info, State>>,
@@ -506,8 +857,14 @@ pub struct WithdrawStake<'info> {
     pub withdrawer: Signer<'info>,
     #[account(mut, token::mint = gsol_mint)]
     pub gsol_token_account: Box<Account<'info, TokenAccount>>,
-    /// CHECK: The uninitialized new stake account.
-    pub new_stake_account: UncheckedAccount<'info>,
+    #[account(
+        init,
+        space = STAKE_ACCOUNT_SIZE,
+        payer = withdrawer,
+        owner = anchor_lang::solana_program::stake::program::ID,
+    )]
+    /// The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program.
+    pub new_stake_account: Account<'info, StakeAccount>,
 
     #[account(mut)]
     pub pool_mint: Box<Account<'info, Mint>>,
@@ -575,6 +932,19 @@ pub struct ExtractYield<'info> {
         has_one = yield_account
     )]
     pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    /// CHECK: Matches the yield key stored in the state.
+    pub yield_account: UncheckedAccount<'info>,
+    #[account(mut, address = sunrise_state.treasury)]
+    /// CHECK: Matches the treasury key stored in the state.
+    /// Only needed while the state charges a protocol fee.
+    pub treasury: Option<UncheckedAccount<'info>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
     #[account(
         mut,
         has_one = pool_mint
@@ -587,10 +957,6 @@ pub struct ExtractYield<'info> {
     #[account(mut)]
     pub pool_mint: Box<Account<'info, Mint>>,
 
-    #[account(mut)]
-    /// CHECK: Matches the yield key stored in the state.
-    pub yield_account: UncheckedAccount<'info>,
-
     #[account(
     init_if_needed,
     space = STAKE_ACCOUNT_SIZE,
@@ -643,10 +1009,6 @@ pub struct ExtractYield<'info> {
     pub native_stake_program: Program<'info, NativeStakeProgram>,
     /// CHECK: Checked by CPI to SPL Stake program.
     pub sysvar_stake_history: UncheckedAccount<'info>,
-    /// CHECK: Checked by CPI to Sunrise.
-    pub sysvar_instructions: UncheckedAccount<'info>,
-
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
     pub spl_stake_pool_program: Program<'info, SplStakePool>,
 
     pub system_program: Program<'info, System>,
@@ -666,6 +1028,18 @@ pub struct UpdateEpochReport<'info> {
     mut, // Update the extractable yield on the state's epoch report.
     )]
     pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    /// Required to update the core state epoch report
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Account<'info, Mint>,
+    #[account(mut)]
+    /// CHECK: Updated with the epoch report - verified in CPI to Sunrise program.
+    pub backing_oracle: UncheckedAccount<'info>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
     pub stake_pool: Box<Account<'info, StakePool>>,
     pub pool_mint: Box<Account<'info, Mint>>,
 
@@ -684,14 +1058,67 @@ pub struct UpdateEpochReport<'info> {
         token::authority = vault_authority
     )]
     pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+}
 
-    /// Required to update the core state epoch report
-    /// Verified in CPI to Sunrise program.
-    pub gsol_mint: Account<'info, Mint>,
+#[derive(Accounts)]
+pub struct View<'info> {
+    #[account(
+        has_one = stake_pool,
+        has_one = sunrise_state,
+        seeds = [STATE, sunrise_state.key().as_ref(), stake_pool.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
 
-    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
-    /// CHECK: Checked by CPI to Sunrise.
-    pub sysvar_instructions: UncheckedAccount<'info>,
+    pub stake_pool: Box<Account<'info, StakePool>>,
+    pub pool_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        token::mint = pool_mint,
+        token::authority = vault_authority
+    )]
+    pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshPool<'info> {
+    #[account(has_one = stake_pool)]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// CHECK: Checked by CPI to SPL StakePool program.
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool program.
+    pub validator_list: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool program.
+    pub reserve_stake_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool program.
+    pub manager_fee_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    /// CHECK: Checked by CPI to SPL StakePool program.
+    pub sysvar_stake_history: UncheckedAccount<'info>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+
+    pub spl_stake_pool_program: Program<'info, SplStakePool>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
@@ -705,7 +1132,6 @@ pub struct Burn<'info> {
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
     pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
-    pub stake_pool: Box<Account<'info, StakePool>>,
 
     #[account(mut)]
     pub burner: Signer<'info>,
@@ -720,12 +1146,135 @@ pub struct Burn<'info> {
 
     pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
 
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
-#[derive(Accounts)]
-pub struct Noop {}
+#[derive(Accounts, Clone)]
+pub struct OrderWithdrawal<'info> {
+    #[account(
+        has_one = sunrise_state,
+        has_one = stake_pool,
+        seeds = [STATE, sunrise_state.key().as_ref(), stake_pool.key().as_ref()],
+        bump
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub sunrise_state: Box<Account<'info, sunrise_core::State>>,
+
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+    #[account(mut, token::mint = gsol_mint)]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// Verified in CPI to Sunrise program.
+    pub gsol_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Checked by CPI to Sunrise.
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    pub sunrise_program: Program<'info, sunrise_core_cpi::program::SunriseCore>,
+
+    #[account(mut)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    #[account(
+        init,
+        space = STAKE_ACCOUNT_SIZE,
+        payer = withdrawer,
+        owner = anchor_lang::solana_program::stake::program::ID,
+    )]
+    /// The uninitialized new stake account. Will be initialised by CPI to the SPL StakePool program.
+    pub new_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        space = WithdrawalTicket::SPACE,
+        payer = withdrawer,
+        seeds = [
+            state.key().as_ref(),
+            WITHDRAWAL_TICKET,
+            new_stake_account.key().as_ref()
+        ],
+        bump
+    )]
+    pub withdrawal_ticket: Box<Account<'info, WithdrawalTicket>>,
+
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = pool_mint,
+        token::authority = vault_authority
+    )]
+    pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by CPI to SPL StakePool program.
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool program.
+    pub validator_stake_list: UncheckedAccount<'info>,
+    #[account(mut)]
+    // The SPL StakePool program checks that this is either
+    // the stake account of a recognized validator, or the
+    // pool's reserve stake account.
+    /// CHECK: The stake account to split from.
+    pub stake_account_to_split: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to SPL StakePool Program.
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+    pub spl_stake_pool_program: Program<'info, SplStakePool>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts, Clone)]
+pub struct RedeemTicket<'info> {
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = stake_account,
+        has_one = beneficiary,
+        close = beneficiary,
+    )]
+    pub withdrawal_ticket: Box<Account<'info, WithdrawalTicket>>,
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [
+            state.key().as_ref(),
+            VAULT_AUTHORITY
+        ],
+        bump = state.vault_authority_bump
+    )]
+    /// CHECK: The vault authority PDA with verified seeds.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub sysvar_clock: Sysvar<'info, Clock>,
+    /// CHECK: Checked by CPI to SPL Stake program.
+    pub sysvar_stake_history: UncheckedAccount<'info>,
+    pub native_stake_program: Program<'info, NativeStakeProgram>,
+}
 
 #[error_code]
 pub enum SplBeamError {
@@ -739,4 +1288,18 @@ pub enum SplBeamError {
     YieldStakeAccountNotCooledDown,
     #[msg("The yield being extracted is insufficient to cover the rent of the stake account")]
     InsufficientYieldToExtract,
+    #[msg("The withdrawal paid out fewer lamports than the requested minimum")]
+    WithdrawalBelowMinimum,
+    #[msg("The stake account of this withdrawal ticket cannot yet be claimed")]
+    WithdrawalTicketNotRedeemable,
+    #[msg("The validator is not in the stake pool's validator list")]
+    ValidatorNotInPool,
+    #[msg("The stake pool only accepts stake deposits delegated to its preferred validator")]
+    NotPreferredDepositValidator,
+    #[msg("The stake account is not the stake pool's stake account for the validator")]
+    InvalidValidatorStakeAccount,
+    #[msg("The stake pool has not been updated in the current epoch")]
+    StakePoolNotUpdated,
+    #[msg("The validator list is not the stake pool's validator list")]
+    InvalidValidatorList,
 }
@@ -4,3 +4,5 @@ pub const VAULT_AUTHORITY: &[u8] = b"vault_authority";
 pub const STATE: &[u8] = b"sunrise_spl";
 
 pub const EXTRACT_YIELD_STAKE_ACCOUNT: &[u8] = b"extract_yield_stake_account";
+/// Seed of a ticket mapping an ordered withdrawal to its beneficiary.
+pub const WITHDRAWAL_TICKET: &[u8] = b"withdrawal_ticket";
@@ -46,3 +46,25 @@ impl From<StateEntry> for State {
         }
     }
 }
+
+/// Maps a deactivating stake account, created by ordering a withdrawal, to the gSOL holder
+/// that ordered it.
+#[account]
+#[derive(Debug, Default)]
+pub struct WithdrawalTicket {
+    /// The beam state that the withdrawal was ordered from.
+    pub state: Pubkey,
+
+    /// The stake account holding the withdrawn lamports.
+    pub stake_account: Pubkey,
+
+    /// The account that can redeem the ticket.
+    pub beneficiary: Pubkey,
+}
+
+impl WithdrawalTicket {
+    pub const SPACE: usize = 8 +  /*discriminator*/
+        32 + /*state*/
+        32 + /*stake_account*/
+        32; /*beneficiary*/
+}
@@ -5,25 +5,115 @@ use anchor_lang::{
     solana_program::stake::state::StakeStateV2,
 };
 use anchor_spl::token::TokenAccount;
+pub use beam_math::{proportional, proportional_round_up};
+use spl_stake_pool::state::ValidatorStakeInfo;
+use std::num::NonZeroU32;
+use sunrise_beam_interface::view::{DepositQuote, WithdrawQuote};
 use sunrise_core::BeamError;
 
-/// calculate amount*numerator/denominator
-/// as value  = shares * share_price where share_price=total_value/total_shares
-/// or shares = amount_value / share_price where share_price=total_value/total_shares
-///     => shares = amount_value * 1/share_price where 1/share_price=total_shares/total_value
-pub fn proportional(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
-    if denominator == 0 {
-        return Ok(amount);
-    }
-    u64::try_from((amount as u128) * (numerator as u128) / (denominator as u128))
-        .map_err(|_| error!(crate::SplBeamError::CalculationFailure))
+pub fn pool_tokens_from_lamports(stake_pool: &StakePool, lamports: u64) -> Result<u64> {
+    let token_supply = stake_pool.pool_token_supply;
+    let total_lamports = stake_pool.total_lamports;
+
+    Ok(proportional(lamports, token_supply, total_lamports)?)
 }
 
-pub fn pool_tokens_from_lamports(stake_pool: &StakePool, lamports: u64) -> Result<u64> {
+/// Same as [pool_tokens_from_lamports], but rounds up to the nearest whole pool token.
+pub fn pool_tokens_from_lamports_round_up(stake_pool: &StakePool, lamports: u64) -> Result<u64> {
     let token_supply = stake_pool.pool_token_supply;
     let total_lamports = stake_pool.total_lamports;
 
-    proportional(lamports, token_supply, total_lamports)
+    Ok(proportional_round_up(
+        lamports,
+        token_supply,
+        total_lamports,
+    )?)
+}
+
+pub fn lamports_from_pool_tokens(stake_pool: &StakePool, pool_tokens: u64) -> Result<u64> {
+    let token_supply = stake_pool.pool_token_supply;
+    let total_lamports = stake_pool.total_lamports;
+
+    Ok(proportional(pool_tokens, total_lamports, token_supply)?)
+}
+
+/// Quotes a deposit of `lamports` into the stake pool, net of the pool's SOL deposit fee.
+/// The beam is its own referrer, so it receives the referral share of the fee back.
+/// The gSOL minted is the value of the pool tokens received, at the pool's exchange rate
+/// after the deposit, as in [calculate_deposited_value].
+pub fn quote_deposit(stake_pool: &StakePool, lamports: u64) -> Result<DepositQuote> {
+    let pool_tokens_minted = stake_pool
+        .calc_pool_tokens_for_deposit(lamports)
+        .ok_or(crate::SplBeamError::CalculationFailure)?;
+    let deposit_fee = stake_pool
+        .calc_pool_tokens_sol_deposit_fee(pool_tokens_minted)
+        .ok_or(crate::SplBeamError::CalculationFailure)?;
+    let referral_fee = stake_pool
+        .calc_pool_tokens_sol_referral_fee(deposit_fee)
+        .ok_or(crate::SplBeamError::CalculationFailure)?;
+    let pool_tokens_received = pool_tokens_minted
+        .checked_sub(deposit_fee)
+        .and_then(|tokens| tokens.checked_add(referral_fee))
+        .ok_or(crate::SplBeamError::CalculationFailure)?;
+
+    let total_lamports = stake_pool
+        .total_lamports
+        .checked_add(lamports)
+        .ok_or(crate::SplBeamError::CalculationFailure)?;
+    let token_supply = stake_pool
+        .pool_token_supply
+        .checked_add(pool_tokens_minted)
+        .ok_or(crate::SplBeamError::CalculationFailure)?;
+    let gsol_out = proportional(pool_tokens_received, total_lamports, token_supply)?;
+
+    Ok(DepositQuote {
+        gsol_out,
+        beam_tokens_out: pool_tokens_received,
+    })
+}
+
+/// Quotes a withdrawal of `lamports` from the stake pool's reserve, net of the pool's SOL withdrawal fee.
+pub fn quote_withdraw(stake_pool: &StakePool, lamports: u64) -> Result<WithdrawQuote> {
+    let pool_tokens = pool_tokens_from_lamports(stake_pool, lamports)?;
+    let withdrawal_fee = stake_pool
+        .calc_pool_tokens_sol_withdrawal_fee(pool_tokens)
+        .ok_or(crate::SplBeamError::CalculationFailure)?;
+    let lamports_out = pool_tokens
+        .checked_sub(withdrawal_fee)
+        .and_then(|pool_tokens_burnt| stake_pool.calc_lamports_withdraw_amount(pool_tokens_burnt))
+        .ok_or(crate::SplBeamError::CalculationFailure)?;
+
+    Ok(WithdrawQuote {
+        lamports_out,
+        beam_tokens_in: pool_tokens,
+    })
+}
+
+/// Calculates the value in lamports of the pool tokens received into the vault
+/// since it held `pool_tokens_before`.
+/// Both accounts are reloaded first, so that the value reflects any fees charged by the pool
+/// and the pool's exchange rate after the deposit.
+pub fn calculate_deposited_value(
+    stake_pool: &mut Account<StakePool>,
+    pool_token_vault: &mut Account<TokenAccount>,
+    pool_tokens_before: u64,
+) -> Result<u64> {
+    stake_pool.reload()?;
+    pool_token_vault.reload()?;
+
+    let pool_tokens_received = pool_token_vault
+        .amount
+        .checked_sub(pool_tokens_before)
+        .ok_or(crate::SplBeamError::CalculationFailure)?;
+    let deposited_value = lamports_from_pool_tokens(stake_pool, pool_tokens_received)?;
+
+    msg!(
+        "pool_tokens_received: {}, deposited_value: {}",
+        pool_tokens_received,
+        deposited_value
+    );
+
+    Ok(deposited_value)
 }
 
 pub fn get_delegated_stake_amount(stake_account: &AccountInfo) -> Result<u64> {
@@ -36,15 +126,118 @@ pub fn get_delegated_stake_amount(stake_account: &AccountInfo) -> Result<u64> {
     }
 }
 
+/// Checks that the stake pool was updated in `current_epoch`.
+/// Until the pool is updated after an epoch boundary, its total lamports and pool token supply
+/// are stale, so any value derived from them is mispriced.
+pub fn check_pool_updated(stake_pool: &StakePool, current_epoch: u64) -> Result<()> {
+    require_eq!(
+        stake_pool.last_update_epoch,
+        current_epoch,
+        crate::SplBeamError::StakePoolNotUpdated
+    );
+    Ok(())
+}
+
+/// The size of the validator list header: account type, max validators and vec length.
+const VALIDATOR_LIST_HEADER_LEN: usize = 1 + 4 + 4;
+
+/// Splits the serialized validator list `data` into the serialized entry of each validator.
+/// The list is only read, so that it need not be borrowed mutably.
+fn validator_entries(data: &[u8]) -> Result<std::slice::ChunksExact<u8>> {
+    let len = data
+        .get(VALIDATOR_LIST_HEADER_LEN - 4..VALIDATOR_LIST_HEADER_LEN)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+    let entry_len = std::mem::size_of::<ValidatorStakeInfo>();
+    let entries = data
+        .get(VALIDATOR_LIST_HEADER_LEN..VALIDATOR_LIST_HEADER_LEN + len * entry_len)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    Ok(entries.chunks_exact(entry_len))
+}
+
+/// Finds the entry of the validator with vote account `vote_account` in the serialized
+/// validator list `data`.
+fn find_validator(data: &[u8], vote_account: &Pubkey) -> Result<Option<ValidatorStakeInfo>> {
+    let validator = validator_entries(data)?
+        .find(|entry| ValidatorStakeInfo::memcmp_pubkey(entry, vote_account))
+        .map(try_from_slice_unchecked::<ValidatorStakeInfo>)
+        .transpose()?;
+    Ok(validator)
+}
+
+/// Gets the number of validators in the pool's validator list.
+pub fn validator_list_len(validator_list: &AccountInfo) -> Result<usize> {
+    let data = validator_list.data.borrow();
+    Ok(validator_entries(&data)?.len())
+}
+
+/// Gets the vote account that a stake account is delegated to.
+pub fn get_delegated_vote_account(stake_account: &AccountInfo) -> Result<Pubkey> {
+    let stake_state = try_from_slice_unchecked::<StakeStateV2>(&stake_account.data.borrow())?;
+
+    match stake_state.delegation() {
+        Some(delegation) => Ok(delegation.voter_pubkey),
+        None => Err(crate::SplBeamError::NotDelegated.into()),
+    }
+}
+
+/// Finds the address of the pool's stake account for the validator with vote account `vote_account`.
+/// Errors if the validator is not in the pool's validator list.
+pub fn find_validator_stake_account(
+    stake_pool: &Pubkey,
+    validator_list: &AccountInfo,
+    vote_account: &Pubkey,
+) -> Result<Pubkey> {
+    let data = validator_list.data.borrow();
+    let validator =
+        find_validator(&data, vote_account)?.ok_or(crate::SplBeamError::ValidatorNotInPool)?;
+
+    let (validator_stake_account, _) = spl_stake_pool::find_stake_program_address(
+        &spl_stake_pool::ID,
+        vote_account,
+        stake_pool,
+        NonZeroU32::new(validator.validator_seed_suffix.into()),
+    );
+
+    Ok(validator_stake_account)
+}
+
 /// Calculates the amount of yield that can be extracted from this pool.
-/// This is calculated as:
-/// The value of the pool tokens minus the amount of SOL staked in the beam
+/// This is the [surplus][calculate_surplus] of the beam, or zero if it is in deficit.
 pub fn calculate_extractable_yield(
     sunrise_state: &sunrise_core::State,
     beam_state: &Account<State>,
     stake_pool: &StakePool,
     pool_token_vault: &TokenAccount,
 ) -> Result<u64> {
+    let surplus = calculate_surplus(sunrise_state, beam_state, stake_pool, pool_token_vault)?;
+    Ok(surplus.max(0) as u64)
+}
+
+/// Reduces a positive `gross_surplus` by the pool's stake withdrawal fee,
+/// which is charged when the surplus is extracted. A deficit is returned as-is.
+pub fn calculate_net_surplus(stake_pool: &StakePool, gross_surplus: i64) -> Result<i64> {
+    if gross_surplus > 0 {
+        let fee = proportional(
+            gross_surplus as u64,
+            stake_pool.stake_withdrawal_fee.numerator,
+            stake_pool.stake_withdrawal_fee.denominator,
+        )?;
+        Ok(gross_surplus.saturating_sub(fee as i64))
+    } else {
+        Ok(gross_surplus)
+    }
+}
+
+/// Calculates the surplus of this beam, which is negative if the beam is in deficit.
+/// This is calculated as:
+/// The value of the pool tokens minus the amount of SOL staked in the beam
+pub fn calculate_surplus(
+    sunrise_state: &sunrise_core::State,
+    beam_state: &Account<State>,
+    stake_pool: &StakePool,
+    pool_token_vault: &TokenAccount,
+) -> Result<i64> {
     // Calculate the beam's ownership of the stake pool state
     let total_lamports = stake_pool.total_lamports; // the total number of lamports staked in the pool
     let token_supply = stake_pool.pool_token_supply; // the total number of pool tokens in existence
@@ -67,7 +260,7 @@ pub fn calculate_extractable_yield(
 
     msg!("staked_sol: {}", staked_sol);
 
-    Ok(owned_pool_value.saturating_sub(staked_sol))
+    Ok(beam_math::surplus(owned_pool_value, staked_sol)?)
 }
 
 #[cfg(test)]
@@ -78,6 +271,7 @@ mod utils_tests {
     use anchor_spl::token::spl_token;
     use anchor_spl::token::spl_token::state::AccountState;
     use rstest::rstest;
+    use spl_stake_pool::state::{AccountType, ValidatorList, ValidatorListHeader};
     use std::cell::RefCell;
     use std::rc::Rc;
     use sunrise_core::BeamDetails;
@@ -190,6 +384,121 @@ mod utils_tests {
         assert_eq!(pool_tokens_from_lamports(&stake_pool, 1000).unwrap(), 971);
     }
 
+    #[test]
+    fn test_proportional_round_up() {
+        assert_eq!(proportional_round_up(100, 1, 1).unwrap(), 100);
+        assert_eq!(proportional_round_up(100, 1, 3).unwrap(), 34);
+        assert_eq!(proportional_round_up(100, 2, 3).unwrap(), 67);
+        assert_eq!(proportional_round_up(100, 0, 1).unwrap(), 0);
+        assert_eq!(proportional_round_up(100, 1, 0).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_pool_tokens_from_lamports_round_up() {
+        let stake_pool = create_stake_pool();
+        assert_eq!(
+            pool_tokens_from_lamports_round_up(&stake_pool, 100).unwrap(),
+            98
+        );
+        assert_eq!(
+            pool_tokens_from_lamports_round_up(&stake_pool, 0).unwrap(),
+            0
+        );
+        assert_eq!(
+            pool_tokens_from_lamports_round_up(&stake_pool, 1000).unwrap(),
+            972
+        );
+    }
+
+    #[test]
+    fn test_quote_deposit() {
+        // The deposit fee is 8 bps of the 971 pool tokens minted, rounded up to 1,
+        // which the beam receives back as the referrer.
+        let stake_pool = create_stake_pool();
+        assert_eq!(
+            quote_deposit(&stake_pool, 1000).unwrap(),
+            DepositQuote {
+                gsol_out: 999,
+                beam_tokens_out: 971,
+            }
+        );
+    }
+
+    #[test]
+    fn test_quote_withdraw() {
+        // The withdrawal fee is 3 bps of the 971 pool tokens, rounded up to 1.
+        let stake_pool = create_stake_pool();
+        assert_eq!(
+            quote_withdraw(&stake_pool, 1000).unwrap(),
+            WithdrawQuote {
+                lamports_out: 998,
+                beam_tokens_in: 971,
+            }
+        );
+    }
+
+    #[test]
+    fn test_calculate_net_surplus() {
+        // The stake withdrawal fee is 10 bps.
+        let stake_pool = create_stake_pool();
+        assert_eq!(calculate_net_surplus(&stake_pool, 10_000).unwrap(), 9_990);
+        assert_eq!(calculate_net_surplus(&stake_pool, -10).unwrap(), -10);
+    }
+
+    #[test]
+    fn test_check_pool_updated() {
+        // The pool was last updated in epoch 442.
+        let stake_pool = create_stake_pool();
+        assert!(check_pool_updated(&stake_pool, 442).is_ok());
+        assert_eq!(
+            check_pool_updated(&stake_pool, 443).unwrap_err(),
+            crate::SplBeamError::StakePoolNotUpdated.into()
+        );
+    }
+
+    #[test]
+    fn test_find_validator() {
+        let vote_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let validator_list = ValidatorList {
+            header: ValidatorListHeader {
+                account_type: AccountType::ValidatorList,
+                max_validators: 3,
+            },
+            validators: vote_accounts
+                .iter()
+                .enumerate()
+                .map(|(i, vote_account)| ValidatorStakeInfo {
+                    vote_account_address: *vote_account,
+                    validator_seed_suffix: (i as u32).into(),
+                    ..Default::default()
+                })
+                .collect(),
+        };
+        // The account is allocated for max_validators, so it is padded after the entries.
+        let mut data = validator_list.try_to_vec().unwrap();
+        data.resize(
+            VALIDATOR_LIST_HEADER_LEN + 3 * std::mem::size_of::<ValidatorStakeInfo>(),
+            0,
+        );
+
+        assert_eq!(validator_entries(&data).unwrap().len(), 2);
+        let validator = find_validator(&data, &vote_accounts[1]).unwrap().unwrap();
+        assert_eq!(validator.vote_account_address, vote_accounts[1]);
+        assert_eq!(u32::from(validator.validator_seed_suffix), 1);
+        assert!(find_validator(&data, &Pubkey::new_unique())
+            .unwrap()
+            .is_none());
+        assert!(validator_entries(&data[..VALIDATOR_LIST_HEADER_LEN + 1]).is_err());
+    }
+
+    #[test]
+    fn test_lamports_from_pool_tokens() {
+        let stake_pool = create_stake_pool();
+        assert_eq!(lamports_from_pool_tokens(&stake_pool, 97).unwrap(), 99);
+        assert_eq!(lamports_from_pool_tokens(&stake_pool, 0).unwrap(), 0);
+        assert_eq!(lamports_from_pool_tokens(&stake_pool, 971).unwrap(), 999);
+    }
+
     #[rstest]
     // total supply is 77520677832, total lamports is 79795522517,
     // so the value of one pool token is 79795522517 / 77520677832 = 1.029345
@@ -230,4 +539,37 @@ mod utils_tests {
 
         Ok(())
     }
+
+    #[rstest]
+    // 48 pool tokens are worth 49.4 lamports, rounded down to 49.
+    // 50 lamports are in the beam, so the beam is 1 lamport in deficit.
+    #[case::deficit(48, 50, -1)]
+    #[case::surplus(60, 50, 11)]
+    fn test_calculate_surplus(
+        #[case] pool_value: u64,
+        #[case] issued_gsol: u64,
+        #[case] expected_surplus: i64,
+    ) -> Result<()> {
+        let mut sunrise_state = create_sunrise_state();
+        let stake_pool = create_stake_pool();
+
+        let (beam_state, beam_key) =
+            create_and_register_beam_state(&mut sunrise_state, issued_gsol)?;
+        let beam_state_account_info = create_mock_account_info(&beam_state, &crate::ID, &beam_key);
+        let beam_state_account = Account::try_from(&beam_state_account_info)?;
+
+        let pool_token_vault =
+            clone_token_account_with_amount(&TokenAccount::default(), pool_value)?;
+
+        let surplus = calculate_surplus(
+            &sunrise_state,
+            &beam_state_account,
+            &stake_pool,
+            &pool_token_vault,
+        )
+        .unwrap();
+        assert_eq!(surplus, expected_surplus);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,371 @@
+//! Integration tests of the SPL beam against the devnet stake pool in `packages/tests/fixtures`.
+//!
+//! Withdrawals of stake split from a validator run against the pool with a delegated validator
+//! stake account added, as the fixture's reserve stake account is not delegated.
+
+use beam_test_utils::beams::initialize_spl_beam;
+use beam_test_utils::{
+    add_spl_stake_pool, add_spl_stake_pool_with_validator_stake, beam_program_test, suite,
+    BeamTestContext, SPL_STAKE_POOL_EPOCH, SPL_STAKE_POOL_VALIDATOR,
+};
+use solana_program_test::{tokio, ProgramTest};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::stake::state::StakeStateV2;
+use sunrise_client::spl::{self, SplClient, WithdrawalTicket};
+use sunrise_client::BeamValue;
+
+const DEPOSIT: u64 = 10 * LAMPORTS_PER_SOL;
+const WITHDRAWAL: u64 = 5 * LAMPORTS_PER_SOL;
+const BURN: u64 = LAMPORTS_PER_SOL;
+const DELAYED_WITHDRAWAL: u64 = 3 * LAMPORTS_PER_SOL;
+const VALIDATOR_STAKE: u64 = 100 * LAMPORTS_PER_SOL;
+
+fn program_test() -> ProgramTest {
+    let mut program_test = beam_program_test!(spl_beam, spl::ID);
+    add_spl_stake_pool(&mut program_test);
+    program_test
+}
+
+/// As `program_test`, with stake delegated to [SPL_STAKE_POOL_VALIDATOR] to withdraw from.
+fn program_test_with_validator_stake() -> ProgramTest {
+    let mut program_test = beam_program_test!(spl_beam, spl::ID);
+    add_spl_stake_pool_with_validator_stake(&mut program_test, VALIDATOR_STAKE);
+    program_test
+}
+
+/// Start in the epoch that the stake pool was last updated in, with an initialized beam
+/// that is not yet registered, and a funded staker with a gSOL token account.
+async fn setup() -> (BeamTestContext, SplClient, Keypair, Pubkey) {
+    setup_with(program_test()).await
+}
+
+/// As `setup`, against the accounts of `program_test`.
+async fn setup_with(program_test: ProgramTest) -> (BeamTestContext, SplClient, Keypair, Pubkey) {
+    setup_in(BeamTestContext::start(program_test).await).await
+}
+
+/// As `setup`, in an already started `context`.
+async fn setup_in(mut context: BeamTestContext) -> (BeamTestContext, SplClient, Keypair, Pubkey) {
+    context.warp_to_epoch(SPL_STAKE_POOL_EPOCH).await;
+    let beam = initialize_spl_beam(&mut context).await;
+    let (staker, gsol_account) = context.create_staker().await;
+    (context, beam, staker, gsol_account)
+}
+
+#[tokio::test]
+async fn cannot_deposit_before_registration() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    suite::cannot_deposit_before_registration(&mut context, &beam, &staker, &gsol_account, DEPOSIT)
+        .await;
+}
+
+#[tokio::test]
+async fn can_deposit_and_withdraw() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    suite::can_deposit_and_withdraw(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        WITHDRAWAL,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn cannot_withdraw_below_the_minimum_out() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    suite::cannot_withdraw_below_the_minimum_out(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        WITHDRAWAL,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn can_extract_the_yield_of_burned_gsol() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    let gsol = suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    // Burning gSOL leaves the beam holding more value than its gSOL supply.
+    let vault_balance = context.token_balance(&beam.pool_token_vault()).await;
+    let burn = beam.burn(&staker.pubkey(), &gsol_account, BURN);
+    context.send(&[burn], &[&staker]).await.unwrap();
+    assert_eq!(context.token_balance(&gsol_account).await, gsol - BURN);
+    assert_eq!(
+        context.token_balance(&beam.pool_token_vault()).await,
+        vault_balance
+    );
+
+    let value: BeamValue = context.view(beam.get_beam_value()).await;
+    assert_eq!(value.gsol_supply, gsol - BURN);
+    assert!(value.surplus > 0);
+
+    let update_epoch_report = beam.update_epoch_report();
+    context.send(&[update_epoch_report], &[]).await.unwrap();
+    context.refresh_sunrise().await;
+    let report = &context.sunrise.state.epoch_report.beam_epoch_details[0];
+    assert_eq!(report.epoch, SPL_STAKE_POOL_EPOCH);
+    assert_eq!(report.extractable_yield, value.surplus as u64);
+
+    let yield_account = context.sunrise.state.yield_account;
+    let yield_balance = context.balance(&yield_account).await;
+    let extract_yield = beam.extract_yield(&context.payer());
+    context.send(&[extract_yield], &[]).await.unwrap();
+
+    context.refresh_sunrise().await;
+    let report = &context.sunrise.state.epoch_report.beam_epoch_details[0];
+    let extracted_yield = report.extracted_yield;
+    assert_eq!(
+        context.balance(&yield_account).await,
+        yield_balance + extracted_yield
+    );
+    // The stake withdrawn from the pool differs slightly from the estimate, due to rounding.
+    assert!(extracted_yield.abs_diff(value.surplus as u64) < 3000);
+}
+
+#[tokio::test]
+async fn can_extract_yield_without_a_treasury() {
+    let context = BeamTestContext::start_without_treasury(program_test()).await;
+    let (mut context, beam, staker, gsol_account) = setup_in(context).await;
+    context.register_beam(&beam.state_address).await;
+    suite::can_extract_yield_without_a_treasury(
+        &mut context,
+        &beam,
+        &staker,
+        &gsol_account,
+        DEPOSIT,
+        BURN,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn can_withdraw_stake_from_a_validator() {
+    let (mut context, beam, staker, gsol_account) =
+        setup_with(program_test_with_validator_stake()).await;
+    context.register_beam(&beam.state_address).await;
+    let gsol = suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    let stake_account = Keypair::new();
+    let withdraw_stake = beam.withdraw_stake(
+        &staker.pubkey(),
+        &gsol_account,
+        &stake_account.pubkey(),
+        WITHDRAWAL,
+        Some(SPL_STAKE_POOL_VALIDATOR),
+    );
+    context
+        .send(&[withdraw_stake], &[&staker, &stake_account])
+        .await
+        .unwrap();
+
+    // The lamports requested are withdrawn, less the rent of the stake account,
+    // and only those are burned.
+    let stake_account_rent = context
+        .rent()
+        .await
+        .minimum_balance(StakeStateV2::size_of());
+    let withdrawn = WITHDRAWAL - stake_account_rent;
+    assert_eq!(context.token_balance(&gsol_account).await, gsol - withdrawn);
+
+    // The stake account holds the withdrawal, less the pool's stake withdrawal fee,
+    // delegated to the validator and owned by the staker.
+    let account = context.account(&stake_account.pubkey()).await.unwrap();
+    let stake_state: StakeStateV2 = account.deserialize_data().unwrap();
+    let authorized = stake_state.authorized().unwrap();
+    assert_eq!(authorized.staker, staker.pubkey());
+    assert_eq!(authorized.withdrawer, staker.pubkey());
+    let delegation = stake_state.delegation().unwrap();
+    assert_eq!(delegation.voter_pubkey, SPL_STAKE_POOL_VALIDATOR);
+    assert!(delegation.stake <= withdrawn);
+    assert!(delegation.stake > withdrawn - withdrawn / 100);
+}
+
+#[tokio::test]
+async fn withdrawing_stake_burns_only_the_lamports_withdrawn() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    let gsol = suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+    let staker_balance = context.balance(&staker.pubkey()).await;
+
+    // Without validator stake to split from, the stake is split from the pool's reserve.
+    let stake_account = Keypair::new();
+    let withdraw_stake = beam.withdraw_stake(
+        &staker.pubkey(),
+        &gsol_account,
+        &stake_account.pubkey(),
+        WITHDRAWAL,
+        None,
+    );
+    context
+        .send(&[withdraw_stake], &[&staker, &stake_account])
+        .await
+        .unwrap();
+
+    // The staker pays the rent of the new stake account, so it is deducted from the lamports
+    // withdrawn from the pool, and only the lamports withdrawn are burned.
+    let stake_account_rent = context
+        .rent()
+        .await
+        .minimum_balance(StakeStateV2::size_of());
+    let withdrawn = WITHDRAWAL - stake_account_rent;
+    assert_eq!(context.token_balance(&gsol_account).await, gsol - withdrawn);
+    assert_eq!(
+        context.balance(&staker.pubkey()).await,
+        staker_balance - stake_account_rent
+    );
+
+    // The stake account holds its rent and the withdrawal, less the pool's stake withdrawal fee.
+    let stake_balance = context.balance(&stake_account.pubkey()).await - stake_account_rent;
+    assert!(stake_balance <= withdrawn);
+    assert!(stake_balance > withdrawn - withdrawn / 100);
+}
+
+#[tokio::test]
+async fn can_order_a_withdrawal_and_redeem_the_ticket() {
+    let (mut context, beam, staker, gsol_account) =
+        setup_with(program_test_with_validator_stake()).await;
+    context.register_beam(&beam.state_address).await;
+    let gsol = suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    let stake_account = Keypair::new();
+    let order_withdrawal = beam.order_withdrawal(
+        &staker.pubkey(),
+        &gsol_account,
+        &stake_account.pubkey(),
+        DELAYED_WITHDRAWAL,
+        Some(SPL_STAKE_POOL_VALIDATOR),
+    );
+    context
+        .send(&[order_withdrawal], &[&staker, &stake_account])
+        .await
+        .unwrap();
+
+    // The rent of the stake account is deducted from the withdrawal,
+    // and only the lamports withdrawn are burned.
+    let stake_account_rent = context
+        .rent()
+        .await
+        .minimum_balance(StakeStateV2::size_of());
+    let withdrawn = DELAYED_WITHDRAWAL - stake_account_rent;
+    assert_eq!(context.token_balance(&gsol_account).await, gsol - withdrawn);
+
+    let ticket_address =
+        spl::derive_withdrawal_ticket(&spl::ID, &beam.state_address, &stake_account.pubkey()).0;
+    let ticket: WithdrawalTicket = context.fetch(&ticket_address).await;
+    assert_eq!(ticket.state, beam.state_address);
+    assert_eq!(ticket.stake_account, stake_account.pubkey());
+    assert_eq!(ticket.beneficiary, staker.pubkey());
+
+    // The stake is still deactivating in the epoch it was ordered in.
+    let redeem_ticket = beam.redeem_ticket(&ticket);
+    assert!(context
+        .send(&[redeem_ticket.clone()], &[&staker])
+        .await
+        .is_err());
+
+    context.warp_to_epoch(SPL_STAKE_POOL_EPOCH + 1).await;
+    let staker_balance = context.balance(&staker.pubkey()).await;
+    let stake_balance = context.balance(&stake_account.pubkey()).await;
+    let ticket_balance = context.balance(&ticket_address).await;
+    context.send(&[redeem_ticket], &[&staker]).await.unwrap();
+
+    assert_eq!(
+        context.balance(&staker.pubkey()).await,
+        staker_balance + stake_balance + ticket_balance
+    );
+    assert!(context.account(&ticket_address).await.is_none());
+}
+
+#[tokio::test]
+async fn can_order_a_withdrawal_from_the_reserve_and_redeem_the_ticket_at_once() {
+    let (mut context, beam, staker, gsol_account) = setup().await;
+    context.register_beam(&beam.state_address).await;
+    let gsol = suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    // Without validator stake to split from, the stake is split from the pool's reserve.
+    let stake_account = Keypair::new();
+    let order_withdrawal = beam.order_withdrawal(
+        &staker.pubkey(),
+        &gsol_account,
+        &stake_account.pubkey(),
+        DELAYED_WITHDRAWAL,
+        None,
+    );
+    context
+        .send(&[order_withdrawal], &[&staker, &stake_account])
+        .await
+        .unwrap();
+
+    let stake_account_rent = context
+        .rent()
+        .await
+        .minimum_balance(StakeStateV2::size_of());
+    let withdrawn = DELAYED_WITHDRAWAL - stake_account_rent;
+    assert_eq!(context.token_balance(&gsol_account).await, gsol - withdrawn);
+
+    // The reserve's stake is not delegated, so it is not deactivated and needs no cooldown.
+    let account = context.account(&stake_account.pubkey()).await.unwrap();
+    let stake_state: StakeStateV2 = account.deserialize_data().unwrap();
+    assert!(matches!(stake_state, StakeStateV2::Initialized(_)));
+
+    let ticket_address =
+        spl::derive_withdrawal_ticket(&spl::ID, &beam.state_address, &stake_account.pubkey()).0;
+    let ticket: WithdrawalTicket = context.fetch(&ticket_address).await;
+    let staker_balance = context.balance(&staker.pubkey()).await;
+    let stake_balance = context.balance(&stake_account.pubkey()).await;
+    let ticket_balance = context.balance(&ticket_address).await;
+    let redeem_ticket = beam.redeem_ticket(&ticket);
+    context.send(&[redeem_ticket], &[&staker]).await.unwrap();
+
+    assert_eq!(
+        context.balance(&staker.pubkey()).await,
+        staker_balance + stake_balance + ticket_balance
+    );
+    assert!(context.account(&ticket_address).await.is_none());
+}
+
+#[tokio::test]
+async fn cannot_redeem_another_beneficiarys_ticket() {
+    let (mut context, beam, staker, gsol_account) =
+        setup_with(program_test_with_validator_stake()).await;
+    context.register_beam(&beam.state_address).await;
+    suite::deposit(&mut context, &beam, &staker, &gsol_account, DEPOSIT).await;
+
+    let stake_account = Keypair::new();
+    let order_withdrawal = beam.order_withdrawal(
+        &staker.pubkey(),
+        &gsol_account,
+        &stake_account.pubkey(),
+        DELAYED_WITHDRAWAL,
+        Some(SPL_STAKE_POOL_VALIDATOR),
+    );
+    context
+        .send(&[order_withdrawal], &[&staker, &stake_account])
+        .await
+        .unwrap();
+    context.warp_to_epoch(SPL_STAKE_POOL_EPOCH + 1).await;
+
+    let ticket_address =
+        spl::derive_withdrawal_ticket(&spl::ID, &beam.state_address, &stake_account.pubkey()).0;
+    let ticket: WithdrawalTicket = context.fetch(&ticket_address).await;
+    let thief = context.create_user(LAMPORTS_PER_SOL).await;
+    let redeem_ticket = beam.redeem_ticket(&WithdrawalTicket {
+        beneficiary: thief.pubkey(),
+        ..ticket
+    });
+
+    assert!(context.send(&[redeem_ticket], &[&thief]).await.is_err());
+    assert!(context.account(&ticket_address).await.is_some());
+}
@@ -1,7 +1,10 @@
-use crate::{system, token, utils, BeamError, BurnGsol};
+use crate::{system, token, utils, BurnGsol};
 use anchor_lang::prelude::*;
 
-pub fn handler(ctx: Context<BurnGsol>, amount_in_lamports: u64) -> Result<()> {
+pub fn handler<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, BurnGsol>,
+    amount_in_lamports: u64,
+) -> Result<()> {
     let state = &mut ctx.accounts.state;
 
     // Check that the requesting program is valid.
@@ -9,24 +12,8 @@ pub fn handler(ctx: Context<BurnGsol>, amount_in_lamports: u64) -> Result<()> {
         utils::get_cpi_program_id(&ctx.accounts.sysvar_instructions.to_account_info())?;
     system::checked_find_beam_idx(state, &ctx.accounts.beam, &cpi_program)?;
 
-    let details = state
-        .get_mut_beam_details(&ctx.accounts.beam.key())
-        .ok_or(BeamError::UnidentifiedBeam)?;
-
     // Can't burn more gsol than this beam is responsible for.
-    if details.partial_gsol_supply < amount_in_lamports {
-        msg!(
-            "Beam supply {}, requested burn {}",
-            details.partial_gsol_supply,
-            amount_in_lamports
-        );
-        return Err(BeamError::BurnWindowExceeded.into());
-    }
-
-    details.partial_gsol_supply = details
-        .partial_gsol_supply
-        .checked_sub(amount_in_lamports)
-        .unwrap();
+    state.burn_gsol(&ctx.accounts.beam.key(), amount_in_lamports)?;
     token::burn(
         amount_in_lamports,
         &ctx.accounts.gsol_mint.to_account_info(),
@@ -35,5 +22,22 @@ pub fn handler(ctx: Context<BurnGsol>, amount_in_lamports: u64) -> Result<()> {
         &ctx.accounts.token_program,
     )?;
 
+    // Record the withdrawal against the owner's attribution, if they registered one.
+    let state = &ctx.accounts.state;
+    if let Some(mut attribution) = system::load_attribution(
+        &state.key(),
+        &ctx.accounts.burn_gsol_from_owner.key(),
+        ctx.remaining_accounts,
+    )? {
+        ctx.accounts.burn_gsol_from.reload()?;
+        attribution.record_burn(
+            amount_in_lamports,
+            ctx.accounts.burn_gsol_from.amount,
+            state.yield_per_gsol,
+            Clock::get()?.epoch,
+        )?;
+        attribution.exit(&crate::ID)?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,48 @@
+use crate::seeds::IMPACT_CERTIFICATE;
+use crate::state::ImpactCertificate;
+use crate::{token, BeamError, ClaimImpactCertificate};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<ClaimImpactCertificate>, period: u64) -> Result<()> {
+    let epoch = Clock::get()?.epoch;
+    require_eq!(
+        period,
+        ImpactCertificate::period(epoch),
+        BeamError::InvalidImpactPeriod
+    );
+
+    let state = &ctx.accounts.state;
+    let certificate = &mut ctx.accounts.certificate;
+    ctx.accounts.attribution.certify(
+        certificate,
+        ctx.accounts.gsol_token_account.amount,
+        state.yield_per_gsol,
+        epoch,
+    )?;
+
+    certificate.state = state.key();
+    certificate.owner = ctx.accounts.owner.key();
+    certificate.mint = ctx.accounts.certificate_mint.key();
+    certificate.bump = ctx.bumps.certificate;
+    certificate.period = period;
+    certificate.project = ctx.accounts.project.key();
+
+    let state_address = state.key();
+    let owner_address = ctx.accounts.owner.key();
+    let period_bytes = period.to_le_bytes();
+    let seeds = &[
+        state_address.as_ref(),
+        IMPACT_CERTIFICATE,
+        owner_address.as_ref(),
+        period_bytes.as_ref(),
+        &[certificate.bump],
+    ];
+
+    token::issue_certificate(
+        &ctx.accounts.certificate_mint.to_account_info(),
+        &ctx.accounts.certificate.to_account_info(),
+        &ctx.accounts.certificate_token_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        seeds,
+    )
+}
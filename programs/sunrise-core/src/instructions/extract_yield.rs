@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{system, utils, BeamError, ExtractYield};
+use crate::{system, utils, ExtractYield};
 
 /// CPI request from a beam program to extract yield from Sunrise
 /// This can only be invoked by a valid beam.
@@ -14,25 +14,18 @@ pub fn handler(ctx: Context<ExtractYield>, amount_in_lamports: u64) -> Result<()
     let cpi_program =
         utils::get_cpi_program_id(&ctx.accounts.sysvar_instructions.to_account_info())?;
     let beam_idx = system::checked_find_beam_idx(state, &ctx.accounts.beam, &cpi_program)?;
-    let beam_epoch_details = &state.epoch_report.beam_epoch_details[beam_idx];
 
-    // The epoch report must be already updated for this epoch and beam
-    require!(
-        beam_epoch_details.epoch == current_epoch,
-        BeamError::EpochReportNotUpToDate
-    );
+    // Update the extracted yield on the epoch report for the beam, if the epoch report has
+    // already been updated for this epoch and beam, and no beam has a deficit.
+    // The beam sends the protocol fee share of the extracted yield to the treasury.
+    let protocol_fee = state.extract_yield(beam_idx, amount_in_lamports, current_epoch)?;
 
     msg!(
-        "Registering extracted yield of {} lamports for beam {}",
+        "Registered extracted yield of {} lamports for beam {}, with a protocol fee of {} lamports",
         amount_in_lamports,
-        beam_idx
+        beam_idx,
+        protocol_fee
     );
 
-    // Update the extracted yield on the epoch report for the beam,
-    // if the epoch report has already been updated for this epoch and beam (TODO is this check strictly necessary?)
-    state
-        .epoch_report
-        .extract_yield_for_beam(beam_idx, amount_in_lamports, current_epoch)?;
-
     Ok(())
 }
@@ -0,0 +1,10 @@
+use crate::{state::AttributedYield, ViewAttribution};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<ViewAttribution>) -> Result<AttributedYield> {
+    ctx.accounts.attribution.attributed_yield(
+        ctx.accounts.gsol_token_account.amount,
+        ctx.accounts.state.yield_per_gsol,
+        Clock::get()?.epoch,
+    )
+}
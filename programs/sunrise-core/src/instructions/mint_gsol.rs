@@ -1,40 +1,24 @@
-use crate::{system, token, utils, BeamError, MintGsol};
+use crate::{system, token, utils, MintGsol};
 use anchor_lang::prelude::*;
 
-pub fn handler(ctx: Context<MintGsol>, amount_in_lamports: u64) -> Result<()> {
+pub fn handler<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, MintGsol>,
+    amount_in_lamports: u64,
+) -> Result<()> {
     let amount = amount_in_lamports;
     let state = &mut ctx.accounts.state;
-    let gsol_mint = &ctx.accounts.gsol_mint;
 
     // Check that the executing program is valid.
     let cpi_program =
         utils::get_cpi_program_id(&ctx.accounts.sysvar_instructions.to_account_info())?;
     system::checked_find_beam_idx(state, &ctx.accounts.beam, &cpi_program)?;
 
-    let pre_supply = state.pre_supply;
-    let effective_supply = gsol_mint.supply.checked_sub(pre_supply).unwrap();
-
-    let details = state
-        .get_mut_beam_details(&ctx.accounts.beam.key())
-        .ok_or(BeamError::UnidentifiedBeam)?;
-
-    let mint_window = if effective_supply != 0 {
-        (details.allocation as u64)
-            .checked_mul(effective_supply)
-            .unwrap()
-            .checked_div(100)
-            .unwrap()
-    } else {
-        // Mint initially with no restrictions. The allocations will come into effect afterwards.
-        amount
-    };
-
-    if details.partial_gsol_supply > mint_window {
-        return Err(BeamError::MintWindowExceeded.into());
-    }
-
-    // Increase the partial circulating gsol supply for the beam.
-    details.partial_gsol_supply = details.partial_gsol_supply.checked_add(amount).unwrap();
+    // Increase the partial circulating gsol supply for the beam, within its mint window.
+    state.mint_gsol(
+        &ctx.accounts.beam.key(),
+        amount,
+        ctx.accounts.gsol_mint.supply,
+    )?;
     token::mint_to(
         amount,
         &ctx.accounts.gsol_mint.to_account_info(),
@@ -44,5 +28,22 @@ pub fn handler(ctx: Context<MintGsol>, amount_in_lamports: u64) -> Result<()> {
         &ctx.accounts.state,
     )?;
 
+    // Record the deposit against the depositor's attribution, if they registered one.
+    let state = &ctx.accounts.state;
+    if let Some(mut attribution) = system::load_attribution(
+        &state.key(),
+        &ctx.accounts.mint_gsol_to.owner,
+        ctx.remaining_accounts,
+    )? {
+        ctx.accounts.mint_gsol_to.reload()?;
+        attribution.record_mint(
+            amount,
+            ctx.accounts.mint_gsol_to.amount,
+            state.yield_per_gsol,
+            Clock::get()?.epoch,
+        )?;
+        attribution.exit(&crate::ID)?;
+    }
+
     Ok(())
 }
@@ -1,24 +1,38 @@
 pub mod burn_gsol;
+pub mod claim_impact_certificate;
 pub mod export_mint_authority;
 pub mod extract_yield;
+pub mod get_attributed_yield;
 pub mod mint_gsol;
+pub mod register_attribution;
+pub mod register_backing_oracle;
 pub mod register_beam;
+pub mod register_impact_project;
 pub mod register_state;
 pub mod remove_beam;
 pub mod resize_allocations;
+pub mod resize_backing_oracle;
+pub mod socialise_deficit;
 pub mod transfer_gsol;
 pub mod update_allocations;
 pub mod update_epoch_report;
 pub mod update_state;
 
 pub use burn_gsol::*;
+pub use claim_impact_certificate::*;
 pub use export_mint_authority::*;
 pub use extract_yield::*;
+pub use get_attributed_yield::*;
 pub use mint_gsol::*;
+pub use register_attribution::*;
+pub use register_backing_oracle::*;
 pub use register_beam::*;
+pub use register_impact_project::*;
 pub use register_state::*;
 pub use remove_beam::*;
 pub use resize_allocations::*;
+pub use resize_backing_oracle::*;
+pub use socialise_deficit::*;
 pub use transfer_gsol::*;
 pub use update_allocations::*;
 pub use update_epoch_report::*;
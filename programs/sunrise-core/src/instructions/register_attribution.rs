@@ -0,0 +1,18 @@
+use crate::RegisterAttribution;
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<RegisterAttribution>) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let attribution = &mut ctx.accounts.attribution;
+
+    attribution.state = state.key();
+    attribution.owner = ctx.accounts.owner.key();
+    attribution.bump = ctx.bumps.attribution;
+    // Only yield extracted from now on is attributed.
+    let epoch = Clock::get()?.epoch;
+    attribution.updated_epoch = epoch;
+    attribution.certified_epoch = epoch;
+    attribution.yield_per_gsol = state.yield_per_gsol;
+
+    Ok(())
+}
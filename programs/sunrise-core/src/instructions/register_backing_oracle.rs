@@ -0,0 +1,12 @@
+use crate::RegisterBackingOracle;
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<RegisterBackingOracle>) -> Result<()> {
+    let state = &ctx.accounts.state;
+    ctx.accounts.backing_oracle.register(
+        state.key(),
+        ctx.bumps.backing_oracle,
+        state,
+        Clock::get()?.slot,
+    )
+}
@@ -0,0 +1,19 @@
+use crate::state::ImpactProject;
+use crate::{BeamError, RegisterImpactProject};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<RegisterImpactProject>, name: String) -> Result<()> {
+    require_gte!(
+        ImpactProject::MAX_NAME_LEN,
+        name.len(),
+        BeamError::ImpactProjectNameTooLong
+    );
+
+    let project = &mut ctx.accounts.project;
+    project.state = ctx.accounts.state.key();
+    project.recipient = ctx.accounts.recipient.key();
+    project.bump = ctx.bumps.project;
+    project.name = name;
+
+    Ok(())
+}
@@ -9,5 +9,13 @@ pub fn handler(ctx: Context<RegisterState>, input: RegisterStateInput) -> Result
     let mint_supply = ctx.accounts.gsol_mint.supply;
 
     state_account.register(input, auth_bump, &mint_key, mint_supply)?;
-    Ok(())
+
+    // The backing oracle is created with the state, so that beams can report from the start.
+    let state = &ctx.accounts.state;
+    ctx.accounts.backing_oracle.register(
+        state.key(),
+        ctx.bumps.backing_oracle,
+        state,
+        Clock::get()?.slot,
+    )
 }
@@ -1,7 +1,6 @@
 use crate::{
-    state::{BeamDetails, State},
-    utils::resize_account,
-    ResizeAllocations,
+    instructions::resize_backing_oracle::resize_to_allocations, state::State,
+    utils::resize_account, ResizeAllocations,
 };
 use anchor_lang::prelude::*;
 
@@ -17,12 +16,16 @@ pub fn handler(ctx: Context<ResizeAllocations>, to_add: usize) -> Result<()> {
         new_size,
     )?;
 
-    // Extend allocations with default beam-details objects.
-    state
-        .allocations
-        .extend(std::iter::repeat(BeamDetails::default()).take(to_add));
+    // Extend allocations with default beam-details objects, keeping the epoch report aligned.
+    state.extend_allocations(to_add, Clock::get()?.epoch);
 
-    Ok(())
+    // Keep the backing oracle's capacity in line with the allocations, so that reports don't fail.
+    resize_to_allocations(
+        &mut ctx.accounts.backing_oracle,
+        &ctx.accounts.state,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )
 }
 
 fn calculate_new_size(state: &State, additional_beams: usize) -> usize {
@@ -37,6 +40,7 @@ fn calculate_new_size(state: &State, additional_beams: usize) -> usize {
 #[cfg(test)]
 pub mod resize_checks {
     use super::*;
+    use crate::state::BeamDetails;
 
     #[test]
     fn test_calculate_new_size() {
@@ -0,0 +1,40 @@
+use crate::{
+    state::{BackingOracle, BeamBacking, State},
+    utils::resize_account,
+    ResizeBackingOracle,
+};
+use anchor_lang::prelude::*;
+
+pub fn handler(ctx: Context<ResizeBackingOracle>) -> Result<()> {
+    resize_to_allocations(
+        &mut ctx.accounts.backing_oracle,
+        &ctx.accounts.state,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )
+}
+
+/// Grows the `oracle` to the capacity of the state's allocations, if it is smaller.
+pub fn resize_to_allocations<'info>(
+    oracle: &mut Account<'info, BackingOracle>,
+    state: &State,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let capacity = state.allocations.len();
+    if oracle.beam_backing.len() >= capacity {
+        return Ok(());
+    }
+
+    resize_account(
+        &oracle.to_account_info(),
+        payer,
+        system_program,
+        BackingOracle::size(capacity),
+    )?;
+
+    oracle.beam_backing.resize(capacity, BeamBacking::default());
+    oracle.update(state, Clock::get()?.slot)?;
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::{BeamError, SocialiseDeficit};
+use anchor_lang::prelude::*;
+
+/// Covers part of one beam's deficit with another beam's surplus.
+///
+/// The covered amount of gSOL is reassigned from the beam in deficit to the covering beam,
+/// which then backs it with its surplus instead of releasing that surplus as yield.
+pub fn handler(
+    ctx: Context<SocialiseDeficit>,
+    beam: Pubkey,
+    covering_beam: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let current_epoch = Clock::get()?.epoch;
+
+    let beam_idx = state
+        .find_beam_index(&beam)
+        .ok_or(BeamError::UnidentifiedBeam)?;
+    let covering_beam_idx = state
+        .find_beam_index(&covering_beam)
+        .ok_or(BeamError::UnidentifiedBeam)?;
+
+    msg!(
+        "Covering {} lamports of deficit for beam {} with surplus from beam {}",
+        amount,
+        beam_idx,
+        covering_beam_idx
+    );
+
+    state.socialise_deficit(beam_idx, covering_beam_idx, amount, current_epoch)
+}
@@ -7,7 +7,7 @@ use crate::{system, utils, UpdateEpochReport};
 /// However, a beam is allowed to call in multiple times to update its epoch report for a given epoch.
 pub fn handler<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, UpdateEpochReport>,
-    extractable_yield: u64,
+    surplus: i64,
 ) -> Result<()> {
     let state = &mut ctx.accounts.state;
     let current_epoch = Clock::get().unwrap().epoch;
@@ -17,19 +17,18 @@ pub fn handler<'c: 'info, 'info>(
         utils::get_cpi_program_id(&ctx.accounts.sysvar_instructions.to_account_info())?;
     let beam_idx = system::checked_find_beam_idx(state, &ctx.accounts.beam, &cpi_program)?;
 
-    msg!(
-        "Updating extractable yield for beam {} to {}",
-        beam_idx,
-        extractable_yield
-    );
+    msg!("Updating surplus for beam {} to {}", beam_idx, surplus);
 
-    // Update the epoch report with the current extractable yield
-    state
-        .epoch_report
-        .update_extractable_yield_and_epoch_for_beam(beam_idx, current_epoch, extractable_yield);
+    // Update the epoch report with the current surplus (extractable yield or deficit)
+    state.update_surplus_and_epoch_for_beam(beam_idx, current_epoch, surplus);
 
     // Update the current gsol supply
     state.epoch_report.current_gsol_supply = ctx.accounts.gsol_mint.supply;
 
+    // Update the backing oracle with the new report
+    ctx.accounts
+        .backing_oracle
+        .update(state, Clock::get()?.slot)?;
+
     Ok(())
 }
@@ -4,6 +4,5 @@ use crate::state::*;
 use crate::UpdateState;
 
 pub fn handler(ctx: Context<UpdateState>, input: UpdateStateInput) -> Result<()> {
-    ctx.accounts.state.update(input);
-    Ok(())
+    ctx.accounts.state.update(input)
 }
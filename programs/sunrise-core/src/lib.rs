@@ -2,6 +2,8 @@
 
 mod instructions;
 pub mod seeds;
+#[cfg(test)]
+mod simulation;
 mod state;
 mod system;
 mod token;
@@ -9,12 +11,15 @@ mod utils;
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use instructions::*;
 use seeds::*;
 
 pub use state::{
-    AllocationUpdate, BeamDetails, EpochReport, RegisterStateInput, State, UpdateStateInput,
+    AllocationUpdate, AttributedYield, Attribution, BackingOracle, BeamBacking, BeamDetails,
+    EpochReport, ImpactCertificate, ImpactProject, RegisterStateInput, State, UpdateStateInput,
+    IMPACT_PERIOD_EPOCHS, ONE_GSOL, YIELD_PER_GSOL_SCALE,
 };
 
 declare_id!("suncPB4RR39bMwnRhCym6ZLKqMfnFG83vjzVVuXNhCq");
@@ -23,12 +28,14 @@ declare_id!("suncPB4RR39bMwnRhCym6ZLKqMfnFG83vjzVVuXNhCq");
 pub mod sunrise_core {
     use super::*;
 
-    /// Initializes a [State], setting its initial parameters.
+    /// Initializes a [State], setting its initial parameters, and its [BackingOracle].
     pub fn register_state(ctx: Context<RegisterState>, input: RegisterStateInput) -> Result<()> {
         register_state::handler(ctx, input)
     }
 
     /// Updates a [State] without modifying its [BeamDetails] list.
+    ///
+    /// Errors if the protocol fee exceeds 100%, or is set without a treasury.
     pub fn update_state(ctx: Context<UpdateState>, input: UpdateStateInput) -> Result<()> {
         update_state::handler(ctx, input)
     }
@@ -43,7 +50,8 @@ pub mod sunrise_core {
         register_beam::handler(ctx)
     }
 
-    /// Resize the state so it can append `additional` more allocations.
+    /// Resize the state so it can append `additional` more allocations,
+    /// and its [BackingOracle] to match.
     pub fn resize_allocations(ctx: Context<ResizeAllocations>, additional_beams: u8) -> Result<()> {
         resize_allocations::handler(ctx, additional_beams as usize)
     }
@@ -63,14 +71,20 @@ pub mod sunrise_core {
     ///
     /// This checks for the signature of the account with the registered key, and
     /// verifies that the immediate calling program owns that account.
-    pub fn mint_gsol(ctx: Context<MintGsol>, amount: u64) -> Result<()> {
+    pub fn mint_gsol<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MintGsol>,
+        amount: u64,
+    ) -> Result<()> {
         mint_gsol::handler(ctx, amount)
     }
 
     /// CPI request from a beam program to burn gSol.
     ///
     /// Same invariants as for [minting][sunrise_core::mint_gsol()].
-    pub fn burn_gsol(ctx: Context<BurnGsol>, amount: u64) -> Result<()> {
+    pub fn burn_gsol<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, BurnGsol>,
+        amount: u64,
+    ) -> Result<()> {
         burn_gsol::handler(ctx, amount)
     }
 
@@ -88,7 +102,7 @@ pub mod sunrise_core {
 
     /// Removes a beam from the state.
     ///
-    /// Errors if the beam's allocation is not set to zero.
+    /// Errors if the beam's allocation is not set to zero, or if it has a deficit.
     pub fn remove_beam(ctx: Context<RemoveBeam>, beam: Pubkey) -> Result<()> {
         remove_beam::handler(ctx, beam)
     }
@@ -99,17 +113,94 @@ pub mod sunrise_core {
     }
 
     /// Updates the Epoch Report Account, which stores the amount of yield extracted or extractable over time
+    ///
+    /// The beam reports a signed `surplus`: positive values are extractable yield and
+    /// negative values are recorded as a deficit against the beam.
+    /// The [BackingOracle] is updated with the new report.
     pub fn update_epoch_report<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, UpdateEpochReport>,
-        extractable_yield: u64,
+        surplus: i64,
     ) -> Result<()> {
-        update_epoch_report::handler(ctx, extractable_yield)
+        update_epoch_report::handler(ctx, surplus)
     }
 
     /// CPI request from a beam program to extract yield from Sunrise
+    ///
+    /// The protocol fee share of the extracted yield, which the beam sends to the treasury,
+    /// is recorded on the epoch report.
+    ///
+    /// Errors while any beam has an outstanding deficit.
     pub fn extract_yield(ctx: Context<ExtractYield>, amount_in_lamports: u64) -> Result<()> {
         extract_yield::handler(ctx, amount_in_lamports)
     }
+
+    /// Covers `amount` of the deficit of `beam` with the surplus of `covering_beam`.
+    ///
+    /// Errors if either beam has not reported this epoch, or if `amount` exceeds
+    /// the deficit of `beam` or the surplus of `covering_beam`.
+    pub fn socialise_deficit(
+        ctx: Context<SocialiseDeficit>,
+        beam: Pubkey,
+        covering_beam: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        socialise_deficit::handler(ctx, beam, covering_beam, amount)
+    }
+
+    /// Initializes the [BackingOracle] of a state, with capacity for all of its allocations.
+    ///
+    /// Only needed for states registered before the oracle was created with the state.
+    pub fn register_backing_oracle(ctx: Context<RegisterBackingOracle>) -> Result<()> {
+        register_backing_oracle::handler(ctx)
+    }
+
+    /// Resizes the [BackingOracle] to match the capacity of the state's allocations.
+    ///
+    /// Only needed for states whose allocations were resized before their oracle was registered.
+    pub fn resize_backing_oracle(ctx: Context<ResizeBackingOracle>) -> Result<()> {
+        resize_backing_oracle::handler(ctx)
+    }
+
+    /// Opts the `owner` in to yield attribution, by initializing their [Attribution].
+    ///
+    /// Once it is registered, the gSOL minted to and burned from the owner through a beam
+    /// that is passed the attribution is recorded in it.
+    /// Only yield extracted after registering is attributed.
+    pub fn register_attribution(ctx: Context<RegisterAttribution>) -> Result<()> {
+        register_attribution::handler(ctx)
+    }
+
+    /// View: returns the [AttributedYield] of an [Attribution] as of the current epoch,
+    /// with its balance capped at the gSOL held in the owner's token account.
+    pub fn get_attributed_yield(ctx: Context<ViewAttribution>) -> Result<AttributedYield> {
+        get_attributed_yield::handler(ctx)
+    }
+
+    /// Registers an [ImpactProject] funded by the state's yield, to be credited by
+    /// [ImpactCertificate]s.
+    ///
+    /// Errors if the name is longer than [ImpactProject::MAX_NAME_LEN] bytes.
+    pub fn register_impact_project(
+        ctx: Context<RegisterImpactProject>,
+        name: String,
+    ) -> Result<()> {
+        register_impact_project::handler(ctx, name)
+    }
+
+    /// Issues the `owner` an [ImpactCertificate] for the yield attributed to them since
+    /// their last certificate, crediting the [ImpactProject] of their choice,
+    /// along with a non-transferable certificate token.
+    ///
+    /// The attributed balance is first capped at the gSOL held in the owner's token account.
+    ///
+    /// Errors if `period` is not the current period, if a certificate was already claimed
+    /// in it, or if no yield has been attributed since the last certificate.
+    pub fn claim_impact_certificate(
+        ctx: Context<ClaimImpactCertificate>,
+        period: u64,
+    ) -> Result<()> {
+        claim_impact_certificate::handler(ctx, period)
+    }
 }
 
 #[derive(Accounts)]
@@ -137,6 +228,18 @@ pub struct RegisterState<'info> {
     )]
     pub gsol_mint_authority: UncheckedAccount<'info>,
 
+    #[account(
+        init,
+        payer = payer,
+        space = BackingOracle::size(input.initial_capacity as usize),
+        seeds = [
+            state.key().as_ref(),
+            BACKING_ORACLE
+        ],
+        bump
+    )]
+    pub backing_oracle: Box<Account<'info, BackingOracle>>,
+
     pub system_program: Program<'info, System>,
 
     pub rent: Sysvar<'info, Rent>,
@@ -178,6 +281,198 @@ pub struct RemoveBeam<'info> {
     pub update_authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct RegisterBackingOracle<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub state: Account<'info, State>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = BackingOracle::size(state.allocations.len()),
+        seeds = [
+            state.key().as_ref(),
+            BACKING_ORACLE
+        ],
+        bump
+    )]
+    pub backing_oracle: Account<'info, BackingOracle>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResizeBackingOracle<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            BACKING_ORACLE
+        ],
+        bump = backing_oracle.bump
+    )]
+    pub backing_oracle: Account<'info, BackingOracle>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterAttribution<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    pub state: Account<'info, State>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Attribution::SIZE,
+        seeds = [
+            state.key().as_ref(),
+            ATTRIBUTION,
+            owner.key().as_ref()
+        ],
+        bump
+    )]
+    pub attribution: Account<'info, Attribution>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ViewAttribution<'info> {
+    pub state: Account<'info, State>,
+
+    #[account(has_one = state)]
+    pub attribution: Account<'info, Attribution>,
+
+    /// The owner's gSOL token account, whose balance caps the attributed balance.
+    #[account(
+        token::mint = state.gsol_mint,
+        token::authority = attribution.owner,
+    )]
+    pub gsol_token_account: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterImpactProject<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(has_one = update_authority)]
+    pub state: Account<'info, State>,
+
+    pub update_authority: Signer<'info>,
+
+    /// CHECK: The account of the project that receives its share of the yield.
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ImpactProject::SIZE,
+        seeds = [
+            state.key().as_ref(),
+            IMPACT_PROJECT,
+            recipient.key().as_ref()
+        ],
+        bump
+    )]
+    pub project: Account<'info, ImpactProject>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u64)]
+pub struct ClaimImpactCertificate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = owner,
+    )]
+    pub attribution: Box<Account<'info, Attribution>>,
+
+    /// The owner's gSOL token account, whose balance caps the attributed balance.
+    #[account(
+        token::mint = state.gsol_mint,
+        token::authority = owner,
+    )]
+    pub gsol_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(has_one = state)]
+    pub project: Box<Account<'info, ImpactProject>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ImpactCertificate::SIZE,
+        seeds = [
+            state.key().as_ref(),
+            IMPACT_CERTIFICATE,
+            owner.key().as_ref(),
+            period.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub certificate: Box<Account<'info, ImpactCertificate>>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = certificate,
+        mint::freeze_authority = certificate,
+        seeds = [
+            certificate.key().as_ref(),
+            IMPACT_CERTIFICATE_MINT
+        ],
+        bump
+    )]
+    pub certificate_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = certificate_mint,
+        associated_token::authority = owner,
+    )]
+    pub certificate_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SocialiseDeficit<'info> {
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Account<'info, State>,
+
+    pub update_authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct BurnGsol<'info> {
     #[account(
@@ -310,11 +605,21 @@ pub struct ResizeAllocations<'info> {
     #[account(mut, has_one = update_authority)]
     pub state: Account<'info, State>,
 
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            BACKING_ORACLE
+        ],
+        bump = backing_oracle.bump
+    )]
+    pub backing_oracle: Account<'info, BackingOracle>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(extractable_yield: u64)]
+#[instruction(surplus: i64)]
 pub struct UpdateEpochReport<'info> {
     #[account(
         mut,
@@ -328,6 +633,16 @@ pub struct UpdateEpochReport<'info> {
 
     pub gsol_mint: Box<Account<'info, Mint>>,
 
+    #[account(
+        mut,
+        seeds = [
+            state.key().as_ref(),
+            BACKING_ORACLE
+        ],
+        bump = backing_oracle.bump
+    )]
+    pub backing_oracle: Box<Account<'info, BackingOracle>>,
+
     /// CHECK: Verified Instructions Sysvar.
     #[account(address = sysvar::instructions::ID)]
     pub sysvar_instructions: UncheckedAccount<'info>,
@@ -401,4 +716,52 @@ pub enum BeamError {
     /// Overflow error
     #[msg("Overflow")]
     Overflow,
+
+    /// Thrown when attempting to extract yield while any beam has an outstanding deficit
+    #[msg("A beam has an outstanding deficit")]
+    OutstandingDeficit,
+
+    /// Thrown if a socialised amount exceeds the beam's recorded deficit
+    #[msg("Amount exceeds the beam's deficit")]
+    DeficitExceeded,
+
+    /// Thrown if a socialised amount exceeds the covering beam's surplus
+    #[msg("Insufficient surplus in the covering beam")]
+    InsufficientSurplus,
+
+    /// Thrown if the backing oracle has less capacity than the state's allocations
+    #[msg("The backing oracle must be resized")]
+    BackingOracleTooSmall,
+
+    /// Thrown if the backing oracle was last updated longer ago than the reader accepts
+    #[msg("The backing oracle is stale")]
+    StaleBackingOracle,
+
+    /// Thrown if a beam passes an attribution that doesn't belong to the depositor
+    #[msg("The attribution does not belong to this depositor")]
+    InvalidAttribution,
+
+    /// Thrown if an impact certificate is claimed for a period other than the current one
+    #[msg("Impact certificates can only be claimed for the current period")]
+    InvalidImpactPeriod,
+
+    /// Thrown if no yield has been attributed since the last impact certificate
+    #[msg("No yield has been attributed since the last impact certificate")]
+    NoImpactToCertify,
+
+    /// Thrown if a beam extracts yield with a protocol fee due but without the treasury account
+    #[msg("The treasury must be passed while a protocol fee is charged")]
+    MissingTreasury,
+
+    /// Thrown if the beam account did not sign a mint, burn or report request
+    #[msg("The beam must sign the request")]
+    MissingBeamSignature,
+
+    /// Thrown if a token account does not hold the token of an impact certificate for its owner
+    #[msg("The token account does not hold the impact certificate's token")]
+    InvalidImpactCertificate,
+
+    /// Thrown if an impact project is registered with a name that is too long
+    #[msg("The impact project's name is too long")]
+    ImpactProjectNameTooLong,
 }
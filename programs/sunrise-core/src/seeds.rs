@@ -1 +1,6 @@
 pub const GSOL_AUTHORITY: &[u8] = b"gsol_mint_authority";
+pub const BACKING_ORACLE: &[u8] = b"backing_oracle";
+pub const ATTRIBUTION: &[u8] = b"attribution";
+pub const IMPACT_CERTIFICATE: &[u8] = b"impact_certificate";
+pub const IMPACT_CERTIFICATE_MINT: &[u8] = b"impact_certificate_mint";
+pub const IMPACT_PROJECT: &[u8] = b"impact_project";
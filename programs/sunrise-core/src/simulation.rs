@@ -0,0 +1,325 @@
+//! A stateful simulation of the core accounting.
+//!
+//! Drives a [State] and its [BackingOracle] through random sequences of the operations that the
+//! instructions perform on them, checking the accounting invariants after every step.
+//! As with a transaction, an operation that errors leaves the simulation untouched.
+//! Failing sequences are shrunk by proptest to a minimal reproduction.
+
+use crate::state::{AllocationUpdate, BackingOracle, BeamBacking, BeamDetails, EpochReport, State};
+use crate::BeamError;
+use anchor_lang::prelude::*;
+use proptest::prelude::*;
+use std::collections::HashSet;
+
+/// The number of distinct beams that operations choose from.
+const BEAMS: usize = 6;
+
+fn beam_key(beam: usize) -> Pubkey {
+    Pubkey::new_from_array([beam as u8 + 1; 32])
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    RegisterBeam(usize),
+    ResizeAllocations(u8),
+    ResizeBackingOracle,
+    UpdateAllocations(Vec<(usize, u8)>),
+    /// Allocations in proportion to the weights across the registered beams, summing to 100.
+    Rebalance([u8; BEAMS]),
+    MintGsol(usize, u64),
+    BurnGsol(usize, u64),
+    TransferGsol(usize, usize, u64),
+    UpdateEpochReport(usize, i64),
+    ExtractYield(usize, u64),
+    SocialiseDeficit(usize, usize, u64),
+    RemoveBeam(usize),
+    AdvanceEpoch,
+}
+
+/// Mostly realistic amounts, with the occasional extreme one.
+fn amount() -> impl Strategy<Value = u64> {
+    prop_oneof![9 => 0..1_000_000_000u64, 1 => any::<u64>()]
+}
+
+fn surplus() -> impl Strategy<Value = i64> {
+    prop_oneof![9 => -1_000_000_000..1_000_000_000i64, 1 => any::<i64>()]
+}
+
+fn beam() -> impl Strategy<Value = usize> {
+    0..BEAMS
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        2 => beam().prop_map(Op::RegisterBeam),
+        1 => (0..3u8).prop_map(Op::ResizeAllocations),
+        1 => Just(Op::ResizeBackingOracle),
+        1 => prop::collection::vec((beam(), any::<u8>()), 0..3).prop_map(Op::UpdateAllocations),
+        2 => any::<[u8; BEAMS]>().prop_map(Op::Rebalance),
+        4 => (beam(), amount()).prop_map(|(beam, amount)| Op::MintGsol(beam, amount)),
+        3 => (beam(), amount()).prop_map(|(beam, amount)| Op::BurnGsol(beam, amount)),
+        2 => (beam(), beam(), amount())
+            .prop_map(|(beam, recipient, amount)| Op::TransferGsol(beam, recipient, amount)),
+        3 => (beam(), surplus()).prop_map(|(beam, surplus)| Op::UpdateEpochReport(beam, surplus)),
+        2 => (beam(), amount()).prop_map(|(beam, amount)| Op::ExtractYield(beam, amount)),
+        2 => (beam(), beam(), amount())
+            .prop_map(|(beam, covering, amount)| Op::SocialiseDeficit(beam, covering, amount)),
+        1 => beam().prop_map(Op::RemoveBeam),
+        1 => Just(Op::AdvanceEpoch),
+    ]
+}
+
+/// Allocations in proportion to `weights` across the registered beams, summing to 100.
+fn rebalance(state: &State, weights: &[u8; BEAMS]) -> Vec<AllocationUpdate> {
+    let registered: Vec<usize> = (0..BEAMS)
+        .filter(|&beam| state.contains_beam(&beam_key(beam)))
+        .collect();
+    let total_weight: u32 = registered
+        .iter()
+        .map(|&beam| weights[beam] as u32 + 1)
+        .sum();
+
+    let mut remaining = 100;
+    registered
+        .iter()
+        .enumerate()
+        .map(|(i, &beam)| {
+            let allocation = if i + 1 == registered.len() {
+                remaining
+            } else {
+                (weights[beam] as u32 + 1) * 100 / total_weight
+            };
+            remaining -= allocation;
+            AllocationUpdate {
+                beam: beam_key(beam),
+                new_allocation: allocation as u8,
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+struct Simulation {
+    state: State,
+    oracle: BackingOracle,
+    /// The supply of the gSOL mint.
+    gsol_supply: u64,
+    /// The gSOL issued by removed beams, which no beam is responsible for any more.
+    written_off_supply: u64,
+    /// Whether the allocations have been set, after which they always sum to 100.
+    allocated: bool,
+    epoch: u64,
+    slot: u64,
+}
+
+impl Simulation {
+    /// A state registered with `initial_capacity` and an oracle of the same capacity,
+    /// taking over a gSOL mint with `pre_supply` in circulation.
+    fn new(pre_supply: u64, initial_capacity: u8, protocol_fee_bps: u16) -> Self {
+        let initial_capacity = initial_capacity as usize;
+        Simulation {
+            state: State {
+                pre_supply,
+                treasury: Pubkey::new_unique(),
+                protocol_fee_bps,
+                allocations: vec![BeamDetails::default(); initial_capacity],
+                epoch_report: EpochReport::new(initial_capacity, 0),
+                ..Default::default()
+            },
+            oracle: BackingOracle {
+                beam_backing: vec![BeamBacking::default(); initial_capacity],
+                ..Default::default()
+            },
+            gsol_supply: pre_supply,
+            written_off_supply: 0,
+            allocated: false,
+            epoch: 0,
+            slot: 0,
+        }
+    }
+
+    /// Apply `op` to a copy of the simulation, keeping the copy only if the operation succeeds.
+    fn apply(&mut self, op: &Op) {
+        self.slot += 1;
+        let mut next = self.clone();
+        if next.try_apply(op).is_ok() {
+            *self = next;
+        }
+    }
+
+    fn beam_index(&self, beam: usize) -> Result<usize> {
+        self.state
+            .find_beam_index(&beam_key(beam))
+            .ok_or(BeamError::UnidentifiedBeam.into())
+    }
+
+    fn try_apply(&mut self, op: &Op) -> Result<()> {
+        match *op {
+            Op::RegisterBeam(beam) => self.state.add_beam(BeamDetails::new(beam_key(beam), 0)),
+            Op::ResizeAllocations(additional_beams) => {
+                self.state
+                    .extend_allocations(additional_beams as usize, self.epoch);
+                Ok(())
+            }
+            Op::ResizeBackingOracle => {
+                let capacity = self.state.allocations.len();
+                if self.oracle.beam_backing.len() < capacity {
+                    self.oracle
+                        .beam_backing
+                        .resize(capacity, BeamBacking::default());
+                    self.oracle.update(&self.state, self.slot)?;
+                }
+                Ok(())
+            }
+            Op::UpdateAllocations(ref updates) => {
+                self.state.update_allocations(
+                    updates
+                        .iter()
+                        .map(|&(beam, new_allocation)| AllocationUpdate {
+                            beam: beam_key(beam),
+                            new_allocation,
+                        })
+                        .collect(),
+                )?;
+                self.allocated = true;
+                Ok(())
+            }
+            Op::Rebalance(ref weights) => {
+                self.state
+                    .update_allocations(rebalance(&self.state, weights))?;
+                self.allocated = true;
+                Ok(())
+            }
+            Op::MintGsol(beam, amount) => {
+                // The token program rejects mints that would overflow the supply.
+                let gsol_supply = self
+                    .gsol_supply
+                    .checked_add(amount)
+                    .ok_or(BeamError::Overflow)?;
+                self.state
+                    .mint_gsol(&beam_key(beam), amount, self.gsol_supply)?;
+                self.gsol_supply = gsol_supply;
+                Ok(())
+            }
+            Op::BurnGsol(beam, amount) => {
+                self.state.burn_gsol(&beam_key(beam), amount)?;
+                self.gsol_supply -= amount;
+                Ok(())
+            }
+            Op::TransferGsol(beam, recipient, amount) => {
+                self.state
+                    .transfer_gsol(&beam_key(beam), &beam_key(recipient), amount)
+            }
+            Op::UpdateEpochReport(beam, surplus) => {
+                let beam_idx = self.beam_index(beam)?;
+                self.state
+                    .update_surplus_and_epoch_for_beam(beam_idx, self.epoch, surplus);
+                self.state.epoch_report.current_gsol_supply = self.gsol_supply;
+                self.oracle.update(&self.state, self.slot)
+            }
+            Op::ExtractYield(beam, amount) => {
+                let beam_idx = self.beam_index(beam)?;
+                self.state.extract_yield(beam_idx, amount, self.epoch)?;
+                Ok(())
+            }
+            Op::SocialiseDeficit(beam, covering_beam, amount) => {
+                let beam_idx = self.beam_index(beam)?;
+                let covering_beam_idx = self.beam_index(covering_beam)?;
+                self.state
+                    .socialise_deficit(beam_idx, covering_beam_idx, amount, self.epoch)
+            }
+            Op::RemoveBeam(beam) => {
+                let key = beam_key(beam);
+                let partial_gsol_supply = self
+                    .state
+                    .get_beam_details(&key)
+                    .map(|details| details.partial_gsol_supply)
+                    .unwrap_or_default();
+                self.state.remove_beam(&key)?;
+                self.written_off_supply += partial_gsol_supply;
+                Ok(())
+            }
+            Op::AdvanceEpoch => {
+                self.epoch += 1;
+                Ok(())
+            }
+        }
+    }
+
+    fn check_invariants(&self) {
+        let state = &self.state;
+        let beam_epoch_details = &state.epoch_report.beam_epoch_details;
+
+        assert_eq!(
+            state.allocations.len(),
+            beam_epoch_details.len(),
+            "the epoch report is out of step with the allocations"
+        );
+
+        let registered: Vec<&BeamDetails> = state
+            .allocations
+            .iter()
+            .filter(|details| details.key != Pubkey::default())
+            .collect();
+        let keys: HashSet<Pubkey> = registered.iter().map(|details| details.key).collect();
+        assert_eq!(keys.len(), registered.len(), "a beam is registered twice");
+
+        let allocation: u32 = state
+            .allocations
+            .iter()
+            .map(|details| details.allocation as u32)
+            .sum();
+        assert_eq!(allocation, if self.allocated { 100 } else { 0 });
+
+        // Every gSOL issued since the program took over the mint is accounted for.
+        let partial_gsol_supply: u128 = state
+            .allocations
+            .iter()
+            .map(|details| details.partial_gsol_supply as u128)
+            .sum();
+        assert_eq!(
+            state.pre_supply as u128 + partial_gsol_supply + self.written_off_supply as u128,
+            self.gsol_supply as u128,
+            "the partial gSOL supplies don't add up to the mint supply"
+        );
+
+        for (details, epoch_details) in state.allocations.iter().zip(beam_epoch_details) {
+            if details.key == Pubkey::default() {
+                assert_eq!(*details, BeamDetails::default());
+            }
+            assert!(
+                epoch_details.extractable_yield == 0 || details.deficit == 0,
+                "a beam has both a surplus and a deficit"
+            );
+            assert!(details.protocol_fee <= epoch_details.extracted_yield);
+        }
+
+        // The oracle reflects the state as of its last update.
+        if self.oracle.updated_slot == self.slot {
+            assert_eq!(self.oracle.gsol_supply as u128, partial_gsol_supply);
+            assert_eq!(self.oracle.beam_backing.len(), state.allocations.len());
+            for (details, backing) in state.allocations.iter().zip(&self.oracle.beam_backing) {
+                assert_eq!(details.key, backing.beam);
+            }
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn accounting_invariants_hold(
+        pre_supply in prop_oneof![Just(0u64), amount()],
+        initial_capacity in 0..4u8,
+        protocol_fee_bps in 0..=beam_math::MAX_BPS,
+        ops in prop::collection::vec(op(), 1..64),
+    ) {
+        let mut simulation = Simulation::new(pre_supply, initial_capacity, protocol_fee_bps);
+        simulation.check_invariants();
+        for op in &ops {
+            simulation.apply(op);
+            simulation.check_invariants();
+        }
+    }
+}
@@ -3,7 +3,7 @@ use anchor_lang::prelude::*;
 
 /// The state for the Sunrise beam controller program.
 #[account]
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct State {
     /// Update authority for this state.
     pub update_authority: Pubkey,
@@ -21,8 +21,24 @@ pub struct State {
     /// The Sunrise yield account.
     pub yield_account: Pubkey,
 
+    /// The account receiving the protocol fee on extracted yield.
+    pub treasury: Pubkey,
+
+    /// The share of extracted yield sent to the treasury, in basis points.
+    pub protocol_fee_bps: u16,
+
+    /// The cumulative yield extracted per gSOL issued through the beams, net of the protocol fee,
+    /// scaled by [YIELD_PER_GSOL_SCALE].
+    /// Used to attribute the extracted yield to depositors with an [Attribution].
+    pub yield_per_gsol: u128,
+
+    /// The beam router program, trusted to call beams on behalf of users as the top-level program.
+    /// The default key trusts no router.
+    pub beam_router: Pubkey,
+
     /// Reserved space for adding future fields.
-    pub reserved_space: [u32; 32], // 128 bytes - used u32;32 over u8;128 to take advantage of rust's built-in default trait implementation for 32-sized arrays
+    /// New fields are carved out of this space, so that existing state accounts keep their layout.
+    pub reserved_space: [u8; 46],
 
     /// Holds [BeamDetails] for all supported beams.
     pub allocations: Vec<BeamDetails>,
@@ -45,8 +61,14 @@ pub struct BeamDetails {
     /// A beam in drain accepts withdrawals but not deposits.
     pub draining_mode: bool,
 
+    /// The amount by which the beam's value fell short of its partial gSOL supply when it last reported.
+    pub deficit: u64,
+
+    /// The share of the yield extracted by this beam that was sent to the treasury.
+    pub protocol_fee: u64,
+
     /// Reserved space for adding future fields.
-    pub reserved_space: [u32; 32], // 128 bytes - used u32;32 over u8;128 to take advantage of rust's built-in default trait implementation for 32-sized arrays
+    pub reserved_space: [u32; 28], // 112 bytes - used u32;28 over u8;112 to take advantage of rust's built-in default trait implementation for arrays of up to 32
 }
 
 impl BeamDetails {
@@ -55,7 +77,9 @@ impl BeamDetails {
         1 +  // allocation
         8 +  // minted
         1 + // draining_mode
-        128; // reserved_space
+        8 + // deficit
+        8 + // protocol_fee
+        112; // reserved_space
 
     /// Create a new instance of Self.
     pub fn new(key: Pubkey, allocation: u8) -> Self {
@@ -64,11 +88,33 @@ impl BeamDetails {
             allocation,
             partial_gsol_supply: 0,
             draining_mode: false, // initially set draining_mode to false.
+            deficit: 0,
+            protocol_fee: 0,
             reserved_space: Default::default(),
         }
     }
 }
 
+// Implemented by hand, as arrays of more than 32 elements don't implement Default.
+impl Default for State {
+    fn default() -> Self {
+        State {
+            update_authority: Pubkey::default(),
+            gsol_mint: Pubkey::default(),
+            pre_supply: 0,
+            gsol_mint_authority_bump: 0,
+            yield_account: Pubkey::default(),
+            treasury: Pubkey::default(),
+            protocol_fee_bps: 0,
+            yield_per_gsol: 0,
+            beam_router: Pubkey::default(),
+            reserved_space: [0; 46],
+            allocations: Vec::default(),
+            epoch_report: EpochReport::default(),
+        }
+    }
+}
+
 impl State {
     /// The size of a state account with an empty allocations vector.
     pub const SIZE_WITH_ZERO_BEAMS: usize = 8 + // discriminator 
@@ -77,7 +123,11 @@ impl State {
         8 +  // pre_supply
         1 +  // gsol_mint_authority_bump
         32 + // yield_account
-        128 + // reserved_space
+        32 + // treasury
+        2 +  // protocol_fee_bps
+        16 + // yield_per_gsol
+        32 + // beam_router
+        46 + // reserved_space
         4; // allocations vec size
            // Does not include epoch_report min size (included in size() and size
 
@@ -129,7 +179,9 @@ impl State {
     }
 
     /// Update the fields of a [State] object.
-    pub fn update(&mut self, input: UpdateStateInput) {
+    ///
+    /// Errors if the protocol fee exceeds 100%, or is set without a treasury.
+    pub fn update(&mut self, input: UpdateStateInput) -> Result<()> {
         if let Some(update_authority) = input.new_update_authority {
             self.update_authority = update_authority;
         }
@@ -142,6 +194,38 @@ impl State {
         if let Some(yield_account) = input.new_yield_account {
             self.yield_account = yield_account;
         }
+        if let Some(treasury) = input.new_treasury {
+            self.treasury = treasury;
+        }
+        if let Some(beam_router) = input.new_beam_router {
+            self.beam_router = beam_router;
+        }
+        if let Some(protocol_fee_bps) = input.new_protocol_fee_bps {
+            require_gte!(
+                beam_math::MAX_BPS,
+                protocol_fee_bps,
+                BeamError::InvalidParameter
+            );
+            self.protocol_fee_bps = protocol_fee_bps;
+        }
+        if self.protocol_fee_bps > 0 && self.treasury == Pubkey::default() {
+            return Err(BeamError::InvalidParameter.into());
+        }
+
+        Ok(())
+    }
+
+    /// Whether `program_id` is the trusted beam router.
+    pub fn is_beam_router(&self, program_id: &Pubkey) -> bool {
+        self.beam_router != Pubkey::default() && self.beam_router == *program_id
+    }
+
+    /// The protocol fee on `extracted_lamports` of yield, rounded up in favour of the treasury.
+    pub fn protocol_fee(&self, extracted_lamports: u64) -> Result<u64> {
+        Ok(beam_math::fee_bps(
+            extracted_lamports,
+            self.protocol_fee_bps,
+        )?)
     }
 
     /// Add a new [BeamDetails] to the state.
@@ -188,12 +272,16 @@ impl State {
     /// Errors if:
     /// * The beam is not present in the state's allocations vector.
     /// * The beam is present but its allocation is not zero.
+    /// * The beam has an outstanding deficit, which must be socialised first.
     pub fn remove_beam(&mut self, beam: &Pubkey) -> Result<()> {
-        let found = self.get_mut_beam_details(beam);
+        let index = self.find_beam_index(beam);
 
-        if let Some(allocation) = found {
+        if let Some(index) = index {
+            let allocation = &mut self.allocations[index];
             if allocation.allocation != 0 {
                 Err(BeamError::NonZeroAllocation.into())
+            } else if allocation.deficit != 0 {
+                Err(BeamError::OutstandingDeficit.into())
             } else {
                 *allocation = BeamDetails::default();
                 Ok(())
@@ -211,6 +299,244 @@ impl State {
     pub fn find_beam_index(&self, key: &Pubkey) -> Option<usize> {
         self.allocations.iter().position(|x| x.key == *key)
     }
+
+    /// Append `additional_beams` empty slots to the allocations, and matching entries to the epoch report.
+    pub fn extend_allocations(&mut self, additional_beams: usize, current_epoch: u64) {
+        self.allocations
+            .extend(std::iter::repeat(BeamDetails::default()).take(additional_beams));
+        self.epoch_report
+            .beam_epoch_details
+            .extend(std::iter::repeat(BeamEpochDetails::new(current_epoch)).take(additional_beams));
+    }
+
+    /// Replace the allocations of the beams in `new_allocations`.
+    ///
+    /// Errors if one of the beams is not present, or if the allocations don't sum to 100 afterwards.
+    pub fn update_allocations(&mut self, new_allocations: Vec<AllocationUpdate>) -> Result<()> {
+        for update in new_allocations {
+            // Find the matching beam-details struct by its key and replace it.
+            if let Some(details) = self.get_mut_beam_details(&update.beam) {
+                details.allocation = update.new_allocation;
+            } else {
+                return Err(BeamError::UnidentifiedBeam.into());
+            }
+        }
+
+        // Ensure that the allocations are sane.
+        let sum: u32 = self.allocations.iter().map(|a| a.allocation as u32).sum();
+        require_eq!(sum, 100);
+
+        Ok(())
+    }
+
+    /// Record `amount` of gSOL minted by `beam`, given the supply of the gSOL mint before minting.
+    ///
+    /// Once gSOL is in circulation, a beam can only mint while its partial supply
+    /// is within its allocation of the supply issued since this program took over the mint.
+    pub fn mint_gsol(&mut self, beam: &Pubkey, amount: u64, gsol_mint_supply: u64) -> Result<()> {
+        let effective_supply = gsol_mint_supply
+            .checked_sub(self.pre_supply)
+            .ok_or(BeamError::Overflow)?;
+
+        let details = self
+            .get_mut_beam_details(beam)
+            .ok_or(BeamError::UnidentifiedBeam)?;
+
+        let mint_window = if effective_supply != 0 {
+            beam_math::proportional(effective_supply, details.allocation as u64, 100)?
+        } else {
+            // Mint initially with no restrictions. The allocations will come into effect afterwards.
+            amount
+        };
+
+        if details.partial_gsol_supply > mint_window {
+            return Err(BeamError::MintWindowExceeded.into());
+        }
+
+        // Increase the partial circulating gsol supply for the beam.
+        details.partial_gsol_supply = details
+            .partial_gsol_supply
+            .checked_add(amount)
+            .ok_or(BeamError::Overflow)?;
+
+        Ok(())
+    }
+
+    /// Record `amount` of gSOL burned by `beam`.
+    ///
+    /// Errors if the beam is not present or is responsible for less than `amount`.
+    pub fn burn_gsol(&mut self, beam: &Pubkey, amount: u64) -> Result<()> {
+        let details = self
+            .get_mut_beam_details(beam)
+            .ok_or(BeamError::UnidentifiedBeam)?;
+
+        // Can't burn more gsol than this beam is responsible for.
+        if details.partial_gsol_supply < amount {
+            msg!(
+                "Beam supply {}, requested burn {}",
+                details.partial_gsol_supply,
+                amount
+            );
+            return Err(BeamError::BurnWindowExceeded.into());
+        }
+        details.partial_gsol_supply -= amount;
+
+        Ok(())
+    }
+
+    /// Move responsibility for `amount` of gSOL from `beam` to `recipient_beam`.
+    ///
+    /// Errors if either beam is not present or `beam` is responsible for less than `amount`.
+    pub fn transfer_gsol(
+        &mut self,
+        beam: &Pubkey,
+        recipient_beam: &Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        // Can't transfer more gsol than this beam is responsible for.
+        self.burn_gsol(beam, amount)?;
+
+        let target_beam_details = self
+            .get_mut_beam_details(recipient_beam)
+            .ok_or(BeamError::UnidentifiedBeam)?;
+        target_beam_details.partial_gsol_supply = target_beam_details
+            .partial_gsol_supply
+            .checked_add(amount)
+            .ok_or(BeamError::Overflow)?;
+
+        Ok(())
+    }
+
+    /// Record `amount` of yield extracted by the beam at `beam_idx` in `current_epoch`,
+    /// returning the protocol fee that the beam sends to the treasury.
+    ///
+    /// Errors if the beam has not reported this epoch, or while any beam has a deficit.
+    pub fn extract_yield(
+        &mut self,
+        beam_idx: usize,
+        amount: u64,
+        current_epoch: u64,
+    ) -> Result<u64> {
+        // The epoch report must be already updated for this epoch and beam
+        require!(
+            self.epoch_report
+                .is_epoch_reported_for_beam_idx(current_epoch, beam_idx),
+            BeamError::EpochReportNotUpToDate
+        );
+
+        // No yield leaves the protocol while any beam is short of the gSOL it issued
+        require!(!self.has_deficit(), BeamError::OutstandingDeficit);
+
+        let protocol_fee = self.protocol_fee(amount)?;
+        self.epoch_report
+            .extract_yield_for_beam(beam_idx, amount, current_epoch)?;
+        let beam_details = &mut self.allocations[beam_idx];
+        beam_details.protocol_fee = beam_details
+            .protocol_fee
+            .checked_add(protocol_fee)
+            .ok_or(BeamError::Overflow)?;
+        // Only the yield left after the protocol fee funds the depositors' impact.
+        self.accrue_yield_per_gsol(amount.saturating_sub(protocol_fee))?;
+
+        Ok(protocol_fee)
+    }
+
+    /// Spread `amount` of extracted yield over the gSOL currently issued through the beams.
+    fn accrue_yield_per_gsol(&mut self, amount: u64) -> Result<()> {
+        let gsol_supply: u128 = self
+            .allocations
+            .iter()
+            .map(|x| x.partial_gsol_supply as u128)
+            .sum();
+        // With no gSOL issued, there is no one to attribute the yield to.
+        if gsol_supply == 0 {
+            return Ok(());
+        }
+
+        let increase = (amount as u128)
+            .checked_mul(YIELD_PER_GSOL_SCALE)
+            .ok_or(BeamError::Overflow)?
+            / gsol_supply;
+        self.yield_per_gsol = self
+            .yield_per_gsol
+            .checked_add(increase)
+            .ok_or(BeamError::Overflow)?;
+
+        Ok(())
+    }
+
+    /// Records the surplus reported by the beam at `beam_idx` for `epoch`.
+    /// A positive surplus is extractable yield, and a negative surplus is a deficit.
+    pub fn update_surplus_and_epoch_for_beam(&mut self, beam_idx: usize, epoch: u64, surplus: i64) {
+        self.epoch_report
+            .update_extractable_yield_and_epoch_for_beam(beam_idx, epoch, surplus.max(0) as u64);
+        self.allocations[beam_idx].deficit = surplus.min(0).unsigned_abs();
+    }
+
+    /// The total deficit of all beams, i.e. the value by which they fall short of the gSOL they issued.
+    pub fn total_deficit(&self) -> u64 {
+        self.allocations.iter().map(|x| x.deficit).sum()
+    }
+
+    pub fn has_deficit(&self) -> bool {
+        self.allocations.iter().any(|x| x.deficit > 0)
+    }
+
+    /// The total protocol fee sent to the treasury on the yield extracted by the beams.
+    pub fn protocol_fees(&self) -> u64 {
+        self.allocations.iter().map(|x| x.protocol_fee).sum()
+    }
+
+    /// Cover `amount` of the deficit of the beam at `beam_idx` with the surplus of the beam at
+    /// `covering_beam_idx`, which takes over responsibility for that much of the beam's gSOL.
+    ///
+    /// Both beams must have reported for `current_epoch`.
+    /// Errors if `amount` exceeds the deficit or the partial gSOL supply of the beam,
+    /// or the surplus of the covering beam.
+    pub fn socialise_deficit(
+        &mut self,
+        beam_idx: usize,
+        covering_beam_idx: usize,
+        amount: u64,
+        current_epoch: u64,
+    ) -> Result<()> {
+        require!(
+            self.epoch_report
+                .is_epoch_reported_for_beam_idx(current_epoch, beam_idx)
+                && self
+                    .epoch_report
+                    .is_epoch_reported_for_beam_idx(current_epoch, covering_beam_idx),
+            BeamError::EpochReportNotUpToDate
+        );
+        require_neq!(beam_idx, covering_beam_idx, BeamError::InvalidParameter);
+
+        let beam_details = &self.allocations[beam_idx];
+        let deficit = beam_details
+            .deficit
+            .checked_sub(amount)
+            .ok_or(BeamError::DeficitExceeded)?;
+        let partial_gsol_supply = beam_details
+            .partial_gsol_supply
+            .checked_sub(amount)
+            .ok_or(BeamError::DeficitExceeded)?;
+        let covering_beam_surplus = self.epoch_report.beam_epoch_details[covering_beam_idx]
+            .extractable_yield
+            .checked_sub(amount)
+            .ok_or(BeamError::InsufficientSurplus)?;
+        let covering_partial_gsol_supply = self.allocations[covering_beam_idx]
+            .partial_gsol_supply
+            .checked_add(amount)
+            .ok_or(BeamError::Overflow)?;
+
+        let beam_details = &mut self.allocations[beam_idx];
+        beam_details.deficit = deficit;
+        beam_details.partial_gsol_supply = partial_gsol_supply;
+        self.epoch_report.beam_epoch_details[covering_beam_idx].extractable_yield =
+            covering_beam_surplus;
+        self.allocations[covering_beam_idx].partial_gsol_supply = covering_partial_gsol_supply;
+
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -228,6 +554,9 @@ pub struct UpdateStateInput {
     pub new_yield_account: Option<Pubkey>,
     pub new_gsol_mint: Option<Pubkey>,
     pub new_gsol_mint_authority_bump: Option<u8>,
+    pub new_treasury: Option<Pubkey>,
+    pub new_protocol_fee_bps: Option<u16>,
+    pub new_beam_router: Option<Pubkey>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -305,8 +634,7 @@ impl EpochReport {
         beam_details.extracted_yield = beam_details
             .extracted_yield
             .checked_add(yield_amount)
-            .ok_or(BeamError::Overflow)
-            .unwrap();
+            .ok_or(BeamError::Overflow)?;
 
         // The extractable yield should be reduced (most likely to zero)
         beam_details.extractable_yield =
@@ -347,6 +675,431 @@ impl BeamEpochDetails {
     }
 }
 
+/// The number of base units in one gSOL.
+pub const ONE_GSOL: u64 = 1_000_000_000;
+
+/// The scale of [State::yield_per_gsol], giving it enough precision to attribute yield
+/// to small balances when a large supply is in circulation.
+pub const YIELD_PER_GSOL_SCALE: u128 = 1_000_000_000_000;
+
+/// A depositor's opt-in record of the gSOL minted to and burned from them by the beams,
+/// which attributes to them a share of the yield extracted while they hold it.
+///
+/// The beams update the attribution when it is passed after the accounts of a deposit
+/// or withdrawal. gSOL moved between token accounts is not followed.
+#[account]
+#[derive(Debug, Default)]
+pub struct Attribution {
+    /// The state this attribution is recorded against.
+    pub state: Pubkey,
+
+    /// The depositor.
+    pub owner: Pubkey,
+
+    /// Bump of the attribution PDA.
+    pub bump: u8,
+
+    /// The gSOL attributed to the owner.
+    pub gsol_balance: u64,
+
+    /// The gSOL balance summed over each epoch it was held for, in gSOL-epochs.
+    pub time_weighted_balance: u128,
+
+    /// The yield attributed to the owner as of the last update, in lamports.
+    pub attributed_yield: u64,
+
+    /// The epoch of the last update.
+    pub updated_epoch: u64,
+
+    /// The state's [yield_per_gsol][State::yield_per_gsol] as of the last update.
+    pub yield_per_gsol: u128,
+
+    /// The epoch that the last [ImpactCertificate] runs up to, or the registration epoch.
+    pub certified_epoch: u64,
+
+    /// The attributed yield already covered by impact certificates, in lamports.
+    pub certified_yield: u64,
+
+    /// The time-weighted balance already covered by impact certificates, in gSOL-epochs.
+    pub certified_time_weighted_balance: u128,
+}
+
+impl Attribution {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // state
+        32 + // owner
+        1 +  // bump
+        8 +  // gsol_balance
+        16 + // time_weighted_balance
+        8 +  // attributed_yield
+        8 +  // updated_epoch
+        16 + // yield_per_gsol
+        8 +  // certified_epoch
+        8 +  // certified_yield
+        16; // certified_time_weighted_balance
+
+    /// Bring the time-weighted balance and the attributed yield up to `epoch`,
+    /// given the state's current `yield_per_gsol` and the gSOL `held` in the owner's token account.
+    ///
+    /// gSOL moved out of the token account is not followed, so the balance is capped at `held`
+    /// first, and no yield is attributed to gSOL that the owner no longer holds.
+    pub fn accrue(&mut self, held: u64, yield_per_gsol: u128, epoch: u64) -> Result<()> {
+        self.gsol_balance = self.gsol_balance.min(held);
+        let held_epochs = epoch.saturating_sub(self.updated_epoch);
+        self.time_weighted_balance = (self.gsol_balance as u128)
+            .checked_mul(held_epochs as u128)
+            .and_then(|x| x.checked_add(self.time_weighted_balance))
+            .ok_or(BeamError::Overflow)?;
+
+        let attributed_yield = (self.gsol_balance as u128)
+            .checked_mul(yield_per_gsol.saturating_sub(self.yield_per_gsol))
+            .ok_or(BeamError::Overflow)?
+            / YIELD_PER_GSOL_SCALE;
+        self.attributed_yield = u64::try_from(attributed_yield)
+            .ok()
+            .and_then(|x| x.checked_add(self.attributed_yield))
+            .ok_or(BeamError::Overflow)?;
+
+        self.updated_epoch = self.updated_epoch.max(epoch);
+        self.yield_per_gsol = yield_per_gsol;
+
+        Ok(())
+    }
+
+    /// Record `amount` of gSOL minted to the owner, whose token account now holds `token_balance`.
+    pub fn record_mint(
+        &mut self,
+        amount: u64,
+        token_balance: u64,
+        yield_per_gsol: u128,
+        epoch: u64,
+    ) -> Result<()> {
+        self.accrue(token_balance.saturating_sub(amount), yield_per_gsol, epoch)?;
+        self.gsol_balance = self
+            .gsol_balance
+            .checked_add(amount)
+            .ok_or(BeamError::Overflow)?;
+        Ok(())
+    }
+
+    /// Record `amount` of gSOL burned from the owner, whose token account now holds `token_balance`.
+    ///
+    /// The owner may burn gSOL that was not attributed to them, such as gSOL they held before
+    /// registering, so the balance only falls to zero.
+    pub fn record_burn(
+        &mut self,
+        amount: u64,
+        token_balance: u64,
+        yield_per_gsol: u128,
+        epoch: u64,
+    ) -> Result<()> {
+        self.accrue(token_balance.saturating_add(amount), yield_per_gsol, epoch)?;
+        self.gsol_balance = self.gsol_balance.saturating_sub(amount);
+        Ok(())
+    }
+
+    /// The attribution as of `epoch`, given the state's current `yield_per_gsol`
+    /// and the `token_balance` of the owner's gSOL token account.
+    pub fn attributed_yield(
+        &self,
+        token_balance: u64,
+        yield_per_gsol: u128,
+        epoch: u64,
+    ) -> Result<AttributedYield> {
+        let mut attribution = self.clone();
+        attribution.accrue(token_balance, yield_per_gsol, epoch)?;
+        Ok(AttributedYield {
+            gsol_balance: attribution.gsol_balance,
+            time_weighted_balance: attribution.time_weighted_balance,
+            attributed_yield: attribution.attributed_yield,
+        })
+    }
+
+    /// Record the yield attributed since the last certificate, up to `epoch`, on `certificate`,
+    /// given the `token_balance` of the owner's gSOL token account.
+    ///
+    /// Errors if no yield has been attributed since the last certificate.
+    pub fn certify(
+        &mut self,
+        certificate: &mut ImpactCertificate,
+        token_balance: u64,
+        yield_per_gsol: u128,
+        epoch: u64,
+    ) -> Result<()> {
+        self.accrue(token_balance, yield_per_gsol, epoch)?;
+        let attributed_yield = self.attributed_yield - self.certified_yield;
+        require_gt!(attributed_yield, 0, BeamError::NoImpactToCertify);
+
+        certificate.start_epoch = self.certified_epoch;
+        certificate.end_epoch = epoch;
+        certificate.attributed_yield = attributed_yield;
+        certificate.time_weighted_balance =
+            self.time_weighted_balance - self.certified_time_weighted_balance;
+
+        self.certified_epoch = epoch;
+        self.certified_yield = self.attributed_yield;
+        self.certified_time_weighted_balance = self.time_weighted_balance;
+
+        Ok(())
+    }
+}
+
+/// The number of epochs in an impact certificate period, roughly a month.
+pub const IMPACT_PERIOD_EPOCHS: u64 = 13;
+
+/// A record of the yield that a depositor funded over a range of epochs, claimed from
+/// their [Attribution] at most once per period, crediting an [ImpactProject].
+///
+/// The certificate is issued to the owner as the only token of its `mint`, which is
+/// frozen in the owner's token account so that it can't be transferred.
+///
+/// The token carries no Metaplex or Token-2022 metadata: this account is its metadata.
+/// The mint is a PDA of the certificate (seeds `[certificate, "impact_certificate_mint"]`),
+/// and the certificate remains its freeze authority, so a certificate can be found from its
+/// token and checked with [ImpactCertificate::verify].
+#[account]
+#[derive(Debug, Default)]
+pub struct ImpactCertificate {
+    /// The state this certificate is issued against.
+    pub state: Pubkey,
+
+    /// The depositor the certificate is issued to.
+    pub owner: Pubkey,
+
+    /// The mint of the certificate token.
+    pub mint: Pubkey,
+
+    /// Bump of the certificate PDA.
+    pub bump: u8,
+
+    /// The period the certificate was claimed in. See [ImpactCertificate::period].
+    pub period: u64,
+
+    /// The first epoch covered by the certificate.
+    pub start_epoch: u64,
+
+    /// The epoch the certificate was claimed in, which ends the range it covers.
+    pub end_epoch: u64,
+
+    /// The yield attributed to the owner over the range, in lamports.
+    pub attributed_yield: u64,
+
+    /// The owner's gSOL balance summed over each epoch in the range, in gSOL-epochs.
+    pub time_weighted_balance: u128,
+
+    /// The [ImpactProject] that the owner credited the yield to.
+    pub project: Pubkey,
+}
+
+impl ImpactCertificate {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // state
+        32 + // owner
+        32 + // mint
+        1 +  // bump
+        8 +  // period
+        8 +  // start_epoch
+        8 +  // end_epoch
+        8 +  // attributed_yield
+        16 + // time_weighted_balance
+        32; // project
+
+    /// The impact certificate period containing `epoch`.
+    pub fn period(epoch: u64) -> u64 {
+        epoch / IMPACT_PERIOD_EPOCHS
+    }
+
+    /// Check that `token_account` holds this certificate's token for its owner.
+    pub fn verify(&self, token_account: &anchor_spl::token::TokenAccount) -> Result<()> {
+        require!(
+            token_account.mint == self.mint
+                && token_account.owner == self.owner
+                && token_account.amount == 1,
+            BeamError::InvalidImpactCertificate
+        );
+        Ok(())
+    }
+}
+
+/// A project funded by the yield of a state, which depositors can credit the yield attributed
+/// to them to in their [ImpactCertificate]s. Registered by the state's update authority.
+#[account]
+#[derive(Debug, Default)]
+pub struct ImpactProject {
+    /// The state whose yield funds the project.
+    pub state: Pubkey,
+
+    /// The account of the project that receives its share of the yield.
+    pub recipient: Pubkey,
+
+    /// Bump of the project PDA.
+    pub bump: u8,
+
+    /// The project's name, of at most [ImpactProject::MAX_NAME_LEN] bytes.
+    pub name: String,
+}
+
+impl ImpactProject {
+    pub const MAX_NAME_LEN: usize = 32;
+
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // state
+        32 + // recipient
+        1 +  // bump
+        4 + Self::MAX_NAME_LEN; // name
+}
+
+/// The yield attributed to a depositor, as returned by the `get_attributed_yield` view.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct AttributedYield {
+    /// The gSOL attributed to the depositor.
+    pub gsol_balance: u64,
+
+    /// The gSOL balance summed over each epoch it was held for, in gSOL-epochs.
+    pub time_weighted_balance: u128,
+
+    /// The cumulative yield attributed to the depositor, in lamports.
+    pub attributed_yield: u64,
+}
+
+/// The value backing gSOL, as last reported by the beams.
+/// Updated whenever a beam updates its epoch report.
+#[account]
+#[derive(Debug, Default)]
+pub struct BackingOracle {
+    /// The state this oracle reports on.
+    pub state: Pubkey,
+
+    /// Bump of the oracle PDA.
+    pub bump: u8,
+
+    /// The slot at which the oracle was last updated.
+    pub updated_slot: u64,
+
+    /// The gSOL supply issued through the beams.
+    pub gsol_supply: u64,
+
+    /// The total value backing the gSOL supply, in lamports.
+    pub total_backing: u64,
+
+    /// The value backing [ONE_GSOL], in lamports.
+    pub backing_per_gsol: u64,
+
+    /// Holds the [BeamBacking] of each beam, in the same order as the state's allocations.
+    pub beam_backing: Vec<BeamBacking>,
+}
+
+/// The value backing the gSOL issued by a beam.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BeamBacking {
+    pub beam: Pubkey,
+
+    /// The value held by the beam, net of any exit fees, in lamports.
+    pub backing: u64,
+}
+
+impl BeamBacking {
+    pub const SIZE: usize = 32 + // beam
+        8; // backing
+}
+
+impl BackingOracle {
+    /// Initialize the oracle of the `state` at `state_address`, with capacity for all of its allocations.
+    pub fn register(
+        &mut self,
+        state_address: Pubkey,
+        bump: u8,
+        state: &State,
+        slot: u64,
+    ) -> Result<()> {
+        self.state = state_address;
+        self.bump = bump;
+        // Filled to capacity, as with the state's allocations.
+        self.beam_backing = vec![BeamBacking::default(); state.allocations.len()];
+        self.update(state, slot)
+    }
+
+    /// The size of an oracle account with an empty beam_backing vector.
+    pub const SIZE_WITH_ZERO_BEAMS: usize = 8 + // discriminator
+        32 + // state
+        1 +  // bump
+        8 +  // updated_slot
+        8 +  // gsol_supply
+        8 +  // total_backing
+        8 +  // backing_per_gsol
+        4; // beam_backing vec size
+
+    /// Calculate the borsh-serialized size of an oracle with `beam_count` number of beams.
+    pub fn size(beam_count: usize) -> usize {
+        Self::SIZE_WITH_ZERO_BEAMS + (BeamBacking::SIZE * beam_count)
+    }
+
+    /// Recalculates the backing of each beam from the state at `slot`.
+    ///
+    /// A beam's backing is the gSOL it issued plus its surplus (or less its deficit)
+    /// at the time of its last epoch report.
+    /// Like the state's allocations, the beam_backing vec is kept filled to its capacity.
+    ///
+    /// Errors if the oracle has less capacity than the state.
+    pub fn update(&mut self, state: &State, slot: u64) -> Result<()> {
+        require_gte!(
+            self.beam_backing.len(),
+            state.allocations.len(),
+            BeamError::BackingOracleTooSmall
+        );
+
+        let gsol_supply = state
+            .allocations
+            .iter()
+            .try_fold(0u64, |sum, x| sum.checked_add(x.partial_gsol_supply))
+            .ok_or(BeamError::Overflow)?;
+        let mut total_backing: u64 = 0;
+        for (index, entry) in self.beam_backing.iter_mut().enumerate() {
+            *entry = match state.allocations.get(index) {
+                Some(details) if details.key != Pubkey::default() => {
+                    let epoch_details = state
+                        .epoch_report
+                        .beam_epoch_details
+                        .get(index)
+                        .cloned()
+                        .unwrap_or_default();
+                    let backing = (details.partial_gsol_supply as i128)
+                        + (epoch_details.extractable_yield as i128)
+                        - (details.deficit as i128);
+                    BeamBacking {
+                        beam: details.key,
+                        backing: u64::try_from(backing.max(0)).map_err(|_| BeamError::Overflow)?,
+                    }
+                }
+                _ => BeamBacking::default(),
+            };
+            total_backing = total_backing
+                .checked_add(entry.backing)
+                .ok_or(BeamError::Overflow)?;
+        }
+
+        self.updated_slot = slot;
+        self.gsol_supply = gsol_supply;
+        self.total_backing = total_backing;
+        // With no gSOL issued, each gSOL is valued at one SOL.
+        self.backing_per_gsol = beam_math::proportional(ONE_GSOL, total_backing, gsol_supply)?;
+
+        Ok(())
+    }
+
+    /// The value backing [ONE_GSOL], in lamports, for integrators reading the oracle.
+    ///
+    /// Errors if the oracle was last updated more than `max_age_slots` before `current_slot`.
+    pub fn checked_backing_per_gsol(&self, current_slot: u64, max_age_slots: u64) -> Result<u64> {
+        require_gte!(
+            self.updated_slot.saturating_add(max_age_slots),
+            current_slot,
+            BeamError::StaleBackingOracle
+        );
+        Ok(self.backing_per_gsol)
+    }
+}
+
 #[cfg(test)]
 mod internal_tests {
     use super::*;
@@ -468,6 +1221,14 @@ mod internal_tests {
             )
         );
         assert!(state.remove_beam(&keys[1]).is_err());
+
+        // Fails because the beam has a deficit.
+        state.allocations[2].deficit = 10;
+        assert_eq!(
+            state.remove_beam(&keys[2]).unwrap_err(),
+            BeamError::OutstandingDeficit.into()
+        );
+        state.allocations[2].deficit = 0;
         assert!(state.remove_beam(&keys[2]).is_ok());
 
         // Fails because key[2] has been removed and is no longer in allocations.
@@ -523,4 +1284,457 @@ mod internal_tests {
         let size = state.size_inner();
         assert_eq!(size, State::size(10));
     }
+    #[test]
+    fn test_protocol_fee() {
+        let mut state = State::default();
+        assert_eq!(state.protocol_fee(1_000).unwrap(), 0);
+
+        // A fee can't be set without a treasury
+        let mut input = UpdateStateInput {
+            new_protocol_fee_bps: Some(500),
+            ..Default::default()
+        };
+        assert!(state.update(input.clone()).is_err());
+
+        input.new_treasury = Some(Pubkey::new_unique());
+        state.update(input).unwrap();
+        assert_eq!(state.protocol_fee(1_000).unwrap(), 50);
+        assert_eq!(state.protocol_fee(1_001).unwrap(), 51);
+
+        // More than 100%
+        let input = UpdateStateInput {
+            new_protocol_fee_bps: Some(10_001),
+            ..Default::default()
+        };
+        assert!(state.update(input).is_err());
+
+        state.allocations = vec![BeamDetails::new(Pubkey::new_unique(), 100)];
+        state.epoch_report = EpochReport::new(1, 0);
+        assert_eq!(state.extract_yield(0, 1_000, 0).unwrap(), 50);
+        assert_eq!(state.epoch_report.extracted_yield(), 1_000);
+        assert_eq!(state.protocol_fees(), 50);
+    }
+    #[test]
+    fn test_backing_oracle() {
+        let key1 = Pubkey::new_unique();
+        let key2 = Pubkey::new_unique();
+        let mut details1 = BeamDetails::new(key1, 50);
+        details1.partial_gsol_supply = 1_000;
+        let mut details2 = BeamDetails::new(key2, 50);
+        details2.partial_gsol_supply = 1_000;
+        let mut state = State {
+            allocations: vec![details1, details2, BeamDetails::default()],
+            epoch_report: EpochReport::new(3, 0),
+            ..Default::default()
+        };
+        state.update_surplus_and_epoch_for_beam(0, 1, 100);
+        state.update_surplus_and_epoch_for_beam(1, 1, -50);
+
+        // Too small for the state
+        let mut oracle = BackingOracle {
+            beam_backing: vec![BeamBacking::default(); 2],
+            ..Default::default()
+        };
+        assert!(oracle.update(&state, 10).is_err());
+
+        oracle.beam_backing = vec![BeamBacking::default(); 4];
+        oracle.update(&state, 10).unwrap();
+        assert_eq!(oracle.beam_backing[0].beam, key1);
+        assert_eq!(oracle.beam_backing[0].backing, 1_100);
+        assert_eq!(oracle.beam_backing[1].backing, 950);
+        assert_eq!(oracle.beam_backing[2], BeamBacking::default());
+        assert_eq!(oracle.gsol_supply, 2_000);
+        assert_eq!(oracle.total_backing, 2_050);
+        assert_eq!(oracle.backing_per_gsol, 1_025_000_000);
+
+        assert_eq!(
+            oracle.checked_backing_per_gsol(20, 10).unwrap(),
+            1_025_000_000
+        );
+        assert!(oracle.checked_backing_per_gsol(21, 10).is_err());
+
+        // No gSOL issued
+        let mut oracle = BackingOracle {
+            beam_backing: vec![BeamBacking::default(); 3],
+            ..Default::default()
+        };
+        oracle.update(&State::default(), 10).unwrap();
+        assert_eq!(oracle.backing_per_gsol, ONE_GSOL);
+    }
+    #[test]
+    fn test_update_surplus() {
+        let mut state = State {
+            allocations: vec![
+                BeamDetails::new(Pubkey::new_unique(), 50),
+                BeamDetails::new(Pubkey::new_unique(), 50),
+            ],
+            epoch_report: EpochReport::new(2, 0),
+            ..Default::default()
+        };
+
+        state.update_surplus_and_epoch_for_beam(0, 1, 100);
+        state.update_surplus_and_epoch_for_beam(1, 1, -40);
+        assert_eq!(state.epoch_report.extractable_yield(), 100);
+        assert_eq!(state.total_deficit(), 40);
+        assert!(state.has_deficit());
+
+        // A later report replaces the deficit
+        state.update_surplus_and_epoch_for_beam(1, 1, 5);
+        assert_eq!(state.epoch_report.extractable_yield(), 105);
+        assert_eq!(state.total_deficit(), 0);
+        assert!(!state.has_deficit());
+    }
+    #[test]
+    fn test_socialise_deficit() {
+        let mut details = vec![
+            BeamDetails::new(Pubkey::new_unique(), 50),
+            BeamDetails::new(Pubkey::new_unique(), 50),
+            BeamDetails::new(Pubkey::new_unique(), 0),
+        ];
+        details[1].partial_gsol_supply = 60;
+        let mut state = State {
+            allocations: details,
+            epoch_report: EpochReport::new(3, 0),
+            ..Default::default()
+        };
+        state.update_surplus_and_epoch_for_beam(0, 1, 100);
+        state.update_surplus_and_epoch_for_beam(1, 1, -40);
+
+        // The covering beam has not reported this epoch
+        assert!(state.socialise_deficit(1, 2, 10, 1).is_err());
+        // More than the deficit
+        assert_eq!(
+            state.socialise_deficit(1, 0, 41, 1).unwrap_err(),
+            BeamError::DeficitExceeded.into()
+        );
+
+        state.socialise_deficit(1, 0, 40, 1).unwrap();
+        assert!(!state.has_deficit());
+        assert_eq!(
+            state.epoch_report.beam_epoch_details[0].extractable_yield,
+            60
+        );
+        assert_eq!(state.allocations[0].partial_gsol_supply, 40);
+        assert_eq!(state.allocations[1].partial_gsol_supply, 20);
+
+        // More than the surplus
+        state.update_surplus_and_epoch_for_beam(1, 1, -80);
+        assert_eq!(
+            state.socialise_deficit(1, 0, 70, 1).unwrap_err(),
+            BeamError::DeficitExceeded.into()
+        );
+        state.allocations[1].partial_gsol_supply = 80;
+        assert_eq!(
+            state.socialise_deficit(1, 0, 70, 1).unwrap_err(),
+            BeamError::InsufficientSurplus.into()
+        );
+    }
+    #[test]
+    fn test_extend_allocations() {
+        let key = Pubkey::new_unique();
+        let mut state = State {
+            allocations: vec![BeamDetails::new(key, 100)],
+            epoch_report: EpochReport::new(1, 3),
+            ..Default::default()
+        };
+
+        state.extend_allocations(2, 5);
+
+        // The epoch report stays aligned with the allocations, so the new beams can report.
+        assert_eq!(state.allocations.len(), 3);
+        assert_eq!(state.allocations[0].key, key);
+        assert_eq!(
+            state.epoch_report.beam_epoch_details,
+            vec![
+                BeamEpochDetails::new(3),
+                BeamEpochDetails::new(5),
+                BeamEpochDetails::new(5)
+            ]
+        );
+        state.update_surplus_and_epoch_for_beam(2, 6, 10);
+        assert!(state.epoch_report.is_epoch_reported_for_beam_idx(6, 2));
+    }
+    #[test]
+    fn test_update_allocations() {
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut state = State {
+            allocations: keys.iter().map(|key| BeamDetails::new(*key, 0)).collect(),
+            ..Default::default()
+        };
+        let updates = |first: u8, second: u8| {
+            vec![
+                AllocationUpdate {
+                    beam: keys[0],
+                    new_allocation: first,
+                },
+                AllocationUpdate {
+                    beam: keys[1],
+                    new_allocation: second,
+                },
+            ]
+        };
+
+        state.update_allocations(updates(60, 40)).unwrap();
+        assert_eq!(state.allocations[0].allocation, 60);
+        assert_eq!(state.allocations[1].allocation, 40);
+
+        // 200 + 156 wraps around to 100 in a u8, so the sum must be taken in a wider type.
+        assert_eq!(
+            state.update_allocations(updates(200, 156)).unwrap_err(),
+            anchor_lang::error::ErrorCode::RequireEqViolated.into()
+        );
+    }
+    #[test]
+    fn test_mint_gsol_overflow() {
+        let key = Pubkey::new_unique();
+        let mut state = State {
+            allocations: vec![BeamDetails::new(key, 100)],
+            pre_supply: 100,
+            ..Default::default()
+        };
+
+        // A mint supply below the pre-supply
+        assert_eq!(
+            state.mint_gsol(&key, 10, 50).unwrap_err(),
+            BeamError::Overflow.into()
+        );
+
+        // A partial supply that can't grow any further
+        state.pre_supply = 0;
+        state.allocations[0].partial_gsol_supply = u64::MAX;
+        assert_eq!(
+            state.mint_gsol(&key, 1, u64::MAX).unwrap_err(),
+            BeamError::Overflow.into()
+        );
+    }
+    #[test]
+    fn test_extract_yield_overflow() {
+        let mut state = State {
+            allocations: vec![BeamDetails::new(Pubkey::new_unique(), 100)],
+            epoch_report: EpochReport::new(1, 1),
+            ..Default::default()
+        };
+        state.epoch_report.beam_epoch_details[0].extracted_yield = u64::MAX;
+
+        assert_eq!(
+            state.extract_yield(0, 1, 1).unwrap_err(),
+            BeamError::Overflow.into()
+        );
+    }
+    #[test]
+    fn test_state_layout() {
+        // New fields are carved out of the reserved space, so existing accounts keep their size.
+        assert_eq!(State::SIZE_WITH_ZERO_BEAMS, 245);
+        assert_eq!(BeamDetails::SIZE, 170);
+
+        let state = State {
+            allocations: vec![BeamDetails::default(); 2],
+            epoch_report: EpochReport::new(2, 0),
+            ..Default::default()
+        };
+        assert_eq!(state.try_to_vec().unwrap().len() + 8, State::size(2));
+    }
+
+    #[test]
+    fn test_attribution() {
+        let key = Pubkey::new_unique();
+        let mut details = BeamDetails::new(key, 100);
+        details.partial_gsol_supply = 4_000;
+        let mut state = State {
+            allocations: vec![details],
+            epoch_report: EpochReport::new(1, 0),
+            ..Default::default()
+        };
+
+        // A quarter of the supply, held from epoch 0
+        let mut attribution = Attribution::default();
+        attribution
+            .record_mint(1_000, 1_000, state.yield_per_gsol, 0)
+            .unwrap();
+
+        state.extract_yield(0, 400, 0).unwrap();
+        let attributed = attribution
+            .attributed_yield(1_000, state.yield_per_gsol, 2)
+            .unwrap();
+        assert_eq!(attributed.attributed_yield, 100);
+        assert_eq!(attributed.time_weighted_balance, 2_000);
+
+        // Half of the attributed balance is withdrawn in epoch 2
+        attribution
+            .record_burn(500, 500, state.yield_per_gsol, 2)
+            .unwrap();
+        state.update_surplus_and_epoch_for_beam(0, 3, 300);
+        state.extract_yield(0, 400, 3).unwrap();
+        let attributed = attribution
+            .attributed_yield(500, state.yield_per_gsol, 3)
+            .unwrap();
+        assert_eq!(attributed.gsol_balance, 500);
+        assert_eq!(attributed.attributed_yield, 150);
+        assert_eq!(attributed.time_weighted_balance, 2_500);
+
+        // Transferring gSOL away caps the balance at what is left in the token account,
+        // before the yield extracted since the last update is attributed to it
+        attribution
+            .record_burn(50, 50, state.yield_per_gsol, 3)
+            .unwrap();
+        assert_eq!(attribution.gsol_balance, 50);
+        assert_eq!(attribution.attributed_yield, 110);
+
+        // Burning more than the attributed balance
+        attribution
+            .record_burn(1_000, 0, state.yield_per_gsol, 3)
+            .unwrap();
+        assert_eq!(attribution.gsol_balance, 0);
+        assert_eq!(attribution.attributed_yield, 110);
+    }
+
+    #[test]
+    fn test_attribution_is_capped_at_the_gsol_held() {
+        let key = Pubkey::new_unique();
+        let mut details = BeamDetails::new(key, 100);
+        details.partial_gsol_supply = 4_000;
+        let mut state = State {
+            allocations: vec![details],
+            epoch_report: EpochReport::new(1, 0),
+            ..Default::default()
+        };
+
+        let mut attribution = Attribution::default();
+        attribution
+            .record_mint(1_000, 1_000, state.yield_per_gsol, 0)
+            .unwrap();
+
+        // Half of the gSOL is transferred away before the yield is extracted,
+        // so only the half still held is attributed any of it.
+        state.extract_yield(0, 400, 0).unwrap();
+        let attributed = attribution
+            .attributed_yield(500, state.yield_per_gsol, 2)
+            .unwrap();
+        assert_eq!(attributed.gsol_balance, 500);
+        assert_eq!(attributed.attributed_yield, 50);
+        assert_eq!(attributed.time_weighted_balance, 1_000);
+
+        // The balance is capped before a burn, at the gSOL held before burning
+        attribution
+            .record_burn(100, 400, state.yield_per_gsol, 2)
+            .unwrap();
+        assert_eq!(attribution.gsol_balance, 400);
+        assert_eq!(attribution.attributed_yield, 50);
+    }
+
+    #[test]
+    fn test_attribution_is_net_of_the_protocol_fee() {
+        let key = Pubkey::new_unique();
+        let mut details = BeamDetails::new(key, 100);
+        details.partial_gsol_supply = 4_000;
+        let mut state = State {
+            allocations: vec![details],
+            epoch_report: EpochReport::new(1, 0),
+            treasury: Pubkey::new_unique(),
+            protocol_fee_bps: 1_000,
+            ..Default::default()
+        };
+
+        let mut attribution = Attribution::default();
+        attribution
+            .record_mint(4_000, 4_000, state.yield_per_gsol, 0)
+            .unwrap();
+
+        // The whole supply is attributed the yield left after the 10% fee.
+        assert_eq!(state.extract_yield(0, 400, 0).unwrap(), 40);
+        let attributed = attribution
+            .attributed_yield(4_000, state.yield_per_gsol, 1)
+            .unwrap();
+        assert_eq!(attributed.attributed_yield, 360);
+    }
+
+    #[test]
+    fn test_impact_certificate() {
+        let key = Pubkey::new_unique();
+        let mut details = BeamDetails::new(key, 100);
+        details.partial_gsol_supply = 4_000;
+        let mut state = State {
+            allocations: vec![details],
+            epoch_report: EpochReport::new(1, 0),
+            ..Default::default()
+        };
+
+        // A quarter of the supply, held from epoch 0
+        let mut attribution = Attribution::default();
+        attribution
+            .record_mint(1_000, 1_000, state.yield_per_gsol, 0)
+            .unwrap();
+
+        // Nothing has been attributed yet
+        let mut certificate = ImpactCertificate::default();
+        assert_eq!(
+            attribution
+                .certify(&mut certificate, 1_000, state.yield_per_gsol, 1)
+                .unwrap_err(),
+            BeamError::NoImpactToCertify.into()
+        );
+
+        state.extract_yield(0, 400, 0).unwrap();
+        attribution
+            .certify(&mut certificate, 1_000, state.yield_per_gsol, 2)
+            .unwrap();
+        assert_eq!(certificate.start_epoch, 0);
+        assert_eq!(certificate.end_epoch, 2);
+        assert_eq!(certificate.attributed_yield, 100);
+        assert_eq!(certificate.time_weighted_balance, 2_000);
+
+        // The next certificate only covers the yield since the last one
+        state.update_surplus_and_epoch_for_beam(0, 3, 800);
+        state.extract_yield(0, 800, 3).unwrap();
+        let mut certificate = ImpactCertificate::default();
+        attribution
+            .certify(&mut certificate, 1_000, state.yield_per_gsol, 5)
+            .unwrap();
+        assert_eq!(certificate.start_epoch, 2);
+        assert_eq!(certificate.end_epoch, 5);
+        assert_eq!(certificate.attributed_yield, 200);
+        assert_eq!(certificate.time_weighted_balance, 3_000);
+        assert_eq!(attribution.certified_yield, 300);
+
+        assert_eq!(ImpactCertificate::period(IMPACT_PERIOD_EPOCHS - 1), 0);
+        assert_eq!(ImpactCertificate::period(IMPACT_PERIOD_EPOCHS), 1);
+    }
+
+    #[test]
+    fn test_verify_impact_certificate() {
+        use anchor_lang::solana_program::program_pack::Pack;
+        use anchor_spl::token::{spl_token, TokenAccount};
+
+        fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> TokenAccount {
+            let mut data = [0; spl_token::state::Account::LEN];
+            spl_token::state::Account {
+                mint,
+                owner,
+                amount,
+                state: spl_token::state::AccountState::Frozen,
+                ..Default::default()
+            }
+            .pack_into_slice(&mut data);
+            TokenAccount::try_deserialize(&mut data.as_ref()).unwrap()
+        }
+
+        let certificate = ImpactCertificate {
+            owner: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let (mint, owner) = (certificate.mint, certificate.owner);
+        assert!(certificate.verify(&token_account(mint, owner, 1)).is_ok());
+
+        for token_account in [
+            token_account(Pubkey::new_unique(), owner, 1),
+            token_account(mint, Pubkey::new_unique(), 1),
+            token_account(mint, owner, 0),
+        ] {
+            assert_eq!(
+                certificate.verify(&token_account).unwrap_err(),
+                BeamError::InvalidImpactCertificate.into()
+            );
+        }
+    }
 }
@@ -1,18 +1,133 @@
-use crate::{state::State, BeamError};
+use crate::{
+    seeds::ATTRIBUTION,
+    state::{Attribution, State},
+    BeamError,
+};
 use anchor_lang::prelude::{Account, AccountInfo, Pubkey};
 
 /// Verifies that a mint request is valid by:
+/// - Checking that the beam signed the request.
 /// - Checking that the beam is present in the state.
-/// - Checking that the executing program owns the beam.
+/// - Checking that the executing program owns the beam, or is the state's trusted beam router.
+///   The beam router can only reach here through the beam program, as the beam must sign.
 pub fn checked_find_beam_idx(
-    state: &Account<'_, State>,
+    state: &State,
     beam: &AccountInfo,
     cpi_program_id: &Pubkey,
 ) -> Result<usize, BeamError> {
-    if beam.owner != cpi_program_id {
+    if !beam.is_signer {
+        return Err(BeamError::MissingBeamSignature);
+    }
+    if beam.owner != cpi_program_id && !state.is_beam_router(cpi_program_id) {
         return Err(BeamError::UnidentifiedCallingProgram);
     }
 
     let index = state.find_beam_index(beam.key);
     index.ok_or(BeamError::UnidentifiedBeam)
 }
+
+/// Loads the [Attribution] of `owner`, if the beam passed one as the first remaining account.
+///
+/// Returns `None` if no account was passed, or if the owner has not registered the attribution.
+/// Errors if the account is not the attribution PDA of `owner` against `state`.
+pub fn load_attribution<'info>(
+    state: &Pubkey,
+    owner: &Pubkey,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> anchor_lang::Result<Option<Account<'info, Attribution>>> {
+    let Some(account_info) = remaining_accounts.first() else {
+        return Ok(None);
+    };
+    let (address, _) =
+        Pubkey::find_program_address(&[state.as_ref(), ATTRIBUTION, owner.as_ref()], &crate::ID);
+    if *account_info.key != address {
+        return Err(BeamError::InvalidAttribution.into());
+    }
+
+    // The PDA is only owned by this program once the owner has registered it.
+    if *account_info.owner != crate::ID {
+        return Ok(None);
+    }
+
+    Ok(Some(Account::<Attribution>::try_from(account_info)?))
+}
+
+#[cfg(test)]
+mod internal_tests {
+    use super::*;
+    use crate::state::{BeamDetails, UpdateStateInput};
+
+    fn find_beam_idx(
+        state: &State,
+        beam: &Pubkey,
+        beam_program: &Pubkey,
+        is_signer: bool,
+        cpi_program_id: &Pubkey,
+    ) -> anchor_lang::Result<usize> {
+        let mut lamports = 0;
+        let mut data = [];
+        let beam = AccountInfo::new(
+            beam,
+            is_signer,
+            false,
+            &mut lamports,
+            &mut data,
+            beam_program,
+            false,
+            0,
+        );
+        Ok(checked_find_beam_idx(state, &beam, cpi_program_id)?)
+    }
+
+    #[test]
+    fn test_checked_find_beam_idx() {
+        let beam = Pubkey::new_unique();
+        let beam_program = Pubkey::new_unique();
+        let router = Pubkey::new_unique();
+        let mut state = State {
+            allocations: vec![BeamDetails::new(Pubkey::new_unique(), 0)],
+            ..Default::default()
+        };
+        state.allocations.push(BeamDetails::new(beam, 100));
+
+        // Called directly by the beam program
+        assert_eq!(
+            find_beam_idx(&state, &beam, &beam_program, true, &beam_program),
+            Ok(1)
+        );
+        assert_eq!(
+            find_beam_idx(&state, &beam, &beam_program, false, &beam_program),
+            Err(BeamError::MissingBeamSignature.into())
+        );
+        assert_eq!(
+            find_beam_idx(&state, &router, &beam_program, true, &beam_program),
+            Err(BeamError::UnidentifiedBeam.into())
+        );
+
+        // Called through a router that is not trusted
+        assert_eq!(
+            find_beam_idx(&state, &beam, &beam_program, true, &router),
+            Err(BeamError::UnidentifiedCallingProgram.into())
+        );
+
+        // Called through the trusted router
+        state
+            .update(UpdateStateInput {
+                new_beam_router: Some(router),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            find_beam_idx(&state, &beam, &beam_program, true, &router),
+            Ok(1)
+        );
+        assert_eq!(
+            find_beam_idx(&state, &beam, &beam_program, false, &router),
+            Err(BeamError::MissingBeamSignature.into())
+        );
+        assert_eq!(
+            find_beam_idx(&state, &beam, &beam_program, true, &Pubkey::new_unique()),
+            Err(BeamError::UnidentifiedCallingProgram.into())
+        );
+    }
+}
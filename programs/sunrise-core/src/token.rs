@@ -1,7 +1,7 @@
 use crate::seeds::GSOL_AUTHORITY;
 use crate::state::State;
 use anchor_lang::prelude::*;
-use anchor_spl::token;
+use anchor_spl::token::{self, spl_token::instruction::AuthorityType};
 
 /// Mint new tokens to a token-account with the mint-authority's signature.
 pub fn mint_to<'a>(
@@ -47,3 +47,40 @@ pub fn burn<'a>(
     let cpi_ctx = CpiContext::new(cpi_program, accounts);
     token::burn(cpi_ctx, amount)
 }
+
+/// Issue the only token of a certificate `mint` to a token-account and freeze it there,
+/// with the signature of the certificate PDA, which is the mint and freeze authority.
+///
+/// The mint authority is then revoked so that no further tokens can be issued.
+pub fn issue_certificate<'a>(
+    mint: &AccountInfo<'a>,
+    certificate: &AccountInfo<'a>,
+    recipient_token_account: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    certificate_seeds: &[&[u8]],
+) -> Result<()> {
+    let pda_signer = &[certificate_seeds];
+
+    let accounts = token::MintTo {
+        mint: mint.clone(),
+        to: recipient_token_account.clone(),
+        authority: certificate.clone(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.clone(), accounts).with_signer(pda_signer);
+    token::mint_to(cpi_ctx, 1)?;
+
+    let accounts = token::FreezeAccount {
+        account: recipient_token_account.clone(),
+        mint: mint.clone(),
+        authority: certificate.clone(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.clone(), accounts).with_signer(pda_signer);
+    token::freeze_account(cpi_ctx)?;
+
+    let accounts = token::SetAuthority {
+        current_authority: certificate.clone(),
+        account_or_mint: mint.clone(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.clone(), accounts).with_signer(pda_signer);
+    token::set_authority(cpi_ctx, AuthorityType::MintTokens, None)
+}
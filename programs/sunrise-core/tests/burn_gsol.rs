@@ -1 +1,59 @@
+mod helpers;
 
+use anchor_spl::token::Mint;
+use beam_test_utils::error_code;
+use helpers::beams::setup;
+use solana_program_test::tokio;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+use sunrise_core::BeamError;
+
+const DEPOSIT: u64 = 10 * LAMPORTS_PER_SOL;
+const WITHDRAWAL: u64 = 4 * LAMPORTS_PER_SOL;
+
+#[tokio::test]
+async fn a_beam_burns_the_gsol_it_withdraws() {
+    let (mut context, reserve, _, staker, gsol_account) = setup(100).await;
+
+    let deposit = reserve.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+    let withdraw = reserve.withdraw(&staker.pubkey(), &gsol_account, WITHDRAWAL, 0);
+    context.send(&[withdraw], &[&staker]).await.unwrap();
+
+    let gsol_mint = context.sunrise.state.gsol_mint;
+    let mint: Mint = context.fetch(&gsol_mint).await;
+    assert_eq!(mint.supply, DEPOSIT - WITHDRAWAL);
+    assert_eq!(
+        context.token_balance(&gsol_account).await,
+        DEPOSIT - WITHDRAWAL
+    );
+
+    context.refresh_sunrise().await;
+    assert_eq!(
+        context.sunrise.state.allocations[0].partial_gsol_supply,
+        DEPOSIT - WITHDRAWAL
+    );
+}
+
+#[tokio::test]
+async fn a_beam_cannot_burn_more_gsol_than_it_minted() {
+    let (mut context, reserve, marinade, staker, gsol_account) = setup(50).await;
+
+    let deposit = marinade.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+    let gsol = context.token_balance(&gsol_account).await;
+    let deposit = reserve.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+
+    // The reserve holds enough SOL, and the staker enough gSOL, to withdraw more than
+    // the reserve minted, so only the reserve's partial supply stands in the way.
+    context.fund(&reserve.vault_authority(), DEPOSIT).await;
+    let withdraw = reserve.withdraw(&staker.pubkey(), &gsol_account, DEPOSIT + 1, 0);
+    let error = context.send(&[withdraw], &[&staker]).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(BeamError::BurnWindowExceeded))
+    );
+    assert_eq!(context.token_balance(&gsol_account).await, gsol + DEPOSIT);
+}
@@ -0,0 +1,121 @@
+mod helpers;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Mint, TokenAccount};
+use beam_test_utils::error_code;
+use helpers::beams::setup;
+use solana_program_test::tokio;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use sunrise_client::sunrise::{derive_impact_certificate_mint, ImpactCertificate, ImpactProject};
+use sunrise_core::BeamError;
+
+const DEPOSIT: u64 = 10 * LAMPORTS_PER_SOL;
+
+#[tokio::test]
+async fn can_claim_an_impact_certificate_once_per_period() {
+    let (mut context, _, beam, staker, gsol_account) = setup(0).await;
+
+    let register_attribution = context
+        .sunrise
+        .register_attribution(&context.payer(), &staker.pubkey());
+    context
+        .send(&[register_attribution], &[&staker])
+        .await
+        .unwrap();
+    let deposit = beam.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+
+    let update_epoch_report = beam.update_epoch_report();
+    let extract_yield = beam.extract_yield(&context.payer());
+    context
+        .send(&[update_epoch_report, extract_yield], &[])
+        .await
+        .unwrap();
+    context.refresh_sunrise().await;
+    let extracted_yield = context.sunrise.state.epoch_report.beam_epoch_details[1].extracted_yield;
+
+    let recipient = Pubkey::new_unique();
+    let register_impact_project = context.sunrise.register_impact_project(
+        &context.payer(),
+        &recipient,
+        "Solar for schools".to_string(),
+    );
+    context.send(&[register_impact_project], &[]).await.unwrap();
+    let project = context.sunrise.impact_project(&recipient);
+    let impact_project: ImpactProject = context.fetch(&project).await;
+    assert_eq!(impact_project.recipient, recipient);
+    assert_eq!(impact_project.name, "Solar for schools");
+
+    let epoch = context.epoch().await;
+    let period = ImpactCertificate::period(epoch);
+
+    // Only the current period can be claimed.
+    let claim = context.sunrise.claim_impact_certificate(
+        &context.payer(),
+        &staker.pubkey(),
+        &gsol_account,
+        &project,
+        period + 1,
+    );
+    let error = context.send(&[claim], &[&staker]).await.unwrap_err();
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(BeamError::InvalidImpactPeriod))
+    );
+
+    // Only a registered project can be credited.
+    let claim = context.sunrise.claim_impact_certificate(
+        &context.payer(),
+        &staker.pubkey(),
+        &gsol_account,
+        &recipient,
+        period,
+    );
+    assert!(context.send(&[claim], &[&staker]).await.is_err());
+
+    let claim = context.sunrise.claim_impact_certificate(
+        &context.payer(),
+        &staker.pubkey(),
+        &gsol_account,
+        &project,
+        period,
+    );
+    context.send(&[claim], &[&staker]).await.unwrap();
+
+    let certificate_address = context.sunrise.impact_certificate(&staker.pubkey(), period);
+    let certificate: ImpactCertificate = context.fetch(&certificate_address).await;
+    assert_eq!(certificate.owner, staker.pubkey());
+    assert_eq!(certificate.period, period);
+    assert_eq!(certificate.end_epoch, epoch);
+    assert_eq!(certificate.project, project);
+    assert!(certificate.attributed_yield.abs_diff(extracted_yield) <= 1);
+
+    // The certificate token is the only one of its mint, and is frozen with the staker.
+    let certificate_mint =
+        derive_impact_certificate_mint(&sunrise_core::ID, &certificate_address).0;
+    assert_eq!(certificate.mint, certificate_mint);
+    let mint: Mint = context.fetch(&certificate_mint).await;
+    assert_eq!(mint.supply, 1);
+    assert!(mint.mint_authority.is_none());
+    let token_account: TokenAccount = context
+        .fetch(&get_associated_token_address(
+            &staker.pubkey(),
+            &certificate_mint,
+        ))
+        .await;
+    assert_eq!(token_account.amount, 1);
+    assert!(token_account.is_frozen());
+    assert!(certificate.verify(&token_account).is_ok());
+
+    // A second certificate can't be claimed in the same period.
+    let claim = context.sunrise.claim_impact_certificate(
+        &context.payer(),
+        &staker.pubkey(),
+        &gsol_account,
+        &project,
+        period,
+    );
+    assert!(context.send(&[claim], &[&staker]).await.is_err());
+}
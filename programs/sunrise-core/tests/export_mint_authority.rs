@@ -1 +1,39 @@
+mod helpers;
 
+use anchor_lang::error::ErrorCode;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::Mint;
+use helpers::{error_code, setup};
+use solana_program_test::tokio;
+use solana_sdk::program_option::COption;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+
+#[tokio::test]
+async fn can_export_the_mint_authority() {
+    let sunrise = setup(1).await;
+    let new_authority = Pubkey::new_unique();
+
+    sunrise.export_mint_authority(&new_authority).await.unwrap();
+
+    let state = sunrise.fetch_decoded_state().await.unwrap();
+    let account = sunrise.get_account(&state.gsol_mint).await.unwrap();
+    let mint = Mint::try_deserialize(&mut account.data.as_ref()).unwrap();
+    assert_eq!(mint.mint_authority, COption::Some(new_authority));
+}
+
+#[tokio::test]
+async fn only_the_update_authority_can_export_the_mint_authority() {
+    let mut sunrise = setup(1).await;
+    sunrise.set_update_authority(Keypair::new());
+
+    let error = sunrise
+        .export_mint_authority(&Pubkey::new_unique())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(ErrorCode::ConstraintHasOne))
+    );
+}
@@ -0,0 +1,47 @@
+//! Beams to mint and burn gSOL through, as only a registered beam can.
+//!
+//! The reserve beam mints one gSOL per lamport and earns no yield, while the Marinade beam
+//! earns yield from its mSOL, and from the gSOL burned through it.
+
+use beam_test_utils::beams::{initialize_marinade_beam, initialize_reserve_beam};
+use beam_test_utils::{add_marinade, beam_program_test, BeamTestContext};
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use sunrise_client::{marinade_sp, reserve, MarinadeClient, ReserveClient};
+
+/// Sunrise core with the reserve and Marinade beams, and the Marinade program and accounts.
+pub fn program_test() -> ProgramTest {
+    let mut program_test = beam_program_test!(reserve_beam, reserve::ID);
+    program_test.add_program(
+        "marinade_beam",
+        marinade_sp::ID,
+        processor!(marinade_beam::entry),
+    );
+    add_marinade(&mut program_test);
+    program_test
+}
+
+/// Start with the reserve and Marinade beams registered, the reserve with `reserve_allocation`
+/// and the Marinade beam with the rest, and a funded staker with a gSOL token account.
+pub async fn setup(
+    reserve_allocation: u8,
+) -> (
+    BeamTestContext,
+    ReserveClient,
+    MarinadeClient,
+    Keypair,
+    Pubkey,
+) {
+    let mut context = BeamTestContext::start(program_test()).await;
+    let reserve = initialize_reserve_beam(&mut context).await;
+    let marinade = initialize_marinade_beam(&mut context).await;
+    context
+        .register_beams(&[
+            (reserve.state_address, reserve_allocation),
+            (marinade.state_address, 100 - reserve_allocation),
+        ])
+        .await;
+    let (staker, gsol_account) = context.create_staker().await;
+    (context, reserve, marinade, staker, gsol_account)
+}
@@ -6,7 +6,7 @@ use solana_sdk::instruction::Instruction;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{Keypair, Signer};
 use std::cell::RefCell;
-use sunrise_core::State as StateAccount;
+use sunrise_core::{BackingOracle, State as StateAccount};
 
 pub struct SunriseContext {
     pub ctx: RefCell<ProgramTestContext>,
@@ -165,6 +165,14 @@ impl SunriseContext {
         Ok(decoded)
     }
 
+    pub async fn fetch_decoded_backing_oracle(&self) -> Result<BackingOracle> {
+        let address = Self::find_backing_oracle_pda(&self.state).0;
+        let account = &self.get_account(&address).await?;
+        let decoded = BackingOracle::try_deserialize(&mut account.data.as_ref())?;
+
+        Ok(decoded)
+    }
+
     #[allow(dead_code)]
     fn gsol_mint_authority(&self) -> Pubkey {
         self.gsol_mint_authority
@@ -176,4 +184,9 @@ impl SunriseContext {
         let seeds = &[state.as_ref(), sunrise_core::seeds::GSOL_AUTHORITY];
         Pubkey::find_program_address(seeds, &sunrise_core::id())
     }
+
+    pub fn find_backing_oracle_pda(state: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[state.as_ref(), sunrise_core::seeds::BACKING_ORACLE];
+        Pubkey::find_program_address(seeds, &sunrise_core::id())
+    }
 }
@@ -16,6 +16,7 @@ pub fn register_state(
         state: *state,
         gsol_mint: *gsol_mint,
         gsol_mint_authority: *gsol_mint_auth_pda,
+        backing_oracle: find_backing_oracle(state),
         system_program: system_program::id(),
         rent: sysvar::rent::id(),
     };
@@ -56,6 +57,9 @@ pub fn update_state(
             new_yield_account: new_yield_account.map(|n| *n),
             new_gsol_mint: new_gsol_mint.map(|n| *n),
             new_gsol_mint_authority_bump,
+            new_treasury: None,
+            new_protocol_fee_bps: None,
+            new_beam_router: None,
         },
     };
 
@@ -100,6 +104,7 @@ pub fn resize_allocations(
         update_authority: *update_authority,
         payer: *payer,
         state: *state,
+        backing_oracle: find_backing_oracle(state),
         system_program: system_program::id(),
     };
     let data = sunrise_instructions::ResizeAllocations { additional_beams };
@@ -181,3 +186,8 @@ pub fn export_mint_authority(
 
     (accounts, instruction)
 }
+
+fn find_backing_oracle(state: &Pubkey) -> Pubkey {
+    let seeds = &[state.as_ref(), sunrise_core::seeds::BACKING_ORACLE];
+    Pubkey::find_program_address(seeds, &sunrise_core::id()).0
+}
@@ -1,3 +1,7 @@
+// Each test target uses only some of the helpers.
+#![allow(dead_code)]
+
+pub mod beams;
 mod context;
 mod instructions;
 pub mod utils;
@@ -6,6 +10,8 @@ pub use context::*;
 
 use solana_program_test::{processor, BanksClientError, ProgramTest};
 use solana_sdk::program_error::ProgramError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
 use thiserror::Error;
 
 type Result<T> = std::result::Result<T, SunriseContextError>;
@@ -20,6 +26,43 @@ pub fn program_test() -> ProgramTest {
     program_test
 }
 
+/// Start with a registered state with room for `capacity` beams,
+/// whose update authority signs for the returned context.
+pub async fn setup(capacity: u8) -> SunriseContext {
+    let state = Keypair::new();
+    let update_authority = Keypair::new();
+    let gsol_mint = Keypair::new();
+    let (gsol_mint_authority, _) = SunriseContext::find_gsol_mint_authority_pda(&state.pubkey());
+
+    let mut ctx = program_test().start_with_context().await;
+    let instructions =
+        utils::create_token_mint(&mut ctx, &gsol_mint, &gsol_mint_authority, 9).unwrap();
+    utils::send_and_confirm_tx(&mut ctx, instructions, Some(vec![&gsol_mint]))
+        .await
+        .unwrap();
+
+    let mut sunrise = SunriseContext::init(
+        ctx,
+        &state,
+        &gsol_mint,
+        &update_authority.pubkey(),
+        &Pubkey::new_unique(),
+        capacity,
+    )
+    .await
+    .unwrap();
+    sunrise.set_update_authority(update_authority);
+    sunrise
+}
+
+/// The custom error code of a failed transaction.
+pub fn error_code(error: SunriseContextError) -> Option<u32> {
+    match error {
+        SunriseContextError::Client(error) => beam_test_utils::error_code(error),
+        _ => None,
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum SunriseContextError {
     #[error(transparent)]
@@ -1,7 +1,7 @@
 use super::Result;
 use anchor_spl::token::spl_token;
 use anchor_spl::token::Mint;
-use solana_program_test::ProgramTestContext;
+use solana_program_test::{BanksClientError, ProgramTestContext};
 use solana_sdk::instruction::Instruction;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{Keypair, Signer};
@@ -38,15 +38,16 @@ pub async fn send_and_confirm_tx(
     ix: Vec<Instruction>,
     signers: Option<Vec<&Keypair>>,
 ) -> Result<()> {
+    // A new blockhash for each transaction, so that repeating a transaction is not a duplicate.
+    let blockhash = ctx
+        .get_new_latest_blockhash()
+        .await
+        .map_err(BanksClientError::from)?;
     let mut signers = signers.unwrap_or(vec![]);
     signers.push(&ctx.payer);
 
-    let tx = Transaction::new_signed_with_payer(
-        &ix,
-        Some(&ctx.payer.pubkey()),
-        &signers,
-        ctx.last_blockhash,
-    );
+    let tx =
+        Transaction::new_signed_with_payer(&ix, Some(&ctx.payer.pubkey()), &signers, blockhash);
 
     ctx.banks_client.process_transaction(tx).await?;
 
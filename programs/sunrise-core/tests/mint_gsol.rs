@@ -1 +1,56 @@
+mod helpers;
 
+use anchor_spl::token::Mint;
+use beam_test_utils::error_code;
+use helpers::beams::setup;
+use solana_program_test::tokio;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+use sunrise_core::BeamError;
+
+const DEPOSIT: u64 = 10 * LAMPORTS_PER_SOL;
+
+#[tokio::test]
+async fn a_beam_mints_gsol_to_the_depositor() {
+    let (mut context, reserve, _, staker, gsol_account) = setup(100).await;
+
+    let deposit = reserve.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+
+    let gsol_mint = context.sunrise.state.gsol_mint;
+    let mint: Mint = context.fetch(&gsol_mint).await;
+    assert_eq!(mint.supply, DEPOSIT);
+    assert_eq!(context.token_balance(&gsol_account).await, DEPOSIT);
+
+    context.refresh_sunrise().await;
+    let allocations = &context.sunrise.state.allocations;
+    assert_eq!(allocations[0].partial_gsol_supply, DEPOSIT);
+    assert_eq!(allocations[1].partial_gsol_supply, 0);
+}
+
+#[tokio::test]
+async fn a_beam_cannot_mint_beyond_its_allocation_of_the_supply() {
+    let (mut context, reserve, marinade, staker, gsol_account) = setup(0).await;
+
+    // The first mint is unrestricted, as there is no supply to allocate yet.
+    let deposit = reserve.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+
+    // The reserve is now over its allocation of none of the supply.
+    let deposit = reserve.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    let error = context.send(&[deposit], &[&staker]).await.unwrap_err();
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(BeamError::MintWindowExceeded))
+    );
+    assert_eq!(context.token_balance(&gsol_account).await, DEPOSIT);
+
+    // The Marinade beam, allocated all of the supply, can still mint.
+    let deposit = marinade.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+
+    context.refresh_sunrise().await;
+    let allocations = &context.sunrise.state.allocations;
+    assert_eq!(allocations[0].partial_gsol_supply, DEPOSIT);
+    assert!(allocations[1].partial_gsol_supply > 0);
+}
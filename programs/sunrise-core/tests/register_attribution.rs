@@ -0,0 +1,130 @@
+mod helpers;
+
+use anchor_spl::token::spl_token;
+use helpers::beams::setup;
+use solana_program_test::tokio;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+use sunrise_client::sunrise::{AttributedYield, Attribution};
+
+const DEPOSIT: u64 = 10 * LAMPORTS_PER_SOL;
+const BURN: u64 = LAMPORTS_PER_SOL;
+
+#[tokio::test]
+async fn can_attribute_extracted_yield_to_a_depositor() {
+    let (mut context, _, beam, staker, gsol_account) = setup(0).await;
+
+    let register_attribution = context
+        .sunrise
+        .register_attribution(&context.payer(), &staker.pubkey());
+    context
+        .send(&[register_attribution], &[&staker])
+        .await
+        .unwrap();
+    let attribution = context.sunrise.attribution(&staker.pubkey());
+
+    // The deposit and the burn are recorded against the staker's attribution.
+    let deposit = beam.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+    let burn = beam.burn(&staker.pubkey(), &gsol_account, BURN);
+    context.send(&[burn], &[&staker]).await.unwrap();
+
+    let recorded: Attribution = context.fetch(&attribution).await;
+    assert_eq!(recorded.owner, staker.pubkey());
+    assert_eq!(recorded.gsol_balance, DEPOSIT - BURN);
+
+    let update_epoch_report = beam.update_epoch_report();
+    let extract_yield = beam.extract_yield(&context.payer());
+    context
+        .send(&[update_epoch_report, extract_yield], &[])
+        .await
+        .unwrap();
+    context.refresh_sunrise().await;
+    let extracted_yield = context.sunrise.state.epoch_report.beam_epoch_details[1].extracted_yield;
+
+    // The staker holds all of the gSOL issued, so all of the yield is attributed to them.
+    let attributed: AttributedYield = context
+        .view(
+            context
+                .sunrise
+                .get_attributed_yield(&staker.pubkey(), &gsol_account),
+        )
+        .await;
+    assert_eq!(attributed.gsol_balance, DEPOSIT - BURN);
+    assert!(extracted_yield > 0);
+    assert!(attributed.attributed_yield.abs_diff(extracted_yield) <= 1);
+
+    // Once half of it is transferred away, only the gSOL still held is attributed any yield.
+    let recipient = context.create_user(LAMPORTS_PER_SOL).await;
+    let gsol_mint = context.sunrise.state.gsol_mint;
+    let recipient_gsol_account = context
+        .create_token_account(&recipient.pubkey(), &gsol_mint)
+        .await;
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &gsol_account,
+        &recipient_gsol_account,
+        &staker.pubkey(),
+        &[],
+        (DEPOSIT - BURN) / 2,
+    )
+    .unwrap();
+    context.send(&[transfer], &[&staker]).await.unwrap();
+    let attributed: AttributedYield = context
+        .view(
+            context
+                .sunrise
+                .get_attributed_yield(&staker.pubkey(), &gsol_account),
+        )
+        .await;
+    assert_eq!(attributed.gsol_balance, (DEPOSIT - BURN) / 2);
+    assert!(attributed.attributed_yield.abs_diff(extracted_yield / 2) <= 1);
+}
+
+#[tokio::test]
+async fn a_withdrawal_without_the_attribution_goes_unrecorded() {
+    let (mut context, _, beam, staker, gsol_account) = setup(0).await;
+
+    let register_attribution = context
+        .sunrise
+        .register_attribution(&context.payer(), &staker.pubkey());
+    context
+        .send(&[register_attribution], &[&staker])
+        .await
+        .unwrap();
+    let attribution = context.sunrise.attribution(&staker.pubkey());
+    let deposit = beam.deposit(&staker.pubkey(), &gsol_account, DEPOSIT);
+    context.send(&[deposit], &[&staker]).await.unwrap();
+
+    // Passing the attribution is opt-in, so a burn that leaves it out is not recorded.
+    let mut burn = beam.burn(&staker.pubkey(), &gsol_account, BURN);
+    burn.accounts.pop();
+    context.send(&[burn], &[&staker]).await.unwrap();
+    let recorded: Attribution = context.fetch(&attribution).await;
+    assert_eq!(recorded.gsol_balance, DEPOSIT);
+
+    // gSOL burned or transferred away stops being attributed at the owner's next recorded burn.
+    let recipient = context.create_user(LAMPORTS_PER_SOL).await;
+    let gsol_mint = context.sunrise.state.gsol_mint;
+    let recipient_gsol_account = context
+        .create_token_account(&recipient.pubkey(), &gsol_mint)
+        .await;
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &gsol_account,
+        &recipient_gsol_account,
+        &staker.pubkey(),
+        &[],
+        DEPOSIT / 2,
+    )
+    .unwrap();
+    let burn = beam.burn(&staker.pubkey(), &gsol_account, BURN);
+    context.send(&[transfer, burn], &[&staker]).await.unwrap();
+
+    let recorded: Attribution = context.fetch(&attribution).await;
+    assert_eq!(recorded.gsol_balance, DEPOSIT / 2 - 2 * BURN);
+    assert_eq!(
+        context.token_balance(&gsol_account).await,
+        DEPOSIT / 2 - 2 * BURN
+    );
+}
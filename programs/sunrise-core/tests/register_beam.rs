@@ -1 +1,68 @@
+mod helpers;
 
+use anchor_lang::error::ErrorCode;
+use helpers::{error_code, setup};
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use sunrise_core::BeamError;
+
+#[tokio::test]
+async fn can_register_a_beam() {
+    let sunrise = setup(2).await;
+    let beam = Pubkey::new_unique();
+
+    sunrise.register_beam(&beam).await.unwrap();
+
+    let state = sunrise.fetch_decoded_state().await.unwrap();
+    assert_eq!(state.beam_count(), 1);
+    let details = state.get_beam_details(&beam).unwrap();
+    assert_eq!(details.allocation, 0);
+    assert_eq!(details.partial_gsol_supply, 0);
+}
+
+#[tokio::test]
+async fn cannot_register_a_beam_twice() {
+    let sunrise = setup(2).await;
+    let beam = Pubkey::new_unique();
+    sunrise.register_beam(&beam).await.unwrap();
+
+    let error = sunrise.register_beam(&beam).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(BeamError::DuplicateBeamEntry))
+    );
+}
+
+#[tokio::test]
+async fn cannot_register_beyond_the_capacity() {
+    let sunrise = setup(1).await;
+    sunrise.register_beam(&Pubkey::new_unique()).await.unwrap();
+
+    let error = sunrise
+        .register_beam(&Pubkey::new_unique())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(BeamError::NoSpaceInAllocations))
+    );
+}
+
+#[tokio::test]
+async fn only_the_update_authority_can_register_a_beam() {
+    let mut sunrise = setup(2).await;
+    sunrise.set_update_authority(Keypair::new());
+
+    let error = sunrise
+        .register_beam(&Pubkey::new_unique())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(ErrorCode::ConstraintHasOne))
+    );
+}
@@ -1 +1,58 @@
+mod helpers;
 
+use helpers::{error_code, setup};
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use sunrise_core::{AllocationUpdate, BeamError};
+
+#[tokio::test]
+async fn can_remove_a_beam_without_an_allocation() {
+    let sunrise = setup(2).await;
+    let beam = Pubkey::new_unique();
+    sunrise.register_beam(&beam).await.unwrap();
+
+    sunrise.remove_beam(&beam).await.unwrap();
+
+    let state = sunrise.fetch_decoded_state().await.unwrap();
+    assert_eq!(state.beam_count(), 0);
+    assert!(!state.contains_beam(&beam));
+    // The slot is free for another beam.
+    assert_eq!(state.allocations.len(), 2);
+    sunrise.register_beam(&Pubkey::new_unique()).await.unwrap();
+}
+
+#[tokio::test]
+async fn cannot_remove_a_beam_with_an_allocation() {
+    let sunrise = setup(2).await;
+    let beam = Pubkey::new_unique();
+    sunrise.register_beam(&beam).await.unwrap();
+    sunrise
+        .update_allocations(vec![AllocationUpdate {
+            beam,
+            new_allocation: 100,
+        }])
+        .await
+        .unwrap();
+
+    let error = sunrise.remove_beam(&beam).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(BeamError::NonZeroAllocation))
+    );
+}
+
+#[tokio::test]
+async fn cannot_remove_an_unregistered_beam() {
+    let sunrise = setup(2).await;
+
+    let error = sunrise
+        .remove_beam(&Pubkey::new_unique())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(BeamError::UnidentifiedBeam))
+    );
+}
@@ -1 +1,42 @@
+mod helpers;
 
+use anchor_lang::error::ErrorCode;
+use helpers::{error_code, setup};
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+
+#[tokio::test]
+async fn can_resize_the_allocations() {
+    let sunrise = setup(1).await;
+    let beam = Pubkey::new_unique();
+    sunrise.register_beam(&beam).await.unwrap();
+
+    sunrise.resize_allocations(2).await.unwrap();
+
+    // The registered beam keeps its slot, and the epoch report and the backing oracle
+    // grow with the allocations.
+    let state = sunrise.fetch_decoded_state().await.unwrap();
+    assert_eq!(state.allocations.len(), 3);
+    assert_eq!(state.allocations[0].key, beam);
+    assert_eq!(state.epoch_report.beam_epoch_details.len(), 3);
+    let backing_oracle = sunrise.fetch_decoded_backing_oracle().await.unwrap();
+    assert_eq!(backing_oracle.beam_backing.len(), 3);
+
+    // The new slots can take beams.
+    sunrise.register_beam(&Pubkey::new_unique()).await.unwrap();
+    sunrise.register_beam(&Pubkey::new_unique()).await.unwrap();
+}
+
+#[tokio::test]
+async fn only_the_update_authority_can_resize_the_allocations() {
+    let mut sunrise = setup(1).await;
+    sunrise.set_update_authority(Keypair::new());
+
+    let error = sunrise.resize_allocations(2).await.unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(ErrorCode::ConstraintHasOne))
+    );
+}
@@ -1 +1,83 @@
+mod helpers;
 
+use anchor_lang::error::ErrorCode;
+use helpers::{error_code, setup, SunriseContext};
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use sunrise_core::{AllocationUpdate, BeamError};
+
+/// Register two beams with no allocation.
+async fn register_beams(sunrise: &SunriseContext) -> (Pubkey, Pubkey) {
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    sunrise.register_beam(&first).await.unwrap();
+    sunrise.register_beam(&second).await.unwrap();
+    (first, second)
+}
+
+#[tokio::test]
+async fn can_update_the_allocations() {
+    let sunrise = setup(2).await;
+    let (first, second) = register_beams(&sunrise).await;
+
+    sunrise
+        .update_allocations(vec![
+            AllocationUpdate {
+                beam: first,
+                new_allocation: 60,
+            },
+            AllocationUpdate {
+                beam: second,
+                new_allocation: 40,
+            },
+        ])
+        .await
+        .unwrap();
+
+    let state = sunrise.fetch_decoded_state().await.unwrap();
+    assert_eq!(state.get_beam_details(&first).unwrap().allocation, 60);
+    assert_eq!(state.get_beam_details(&second).unwrap().allocation, 40);
+}
+
+#[tokio::test]
+async fn the_allocations_must_sum_to_100() {
+    let sunrise = setup(2).await;
+    let (first, second) = register_beams(&sunrise).await;
+
+    let error = sunrise
+        .update_allocations(vec![
+            AllocationUpdate {
+                beam: first,
+                new_allocation: 60,
+            },
+            AllocationUpdate {
+                beam: second,
+                new_allocation: 30,
+            },
+        ])
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(ErrorCode::RequireEqViolated))
+    );
+}
+
+#[tokio::test]
+async fn cannot_allocate_to_an_unregistered_beam() {
+    let sunrise = setup(2).await;
+    register_beams(&sunrise).await;
+
+    let error = sunrise
+        .update_allocations(vec![AllocationUpdate {
+            beam: Pubkey::new_unique(),
+            new_allocation: 100,
+        }])
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(BeamError::UnidentifiedBeam))
+    );
+}
@@ -1 +1,53 @@
+mod helpers;
 
+use anchor_lang::error::ErrorCode;
+use helpers::{error_code, setup};
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn can_update_the_yield_account() {
+    let sunrise = setup(1).await;
+    let before = sunrise.fetch_decoded_state().await.unwrap();
+    let yield_account = Pubkey::new_unique();
+
+    sunrise
+        .update_state(None, Some(&yield_account), None, None)
+        .await
+        .unwrap();
+
+    let state = sunrise.fetch_decoded_state().await.unwrap();
+    assert_eq!(state.yield_account, yield_account);
+    assert_eq!(state.update_authority, before.update_authority);
+    assert_eq!(state.gsol_mint, before.gsol_mint);
+}
+
+#[tokio::test]
+async fn can_hand_over_the_update_authority() {
+    let mut sunrise = setup(1).await;
+    let new_update_authority = Keypair::new();
+
+    sunrise
+        .update_state(Some(&new_update_authority.pubkey()), None, None, None)
+        .await
+        .unwrap();
+
+    let state = sunrise.fetch_decoded_state().await.unwrap();
+    assert_eq!(state.update_authority, new_update_authority.pubkey());
+
+    // The previous update authority can no longer update the state, but the new one can.
+    let error = sunrise
+        .update_state(None, Some(&Pubkey::new_unique()), None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error_code(error),
+        Some(u32::from(ErrorCode::ConstraintHasOne))
+    );
+    sunrise.set_update_authority(new_update_authority);
+    sunrise
+        .update_state(None, Some(&Pubkey::new_unique()), None, None)
+        .await
+        .unwrap();
+}